@@ -12,6 +12,7 @@
 //! | [`model::basic_types`] | Primitive GML scalar types: [`Code`](model::basic_types::Code), [`Measure`](model::basic_types::Measure) |
 //! | [`model::feature`] | Abstract GML feature class |
 //! | [`model::geometry`] | Full geometry hierarchy: primitives, aggregates, and complexes |
+//! | [`profile`] | GML Simple Features profile checker and downgrading |
 //! | [`util::plane`] | Plane in R³ — point + unit normal |
 //! | [`util::triangulate`] | Earcut-based polygon triangulation |
 //!
@@ -50,6 +51,7 @@
 
 mod error;
 pub mod model;
+pub mod profile;
 pub mod resolver;
 pub mod util;
 
@@ -1,5 +1,5 @@
 use crate::model::abstract_object::{AbstractObject, AsAbstractObject, AsAbstractObjectMut};
-use crate::model::base::Id;
+use crate::model::base::{ForeignContent, Id};
 use crate::model::basic_types::Code;
 
 /// Base data shared by every GML object ([OGC 07-036 §7.2.2.2](https://docs.ogc.org/is/07-036/07-036.pdf), `gml:AbstractGMLType`).
@@ -14,6 +14,8 @@ pub struct AbstractGml {
     id: Option<Id>,
     /// Human-readable names associated with this GML object.
     names: Vec<Code>,
+    /// Attributes and child elements the model does not cover.
+    foreign_content: ForeignContent,
 }
 
 impl AbstractGml {
@@ -38,6 +40,7 @@ impl AbstractGml {
             abstract_object,
             id: None,
             names: Vec::new(),
+            foreign_content: ForeignContent::default(),
         }
     }
 
@@ -90,6 +93,12 @@ pub trait AsAbstractGml: AsAbstractObject {
     fn names(&self) -> &[Code] {
         &self.abstract_gml().names
     }
+
+    /// Returns the XML of this object that the model does not cover. It is
+    /// only filled when reading with unknown content preserved.
+    fn foreign_content(&self) -> &ForeignContent {
+        &self.abstract_gml().foreign_content
+    }
 }
 
 /// Mutable companion to [`AsAbstractGml`].
@@ -126,6 +135,14 @@ pub trait AsAbstractGmlMut: AsAbstractObjectMut + AsAbstractGml {
     fn extend_names(&mut self, names: impl IntoIterator<Item = Code>) {
         self.abstract_gml_mut().names.extend(names);
     }
+
+    fn set_foreign_content(&mut self, foreign_content: ForeignContent) {
+        self.abstract_gml_mut().foreign_content = foreign_content;
+    }
+
+    fn foreign_content_mut(&mut self) -> &mut ForeignContent {
+        &mut self.abstract_gml_mut().foreign_content
+    }
 }
 
 impl AsAbstractGml for AbstractGml {
@@ -2,6 +2,7 @@ use crate::model::feature::AbstractFeatureKind;
 use crate::model::geometry::AbstractGeometryKind;
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum AbstractGmlKind {
     AbstractGeometryKind(AbstractGeometryKind),
     AbstractFeatureKind(AbstractFeatureKind),
@@ -0,0 +1,71 @@
+/// XML that `egml` does not model, kept verbatim so that a document can be
+/// read and written again without losing it.
+///
+/// Attributes are stored as qualified name and unescaped value. Child
+/// elements and comments are stored as raw XML, each anchored to the modelled
+/// child element that preceded it, so that a writer can put it back after that
+/// element even if it writes a different number of children.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ForeignContent {
+    attributes: Vec<(String, String)>,
+    children: Vec<ForeignChild>,
+}
+
+/// A child element or comment of a [`ForeignContent`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForeignChild {
+    /// The modelled child element before this one, or `None` if it preceded
+    /// all of them.
+    pub anchor: Option<ChildAnchor>,
+    /// The raw XML, e.g. `<ext:note>checked</ext:note>` or `<!-- edited -->`.
+    pub xml: String,
+}
+
+/// Identifies a modelled child element among its siblings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChildAnchor {
+    /// Local name of the element, e.g. `interior`.
+    pub local_name: String,
+    /// Number of siblings with the same local name before the element.
+    pub occurrence: usize,
+}
+
+impl ChildAnchor {
+    pub fn new(local_name: impl Into<String>, occurrence: usize) -> Self {
+        Self {
+            local_name: local_name.into(),
+            occurrence,
+        }
+    }
+}
+
+impl ForeignContent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.children.is_empty()
+    }
+
+    pub fn attributes(&self) -> &[(String, String)] {
+        &self.attributes
+    }
+
+    pub fn push_attribute(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.attributes.push((name.into(), value.into()));
+    }
+
+    /// Returns the child elements and comments in document order.
+    pub fn children(&self) -> &[ForeignChild] {
+        &self.children
+    }
+
+    /// Adds a child after the existing ones.
+    pub fn push_child(&mut self, anchor: Option<ChildAnchor>, xml: impl Into<String>) {
+        self.children.push(ForeignChild {
+            anchor,
+            xml: xml.into(),
+        });
+    }
+}
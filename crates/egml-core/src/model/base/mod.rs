@@ -2,11 +2,13 @@
 //!
 //! Every GML object descends from `AbstractGML` ([OGC 07-036 §7.2.2.2](https://docs.ogc.org/is/07-036/07-036.pdf)), which
 //! carries an optional [`Id`] and zero-or-more name strings.  This module
-//! exposes those two building blocks.
+//! exposes those two building blocks, along with the [`ForeignContent`] that
+//! keeps unmodelled XML of an object.
 //!
 //! | Type | Description |
 //! |------|-------------|
 //! | [`AbstractGml`] | Base data shared by every GML object |
+//! | [`ForeignContent`] | Unmodelled attributes and child elements, kept verbatim |
 //! | [`Id`] | Stable, globally unique GML object identifier |
 //! | [`Reference`] | A by-reference-only property (`gml:ReferenceType`) |
 
@@ -14,6 +16,7 @@ mod abstract_gml;
 mod abstract_gml_kind;
 mod association_attributes;
 mod association_attributes_access;
+mod foreign_content;
 mod id;
 mod ownership_attributes;
 mod ownership_attributes_access;
@@ -23,6 +26,7 @@ pub use abstract_gml::*;
 pub use abstract_gml_kind::*;
 pub use association_attributes::*;
 pub use association_attributes_access::*;
+pub use foreign_content::*;
 pub use id::*;
 pub use ownership_attributes::*;
 pub use ownership_attributes_access::*;
@@ -4,7 +4,9 @@ mod geometry_type;
 mod has_geometry_type;
 mod iter_geometries;
 mod recompute_bounding_shape;
+mod snap_to_grid;
 mod triangulate;
+mod visit_properties;
 
 pub use apply_transform::*;
 pub use compute_envelope::*;
@@ -12,4 +14,6 @@ pub use geometry_type::*;
 pub use has_geometry_type::*;
 pub use iter_geometries::*;
 pub use recompute_bounding_shape::*;
+pub use snap_to_grid::*;
 pub use triangulate::*;
+pub use visit_properties::*;
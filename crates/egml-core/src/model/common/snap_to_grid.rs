@@ -0,0 +1,95 @@
+use crate::Error;
+use crate::model::geometry::DirectPosition;
+
+/// Number of decimal places kept per axis, e.g. millimetres horizontally and
+/// centimetres vertically for metric coordinates.
+///
+/// Rounding goes through the decimal representation, so a rounded value
+/// prints with exactly the same digits when formatted with the same number
+/// of decimal places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CoordinatePrecision {
+    decimals: [u8; 3],
+}
+
+impl CoordinatePrecision {
+    pub fn new(x: u8, y: u8, z: u8) -> Self {
+        Self {
+            decimals: [x, y, z],
+        }
+    }
+
+    /// The same number of decimal places on all axes.
+    pub fn uniform(decimals: u8) -> Self {
+        Self::new(decimals, decimals, decimals)
+    }
+
+    /// `horizontal` decimal places for x and y, `vertical` ones for z.
+    pub fn horizontal_vertical(horizontal: u8, vertical: u8) -> Self {
+        Self::new(horizontal, horizontal, vertical)
+    }
+
+    /// Decimal places of the x, y and z axis.
+    pub fn decimals(&self) -> [u8; 3] {
+        self.decimals
+    }
+
+    /// Rounds `value` to the decimal places of `axis` (0 = x, 1 = y, 2 = z).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is greater than 2.
+    pub fn round_ordinate(&self, axis: usize, value: f64) -> f64 {
+        format!("{value:.*}", usize::from(self.decimals[axis]))
+            .parse()
+            .expect("formatted f64 must parse")
+    }
+
+    pub fn round(&self, position: DirectPosition) -> DirectPosition {
+        DirectPosition::new(
+            self.round_ordinate(0, position.x()),
+            self.round_ordinate(1, position.y()),
+            self.round_ordinate(2, position.z()),
+        )
+        .expect("rounded finite coordinates must be finite")
+    }
+}
+
+/// Rounds every position to a [`CoordinatePrecision`] in place.
+///
+/// Positions that become equal to their predecessor are dropped, so curves
+/// and rings keep their invariants.
+///
+/// # Errors
+///
+/// Returns the validation error of a curve or ring that collapses, e.g.
+/// [`Error::TooFewElements`] for a ring left with fewer than three distinct
+/// positions. The geometry is then partially snapped; the collapsed curve or
+/// ring itself is left unchanged.
+pub trait SnapToGrid {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error>;
+}
+
+/// Rounds `points` and drops positions equal to their predecessor.
+pub(crate) fn snap_points(
+    points: &[DirectPosition],
+    precision: CoordinatePrecision,
+) -> Vec<DirectPosition> {
+    let mut snapped: Vec<DirectPosition> = points.iter().map(|x| precision.round(*x)).collect();
+    snapped.dedup();
+    snapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_ordinate_keeps_decimal_digits() {
+        let precision = CoordinatePrecision::horizontal_vertical(3, 2);
+
+        assert_eq!(precision.round_ordinate(0, 678000.94840653), 678000.948);
+        assert_eq!(precision.round_ordinate(2, 417.3802376791456), 417.38);
+        assert_eq!(precision.round_ordinate(2, 0.1 + 0.2), 0.3);
+    }
+}
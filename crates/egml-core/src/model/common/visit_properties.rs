@@ -0,0 +1,55 @@
+use crate::model::geometry::AbstractGeometryProperty;
+use crate::model::geometry::primitives::{
+    AbstractCurveProperty, AbstractRingProperty, AbstractSurfaceProperty, PointProperty,
+    ShellProperty,
+};
+
+/// Walks every geometry property nested within a geometry, recursively, with
+/// mutable access.
+///
+/// Where [`IterGeometries`](crate::model::common::IterGeometries) yields the
+/// geometries themselves and skips properties that only carry an
+/// `xlink:href`, this hands each *property* to a [`PropertyVisitorMut`] — the
+/// place where a reference can be replaced by an inline object or vice versa.
+///
+/// Geometries pass each of their member properties to the matching visitor
+/// method; properties descend into their inline object, if any. Leaf
+/// geometries (e.g. [`LinearRing`](crate::model::geometry::primitives::LinearRing))
+/// have no properties and do nothing.
+pub trait VisitPropertiesMut {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V);
+}
+
+/// Callbacks invoked by [`VisitPropertiesMut`], one per property type that
+/// can occur inside a geometry.
+///
+/// Every method defaults to descending into the property's object, so a
+/// visitor only overrides the properties it cares about. An override decides
+/// for itself whether to continue the walk by calling
+/// [`visit_properties_mut`](VisitPropertiesMut::visit_properties_mut) on the
+/// property — before or after changing it.
+pub trait PropertyVisitorMut {
+    fn visit_ring_property(&mut self, property: &mut AbstractRingProperty) {
+        property.visit_properties_mut(self);
+    }
+
+    fn visit_curve_property(&mut self, property: &mut AbstractCurveProperty) {
+        property.visit_properties_mut(self);
+    }
+
+    fn visit_surface_property(&mut self, property: &mut AbstractSurfaceProperty) {
+        property.visit_properties_mut(self);
+    }
+
+    fn visit_shell_property(&mut self, property: &mut ShellProperty) {
+        property.visit_properties_mut(self);
+    }
+
+    fn visit_point_property(&mut self, property: &mut PointProperty) {
+        property.visit_properties_mut(self);
+    }
+
+    fn visit_geometry_property(&mut self, property: &mut AbstractGeometryProperty) {
+        property.visit_properties_mut(self);
+    }
+}
@@ -1,5 +1,6 @@
+use crate::Error;
 use crate::model::base::{AbstractGml, AsAbstractGml, AsAbstractGmlMut};
-use crate::model::common::ApplyTransform;
+use crate::model::common::{ApplyTransform, CoordinatePrecision, SnapToGrid};
 use crate::model::feature::bounding_shape::BoundingShape;
 use crate::model::geometry::Envelope;
 use nalgebra::{Isometry3, Rotation3, Scale3, Transform3, Vector3};
@@ -76,6 +77,15 @@ impl ApplyTransform for AbstractFeature {
     }
 }
 
+impl SnapToGrid for AbstractFeature {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self.bounded_by.as_mut() {
+            Some(bounding_shape) => bounding_shape.snap_to_grid(precision),
+            None => Ok(()),
+        }
+    }
+}
+
 /// Object-safe read accessor for [`AbstractFeature`] fields.
 pub trait AsAbstractFeature: AsAbstractGml {
     /// Returns a reference to the embedded [`AbstractFeature`] base data.
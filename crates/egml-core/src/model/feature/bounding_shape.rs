@@ -1,5 +1,6 @@
+use crate::Error;
 use crate::model::basic_types::NilReason;
-use crate::model::common::ApplyTransform;
+use crate::model::common::{ApplyTransform, CoordinatePrecision, SnapToGrid};
 use crate::model::geometry::Envelope;
 use nalgebra::{Isometry3, Rotation3, Scale3, Transform3, Vector3};
 
@@ -71,3 +72,12 @@ impl ApplyTransform for BoundingShape {
         }
     }
 }
+
+impl SnapToGrid for BoundingShape {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self.envelope.as_mut() {
+            Some(envelope) => envelope.snap_to_grid(precision),
+            None => Ok(()),
+        }
+    }
+}
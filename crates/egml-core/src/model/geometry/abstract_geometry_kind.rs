@@ -1,6 +1,6 @@
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, GeometryType, HasGeometryType, IterGeometries, Triangulate,
-    Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, GeometryType, HasGeometryType,
+    IterGeometries, PropertyVisitorMut, SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::aggregates::AbstractGeometricAggregateKind;
 use crate::model::geometry::primitives::AbstractGeometricPrimitiveKind;
@@ -114,6 +114,19 @@ impl IterGeometries for AbstractGeometryKind {
     }
 }
 
+impl VisitPropertiesMut for AbstractGeometryKind {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            AbstractGeometryKind::AbstractGeometricAggregateKind(x) => {
+                x.visit_properties_mut(visitor)
+            }
+            AbstractGeometryKind::AbstractGeometricPrimitiveKind(x) => {
+                x.visit_properties_mut(visitor)
+            }
+        }
+    }
+}
+
 impl ApplyTransform for AbstractGeometryKind {
     fn apply_transform(&mut self, transform: Transform3<f64>) {
         match self {
@@ -161,6 +174,15 @@ impl ApplyTransform for AbstractGeometryKind {
     }
 }
 
+impl SnapToGrid for AbstractGeometryKind {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self {
+            AbstractGeometryKind::AbstractGeometricAggregateKind(x) => x.snap_to_grid(precision),
+            AbstractGeometryKind::AbstractGeometricPrimitiveKind(x) => x.snap_to_grid(precision),
+        }
+    }
+}
+
 impl ComputeEnvelope for AbstractGeometryKind {
     fn compute_envelope(&self) -> Option<Envelope> {
         match self {
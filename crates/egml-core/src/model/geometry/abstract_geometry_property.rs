@@ -2,6 +2,7 @@ use crate::model::base::{
     AssociationAttributes, HasAssociationAttributes, HasAssociationAttributesMut,
     HasOwnershipAttributes, HasOwnershipAttributesMut, OwnershipAttributes,
 };
+use crate::model::common::{PropertyVisitorMut, VisitPropertiesMut};
 use crate::model::geometry::abstract_geometry_kind::AbstractGeometryKind;
 use crate::model::xlink::HRef;
 
@@ -89,3 +90,11 @@ impl HasOwnershipAttributesMut for AbstractGeometryProperty {
         &mut self.ownership
     }
 }
+
+impl VisitPropertiesMut for AbstractGeometryProperty {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(object) = &mut self.object {
+            object.visit_properties_mut(visitor);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, GeometryType, HasGeometryType, IterGeometries, Triangulate,
-    Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, GeometryType, HasGeometryType,
+    IterGeometries, PropertyVisitorMut, SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::Envelope;
 use crate::model::geometry::aggregates::{
@@ -146,6 +146,17 @@ impl IterGeometries for AbstractGeometricAggregateKind {
     }
 }
 
+impl VisitPropertiesMut for AbstractGeometricAggregateKind {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            AbstractGeometricAggregateKind::MultiCurve(x) => x.visit_properties_mut(visitor),
+            AbstractGeometricAggregateKind::MultiGeometry(x) => x.visit_properties_mut(visitor),
+            AbstractGeometricAggregateKind::MultiPoint(x) => x.visit_properties_mut(visitor),
+            AbstractGeometricAggregateKind::MultiSurface(x) => x.visit_properties_mut(visitor),
+        }
+    }
+}
+
 impl ApplyTransform for AbstractGeometricAggregateKind {
     fn apply_transform(&mut self, transform: Transform3<f64>) {
         match self {
@@ -198,6 +209,17 @@ impl ApplyTransform for AbstractGeometricAggregateKind {
     }
 }
 
+impl SnapToGrid for AbstractGeometricAggregateKind {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self {
+            AbstractGeometricAggregateKind::MultiCurve(x) => x.snap_to_grid(precision),
+            AbstractGeometricAggregateKind::MultiGeometry(x) => x.snap_to_grid(precision),
+            AbstractGeometricAggregateKind::MultiPoint(x) => x.snap_to_grid(precision),
+            AbstractGeometricAggregateKind::MultiSurface(x) => x.snap_to_grid(precision),
+        }
+    }
+}
+
 impl ComputeEnvelope for AbstractGeometricAggregateKind {
     fn compute_envelope(&self) -> Option<Envelope> {
         match self {
@@ -1,5 +1,8 @@
 use crate::model::base::HasAssociationAttributes;
-use crate::model::common::{ApplyTransform, ComputeEnvelope, IterGeometries};
+use crate::model::common::{
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, VisitPropertiesMut,
+};
 use crate::model::geometry::Envelope;
 use crate::model::geometry::aggregates::{
     AbstractGeometricAggregate, AsAbstractGeometricAggregate, AsAbstractGeometricAggregateMut,
@@ -157,6 +160,15 @@ impl ApplyTransform for MultiCurve {
     }
 }
 
+impl SnapToGrid for MultiCurve {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        self.curve_member
+            .iter_mut()
+            .flat_map(|x| x.object_mut())
+            .try_for_each(|x| x.snap_to_grid(precision))
+    }
+}
+
 impl ComputeEnvelope for MultiCurve {
     /// Returns the union of the bounding boxes of all curve members.
     fn compute_envelope(&self) -> Option<Envelope> {
@@ -200,6 +212,14 @@ impl IterGeometries for MultiCurve {
     }
 }
 
+impl VisitPropertiesMut for MultiCurve {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        for member in &mut self.curve_member {
+            visitor.visit_curve_property(member);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,6 @@
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, IterGeometries, Triangulate, Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::aggregates::{
     AbstractGeometricAggregate, AsAbstractGeometricAggregate, AsAbstractGeometricAggregateMut,
@@ -188,6 +189,21 @@ impl ApplyTransform for MultiGeometry {
     }
 }
 
+impl SnapToGrid for MultiGeometry {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        if let Some(members) = &mut self.geometry_members {
+            members
+                .objects_mut()
+                .iter_mut()
+                .try_for_each(|x| x.snap_to_grid(precision))?;
+        }
+        self.geometry_member
+            .iter_mut()
+            .flat_map(|x| x.object_mut())
+            .try_for_each(|x| x.snap_to_grid(precision))
+    }
+}
+
 impl ComputeEnvelope for MultiGeometry {
     fn compute_envelope(&self) -> Option<Envelope> {
         let from_member: Vec<Envelope> = self
@@ -245,6 +261,19 @@ impl IterGeometries for MultiGeometry {
     }
 }
 
+impl VisitPropertiesMut for MultiGeometry {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(members) = &mut self.geometry_members {
+            for object in members.objects_mut() {
+                object.visit_properties_mut(visitor);
+            }
+        }
+        for member in &mut self.geometry_member {
+            visitor.visit_geometry_property(member);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,6 +2,7 @@ use crate::model::base::{
     AssociationAttributes, HasAssociationAttributes, HasAssociationAttributesMut,
     HasOwnershipAttributes, HasOwnershipAttributesMut, OwnershipAttributes,
 };
+use crate::model::common::{PropertyVisitorMut, VisitPropertiesMut};
 use crate::model::geometry::aggregates::multi_geometry::MultiGeometry;
 use crate::model::xlink::HRef;
 
@@ -89,3 +90,11 @@ impl HasOwnershipAttributesMut for MultiGeometryProperty {
         &mut self.ownership
     }
 }
+
+impl VisitPropertiesMut for MultiGeometryProperty {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(object) = &mut self.object {
+            object.visit_properties_mut(visitor);
+        }
+    }
+}
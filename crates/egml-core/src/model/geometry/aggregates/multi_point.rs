@@ -1,4 +1,7 @@
-use crate::model::common::{ApplyTransform, ComputeEnvelope, IterGeometries};
+use crate::model::common::{
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, VisitPropertiesMut,
+};
 use crate::model::geometry::Envelope;
 use crate::model::geometry::aggregates::{
     AbstractGeometricAggregate, AsAbstractGeometricAggregate, AsAbstractGeometricAggregateMut,
@@ -145,6 +148,21 @@ impl ApplyTransform for MultiPoint {
     }
 }
 
+impl SnapToGrid for MultiPoint {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        if let Some(members) = &mut self.point_members {
+            members
+                .objects_mut()
+                .iter_mut()
+                .try_for_each(|x| x.snap_to_grid(precision))?;
+        }
+        self.point_member
+            .iter_mut()
+            .flat_map(|x| x.object_mut())
+            .try_for_each(|x| x.snap_to_grid(precision))
+    }
+}
+
 impl ComputeEnvelope for MultiPoint {
     fn compute_envelope(&self) -> Option<Envelope> {
         let points: Vec<_> = self
@@ -193,3 +211,11 @@ impl IterGeometries for MultiPoint {
         )
     }
 }
+
+impl VisitPropertiesMut for MultiPoint {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        for member in &mut self.point_member {
+            visitor.visit_point_property(member);
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use crate::error::Error;
 use crate::model::base::HasAssociationAttributes;
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, IterGeometries, Triangulate, Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::aggregates::{
     AbstractGeometricAggregate, AsAbstractGeometricAggregate, AsAbstractGeometricAggregateMut,
@@ -218,6 +219,15 @@ impl ApplyTransform for MultiSurface {
     }
 }
 
+impl SnapToGrid for MultiSurface {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        self.surface_member
+            .iter_mut()
+            .flat_map(|x| x.object_mut())
+            .try_for_each(|x| x.snap_to_grid(precision))
+    }
+}
+
 impl ComputeEnvelope for MultiSurface {
     /// Returns the union of the bounding boxes of all surface members.
     fn compute_envelope(&self) -> Option<Envelope> {
@@ -245,6 +255,14 @@ impl IterGeometries for MultiSurface {
     }
 }
 
+impl VisitPropertiesMut for MultiSurface {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        for member in &mut self.surface_member {
+            visitor.visit_surface_property(member);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,7 @@
 use crate::model::base::HasAssociationAttributes;
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, IterGeometries, Triangulate, Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::aggregates::AggregationType;
 use crate::model::geometry::primitives::{
@@ -195,6 +196,15 @@ impl ApplyTransform for CompositeSurface {
     }
 }
 
+impl SnapToGrid for CompositeSurface {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        self.surface_member
+            .iter_mut()
+            .flat_map(|x| x.object_mut())
+            .try_for_each(|x| x.snap_to_grid(precision))
+    }
+}
+
 impl ComputeEnvelope for CompositeSurface {
     /// Returns the union of the bounding boxes of all surface members.
     fn compute_envelope(&self) -> Option<Envelope> {
@@ -222,6 +232,14 @@ impl IterGeometries for CompositeSurface {
     }
 }
 
+impl VisitPropertiesMut for CompositeSurface {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        for member in &mut self.surface_member {
+            visitor.visit_surface_property(member);
+        }
+    }
+}
+
 impl Triangulate for CompositeSurface {
     /// Members that fail to triangulate individually (e.g. a degenerate ring) are
     /// skipped rather than failing the whole aggregate; see their errors via
@@ -1,6 +1,8 @@
 use crate::error::Error;
 use crate::model::AbstractObject;
-use crate::model::common::{ApplyTransform, Triangulate, Triangulation};
+use crate::model::common::{
+    ApplyTransform, CoordinatePrecision, SnapToGrid, Triangulate, Triangulation,
+};
 use crate::model::geometry::DirectPosition;
 use crate::model::geometry::primitives::{AbstractRingKind, Shell};
 use crate::model::geometry::primitives::{
@@ -657,6 +659,14 @@ impl ApplyTransform for Envelope {
     }
 }
 
+impl SnapToGrid for Envelope {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        self.lower_corner = precision.round(self.lower_corner);
+        self.upper_corner = precision.round(self.upper_corner);
+        Ok(())
+    }
+}
+
 impl fmt::Display for Envelope {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -726,7 +736,7 @@ mod tests {
     #[test]
     fn from_envelopes_single_returns_same_envelope() {
         let e = env(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
-        let result = Envelope::from_envelopes(&[e.clone()]).unwrap();
+        let result = Envelope::from_envelopes(std::slice::from_ref(&e)).unwrap();
 
         assert_eq!(result, e);
     }
@@ -1,5 +1,7 @@
+use crate::Error;
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, GeometryType, HasGeometryType, IterGeometries,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, GeometryType, HasGeometryType,
+    IterGeometries, PropertyVisitorMut, SnapToGrid, VisitPropertiesMut,
 };
 use crate::model::geometry::Envelope;
 use crate::model::geometry::primitives::{
@@ -109,6 +111,15 @@ impl IterGeometries for AbstractCurveKind {
     }
 }
 
+impl VisitPropertiesMut for AbstractCurveKind {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            AbstractCurveKind::LineString(x) => x.visit_properties_mut(visitor),
+            AbstractCurveKind::AbstractRingKind(x) => x.visit_properties_mut(visitor),
+        }
+    }
+}
+
 impl ApplyTransform for AbstractCurveKind {
     fn apply_transform(&mut self, transform: Transform3<f64>) {
         match self {
@@ -146,6 +157,15 @@ impl ApplyTransform for AbstractCurveKind {
     }
 }
 
+impl SnapToGrid for AbstractCurveKind {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self {
+            AbstractCurveKind::LineString(x) => x.snap_to_grid(precision),
+            AbstractCurveKind::AbstractRingKind(x) => x.snap_to_grid(precision),
+        }
+    }
+}
+
 impl ComputeEnvelope for AbstractCurveKind {
     fn compute_envelope(&self) -> Option<Envelope> {
         match self {
@@ -1,6 +1,6 @@
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, GeometryType, HasGeometryType, IterGeometries, Triangulate,
-    Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, GeometryType, HasGeometryType,
+    IterGeometries, PropertyVisitorMut, SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::Envelope;
 use crate::model::geometry::primitives::{
@@ -151,6 +151,19 @@ impl IterGeometries for AbstractGeometricPrimitiveKind {
     }
 }
 
+impl VisitPropertiesMut for AbstractGeometricPrimitiveKind {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            AbstractGeometricPrimitiveKind::Point(x) => x.visit_properties_mut(visitor),
+            AbstractGeometricPrimitiveKind::AbstractCurveKind(x) => x.visit_properties_mut(visitor),
+            AbstractGeometricPrimitiveKind::AbstractSurfaceKind(x) => {
+                x.visit_properties_mut(visitor)
+            }
+            AbstractGeometricPrimitiveKind::AbstractSolidKind(x) => x.visit_properties_mut(visitor),
+        }
+    }
+}
+
 impl ApplyTransform for AbstractGeometricPrimitiveKind {
     fn apply_transform(&mut self, transform: Transform3<f64>) {
         match self {
@@ -198,6 +211,17 @@ impl ApplyTransform for AbstractGeometricPrimitiveKind {
     }
 }
 
+impl SnapToGrid for AbstractGeometricPrimitiveKind {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self {
+            AbstractGeometricPrimitiveKind::Point(x) => x.snap_to_grid(precision),
+            AbstractGeometricPrimitiveKind::AbstractCurveKind(x) => x.snap_to_grid(precision),
+            AbstractGeometricPrimitiveKind::AbstractSurfaceKind(x) => x.snap_to_grid(precision),
+            AbstractGeometricPrimitiveKind::AbstractSolidKind(x) => x.snap_to_grid(precision),
+        }
+    }
+}
+
 impl ComputeEnvelope for AbstractGeometricPrimitiveKind {
     fn compute_envelope(&self) -> Option<Envelope> {
         match self {
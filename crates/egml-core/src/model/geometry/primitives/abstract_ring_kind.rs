@@ -1,5 +1,7 @@
+use crate::Error;
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, GeometryType, HasGeometryType, IterGeometries,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, GeometryType, HasGeometryType,
+    IterGeometries, PropertyVisitorMut, SnapToGrid, VisitPropertiesMut,
 };
 use crate::model::geometry::primitives::{
     AbstractRing, AsAbstractRing, AsAbstractRingMut, LinearRing,
@@ -121,6 +123,15 @@ impl IterGeometries for AbstractRingKind {
     }
 }
 
+impl VisitPropertiesMut for AbstractRingKind {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            AbstractRingKind::LinearRing(x) => x.visit_properties_mut(visitor),
+            AbstractRingKind::AbstractRingKind(x) => x.visit_properties_mut(visitor),
+        }
+    }
+}
+
 impl ApplyTransform for AbstractRingKind {
     fn apply_transform(&mut self, transform: Transform3<f64>) {
         match self {
@@ -158,6 +169,15 @@ impl ApplyTransform for AbstractRingKind {
     }
 }
 
+impl SnapToGrid for AbstractRingKind {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self {
+            AbstractRingKind::LinearRing(x) => x.snap_to_grid(precision),
+            AbstractRingKind::AbstractRingKind(x) => x.snap_to_grid(precision),
+        }
+    }
+}
+
 impl ComputeEnvelope for AbstractRingKind {
     fn compute_envelope(&self) -> Option<Envelope> {
         match self {
@@ -1,6 +1,6 @@
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, GeometryType, HasGeometryType, IterGeometries, Triangulate,
-    Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, GeometryType, HasGeometryType,
+    IterGeometries, PropertyVisitorMut, SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::primitives::{
     AbstractSolid, AsAbstractSolid, AsAbstractSolidMut, Solid,
@@ -109,6 +109,14 @@ impl IterGeometries for AbstractSolidKind {
     }
 }
 
+impl VisitPropertiesMut for AbstractSolidKind {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            Self::Solid(x) => x.visit_properties_mut(visitor),
+        }
+    }
+}
+
 impl ApplyTransform for AbstractSolidKind {
     fn apply_transform(&mut self, transform: Transform3<f64>) {
         match self {
@@ -141,6 +149,14 @@ impl ApplyTransform for AbstractSolidKind {
     }
 }
 
+impl SnapToGrid for AbstractSolidKind {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self {
+            Self::Solid(x) => x.snap_to_grid(precision),
+        }
+    }
+}
+
 impl ComputeEnvelope for AbstractSolidKind {
     fn compute_envelope(&self) -> Option<Envelope> {
         match self {
@@ -1,6 +1,6 @@
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, GeometryType, HasGeometryType, IterGeometries, Triangulate,
-    Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, GeometryType, HasGeometryType,
+    IterGeometries, PropertyVisitorMut, SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::complexes::CompositeSurface;
 use crate::model::geometry::primitives::surface_kind::SurfaceKind;
@@ -146,6 +146,18 @@ impl IterGeometries for AbstractSurfaceKind {
     }
 }
 
+impl VisitPropertiesMut for AbstractSurfaceKind {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            AbstractSurfaceKind::CompositeSurface(x) => x.visit_properties_mut(visitor),
+            AbstractSurfaceKind::Polygon(x) => x.visit_properties_mut(visitor),
+            AbstractSurfaceKind::Shell(x) => x.visit_properties_mut(visitor),
+            AbstractSurfaceKind::Surface(x) => x.visit_properties_mut(visitor),
+            AbstractSurfaceKind::SurfaceKind(x) => x.visit_properties_mut(visitor),
+        }
+    }
+}
+
 impl ApplyTransform for AbstractSurfaceKind {
     fn apply_transform(&mut self, transform: Transform3<f64>) {
         match self {
@@ -198,6 +210,18 @@ impl ApplyTransform for AbstractSurfaceKind {
     }
 }
 
+impl SnapToGrid for AbstractSurfaceKind {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self {
+            AbstractSurfaceKind::CompositeSurface(x) => x.snap_to_grid(precision),
+            AbstractSurfaceKind::Polygon(x) => x.snap_to_grid(precision),
+            AbstractSurfaceKind::Shell(x) => x.snap_to_grid(precision),
+            AbstractSurfaceKind::Surface(x) => x.snap_to_grid(precision),
+            AbstractSurfaceKind::SurfaceKind(x) => x.snap_to_grid(precision),
+        }
+    }
+}
+
 impl ComputeEnvelope for AbstractSurfaceKind {
     fn compute_envelope(&self) -> Option<Envelope> {
         match self {
@@ -3,7 +3,7 @@ use crate::model::base::{
     AssociationAttributes, HasAssociationAttributes, HasAssociationAttributesMut,
     HasOwnershipAttributes, HasOwnershipAttributesMut, OwnershipAttributes,
 };
-use crate::model::common::{ApplyTransform, ComputeEnvelope};
+use crate::model::common::{ApplyTransform, ComputeEnvelope, CoordinatePrecision, SnapToGrid};
 use crate::model::geometry::Envelope;
 use crate::model::geometry::primitives::abstract_surface_patch_kind::AbstractSurfacePatchKind;
 use crate::model::xlink::HRef;
@@ -140,6 +140,14 @@ impl ApplyTransform for AbstractSurfacePatchArrayProperty {
     }
 }
 
+impl SnapToGrid for AbstractSurfacePatchArrayProperty {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        self.objects
+            .iter_mut()
+            .try_for_each(|x| x.snap_to_grid(precision))
+    }
+}
+
 impl ComputeEnvelope for AbstractSurfacePatchArrayProperty {
     fn compute_envelope(&self) -> Option<Envelope> {
         let envelopes: Vec<Envelope> = self
@@ -1,5 +1,7 @@
 use crate::Error;
-use crate::model::common::{ApplyTransform, ComputeEnvelope, Triangulate, Triangulation};
+use crate::model::common::{
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, SnapToGrid, Triangulate, Triangulation,
+};
 use crate::model::geometry::Envelope;
 use crate::model::geometry::primitives::{
     AbstractSurfacePatch, AsAbstractSurfacePatch, AsAbstractSurfacePatchMut, PolygonPatch, Triangle,
@@ -122,6 +124,15 @@ impl ApplyTransform for AbstractSurfacePatchKind {
     }
 }
 
+impl SnapToGrid for AbstractSurfacePatchKind {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self {
+            AbstractSurfacePatchKind::PolygonPatch(x) => x.snap_to_grid(precision),
+            AbstractSurfacePatchKind::Triangle(x) => x.snap_to_grid(precision),
+        }
+    }
+}
+
 impl ComputeEnvelope for AbstractSurfacePatchKind {
     fn compute_envelope(&self) -> Option<Envelope> {
         match self {
@@ -2,6 +2,7 @@ use crate::model::base::{
     AssociationAttributes, HasAssociationAttributes, HasAssociationAttributesMut,
     HasOwnershipAttributes, HasOwnershipAttributesMut, OwnershipAttributes,
 };
+use crate::model::common::{PropertyVisitorMut, VisitPropertiesMut};
 use crate::model::geometry::primitives::abstract_surface_kind::AbstractSurfaceKind;
 use crate::model::xlink::HRef;
 
@@ -92,3 +93,11 @@ impl HasOwnershipAttributesMut for AbstractSurfaceProperty {
         &mut self.ownership
     }
 }
+
+impl VisitPropertiesMut for AbstractSurfaceProperty {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(object) = &mut self.object {
+            object.visit_properties_mut(visitor);
+        }
+    }
+}
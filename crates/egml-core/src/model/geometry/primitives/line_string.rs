@@ -1,4 +1,7 @@
-use crate::model::common::{ApplyTransform, ComputeEnvelope, IterGeometries};
+use crate::model::common::{
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, VisitPropertiesMut, snap_points,
+};
 use crate::model::geometry::primitives::{AbstractCurve, AsAbstractCurve, AsAbstractCurveMut};
 use crate::model::geometry::refs::AbstractGeometryKindRef;
 use crate::model::geometry::{DirectPosition, Envelope};
@@ -150,6 +153,15 @@ impl ApplyTransform for LineString {
     }
 }
 
+impl SnapToGrid for LineString {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        let points = snap_points(&self.points, precision);
+        Self::validate(&points)?;
+        self.points = points;
+        Ok(())
+    }
+}
+
 impl ComputeEnvelope for LineString {
     fn compute_envelope(&self) -> Option<Envelope> {
         Some(Envelope::from_points(&self.points).expect("line string must have valid points"))
@@ -162,6 +174,10 @@ impl IterGeometries for LineString {
     }
 }
 
+impl VisitPropertiesMut for LineString {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, _visitor: &mut V) {}
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,9 @@
 use crate::error::Error;
 use crate::model::base::{AsAbstractGml, Id};
-use crate::model::common::{ApplyTransform, ComputeEnvelope, IterGeometries};
+use crate::model::common::{
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, VisitPropertiesMut, snap_points,
+};
 use crate::model::geometry::primitives::{AbstractRing, AsAbstractRing, AsAbstractRingMut};
 use crate::model::geometry::refs::AbstractGeometryKindRef;
 use crate::model::geometry::{DirectPosition, Envelope};
@@ -181,6 +184,18 @@ impl ApplyTransform for LinearRing {
     }
 }
 
+impl SnapToGrid for LinearRing {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        let mut points = snap_points(&self.points, precision);
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        Self::validate_points(&points, self.id())?;
+        self.points = points;
+        Ok(())
+    }
+}
+
 impl ComputeEnvelope for LinearRing {
     /// Returns the axis-aligned bounding box of all positions in this ring.
     fn compute_envelope(&self) -> Option<Envelope> {
@@ -194,6 +209,10 @@ impl IterGeometries for LinearRing {
     }
 }
 
+impl VisitPropertiesMut for LinearRing {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, _visitor: &mut V) {}
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -240,12 +259,24 @@ mod test {
     #[test]
     fn linear_ring_construction_test() {
         let points = vec![
-            DirectPosition::new(601.92791444745251, 1130.4631113024607, 9.0130903915382347)
-                .unwrap(),
-            DirectPosition::new(601.92791832847342, 1130.4631032795705, 9.0130907233102739)
-                .unwrap(),
-            DirectPosition::new(601.92791832847342, 1130.4631032795705, 9.0130907233102739)
-                .unwrap(),
+            DirectPosition::new(
+                601.927_914_447_452_5,
+                1130.4631113024607,
+                9.013_090_391_538_235,
+            )
+            .unwrap(),
+            DirectPosition::new(
+                601.927_918_328_473_4,
+                1130.4631032795705,
+                9.013_090_723_310_274,
+            )
+            .unwrap(),
+            DirectPosition::new(
+                601.927_918_328_473_4,
+                1130.4631032795705,
+                9.013_090_723_310_274,
+            )
+            .unwrap(),
         ];
         let result = LinearRing::new(points);
 
@@ -277,4 +308,34 @@ mod test {
 
         assert_eq!(linear_ring, expected_linear_ring);
     }
+
+    #[test]
+    fn snap_to_grid_drops_merged_vertices_and_rejects_collapse() {
+        let mut ring = LinearRing::new([
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0004, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 1.0, 0.0).unwrap(),
+            DirectPosition::new(0.0001, 0.0, 0.0).unwrap(),
+        ])
+        .unwrap();
+        let mut sliver = LinearRing::new([
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 0.0001, 0.0).unwrap(),
+        ])
+        .unwrap();
+        let precision = CoordinatePrecision::uniform(3);
+
+        ring.snap_to_grid(precision).unwrap();
+        let result = sliver.snap_to_grid(precision);
+
+        assert_eq!(ring.points().len(), 3);
+        assert_eq!(
+            ring.points()[1],
+            DirectPosition::new(1.0, 0.0, 0.0).unwrap()
+        );
+        assert!(matches!(result, Err(Error::TooFewElements { .. })));
+        assert_eq!(sliver.points()[2].y(), 0.0001);
+    }
 }
@@ -1,4 +1,8 @@
-use crate::model::common::{ApplyTransform, ComputeEnvelope, IterGeometries};
+use crate::Error;
+use crate::model::common::{
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, VisitPropertiesMut,
+};
 use crate::model::geometry::primitives::{
     AbstractGeometricPrimitive, AsAbstractGeometricPrimitive, AsAbstractGeometricPrimitiveMut,
 };
@@ -97,6 +101,13 @@ impl ApplyTransform for Point {
     }
 }
 
+impl SnapToGrid for Point {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        self.pos = precision.round(self.pos);
+        Ok(())
+    }
+}
+
 impl ComputeEnvelope for Point {
     /// Returns a degenerate (zero-volume) envelope at the point's position.
     fn compute_envelope(&self) -> Option<Envelope> {
@@ -109,3 +120,7 @@ impl IterGeometries for Point {
         Box::new(std::iter::once(self.into()))
     }
 }
+
+impl VisitPropertiesMut for Point {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, _visitor: &mut V) {}
+}
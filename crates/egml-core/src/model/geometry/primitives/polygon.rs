@@ -1,6 +1,7 @@
 use crate::model::base::HasAssociationAttributes;
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, IterGeometries, Triangulate, Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::primitives::{
     AbstractRingProperty, AbstractSurface, AsAbstractSurface, AsAbstractSurfaceMut,
@@ -252,6 +253,20 @@ impl ApplyTransform for Polygon {
     }
 }
 
+impl SnapToGrid for Polygon {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        if let Some(exterior) = &mut self.exterior
+            && let Some(object) = exterior.object_mut()
+        {
+            object.snap_to_grid(precision)?;
+        }
+        self.interior
+            .iter_mut()
+            .flat_map(|x| x.object_mut())
+            .try_for_each(|x| x.snap_to_grid(precision))
+    }
+}
+
 impl ComputeEnvelope for Polygon {
     fn compute_envelope(&self) -> Option<Envelope> {
         if let Some(exterior) = &self.exterior
@@ -300,6 +315,17 @@ impl IterGeometries for Polygon {
     }
 }
 
+impl VisitPropertiesMut for Polygon {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(exterior) = &mut self.exterior {
+            visitor.visit_ring_property(exterior);
+        }
+        for interior in &mut self.interior {
+            visitor.visit_ring_property(interior);
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -478,7 +504,7 @@ mod test {
             vec![linear_ring_interior.clone(), linear_ring_interior.clone()],
         )
         .expect("should work");
-        let triangulation = polygon.triangulate().expect("should work");
+        let _triangulation = polygon.triangulate().expect("should work");
         // assert_eq!(triangulation.surface().patches_len(), 2);
     }
 }
@@ -1,6 +1,8 @@
 use crate::Error;
 use crate::model::base::HasAssociationAttributes;
-use crate::model::common::{ApplyTransform, ComputeEnvelope, Triangulate, Triangulation};
+use crate::model::common::{
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, SnapToGrid, Triangulate, Triangulation,
+};
 use crate::model::geometry::Envelope;
 use crate::model::geometry::primitives::surface_interpolation::SurfaceInterpolation;
 use crate::model::geometry::primitives::{
@@ -199,6 +201,20 @@ impl ApplyTransform for PolygonPatch {
     }
 }
 
+impl SnapToGrid for PolygonPatch {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        if let Some(exterior) = &mut self.exterior
+            && let Some(object) = exterior.object_mut()
+        {
+            object.snap_to_grid(precision)?;
+        }
+        self.interior
+            .iter_mut()
+            .flat_map(|x| x.object_mut())
+            .try_for_each(|x| x.snap_to_grid(precision))
+    }
+}
+
 impl ComputeEnvelope for PolygonPatch {
     fn compute_envelope(&self) -> Option<Envelope> {
         if let Some(exterior) = &self.exterior
@@ -1,5 +1,6 @@
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, IterGeometries, Triangulate, Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::primitives::{
     AbstractSurface, AbstractSurfaceProperty, AsAbstractSurface, AsAbstractSurfaceMut,
@@ -112,6 +113,14 @@ impl IterGeometries for Shell {
     }
 }
 
+impl VisitPropertiesMut for Shell {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        for member in &mut self.members {
+            visitor.visit_surface_property(member);
+        }
+    }
+}
+
 impl Shell {
     pub fn points(&self) -> Vec<&DirectPosition> {
         self.members
@@ -196,6 +205,15 @@ impl ApplyTransform for Shell {
     }
 }
 
+impl SnapToGrid for Shell {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        self.members
+            .iter_mut()
+            .flat_map(|x| x.object_mut())
+            .try_for_each(|x| x.snap_to_grid(precision))
+    }
+}
+
 impl ComputeEnvelope for Shell {
     /// Returns the union of the bounding boxes of all surface members.
     fn compute_envelope(&self) -> Option<Envelope> {
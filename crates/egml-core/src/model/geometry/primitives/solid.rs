@@ -1,7 +1,8 @@
 use crate::error::Error;
 use crate::model::base::HasAssociationAttributes;
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, IterGeometries, Triangulate, Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::primitives::shell_property::ShellProperty;
 use crate::model::geometry::primitives::{
@@ -172,6 +173,20 @@ impl ApplyTransform for Solid {
     }
 }
 
+impl SnapToGrid for Solid {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        if let Some(exterior) = self.exterior.as_mut()
+            && let Some(object) = exterior.object_mut()
+        {
+            object.snap_to_grid(precision)?;
+        }
+        self.interior
+            .iter_mut()
+            .flat_map(|x| x.object_mut())
+            .try_for_each(|x| x.snap_to_grid(precision))
+    }
+}
+
 impl ComputeEnvelope for Solid {
     /// Returns the union of the bounding boxes of all surface members.
     fn compute_envelope(&self) -> Option<Envelope> {
@@ -222,6 +237,17 @@ impl IterGeometries for Solid {
     }
 }
 
+impl VisitPropertiesMut for Solid {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(exterior) = &mut self.exterior {
+            visitor.visit_shell_property(exterior);
+        }
+        for interior in &mut self.interior {
+            visitor.visit_shell_property(interior);
+        }
+    }
+}
+
 impl Triangulate for Solid {
     /// Triangulates the exterior shell and all interior shells into a single
     /// [`TriangulatedSurface`] covering the full boundary of this solid.
@@ -1,7 +1,8 @@
 use crate::Error;
 use crate::impl_has_geometry_type;
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, IterGeometries, Triangulate, Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::primitives::{
     AbstractSurface, AbstractSurfacePatchArrayProperty, AsAbstractSurface, AsAbstractSurfaceMut,
@@ -136,6 +137,10 @@ impl IterGeometries for Surface {
     }
 }
 
+impl VisitPropertiesMut for Surface {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, _visitor: &mut V) {}
+}
+
 impl ApplyTransform for Surface {
     fn apply_transform(&mut self, transform: Transform3<f64>) {
         self.patches.apply_transform(transform)
@@ -158,6 +163,12 @@ impl ApplyTransform for Surface {
     }
 }
 
+impl SnapToGrid for Surface {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        self.patches.snap_to_grid(precision)
+    }
+}
+
 impl ComputeEnvelope for Surface {
     /// Returns the union of the bounding boxes of all patches.
     fn compute_envelope(&self) -> Option<Envelope> {
@@ -1,6 +1,6 @@
 use crate::model::common::{
-    ApplyTransform, ComputeEnvelope, GeometryType, HasGeometryType, IterGeometries, Triangulate,
-    Triangulation,
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, GeometryType, HasGeometryType,
+    IterGeometries, PropertyVisitorMut, SnapToGrid, Triangulate, Triangulation, VisitPropertiesMut,
 };
 use crate::model::geometry::primitives::{AsSurface, AsSurfaceMut, Surface, TriangulatedSurface};
 use crate::model::geometry::refs::AbstractGeometryKindRef;
@@ -106,6 +106,14 @@ impl IterGeometries for SurfaceKind {
     }
 }
 
+impl VisitPropertiesMut for SurfaceKind {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            SurfaceKind::TriangulatedSurface(x) => x.visit_properties_mut(visitor),
+        }
+    }
+}
+
 impl ApplyTransform for SurfaceKind {
     fn apply_transform(&mut self, transform: Transform3<f64>) {
         match self {
@@ -138,6 +146,14 @@ impl ApplyTransform for SurfaceKind {
     }
 }
 
+impl SnapToGrid for SurfaceKind {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self {
+            SurfaceKind::TriangulatedSurface(x) => x.snap_to_grid(precision),
+        }
+    }
+}
+
 impl ComputeEnvelope for SurfaceKind {
     fn compute_envelope(&self) -> Option<Envelope> {
         match self {
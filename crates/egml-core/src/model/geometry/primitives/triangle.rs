@@ -1,5 +1,7 @@
 use crate::Error;
-use crate::model::common::{ApplyTransform, ComputeEnvelope, Triangulate, Triangulation};
+use crate::model::common::{
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, SnapToGrid, Triangulate, Triangulation,
+};
 use crate::model::geometry::primitives::{
     AbstractRingProperty, AbstractSurfacePatch, AsAbstractSurfacePatch, AsAbstractSurfacePatchMut,
     LinearRing, TriangulatedSurface,
@@ -24,13 +26,6 @@ impl Triangle {
         })
     }
 
-    pub(crate) fn new_unchecked(exterior: AbstractRingProperty) -> Self {
-        Self {
-            abstract_surface_patch: AbstractSurfacePatch::default(),
-            exterior,
-        }
-    }
-
     pub fn from_abstract_surface_patch(
         abstract_surface_patch: AbstractSurfacePatch,
         exterior: AbstractRingProperty,
@@ -163,6 +158,15 @@ impl ApplyTransform for Triangle {
     }
 }
 
+impl SnapToGrid for Triangle {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        match self.exterior.object_mut() {
+            Some(object) => object.snap_to_grid(precision),
+            None => Ok(()),
+        }
+    }
+}
+
 impl ComputeEnvelope for Triangle {
     fn compute_envelope(&self) -> Option<Envelope> {
         self.exterior.object()?.compute_envelope()
@@ -1,5 +1,8 @@
 use crate::error::Error;
-use crate::model::common::{ApplyTransform, ComputeEnvelope, IterGeometries};
+use crate::model::common::{
+    ApplyTransform, ComputeEnvelope, CoordinatePrecision, IterGeometries, PropertyVisitorMut,
+    SnapToGrid, VisitPropertiesMut,
+};
 use crate::model::geometry::primitives::abstract_surface_patch_kind::AbstractSurfacePatchKind;
 use crate::model::geometry::primitives::{
     AbstractSurfacePatchArrayProperty, AsSurface, AsSurfaceMut, Surface, Triangle,
@@ -141,6 +144,10 @@ impl IterGeometries for TriangulatedSurface {
     }
 }
 
+impl VisitPropertiesMut for TriangulatedSurface {
+    fn visit_properties_mut<V: PropertyVisitorMut + ?Sized>(&mut self, _visitor: &mut V) {}
+}
+
 impl ApplyTransform for TriangulatedSurface {
     fn apply_transform(&mut self, transform: Transform3<f64>) {
         self.surface.apply_transform(transform);
@@ -163,6 +170,12 @@ impl ApplyTransform for TriangulatedSurface {
     }
 }
 
+impl SnapToGrid for TriangulatedSurface {
+    fn snap_to_grid(&mut self, precision: CoordinatePrecision) -> Result<(), Error> {
+        self.surface.snap_to_grid(precision)
+    }
+}
+
 impl ComputeEnvelope for TriangulatedSurface {
     /// Returns the axis-aligned bounding box of all triangles.
     fn compute_envelope(&self) -> Option<Envelope> {
@@ -0,0 +1,251 @@
+use crate::Error;
+use crate::model::geometry::AbstractGeometryKind;
+use crate::model::geometry::aggregates::{
+    AbstractGeometricAggregate, AbstractGeometricAggregateKind, MultiSurface,
+};
+use crate::model::geometry::primitives::{
+    AbstractGeometricPrimitiveKind, AbstractSolidKind, AbstractSurface, AbstractSurfaceKind,
+    AbstractSurfacePatchArrayProperty, AbstractSurfacePatchKind, AbstractSurfaceProperty,
+    AsAbstractSolid, AsAbstractSurface, Polygon, PolygonPatch, Surface, SurfaceKind,
+};
+
+/// Rewrites `geometry` so that it uses simple features constructs where
+/// an equivalent one exists.
+///
+/// - `CompositeSurface`, `Shell` and `TriangulatedSurface` become a
+///   `MultiSurface` of `Polygon`s; nested composites are flattened.
+/// - A `Solid` without interior shells becomes a `MultiSurface` of the
+///   members of its exterior shell.
+/// - `Triangle` patches of a `Surface` become `PolygonPatch`es.
+///
+/// `gml:id`, `srsName` and the other attributes of a replaced geometry are
+/// carried over to its replacement, while those of flattened members are
+/// kept on the members themselves. Referenced members (`xlink:href`) are
+/// kept as they are, and geometries without a simple equivalent, such as a
+/// `Solid` with cavities, are returned unchanged.
+///
+/// # Errors
+///
+/// Returns the error of constructing a replacement, e.g. if flattening
+/// leaves a `MultiSurface` that its constructor rejects.
+pub fn downgrade_to_simple_features(
+    geometry: AbstractGeometryKind,
+) -> Result<AbstractGeometryKind, Error> {
+    match geometry {
+        AbstractGeometryKind::AbstractGeometricAggregateKind(x) => Ok(
+            AbstractGeometryKind::AbstractGeometricAggregateKind(downgrade_aggregate(x)?),
+        ),
+        AbstractGeometryKind::AbstractGeometricPrimitiveKind(x) => downgrade_primitive(x),
+    }
+}
+
+fn downgrade_aggregate(
+    aggregate: AbstractGeometricAggregateKind,
+) -> Result<AbstractGeometricAggregateKind, Error> {
+    match aggregate {
+        AbstractGeometricAggregateKind::MultiSurface(x) => {
+            let members = flatten_surface_members(x.surface_member().iter().cloned());
+            let x = MultiSurface::from_abstract_geometric_aggregate(
+                x.abstract_geometric_aggregate,
+                members,
+            )?;
+            Ok(AbstractGeometricAggregateKind::MultiSurface(x))
+        }
+        AbstractGeometricAggregateKind::MultiGeometry(mut x) => {
+            let mut members = x.geometry_member().to_vec();
+            for member in &mut members {
+                if let Some(object) = member.take_object() {
+                    member.set_object(downgrade_to_simple_features(object)?);
+                }
+            }
+            x.set_geometry_member(members);
+            if let Some(mut array) = x.geometry_members().cloned() {
+                let objects = std::mem::take(array.objects_mut());
+                array.set_objects(
+                    objects
+                        .into_iter()
+                        .map(downgrade_to_simple_features)
+                        .collect::<Result<_, _>>()?,
+                );
+                x.set_geometry_members(array);
+            }
+            Ok(AbstractGeometricAggregateKind::MultiGeometry(x))
+        }
+        x => Ok(x),
+    }
+}
+
+fn downgrade_primitive(
+    primitive: AbstractGeometricPrimitiveKind,
+) -> Result<AbstractGeometryKind, Error> {
+    let members = match &primitive {
+        AbstractGeometricPrimitiveKind::AbstractSurfaceKind(AbstractSurfaceKind::Surface(x)) => {
+            return Ok(AbstractGeometryKind::AbstractGeometricPrimitiveKind(
+                AbstractGeometricPrimitiveKind::AbstractSurfaceKind(AbstractSurfaceKind::Surface(
+                    with_polygon_patches(x.clone()),
+                )),
+            ));
+        }
+        AbstractGeometricPrimitiveKind::AbstractSurfaceKind(
+            x @ (AbstractSurfaceKind::CompositeSurface(_)
+            | AbstractSurfaceKind::Shell(_)
+            | AbstractSurfaceKind::SurfaceKind(_)),
+        ) => Some((
+            x.abstract_surface().clone(),
+            flatten_surface_members([AbstractSurfaceProperty::from_object(x.clone())]),
+        )),
+        AbstractGeometricPrimitiveKind::AbstractSolidKind(AbstractSolidKind::Solid(x))
+            if x.interior().is_empty() =>
+        {
+            x.exterior().and_then(|shell| shell.object()).map(|shell| {
+                (
+                    AbstractSurface::new(x.abstract_solid().abstract_geometric_primitive.clone()),
+                    flatten_surface_members(shell.members().iter().cloned()),
+                )
+            })
+        }
+        _ => None,
+    };
+
+    let Some((abstract_surface, members)) = members else {
+        return Ok(AbstractGeometryKind::AbstractGeometricPrimitiveKind(
+            primitive,
+        ));
+    };
+    let multi_surface = MultiSurface::from_abstract_geometric_aggregate(
+        AbstractGeometricAggregate::from_abstract_geometry(
+            abstract_surface
+                .abstract_geometric_primitive
+                .abstract_geometry,
+        ),
+        members,
+    )?;
+    Ok(AbstractGeometryKind::AbstractGeometricAggregateKind(
+        AbstractGeometricAggregateKind::MultiSurface(multi_surface),
+    ))
+}
+
+/// Replaces composites, shells and triangulated surfaces among `members`
+/// by their `Polygon`s.
+fn flatten_surface_members(
+    members: impl IntoIterator<Item = AbstractSurfaceProperty>,
+) -> Vec<AbstractSurfaceProperty> {
+    let mut flattened = Vec::new();
+    for mut member in members {
+        match member.take_object() {
+            Some(AbstractSurfaceKind::CompositeSurface(x)) => {
+                flattened.extend(flatten_surface_members(x.surface_member().iter().cloned()))
+            }
+            Some(AbstractSurfaceKind::Shell(x)) => {
+                flattened.extend(flatten_surface_members(x.members().iter().cloned()))
+            }
+            Some(AbstractSurfaceKind::SurfaceKind(SurfaceKind::TriangulatedSurface(x))) => {
+                flattened.extend(x.triangles().into_iter().map(|triangle| {
+                    AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(
+                        Polygon::from_abstract_surface(
+                            AbstractSurface::default(),
+                            Some(triangle.exterior().clone()),
+                            Vec::new(),
+                        ),
+                    ))
+                }))
+            }
+            Some(AbstractSurfaceKind::Surface(x)) => {
+                member.set_object(AbstractSurfaceKind::Surface(with_polygon_patches(x)));
+                flattened.push(member);
+            }
+            Some(x) => {
+                member.set_object(x);
+                flattened.push(member);
+            }
+            None => flattened.push(member),
+        }
+    }
+    flattened
+}
+
+fn with_polygon_patches(surface: Surface) -> Surface {
+    let mut patches: AbstractSurfacePatchArrayProperty = surface.patches().clone();
+    for patch in patches.objects_mut() {
+        if let AbstractSurfacePatchKind::Triangle(x) = patch {
+            *patch =
+                AbstractSurfacePatchKind::PolygonPatch(PolygonPatch::from_abstract_surface_patch(
+                    x.abstract_surface_patch,
+                    Some(x.exterior().clone()),
+                    Vec::new(),
+                ));
+        }
+    }
+    Surface::from_abstract_surface(surface.abstract_surface, patches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::base::{AsAbstractGml, AsAbstractGmlMut, Id};
+    use crate::model::geometry::DirectPosition;
+    use crate::model::geometry::aggregates::AggregationType;
+    use crate::model::geometry::complexes::CompositeSurface;
+    use crate::model::geometry::primitives::{Triangle, TriangulatedSurface};
+    use crate::profile::{SimpleFeaturesLevel, check_simple_features};
+
+    fn triangle(x: f64) -> Triangle {
+        Triangle::from_points(
+            DirectPosition::new(x, 0.0, 0.0).unwrap(),
+            DirectPosition::new(x + 1.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(x, 1.0, 0.0).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn composite_surface_becomes_flat_multi_surface_of_polygons() {
+        let tin = TriangulatedSurface::from_triangles(vec![triangle(0.0), triangle(2.0)]).unwrap();
+        let mut composite = CompositeSurface::new(
+            [AbstractSurfaceProperty::from_object(
+                AbstractSurfaceKind::SurfaceKind(SurfaceKind::TriangulatedSurface(tin)),
+            )],
+            AggregationType::Set,
+        )
+        .unwrap();
+        composite.set_id(Id::try_from("c1").unwrap());
+        let geometry = AbstractGeometryKind::AbstractGeometricPrimitiveKind(
+            AbstractGeometricPrimitiveKind::AbstractSurfaceKind(
+                AbstractSurfaceKind::CompositeSurface(composite),
+            ),
+        );
+
+        let downgraded = downgrade_to_simple_features(geometry).unwrap();
+
+        let AbstractGeometryKind::AbstractGeometricAggregateKind(
+            AbstractGeometricAggregateKind::MultiSurface(x),
+        ) = &downgraded
+        else {
+            panic!("should become a multi surface");
+        };
+        assert_eq!(x.id(), Some(&Id::try_from("c1").unwrap()));
+        assert_eq!(x.surface_member().len(), 2);
+        assert!(
+            x.surface_member()
+                .iter()
+                .all(|x| matches!(x.object(), Some(AbstractSurfaceKind::Polygon(_))))
+        );
+        assert!(check_simple_features(&downgraded).conforms_to(SimpleFeaturesLevel::Sf0));
+    }
+
+    #[test]
+    fn triangle_patches_become_polygon_patches() {
+        let surface = Surface::new(AbstractSurfacePatchArrayProperty::from_objects(vec![
+            AbstractSurfacePatchKind::Triangle(triangle(0.0)),
+        ]));
+        let geometry = AbstractGeometryKind::AbstractGeometricPrimitiveKind(
+            AbstractGeometricPrimitiveKind::AbstractSurfaceKind(AbstractSurfaceKind::Surface(
+                surface,
+            )),
+        );
+
+        let downgraded = downgrade_to_simple_features(geometry).unwrap();
+
+        assert!(check_simple_features(&downgraded).violations().is_empty());
+    }
+}
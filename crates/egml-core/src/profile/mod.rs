@@ -0,0 +1,7 @@
+//! Conformance to the GML Simple Features profile.
+
+mod downgrade;
+mod simple_features;
+
+pub use downgrade::*;
+pub use simple_features::*;
@@ -0,0 +1,487 @@
+use crate::model::base::{AsAbstractGml, HasAssociationAttributes, Id};
+use crate::model::common::{GeometryType, HasGeometryType, IterGeometries};
+use crate::model::feature::AsAbstractFeature;
+use crate::model::geometry::aggregates::refs::AbstractGeometricAggregateKindRef;
+use crate::model::geometry::primitives::AbstractSurfacePatchKind;
+use crate::model::geometry::primitives::refs::{
+    AbstractGeometricPrimitiveKindRef, AbstractSurfaceKindRef,
+};
+use crate::model::geometry::refs::AbstractGeometryKindRef;
+use std::fmt;
+
+/// Compliance levels of the GML Simple Features profile
+/// ([OGC 10-100r3](https://portal.ogc.org/files/?artifact_id=42729)).
+///
+/// All levels share the same geometry types; they differ in the types and
+/// cardinalities of feature properties, see [`PropertyType`]. SF-0 is the
+/// most restrictive level, and data that conforms to a level also conforms
+/// to every higher one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SimpleFeaturesLevel {
+    /// Every property occurs at most once, has a simple value or an inline
+    /// geometry.
+    Sf0,
+    /// Properties may repeat; values are still simple or inline geometries.
+    Sf1,
+    /// Properties may also have complex values or reference their value,
+    /// e.g. a geometry or another feature, via `xlink:href`.
+    Sf2,
+}
+
+impl SimpleFeaturesLevel {
+    /// All levels, from the most to the least restrictive.
+    pub const ALL: [Self; 3] = [Self::Sf0, Self::Sf1, Self::Sf2];
+}
+
+impl fmt::Display for SimpleFeaturesLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sf0 => write!(f, "SF-0"),
+            Self::Sf1 => write!(f, "SF-1"),
+            Self::Sf2 => write!(f, "SF-2"),
+        }
+    }
+}
+
+/// The type of the value of a feature property, as far as the Simple
+/// Features levels are concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyType {
+    /// A string, number, boolean, date, measure, code or URI. Allowed at
+    /// every level.
+    Simple,
+    /// An inline geometry, to be checked with
+    /// [`SimpleFeaturesChecker::check_geometry`]. Allowed at every level.
+    Geometry,
+    /// A value given by reference via `xlink:href`, e.g. another feature.
+    /// Allowed from SF-2.
+    Reference,
+    /// A structured value, e.g. a nested feature or data type. Allowed from
+    /// SF-2.
+    Complex,
+}
+
+/// A construct that is outside of one or more Simple Features levels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileViolation {
+    pub kind: ProfileViolationKind,
+    /// `gml:id` of the offending geometry or feature, if it has one.
+    pub id: Option<Id>,
+}
+
+impl ProfileViolation {
+    /// Returns `true` if the violation rules out conformance to `level`.
+    pub fn applies_to(&self, level: SimpleFeaturesLevel) -> bool {
+        match self.kind {
+            ProfileViolationKind::UnsupportedGeometry(_)
+            | ProfileViolationKind::UnsupportedSurfacePatch => true,
+            ProfileViolationKind::GeometryReference
+            | ProfileViolationKind::UnsupportedPropertyType { .. } => {
+                level < SimpleFeaturesLevel::Sf2
+            }
+            ProfileViolationKind::RepeatedName { .. }
+            | ProfileViolationKind::RepeatedProperty { .. } => level == SimpleFeaturesLevel::Sf0,
+        }
+    }
+}
+
+impl fmt::Display for ProfileViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ProfileViolationKind::UnsupportedGeometry(x) => {
+                write!(f, "gml:{x} is not a simple features geometry")?
+            }
+            ProfileViolationKind::UnsupportedSurfacePatch => {
+                write!(f, "gml:Surface has patches other than gml:PolygonPatch")?
+            }
+            ProfileViolationKind::GeometryReference => {
+                write!(f, "geometry property references its value via xlink:href")?
+            }
+            ProfileViolationKind::RepeatedName { count } => {
+                write!(f, "feature has {count} gml:name properties")?
+            }
+            ProfileViolationKind::RepeatedProperty { name, count } => {
+                write!(f, "feature has {count} {name} properties")?
+            }
+            ProfileViolationKind::UnsupportedPropertyType {
+                name,
+                property_type,
+            } => write!(f, "feature property {name} has a {property_type:?} value")?,
+        }
+        if let Some(id) = &self.id {
+            write!(f, " (gml:id '{id}')")?;
+        }
+        Ok(())
+    }
+}
+
+/// What a [`ProfileViolation`] is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileViolationKind {
+    /// A geometry type outside of `Point`, `LineString`, `Curve`, `Polygon`,
+    /// `Surface` and their `Multi*` aggregates, e.g. a `CompositeSurface`,
+    /// `Solid` or `TriangulatedSurface`. Violates every level.
+    UnsupportedGeometry(GeometryType),
+    /// A `gml:Surface` with `gml:Triangle` patches. Violates every level.
+    UnsupportedSurfacePatch,
+    /// A geometry property with an `xlink:href` instead of an inline value.
+    /// Violates SF-0 and SF-1.
+    GeometryReference,
+    /// A feature with more than one `gml:name`. Violates SF-0.
+    RepeatedName { count: usize },
+    /// A feature property that occurs more than once. Violates SF-0.
+    RepeatedProperty { name: String, count: usize },
+    /// A feature property with a [`PropertyType::Reference`] or
+    /// [`PropertyType::Complex`] value. Violates SF-0 and SF-1.
+    UnsupportedPropertyType {
+        name: String,
+        property_type: PropertyType,
+    },
+}
+
+/// Outcome of checking data against the Simple Features profile.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProfileReport {
+    violations: Vec<ProfileViolation>,
+}
+
+impl ProfileReport {
+    /// All violations, in the order they were found.
+    pub fn violations(&self) -> &[ProfileViolation] {
+        &self.violations
+    }
+
+    /// The violations that rule out conformance to `level`.
+    pub fn violations_at(
+        &self,
+        level: SimpleFeaturesLevel,
+    ) -> impl Iterator<Item = &ProfileViolation> {
+        self.violations.iter().filter(move |x| x.applies_to(level))
+    }
+
+    pub fn conforms_to(&self, level: SimpleFeaturesLevel) -> bool {
+        self.violations_at(level).next().is_none()
+    }
+
+    /// The most restrictive level the checked data conforms to, or `None` if
+    /// it conforms to none.
+    pub fn strictest_level(&self) -> Option<SimpleFeaturesLevel> {
+        SimpleFeaturesLevel::ALL
+            .into_iter()
+            .find(|x| self.conforms_to(*x))
+    }
+}
+
+/// Collects [`ProfileViolation`]s of features and geometries.
+///
+/// Application schemas define their own feature types, so the checker
+/// cannot find the properties of a feature by itself: pass the feature to
+/// [`check_feature`](Self::check_feature), each of its properties to
+/// [`check_property`](Self::check_property) and each of its geometries to
+/// [`check_geometry`](Self::check_geometry). A report only covers what was
+/// passed in, so a feature conforms to a level only if all of its
+/// properties were checked.
+#[derive(Debug, Clone, Default)]
+pub struct SimpleFeaturesChecker {
+    report: ProfileReport,
+}
+
+impl SimpleFeaturesChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks the properties defined by `gml:AbstractFeatureType`.
+    pub fn check_feature<F: AsAbstractFeature + ?Sized>(&mut self, feature: &F) {
+        let count = feature.names().len();
+        if count > 1 {
+            self.push(ProfileViolationKind::RepeatedName { count }, feature.id());
+        }
+    }
+
+    /// Checks a property of `feature` that occurs `count` times with values
+    /// of `property_type`, e.g. `("bldg:measuredHeight", PropertyType::Simple, 1)`.
+    pub fn check_property<F: AsAbstractFeature + ?Sized>(
+        &mut self,
+        feature: &F,
+        name: &str,
+        property_type: PropertyType,
+        count: usize,
+    ) {
+        if count > 1 {
+            self.push(
+                ProfileViolationKind::RepeatedProperty {
+                    name: name.to_string(),
+                    count,
+                },
+                feature.id(),
+            );
+        }
+        if matches!(
+            property_type,
+            PropertyType::Reference | PropertyType::Complex
+        ) {
+            self.push(
+                ProfileViolationKind::UnsupportedPropertyType {
+                    name: name.to_string(),
+                    property_type,
+                },
+                feature.id(),
+            );
+        }
+    }
+
+    /// Checks `geometry` and every geometry nested within it.
+    pub fn check_geometry<T: IterGeometries + ?Sized>(&mut self, geometry: &T) {
+        for x in geometry.iter_geometries() {
+            self.check_node(x);
+        }
+    }
+
+    pub fn finish(self) -> ProfileReport {
+        self.report
+    }
+
+    fn check_node(&mut self, geometry: AbstractGeometryKindRef<'_>) {
+        let geometry_type = geometry.geometry_type();
+        if !is_simple_geometry_type(geometry_type) {
+            self.push(
+                ProfileViolationKind::UnsupportedGeometry(geometry_type),
+                geometry.id(),
+            );
+            // Its own patches and references need no further reporting.
+            return;
+        }
+
+        if let AbstractGeometryKindRef::AbstractGeometricPrimitiveKind(
+            AbstractGeometricPrimitiveKindRef::AbstractSurfaceKind(
+                AbstractSurfaceKindRef::Surface(x),
+            ),
+        ) = geometry
+            && x.patches()
+                .objects()
+                .iter()
+                .any(|x| !matches!(x, AbstractSurfacePatchKind::PolygonPatch(_)))
+        {
+            self.push(ProfileViolationKind::UnsupportedSurfacePatch, geometry.id());
+        }
+
+        for _ in 0..reference_count(geometry) {
+            self.push(ProfileViolationKind::GeometryReference, geometry.id());
+        }
+    }
+
+    fn push(&mut self, kind: ProfileViolationKind, id: Option<&Id>) {
+        self.report.violations.push(ProfileViolation {
+            kind,
+            id: id.cloned(),
+        });
+    }
+}
+
+/// Checks `geometry` and every geometry nested within it.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::DirectPosition;
+/// use egml_core::model::geometry::primitives::{Triangle, TriangulatedSurface};
+/// use egml_core::profile::{SimpleFeaturesLevel, check_simple_features};
+///
+/// let triangle = Triangle::from_points(
+///     DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+///     DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+///     DirectPosition::new(0.0, 1.0, 0.0).unwrap(),
+/// )
+/// .unwrap();
+/// let tin = TriangulatedSurface::from_triangles(vec![triangle]).unwrap();
+///
+/// let report = check_simple_features(&tin);
+///
+/// assert!(!report.conforms_to(SimpleFeaturesLevel::Sf2));
+/// assert_eq!(report.strictest_level(), None);
+/// ```
+pub fn check_simple_features<T: IterGeometries + ?Sized>(geometry: &T) -> ProfileReport {
+    let mut checker = SimpleFeaturesChecker::new();
+    checker.check_geometry(geometry);
+    checker.finish()
+}
+
+fn is_simple_geometry_type(geometry_type: GeometryType) -> bool {
+    matches!(
+        geometry_type,
+        GeometryType::Point
+            | GeometryType::LineString
+            | GeometryType::Curve
+            | GeometryType::LinearRing
+            | GeometryType::Polygon
+            | GeometryType::Surface
+            | GeometryType::MultiPoint
+            | GeometryType::MultiCurve
+            | GeometryType::MultiSurface
+            | GeometryType::MultiGeometry
+    )
+}
+
+/// Number of properties of `geometry` itself (not of nested geometries)
+/// that carry an `xlink:href`. Only simple features geometries are
+/// inspected; all others are reported as unsupported anyway.
+fn reference_count(geometry: AbstractGeometryKindRef<'_>) -> usize {
+    fn count<'a, P: HasAssociationAttributes + 'a>(
+        properties: impl IntoIterator<Item = &'a P>,
+    ) -> usize {
+        properties
+            .into_iter()
+            .filter(|x| x.href().is_some())
+            .count()
+    }
+
+    match geometry {
+        AbstractGeometryKindRef::AbstractGeometricAggregateKind(x) => match x {
+            AbstractGeometricAggregateKindRef::MultiCurve(x) => count(x.curve_member()),
+            AbstractGeometricAggregateKindRef::MultiGeometry(x) => {
+                count(x.geometry_member()) + count(x.geometry_members())
+            }
+            AbstractGeometricAggregateKindRef::MultiPoint(x) => {
+                count(x.point_member()) + count(x.point_members())
+            }
+            AbstractGeometricAggregateKindRef::MultiSurface(x) => count(x.surface_member()),
+        },
+        AbstractGeometryKindRef::AbstractGeometricPrimitiveKind(
+            AbstractGeometricPrimitiveKindRef::AbstractSurfaceKind(
+                AbstractSurfaceKindRef::Polygon(x),
+            ),
+        ) => count(x.exterior()) + count(x.interior()),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::base::{AbstractGml, AsAbstractGmlMut};
+    use crate::model::basic_types::Code;
+    use crate::model::feature::AbstractFeature;
+    use crate::model::geometry::DirectPosition;
+    use crate::model::geometry::aggregates::{AggregationType, MultiSurface};
+    use crate::model::geometry::complexes::CompositeSurface;
+    use crate::model::geometry::primitives::{
+        AbstractRingKind, AbstractRingProperty, AbstractSurfaceKind, AbstractSurfaceProperty,
+        LinearRing, Polygon,
+    };
+    use crate::model::xlink::HRef;
+
+    fn polygon() -> Polygon {
+        let ring = LinearRing::new([
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(0.0, 1.0, 0.0).unwrap(),
+        ])
+        .unwrap();
+        Polygon::new(
+            Some(AbstractRingProperty::from_object(
+                AbstractRingKind::LinearRing(ring),
+            )),
+            [],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn multi_surface_of_polygons_conforms_to_sf_0() {
+        let multi_surface = MultiSurface::new([AbstractSurfaceProperty::from_object(
+            AbstractSurfaceKind::Polygon(polygon()),
+        )])
+        .unwrap();
+
+        let report = check_simple_features(&multi_surface);
+
+        assert!(report.violations().is_empty());
+        assert_eq!(report.strictest_level(), Some(SimpleFeaturesLevel::Sf0));
+    }
+
+    #[test]
+    fn references_are_allowed_from_sf_2_and_composites_nowhere() {
+        let mut composite = CompositeSurface::new(
+            [AbstractSurfaceProperty::from_object(
+                AbstractSurfaceKind::Polygon(polygon()),
+            )],
+            AggregationType::Set,
+        )
+        .unwrap();
+        composite.set_id(Id::try_from("c1").unwrap());
+        let with_reference = MultiSurface::new([
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(polygon())),
+            AbstractSurfaceProperty::from_href(HRef::from_local("#p1")),
+        ])
+        .unwrap();
+        let with_composite = MultiSurface::new([AbstractSurfaceProperty::from_object(
+            AbstractSurfaceKind::CompositeSurface(composite),
+        )])
+        .unwrap();
+
+        let references = check_simple_features(&with_reference);
+        let composites = check_simple_features(&with_composite);
+
+        assert_eq!(references.strictest_level(), Some(SimpleFeaturesLevel::Sf2));
+        assert_eq!(
+            references.violations_at(SimpleFeaturesLevel::Sf1).count(),
+            1
+        );
+        assert_eq!(composites.violations().len(), 1);
+        assert_eq!(
+            composites.violations()[0].to_string(),
+            "gml:CompositeSurface is not a simple features geometry (gml:id 'c1')"
+        );
+        assert_eq!(composites.strictest_level(), None);
+    }
+
+    #[test]
+    fn repeated_feature_names_violate_sf_0_only() {
+        let mut abstract_gml = AbstractGml::new();
+        abstract_gml.set_names(vec![Code::new("a"), Code::new("b")]);
+        let feature = AbstractFeature::from_abstract_gml(abstract_gml);
+
+        let mut checker = SimpleFeaturesChecker::new();
+        checker.check_feature(&feature);
+        let report = checker.finish();
+
+        assert!(!report.conforms_to(SimpleFeaturesLevel::Sf0));
+        assert_eq!(report.strictest_level(), Some(SimpleFeaturesLevel::Sf1));
+    }
+
+    #[test]
+    fn property_cardinalities_and_types_select_the_level() {
+        let mut abstract_gml = AbstractGml::new();
+        abstract_gml.set_id(Id::try_from("b1").unwrap());
+        let feature = AbstractFeature::from_abstract_gml(abstract_gml);
+        let check = |properties: &[(&str, PropertyType, usize)]| {
+            let mut checker = SimpleFeaturesChecker::new();
+            for (name, property_type, count) in properties {
+                checker.check_property(&feature, name, *property_type, *count);
+            }
+            checker.finish()
+        };
+
+        let simple = check(&[
+            ("bldg:measuredHeight", PropertyType::Simple, 1),
+            ("bldg:lod1Solid", PropertyType::Geometry, 1),
+        ]);
+        let repeated = check(&[("bldg:function", PropertyType::Simple, 2)]);
+        let nested = check(&[("bldg:address", PropertyType::Complex, 1)]);
+        let referenced = check(&[("core:relatedTo", PropertyType::Reference, 3)]);
+
+        assert_eq!(simple.strictest_level(), Some(SimpleFeaturesLevel::Sf0));
+        assert_eq!(repeated.strictest_level(), Some(SimpleFeaturesLevel::Sf1));
+        assert_eq!(
+            repeated.violations()[0].to_string(),
+            "feature has 2 bldg:function properties (gml:id 'b1')"
+        );
+        assert_eq!(nested.strictest_level(), Some(SimpleFeaturesLevel::Sf2));
+        assert_eq!(referenced.strictest_level(), Some(SimpleFeaturesLevel::Sf2));
+        assert_eq!(
+            referenced.violations_at(SimpleFeaturesLevel::Sf0).count(),
+            2
+        );
+    }
+}
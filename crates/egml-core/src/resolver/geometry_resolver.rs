@@ -12,6 +12,7 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, Default)]
 pub struct GeometryResolver<'a> {
     by_id: HashMap<Id, AbstractGeometryKindRef<'a>>,
+    duplicate_ids: Vec<Id>,
 }
 
 impl<'a> GeometryResolver<'a> {
@@ -77,14 +78,16 @@ impl<'a> GeometryResolver<'a> {
     /// Indexes a single geometry by its `id`, if it has one.
     ///
     /// Returns `true` if the geometry had an `id` and was stored. If another
-    /// geometry was already indexed under the same `id`, it is silently
-    /// replaced — `gml:id` is supposed to be unique within a document, so a
-    /// collision indicates malformed input rather than a case this resolver
-    /// needs to arbitrate.
+    /// geometry was already indexed under the same `id`, it is replaced and
+    /// the `id` is recorded in [`duplicate_ids`](Self::duplicate_ids) —
+    /// `gml:id` is supposed to be unique within a document, so a collision
+    /// indicates malformed input.
     pub fn insert(&mut self, geometry: AbstractGeometryKindRef<'a>) -> bool {
         match geometry.id() {
             Some(id) => {
-                self.by_id.insert(id.clone(), geometry);
+                if self.by_id.insert(id.clone(), geometry).is_some() {
+                    self.duplicate_ids.push(id.clone());
+                }
                 true
             }
             None => false,
@@ -138,6 +141,12 @@ impl<'a> GeometryResolver<'a> {
             .and_then(|geometry| T::try_from(geometry).ok())
     }
 
+    /// Ids that were inserted more than once, once per replaced geometry, in
+    /// insertion order.
+    pub fn duplicate_ids(&self) -> &[Id] {
+        &self.duplicate_ids
+    }
+
     /// Returns `true` if `id` is indexed.
     pub fn contains(&self, id: &Id) -> bool {
         self.by_id.contains_key(id)
@@ -294,6 +303,7 @@ mod tests {
             .resolve_as(&Id::try_from("dup").expect("valid id"))
             .unwrap();
         assert_eq!(resolved.pos().x(), 4.0);
+        assert_eq!(resolver.duplicate_ids(), [Id::try_from("dup").unwrap()]);
     }
 
     #[test]
@@ -1,5 +1,7 @@
 //! Reference resolution for `gml:id`/`xlink:href` targets.
 
 mod geometry_resolver;
+mod reference_resolver;
 
 pub use geometry_resolver::*;
+pub use reference_resolver::*;
@@ -0,0 +1,789 @@
+use crate::model::base::{
+    AsAbstractGml, AssociationAttributes, HasAssociationAttributes, HasAssociationAttributesMut, Id,
+};
+use crate::model::common::{IterGeometries, PropertyVisitorMut, VisitPropertiesMut};
+use crate::model::geometry::primitives::{
+    AbstractCurveKind, AbstractCurveProperty, AbstractRingKind, AbstractRingProperty,
+    AbstractSurfaceKind, AbstractSurfaceProperty, Point, PointProperty, Shell, ShellProperty,
+};
+use crate::model::geometry::refs::AbstractGeometryKindRef;
+use crate::model::geometry::{AbstractGeometryKind, AbstractGeometryProperty};
+use crate::model::xlink::HRef;
+use crate::resolver::GeometryResolver;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Replaces local `xlink:href` references with inline clones of the
+/// geometries they point at.
+///
+/// Holds owned copies of the reference targets, so — unlike
+/// [`GeometryResolver`] — it does not borrow the tree it resolves into.
+/// [`resolve`](Self::resolve) fills every ring, curve, surface, shell, point
+/// and geometry property that carries a `#id` reference but no inline object
+/// with a clone of the target. References inside the clone are resolved as
+/// well. The `href` of a resolved property is cleared, since a GML property
+/// holds either an inline object or a reference, not both.
+///
+/// The inlined clone keeps the `gml:id` of its target, so a resolved tree can
+/// contain the same `gml:id` more than once;
+/// [`deduplicate_references`] turns the repeated copies back into references.
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceResolver {
+    targets: HashMap<Id, AbstractGeometryKind>,
+    duplicate_ids: Vec<Id>,
+}
+
+impl ReferenceResolver {
+    /// Creates a resolver without any targets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clones a geometry into the resolver under its `id`, if it has one.
+    ///
+    /// Returns `true` if the geometry had an `id` and was stored. As with
+    /// [`GeometryResolver::insert`], a later geometry with the same `id`
+    /// replaces the earlier one and the `id` is reported in
+    /// [`ResolutionReport::duplicate_ids`].
+    pub fn insert(&mut self, geometry: AbstractGeometryKindRef<'_>) -> bool {
+        match geometry.id() {
+            Some(id) => {
+                if self
+                    .targets
+                    .insert(id.clone(), geometry.to_owned())
+                    .is_some()
+                {
+                    self.duplicate_ids.push(id.clone());
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Clones every geometry within `root` (including `root` itself) that
+    /// carries an `id`.
+    ///
+    /// Each nested geometry is cloned separately, so this costs a multiple of
+    /// the size of `root`. For a single tree,
+    /// [`resolve_local_references`] only clones the geometries that are
+    /// actually referenced.
+    pub fn insert_root<T: IterGeometries>(&mut self, root: &T) {
+        for geometry in root.iter_geometries() {
+            self.insert(geometry);
+        }
+    }
+
+    /// Returns `true` if a target is stored under `id`.
+    pub fn contains(&self, id: &Id) -> bool {
+        self.targets.contains_key(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Inlines the target of every local reference within `root`.
+    ///
+    /// References that cannot be resolved are left untouched and listed in
+    /// the returned report.
+    pub fn resolve<T: VisitPropertiesMut + ?Sized>(&self, root: &mut T) -> ResolutionReport {
+        let mut report = resolve_references(root, None, &mut Table(&self.targets));
+        report.duplicate_ids = self.duplicate_ids.clone();
+        report
+    }
+}
+
+/// Looks targets up in the table of a [`ReferenceResolver`].
+struct Table<'r>(&'r HashMap<Id, AbstractGeometryKind>);
+
+impl ReferenceTargets for Table<'_> {
+    fn target(
+        &mut self,
+        href: &HRef,
+        _base: Option<&str>,
+    ) -> Result<ReferenceTarget, UnresolvedReason> {
+        let HRef::Local(id) = href else {
+            return Err(UnresolvedReason::Remote);
+        };
+        let geometry = Id::try_from(id.as_str())
+            .ok()
+            .and_then(|id| self.0.get(&id))
+            .ok_or(UnresolvedReason::NotFound)?;
+
+        Ok(ReferenceTarget {
+            key: id.clone(),
+            base: None,
+            geometry: geometry.clone(),
+        })
+    }
+}
+
+/// Supplies the geometries that `xlink:href` references point at.
+///
+/// [`ReferenceResolver`] looks targets up in a table of geometries from the
+/// same tree; other implementations can fetch them from other documents.
+pub trait ReferenceTargets {
+    /// Returns the target of `href`, which occurs in the document `base`
+    /// (`None` for the document the resolved tree came from).
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason the reference is left in place.
+    fn target(
+        &mut self,
+        href: &HRef,
+        base: Option<&str>,
+    ) -> Result<ReferenceTarget, UnresolvedReason>;
+}
+
+/// A geometry returned by [`ReferenceTargets::target`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceTarget {
+    /// Identifies the target across all documents (e.g. `tile_2.gml#p1`).
+    /// A reference to a key that is already being inlined is a cycle.
+    pub key: String,
+    /// Document that references within `geometry` occur in.
+    pub base: Option<String>,
+    pub geometry: AbstractGeometryKind,
+}
+
+/// Inlines the target of every reference within `root`, looking targets up
+/// in `targets`.
+///
+/// `base` is the document `root` belongs to and is passed on to
+/// [`ReferenceTargets::target`]. References within an inlined target are
+/// resolved relative to the target's own document. References that cannot
+/// be resolved are left untouched and listed in the returned report.
+pub fn resolve_references<T, S>(
+    root: &mut T,
+    base: Option<&str>,
+    targets: &mut S,
+) -> ResolutionReport
+where
+    T: VisitPropertiesMut + ?Sized,
+    S: ReferenceTargets + ?Sized,
+{
+    let mut inliner = Inliner {
+        targets,
+        base: base.map(str::to_owned),
+        stack: Vec::new(),
+        report: ResolutionReport::default(),
+    };
+    root.visit_properties_mut(&mut inliner);
+    inliner.report
+}
+
+/// Resolves every local `xlink:href` reference within `root` against the
+/// geometries defined in `root` itself.
+///
+/// Only the referenced geometries are cloned. See [`ReferenceResolver`] for
+/// how references are inlined.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::base::{AsAbstractGmlMut, Id};
+/// use egml_core::model::geometry::DirectPosition;
+/// use egml_core::model::geometry::aggregates::MultiSurface;
+/// use egml_core::model::geometry::primitives::{
+///     AbstractRingKind, AbstractRingProperty, AbstractSurfaceKind, AbstractSurfaceProperty,
+///     LinearRing, Polygon,
+/// };
+/// use egml_core::model::xlink::HRef;
+/// use egml_core::resolver::resolve_local_references;
+///
+/// let ring = LinearRing::new([
+///     DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+///     DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+///     DirectPosition::new(0.0, 1.0, 0.0).unwrap(),
+/// ])
+/// .unwrap();
+/// let mut polygon = Polygon::new(
+///     Some(AbstractRingProperty::from_object(AbstractRingKind::LinearRing(ring))),
+///     [],
+/// )
+/// .unwrap();
+/// polygon.set_id(Id::try_from("p1").unwrap());
+///
+/// let mut multi_surface = MultiSurface::new(vec![
+///     AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(polygon)),
+///     AbstractSurfaceProperty::from_href(HRef::from_local("#p1")),
+/// ])
+/// .unwrap();
+///
+/// let report = resolve_local_references(&mut multi_surface);
+///
+/// assert_eq!(report.resolved(), 1);
+/// assert!(multi_surface.surface_member()[1].object().is_some());
+/// ```
+pub fn resolve_local_references<T>(root: &mut T) -> ResolutionReport
+where
+    T: IterGeometries + VisitPropertiesMut,
+{
+    let mut collector = LocalReferenceCollector::default();
+    root.visit_properties_mut(&mut collector);
+
+    let mut resolver = ReferenceResolver::new();
+    let index = GeometryResolver::build(&*root);
+    for id in &collector.ids {
+        if let Some(geometry) = index.resolve(id) {
+            resolver.insert(geometry);
+        }
+    }
+    resolver.duplicate_ids = index.duplicate_ids().to_vec();
+
+    resolver.resolve(root)
+}
+
+/// Replaces every inline object that equals an earlier object with the same
+/// `gml:id` within `root` with a local `xlink:href` to that id — the inverse
+/// of [`resolve_local_references`].
+///
+/// The first occurrence, in document order, stays inline. A later object
+/// that reuses the `gml:id` for different content is left inline as well and
+/// reported in [`DeduplicationReport::conflicting_ids`], since a reference
+/// would point at the wrong object.
+pub fn deduplicate_references<T: VisitPropertiesMut + ?Sized>(root: &mut T) -> DeduplicationReport {
+    let mut deduplicator = Deduplicator::default();
+    root.visit_properties_mut(&mut deduplicator);
+    deduplicator.report
+}
+
+/// Outcome of [`deduplicate_references`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeduplicationReport {
+    replaced: usize,
+    conflicting_ids: Vec<Id>,
+}
+
+impl DeduplicationReport {
+    /// Number of objects that were replaced by a reference.
+    pub fn replaced(&self) -> usize {
+        self.replaced
+    }
+
+    /// `gml:id`s of the objects that were left inline because their content
+    /// differs from the first object with the same id, in the order they were
+    /// visited.
+    pub fn conflicting_ids(&self) -> &[Id] {
+        &self.conflicting_ids
+    }
+
+    /// Returns `true` if no `gml:id` was reused for different content.
+    pub fn is_consistent(&self) -> bool {
+        self.conflicting_ids.is_empty()
+    }
+}
+
+/// Outcome of resolving the references within a geometry tree.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResolutionReport {
+    resolved: usize,
+    unresolved: Vec<UnresolvedReference>,
+    duplicate_ids: Vec<Id>,
+}
+
+impl ResolutionReport {
+    /// Number of properties that received an inline object.
+    pub fn resolved(&self) -> usize {
+        self.resolved
+    }
+
+    /// References that were left in place, in the order they were visited.
+    pub fn unresolved(&self) -> &[UnresolvedReference] {
+        &self.unresolved
+    }
+
+    /// References whose target does not exist.
+    pub fn dangling(&self) -> impl Iterator<Item = &UnresolvedReference> {
+        self.unresolved
+            .iter()
+            .filter(|x| x.reason == UnresolvedReason::NotFound)
+    }
+
+    /// References that were not followed because they lead back to a target
+    /// that was being inlined.
+    pub fn cycles(&self) -> impl Iterator<Item = &UnresolvedReference> {
+        self.unresolved
+            .iter()
+            .filter(|x| x.reason == UnresolvedReason::Cycle)
+    }
+
+    /// `gml:id`s that were defined more than once. References to them
+    /// resolve to the last definition.
+    pub fn duplicate_ids(&self) -> &[Id] {
+        &self.duplicate_ids
+    }
+
+    /// Returns `true` if every reference was resolved.
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+}
+
+/// A reference that [`ReferenceResolver::resolve`] left in place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedReference {
+    pub href: HRef,
+    pub reason: UnresolvedReason,
+}
+
+impl fmt::Display for UnresolvedReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            UnresolvedReason::NotFound => write!(f, "'{}' does not match any gml:id", self.href),
+            UnresolvedReason::TypeMismatch { expected } => {
+                write!(f, "'{}' does not point at a {expected}", self.href)
+            }
+            UnresolvedReason::Cycle => {
+                write!(f, "'{}' is part of a reference cycle", self.href)
+            }
+            UnresolvedReason::Remote => {
+                write!(f, "'{}' points outside of the document", self.href)
+            }
+            UnresolvedReason::LoadFailed { message } => {
+                write!(f, "'{}' cannot be loaded: {message}", self.href)
+            }
+        }
+    }
+}
+
+/// Why a reference could not be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnresolvedReason {
+    /// No target with the referenced `gml:id` is known.
+    NotFound,
+    /// The target exists but cannot be held by the property, e.g. a ring
+    /// property pointing at a polygon. `expected` names what the property
+    /// holds.
+    TypeMismatch { expected: &'static str },
+    /// The target contains a reference back to itself, directly or through
+    /// other targets.
+    Cycle,
+    /// The reference is not a local `#id` fragment.
+    Remote,
+    /// The document containing the target could not be loaded or parsed.
+    LoadFailed { message: String },
+}
+
+/// Collects the ids of all local references, without changing the tree.
+#[derive(Default)]
+struct LocalReferenceCollector {
+    ids: HashSet<Id>,
+}
+
+impl LocalReferenceCollector {
+    fn collect(&mut self, association: &AssociationAttributes) {
+        if let Some(id) = association.local_id().and_then(|x| Id::try_from(x).ok()) {
+            self.ids.insert(id);
+        }
+    }
+}
+
+impl PropertyVisitorMut for LocalReferenceCollector {
+    fn visit_ring_property(&mut self, property: &mut AbstractRingProperty) {
+        self.collect(property.association());
+        property.visit_properties_mut(self);
+    }
+
+    fn visit_curve_property(&mut self, property: &mut AbstractCurveProperty) {
+        self.collect(property.association());
+        property.visit_properties_mut(self);
+    }
+
+    fn visit_surface_property(&mut self, property: &mut AbstractSurfaceProperty) {
+        self.collect(property.association());
+        property.visit_properties_mut(self);
+    }
+
+    fn visit_shell_property(&mut self, property: &mut ShellProperty) {
+        self.collect(property.association());
+        property.visit_properties_mut(self);
+    }
+
+    fn visit_point_property(&mut self, property: &mut PointProperty) {
+        self.collect(property.association());
+        property.visit_properties_mut(self);
+    }
+
+    fn visit_geometry_property(&mut self, property: &mut AbstractGeometryProperty) {
+        self.collect(property.association());
+        property.visit_properties_mut(self);
+    }
+}
+
+/// Replaces repeated inline objects by references to their first occurrence.
+#[derive(Default)]
+struct Deduplicator {
+    /// The first object with each `gml:id`, as it was before descending into
+    /// it.
+    seen: HashMap<Id, FirstOccurrence>,
+    report: DeduplicationReport,
+}
+
+/// An object kept by [`Deduplicator`] to compare later objects against.
+#[derive(PartialEq)]
+enum FirstOccurrence {
+    Ring(AbstractRingKind),
+    Curve(AbstractCurveKind),
+    Surface(AbstractSurfaceKind),
+    Shell(Shell),
+    Point(Point),
+    Geometry(AbstractGeometryKind),
+}
+
+/// Implements a [`PropertyVisitorMut`] method that swaps a repeated inline
+/// object for a reference, or records it and descends into it.
+macro_rules! deduplicate_property {
+    ($method:ident, $property:ty, $variant:ident) => {
+        fn $method(&mut self, property: &mut $property) {
+            let Some(object) = property.object() else {
+                property.visit_properties_mut(self);
+                return;
+            };
+            let Some(id) = object.id().cloned() else {
+                property.visit_properties_mut(self);
+                return;
+            };
+            match self.seen.get(&id) {
+                None => {
+                    self.seen
+                        .insert(id, FirstOccurrence::$variant(object.clone()));
+                }
+                Some(FirstOccurrence::$variant(first)) if first == object => {
+                    property.clear_object();
+                    property.association_mut().href = Some(HRef::from_local_id(id));
+                    self.report.replaced += 1;
+                    return;
+                }
+                Some(_) => self.report.conflicting_ids.push(id),
+            }
+            property.visit_properties_mut(self);
+        }
+    };
+}
+
+impl PropertyVisitorMut for Deduplicator {
+    deduplicate_property!(visit_ring_property, AbstractRingProperty, Ring);
+    deduplicate_property!(visit_curve_property, AbstractCurveProperty, Curve);
+    deduplicate_property!(visit_surface_property, AbstractSurfaceProperty, Surface);
+    deduplicate_property!(visit_shell_property, ShellProperty, Shell);
+    deduplicate_property!(visit_point_property, PointProperty, Point);
+    deduplicate_property!(visit_geometry_property, AbstractGeometryProperty, Geometry);
+}
+
+struct Inliner<'s, S: ?Sized> {
+    targets: &'s mut S,
+    /// Document the properties currently being visited occur in.
+    base: Option<String>,
+    /// Keys of the targets currently being inlined, outermost first.
+    stack: Vec<String>,
+    report: ResolutionReport,
+}
+
+impl<S: ReferenceTargets + ?Sized> Inliner<'_, S> {
+    /// Returns a resolved clone of the target of `association`, or `None` if
+    /// the property has an object already, has no reference, or the reference
+    /// cannot be resolved.
+    fn inline<K>(
+        &mut self,
+        association: &AssociationAttributes,
+        has_object: bool,
+        expected: &'static str,
+    ) -> Option<K>
+    where
+        K: TryFrom<AbstractGeometryKind> + VisitPropertiesMut,
+    {
+        if has_object {
+            return None;
+        }
+        let href = association.href.as_ref()?;
+
+        let target = match self.targets.target(href, self.base.as_deref()) {
+            Ok(target) => target,
+            Err(reason) => return self.unresolved(href, reason),
+        };
+        if self.stack.contains(&target.key) {
+            return self.unresolved(href, UnresolvedReason::Cycle);
+        }
+        let Ok(mut object) = K::try_from(target.geometry) else {
+            return self.unresolved(href, UnresolvedReason::TypeMismatch { expected });
+        };
+
+        self.stack.push(target.key);
+        let base = std::mem::replace(&mut self.base, target.base);
+        object.visit_properties_mut(self);
+        self.base = base;
+        self.stack.pop();
+
+        self.report.resolved += 1;
+        Some(object)
+    }
+
+    fn unresolved<K>(&mut self, href: &HRef, reason: UnresolvedReason) -> Option<K> {
+        self.report.unresolved.push(UnresolvedReference {
+            href: href.clone(),
+            reason,
+        });
+        None
+    }
+}
+
+/// Implements a [`PropertyVisitorMut`] method that inlines the resolved
+/// object, or descends into the property if there is nothing to inline.
+macro_rules! inline_property {
+    ($method:ident, $property:ty, $kind:ty, $expected:literal) => {
+        fn $method(&mut self, property: &mut $property) {
+            let object = self.inline::<$kind>(
+                property.association(),
+                property.object().is_some(),
+                $expected,
+            );
+            match object {
+                Some(object) => {
+                    property.set_object(object);
+                    property.association_mut().href = None;
+                }
+                None => property.visit_properties_mut(self),
+            }
+        }
+    };
+}
+
+impl<S: ReferenceTargets + ?Sized> PropertyVisitorMut for Inliner<'_, S> {
+    inline_property!(
+        visit_ring_property,
+        AbstractRingProperty,
+        AbstractRingKind,
+        "ring"
+    );
+    inline_property!(
+        visit_curve_property,
+        AbstractCurveProperty,
+        AbstractCurveKind,
+        "curve"
+    );
+    inline_property!(
+        visit_surface_property,
+        AbstractSurfaceProperty,
+        AbstractSurfaceKind,
+        "surface"
+    );
+    inline_property!(visit_shell_property, ShellProperty, Shell, "shell");
+    inline_property!(visit_point_property, PointProperty, Point, "point");
+    inline_property!(
+        visit_geometry_property,
+        AbstractGeometryProperty,
+        AbstractGeometryKind,
+        "geometry"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::base::AsAbstractGmlMut;
+    use crate::model::geometry::DirectPosition;
+    use crate::model::geometry::primitives::{LinearRing, Polygon, Solid};
+
+    fn polygon_with_id(id: &str) -> Polygon {
+        let ring = LinearRing::new([
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(0.0, 1.0, 0.0).unwrap(),
+        ])
+        .unwrap();
+        let mut polygon = Polygon::new(
+            Some(AbstractRingProperty::from_object(
+                AbstractRingKind::LinearRing(ring),
+            )),
+            [],
+        )
+        .unwrap();
+        polygon.set_id(Id::try_from(id).expect("valid id"));
+        polygon
+    }
+
+    #[test]
+    fn shell_member_referencing_a_polygon_elsewhere_is_inlined() {
+        let mut shell = Shell::new(vec![
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(polygon_with_id(
+                "p1",
+            ))),
+            AbstractSurfaceProperty::from_href(HRef::from_local("#p1")),
+        ])
+        .unwrap();
+
+        let report = resolve_local_references(&mut shell);
+
+        assert_eq!(report.resolved(), 1);
+        assert!(report.is_complete());
+        let member = &shell.members()[1];
+        assert!(member.href().is_none());
+        assert_eq!(
+            member.object(),
+            Some(&AbstractSurfaceKind::Polygon(polygon_with_id("p1")))
+        );
+    }
+
+    #[test]
+    fn references_inside_an_inlined_target_are_resolved_too() {
+        let mut inner = Shell::new(vec![AbstractSurfaceProperty::from_href(HRef::from_local(
+            "p1",
+        ))])
+        .unwrap();
+        inner.set_id(Id::try_from("s1").expect("valid id"));
+        let outer = Shell::new(vec![
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(polygon_with_id(
+                "p1",
+            ))),
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Shell(inner)),
+        ])
+        .unwrap();
+        let mut solid = Solid::new(Some(ShellProperty::from_href(HRef::from_local("s1")))).unwrap();
+        let mut resolver = ReferenceResolver::new();
+        resolver.insert_root(&outer);
+
+        let report = resolver.resolve(&mut solid);
+
+        assert_eq!(report.resolved(), 2);
+        let shell = solid.exterior().and_then(|x| x.object()).unwrap();
+        assert!(shell.members()[0].object().is_some());
+    }
+
+    #[test]
+    fn unknown_wrong_type_and_remote_references_are_reported() {
+        let mut shell = Shell::new(vec![
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(polygon_with_id(
+                "p1",
+            ))),
+            AbstractSurfaceProperty::from_href(HRef::from_local("#missing")),
+            AbstractSurfaceProperty::from_href(HRef::from_remote("other.gml#p1")),
+        ])
+        .unwrap();
+        let mut polygon = Polygon::new(
+            Some(AbstractRingProperty::from_href(HRef::from_local("p1"))),
+            [],
+        )
+        .unwrap();
+        let mut resolver = ReferenceResolver::new();
+        resolver.insert_root(&shell);
+
+        let shell_report = resolver.resolve(&mut shell);
+        let polygon_report = resolver.resolve(&mut polygon);
+
+        let reasons: Vec<_> = shell_report
+            .unresolved()
+            .iter()
+            .map(|x| x.reason.clone())
+            .collect();
+        assert_eq!(
+            reasons,
+            [UnresolvedReason::NotFound, UnresolvedReason::Remote]
+        );
+        assert_eq!(
+            polygon_report.unresolved()[0].reason,
+            UnresolvedReason::TypeMismatch { expected: "ring" }
+        );
+    }
+
+    #[test]
+    fn deduplicate_restores_references_after_resolution() {
+        let original = Shell::new(vec![
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(polygon_with_id(
+                "p1",
+            ))),
+            AbstractSurfaceProperty::from_href(HRef::from_local("#p1")),
+        ])
+        .unwrap();
+        let mut shell = original.clone();
+        resolve_local_references(&mut shell);
+
+        let report = deduplicate_references(&mut shell);
+
+        assert_eq!(report.replaced(), 1);
+        assert!(report.is_consistent());
+        assert_eq!(shell, original);
+    }
+
+    #[test]
+    fn deduplicate_keeps_different_objects_sharing_an_id_inline() {
+        let mut other = Polygon::new(
+            Some(AbstractRingProperty::from_object(
+                AbstractRingKind::LinearRing(
+                    LinearRing::new([
+                        DirectPosition::new(5.0, 0.0, 0.0).unwrap(),
+                        DirectPosition::new(6.0, 0.0, 0.0).unwrap(),
+                        DirectPosition::new(5.0, 1.0, 0.0).unwrap(),
+                    ])
+                    .unwrap(),
+                ),
+            )),
+            [],
+        )
+        .unwrap();
+        other.set_id(Id::try_from("p1").expect("valid id"));
+        let original = Shell::new(vec![
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(polygon_with_id(
+                "p1",
+            ))),
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(other)),
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(polygon_with_id(
+                "p1",
+            ))),
+        ])
+        .unwrap();
+        let mut shell = original.clone();
+
+        let report = deduplicate_references(&mut shell);
+
+        assert_eq!(report.replaced(), 1);
+        assert_eq!(report.conflicting_ids(), [Id::try_from("p1").unwrap()]);
+        assert_eq!(shell.members()[1], original.members()[1]);
+        assert_eq!(
+            shell.members()[2].href(),
+            Some(&HRef::from_local_id(Id::try_from("p1").unwrap()))
+        );
+    }
+
+    #[test]
+    fn duplicate_ids_and_dangling_references_are_reported() {
+        let mut shell = Shell::new(vec![
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(polygon_with_id(
+                "p1",
+            ))),
+            AbstractSurfaceProperty::from_object(AbstractSurfaceKind::Polygon(polygon_with_id(
+                "p1",
+            ))),
+            AbstractSurfaceProperty::from_href(HRef::from_local("#gone")),
+        ])
+        .unwrap();
+
+        let report = resolve_local_references(&mut shell);
+
+        assert_eq!(report.duplicate_ids(), [Id::try_from("p1").unwrap()]);
+        assert_eq!(report.dangling().count(), 1);
+        assert_eq!(report.cycles().count(), 0);
+    }
+
+    #[test]
+    fn self_referencing_target_is_reported_as_cycle() {
+        let mut shell = Shell::new(vec![AbstractSurfaceProperty::from_href(HRef::from_local(
+            "s1",
+        ))])
+        .unwrap();
+        shell.set_id(Id::try_from("s1").expect("valid id"));
+        let mut resolver = ReferenceResolver::new();
+        resolver.insert_root(&shell);
+
+        let report = resolver.resolve(&mut shell);
+
+        assert_eq!(report.resolved(), 1);
+        assert_eq!(report.unresolved()[0].reason, UnresolvedReason::Cycle);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::Error;
+use crate::cityjson::CityJsonTransform;
+use crate::util::{Ring, surface_properties, to_solid};
+use egml_core::model::geometry::aggregates::{
+    AggregationType, MultiCurve, MultiGeometry, MultiPoint, MultiSurface,
+};
+use egml_core::model::geometry::complexes::CompositeSurface;
+use egml_core::model::geometry::primitives::{
+    AbstractCurveProperty, LineString, Point, PointProperty,
+};
+use egml_core::model::geometry::{AbstractGeometryKind, AbstractGeometryProperty, DirectPosition};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Decodes CityJSON geometry objects against the vertex list of their
+/// document, the inverse of [`CityJsonEncoder`](crate::cityjson::CityJsonEncoder).
+///
+/// `MultiSolid` and `CompositeSolid` are read as a `MultiGeometry` of
+/// `Solid`s. Geometry templates (`GeometryInstance`) are not supported.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::primitives::Solid;
+/// use egml_io::cityjson::CityJsonDecoder;
+/// use egml_io::serde_json::json;
+///
+/// let document = json!({
+///     "type": "CityJSON",
+///     "transform": {"scale": [0.5, 0.5, 0.5], "translate": [10.0, 20.0, 0.0]},
+///     "vertices": [[0, 0, 0], [2, 0, 0], [0, 2, 0], [0, 0, 2]],
+/// });
+/// let decoder = CityJsonDecoder::from_document(&document).unwrap();
+///
+/// let geometry = json!({"type": "Solid", "lod": "1",
+///     "boundaries": [[[[0, 2, 1]], [[0, 1, 3]], [[0, 3, 2]], [[1, 2, 3]]]]});
+/// let solid = Solid::try_from(decoder.decode(&geometry).unwrap()).unwrap();
+///
+/// assert_eq!(solid.points()[1].coords(), [10.0, 21.0, 0.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CityJsonDecoder {
+    vertices: Vec<DirectPosition>,
+}
+
+impl CityJsonDecoder {
+    /// Creates a decoder for `vertices`, which are integers if `transform`
+    /// is given and coordinates otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::CityJson`] if a vertex is not an array of numbers,
+    /// or of integers with a transform, and
+    /// [`Error::UnsupportedDimension`] if it does not have three.
+    pub fn new(vertices: &Value, transform: Option<&CityJsonTransform>) -> Result<Self, Error> {
+        let vertices = as_array(vertices, "vertices")?
+            .iter()
+            .map(|x| {
+                let values = as_array(x, "vertex")?;
+                let coords = match transform {
+                    Some(transform) => transform.dequantize(three(values, |x| {
+                        x.as_i64().ok_or_else(|| {
+                            Error::CityJson("a quantised vertex is not an integer".to_string())
+                        })
+                    })?),
+                    None => three(values, |x| {
+                        x.as_f64()
+                            .ok_or_else(|| Error::CityJson("a vertex is not a number".to_string()))
+                    })?,
+                };
+                Ok(DirectPosition::new(coords[0], coords[1], coords[2])?)
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Self { vertices })
+    }
+
+    /// Creates a decoder for the `vertices` and `transform` of a CityJSON
+    /// `document`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`new`](Self::new), and [`Error::Json`] if the
+    /// `transform` is malformed.
+    pub fn from_document(document: &Value) -> Result<Self, Error> {
+        let vertices = document
+            .get("vertices")
+            .ok_or_else(|| Error::CityJson("missing member 'vertices'".to_string()))?;
+        let transform = match document.get("transform") {
+            None | Some(Value::Null) => None,
+            Some(x) => Some(CityJsonTransform::deserialize(x)?),
+        };
+        Self::new(vertices, transform.as_ref())
+    }
+
+    /// Returns the vertices, with the transform applied.
+    pub fn vertices(&self) -> &[DirectPosition] {
+        &self.vertices
+    }
+
+    /// Returns the geometry of the CityJSON geometry object `geometry`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::CityJson`] for an unsupported geometry type,
+    /// boundaries nested to the wrong depth or a vertex index out of range,
+    /// and an error if the boundaries do not form valid egml geometries,
+    /// e.g. a ring with fewer than three vertices.
+    pub fn decode(&self, geometry: &Value) -> Result<AbstractGeometryKind, Error> {
+        let kind = geometry
+            .get("type")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::CityJson("geometry without type".to_string()))?;
+        let boundaries = geometry
+            .get("boundaries")
+            .ok_or_else(|| Error::CityJson("geometry without boundaries".to_string()))?;
+
+        Ok(match kind {
+            "MultiPoint" => {
+                let mut multi_point = MultiPoint::new(None)?;
+                multi_point.set_point_member(
+                    self.positions(boundaries)?
+                        .into_iter()
+                        .map(|x| PointProperty::from_object(Point::new(x)))
+                        .collect(),
+                );
+                multi_point.into()
+            }
+            "MultiLineString" => MultiCurve::new(list(boundaries, |x| {
+                Ok(AbstractCurveProperty::from_object(
+                    LineString::new(self.positions(x)?)?.into(),
+                ))
+            })?)?
+            .into(),
+            "MultiSurface" => {
+                MultiSurface::new(surface_properties(self.surfaces(boundaries)?)?)?.into()
+            }
+            "CompositeSurface" => CompositeSurface::new(
+                surface_properties(self.surfaces(boundaries)?)?,
+                AggregationType::Array,
+            )?
+            .into(),
+            "Solid" => to_solid(self.shells(boundaries)?)?.into(),
+            "MultiSolid" | "CompositeSolid" => {
+                let mut multi_geometry = MultiGeometry::new(None)?;
+                multi_geometry.set_geometry_member(list(boundaries, |x| {
+                    Ok(AbstractGeometryProperty::from_object(
+                        to_solid(self.shells(x)?)?.into(),
+                    ))
+                })?);
+                multi_geometry.into()
+            }
+            _ => {
+                return Err(Error::CityJson(format!(
+                    "unsupported geometry type '{kind}'"
+                )));
+            }
+        })
+    }
+
+    fn shells(&self, value: &Value) -> Result<Vec<Vec<Vec<Ring>>>, Error> {
+        list(value, |x| self.surfaces(x))
+    }
+
+    fn surfaces(&self, value: &Value) -> Result<Vec<Vec<Ring>>, Error> {
+        list(value, |x| list(x, |x| self.positions(x)))
+    }
+
+    fn positions(&self, value: &Value) -> Result<Vec<DirectPosition>, Error> {
+        list(value, |x| {
+            x.as_u64()
+                .and_then(|x| self.vertices.get(x as usize))
+                .copied()
+                .ok_or_else(|| Error::CityJson(format!("invalid vertex index {x}")))
+        })
+    }
+}
+
+fn as_array<'a>(value: &'a Value, what: &str) -> Result<&'a Vec<Value>, Error> {
+    value
+        .as_array()
+        .ok_or_else(|| Error::CityJson(format!("{what} is not an array")))
+}
+
+/// Reads each element of the boundary array `value` with `item`.
+fn list<T>(value: &Value, item: impl Fn(&Value) -> Result<T, Error>) -> Result<Vec<T>, Error> {
+    as_array(value, "boundaries")?.iter().map(item).collect()
+}
+
+fn three<T>(values: &[Value], item: impl Fn(&Value) -> Result<T, Error>) -> Result<[T; 3], Error> {
+    let items = values.iter().map(item).collect::<Result<Vec<_>, _>>()?;
+    <[T; 3]>::try_from(items).map_err(|x| Error::UnsupportedDimension {
+        found: x.len() as u32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        let transform = CityJsonTransform::new([1.0; 3], [0.0; 3]);
+        assert!(matches!(
+            CityJsonDecoder::new(&json!([[0.5, 0, 0]]), Some(&transform)),
+            Err(Error::CityJson(_))
+        ));
+        assert!(matches!(
+            CityJsonDecoder::new(&json!([[0, 0]]), None),
+            Err(Error::UnsupportedDimension { found: 2 })
+        ));
+
+        let decoder =
+            CityJsonDecoder::new(&json!([[0, 0, 0], [1, 0, 0], [0, 1, 0]]), None).unwrap();
+        for geometry in [
+            json!({"type": "MultiSurface", "boundaries": [[[0, 1, 3]]]}),
+            json!({"type": "MultiSurface", "boundaries": [[0, 1, 2]]}),
+            json!({"type": "GeometryInstance", "boundaries": [0]}),
+            json!({"type": "MultiSurface"}),
+        ] {
+            assert!(
+                matches!(decoder.decode(&geometry), Err(Error::CityJson(_))),
+                "{geometry}"
+            );
+        }
+    }
+}
@@ -0,0 +1,466 @@
+use crate::Error;
+use crate::cityjson::CityJsonTransform;
+use crate::util::{IndexedMesh, MeshVertex, Ring, SimpleGeometry, solid_shells};
+use egml_core::model::geometry::aggregates::{AbstractGeometricAggregateKind, MultiGeometry};
+use egml_core::model::geometry::primitives::{
+    AbstractGeometricPrimitiveKind, AbstractSolidKind, AbstractSurfaceKind, Solid,
+};
+use egml_core::model::geometry::{AbstractGeometryKind, DirectPosition};
+use serde_json::{Value, json};
+
+const FORMAT: &str = "CityJSON";
+
+/// Encodes geometries as CityJSON geometry objects whose `boundaries` index
+/// into one vertex list shared by all encoded geometries.
+///
+/// Equal vertices are stored once. With a [`CityJsonTransform`], vertices are
+/// quantised to integers first, so positions that round to the same integer
+/// vertex are merged as well, and consecutive repetitions of a vertex are
+/// dropped from rings and line strings.
+///
+/// Geometries are mapped as follows:
+///
+/// | egml geometry | CityJSON |
+/// |---------------|----------|
+/// | `Point`, `MultiPoint` | `MultiPoint` |
+/// | `LineString`, `LinearRing`, `MultiCurve` | `MultiLineString` |
+/// | `MultiSurface`, `Polygon`, `Surface`, `TriangulatedSurface` | `MultiSurface` |
+/// | `CompositeSurface`, `Shell` | `CompositeSurface` |
+/// | `Solid` | `Solid` |
+/// | `MultiGeometry` of `Solid`s | `MultiSolid`, or `CompositeSolid` with [`encode_composite_solid`](Self::encode_composite_solid) |
+///
+/// Only the `type` and `boundaries` are written; the `lod` and semantics are
+/// up to the caller.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::DirectPosition;
+/// use egml_io::cityjson::{CityJsonEncoder, CityJsonTransform};
+/// use egml_io::wkt::read_wkt;
+///
+/// let origin = DirectPosition::new(100.0, 200.0, 0.0).unwrap();
+/// let mut encoder = CityJsonEncoder::with_transform(CityJsonTransform::with_decimals(2, &origin));
+///
+/// let surfaces = read_wkt(
+///     "MULTIPOLYGON Z (((100 200 0, 101 200 0, 101 201 0, 100 200 0)), \
+///                      ((101 200 0, 102 200 0, 101 201 0, 101 200 0)))",
+/// )
+/// .unwrap();
+/// let geometry = encoder.encode(&surfaces).unwrap();
+///
+/// assert_eq!(geometry["type"], "MultiSurface");
+/// assert_eq!(geometry["boundaries"].to_string(), "[[[0,1,2]],[[1,3,2]]]");
+/// assert_eq!(
+///     encoder.into_vertices().to_string(),
+///     "[[0,0,0],[100,0,0],[100,100,0],[200,0,0]]"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CityJsonEncoder {
+    transform: Option<CityJsonTransform>,
+    mesh: IndexedMesh<Vertex>,
+}
+
+impl CityJsonEncoder {
+    /// Creates an encoder that stores vertices as floating-point coordinates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an encoder that quantises vertices with `transform`.
+    pub fn with_transform(transform: CityJsonTransform) -> Self {
+        Self {
+            transform: Some(transform),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the transform to write as the `transform` of the document.
+    pub fn transform(&self) -> Option<&CityJsonTransform> {
+        self.transform.as_ref()
+    }
+
+    /// Returns the number of distinct vertices encoded so far.
+    pub fn vertices_len(&self) -> usize {
+        self.mesh.vertices.len()
+    }
+
+    /// Returns the `vertices` of the document.
+    pub fn into_vertices(self) -> Value {
+        self.mesh
+            .vertices
+            .into_iter()
+            .map(|x| match x {
+                Vertex::Quantized(x) => json!(x),
+                Vertex::Coordinates(x) => json!(x),
+            })
+            .collect()
+    }
+
+    /// Returns `geometry` as a CityJSON geometry object with `type` and
+    /// `boundaries`, adding its vertices to the vertex list.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Unrepresentable`] for a `MultiGeometry` with members
+    /// other than solids and for a ring or line string that quantisation
+    /// collapses to fewer than three or two vertices, and an error if a member
+    /// is an unresolved `xlink:href` reference.
+    pub fn encode(&mut self, geometry: &AbstractGeometryKind) -> Result<Value, Error> {
+        let (kind, boundaries) = match geometry {
+            AbstractGeometryKind::AbstractGeometricPrimitiveKind(
+                AbstractGeometricPrimitiveKind::AbstractSolidKind(AbstractSolidKind::Solid(x)),
+            ) => ("Solid", self.solid(x)?),
+            AbstractGeometryKind::AbstractGeometricAggregateKind(
+                AbstractGeometricAggregateKind::MultiGeometry(x),
+            ) => ("MultiSolid", self.solids(x)?),
+            AbstractGeometryKind::AbstractGeometricPrimitiveKind(
+                AbstractGeometricPrimitiveKind::AbstractSurfaceKind(
+                    AbstractSurfaceKind::CompositeSurface(_) | AbstractSurfaceKind::Shell(_),
+                ),
+            ) => match SimpleGeometry::from_geometry(geometry, FORMAT)? {
+                SimpleGeometry::PolyhedralSurface(x) | SimpleGeometry::Tin(x) => {
+                    ("CompositeSurface", self.surfaces(&x)?)
+                }
+                _ => unreachable!("composite surfaces are polyhedral surfaces"),
+            },
+            _ => match SimpleGeometry::from_geometry(geometry, FORMAT)? {
+                SimpleGeometry::Point(x) => ("MultiPoint", json!([self.vertex(&x)])),
+                SimpleGeometry::MultiPoint(x) => ("MultiPoint", self.vertices(&x)),
+                SimpleGeometry::LineString(x) => {
+                    ("MultiLineString", json!([self.line_string(&x)?]))
+                }
+                SimpleGeometry::MultiLineString(x) => (
+                    "MultiLineString",
+                    x.iter()
+                        .map(|x| self.line_string(x))
+                        .collect::<Result<_, _>>()?,
+                ),
+                SimpleGeometry::Polygon(x) | SimpleGeometry::Triangle(x) => {
+                    ("MultiSurface", json!([self.rings(&x)?]))
+                }
+                SimpleGeometry::MultiPolygon(x)
+                | SimpleGeometry::PolyhedralSurface(x)
+                | SimpleGeometry::Tin(x) => ("MultiSurface", self.surfaces(&x)?),
+                SimpleGeometry::GeometryCollection(_) => {
+                    unreachable!("only a MultiGeometry is a geometry collection")
+                }
+            },
+        };
+        Ok(json!({"type": kind, "boundaries": boundaries}))
+    }
+
+    /// Returns `geometry`, a `MultiGeometry` of solids, as a CityJSON
+    /// `CompositeSolid`, which egml has no geometry type of its own for.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`encode`](Self::encode), and
+    /// [`Error::Unrepresentable`] if `geometry` is not a `MultiGeometry`.
+    pub fn encode_composite_solid(
+        &mut self,
+        geometry: &AbstractGeometryKind,
+    ) -> Result<Value, Error> {
+        let AbstractGeometryKind::AbstractGeometricAggregateKind(
+            AbstractGeometricAggregateKind::MultiGeometry(x),
+        ) = geometry
+        else {
+            return Err(Error::Unrepresentable {
+                format: FORMAT,
+                reason: "a CompositeSolid of anything but a MultiGeometry".to_string(),
+            });
+        };
+        Ok(json!({"type": "CompositeSolid", "boundaries": self.solids(x)?}))
+    }
+
+    fn solids(&mut self, multi_geometry: &MultiGeometry) -> Result<Value, Error> {
+        let mut solids = Vec::new();
+        let members = multi_geometry
+            .geometry_member()
+            .iter()
+            .map(|x| x.object().ok_or(Error::UnsupportedXLink))
+            .chain(
+                multi_geometry
+                    .geometry_members()
+                    .map(|x| x.objects())
+                    .unwrap_or(&[])
+                    .iter()
+                    .map(Ok),
+            );
+        for member in members {
+            let AbstractGeometryKind::AbstractGeometricPrimitiveKind(
+                AbstractGeometricPrimitiveKind::AbstractSolidKind(AbstractSolidKind::Solid(solid)),
+            ) = member?
+            else {
+                return Err(Error::Unrepresentable {
+                    format: FORMAT,
+                    reason: "a MultiGeometry with members other than solids".to_string(),
+                });
+            };
+            solids.push(self.solid(solid)?);
+        }
+        Ok(Value::Array(solids))
+    }
+
+    fn solid(&mut self, solid: &Solid) -> Result<Value, Error> {
+        solid_shells(solid, FORMAT)?
+            .iter()
+            .map(|x| self.surfaces(x))
+            .collect()
+    }
+
+    fn surfaces(&mut self, surfaces: &[Vec<Ring>]) -> Result<Value, Error> {
+        surfaces.iter().map(|x| self.rings(x)).collect()
+    }
+
+    /// The rings of a surface without their closing vertex, which CityJSON
+    /// does not repeat.
+    fn rings(&mut self, rings: &[Ring]) -> Result<Value, Error> {
+        rings
+            .iter()
+            .map(|x| {
+                let mut indices = self.path(x);
+                if indices.len() > 1 && indices.first() == indices.last() {
+                    indices.pop();
+                }
+                if indices.len() < 3 {
+                    return Err(Error::Unrepresentable {
+                        format: FORMAT,
+                        reason: "a ring that quantisation collapses to fewer than three vertices"
+                            .to_string(),
+                    });
+                }
+                Ok(json!(indices))
+            })
+            .collect()
+    }
+
+    fn line_string(&mut self, positions: &[DirectPosition]) -> Result<Value, Error> {
+        let indices = self.path(positions);
+        if indices.len() < 2 {
+            return Err(Error::Unrepresentable {
+                format: FORMAT,
+                reason: "a line string that quantisation collapses to a single vertex".to_string(),
+            });
+        }
+        Ok(json!(indices))
+    }
+
+    /// The indices of `positions` without consecutive repetitions, which
+    /// quantising positions closer than the scale produces.
+    fn path(&mut self, positions: &[DirectPosition]) -> Vec<usize> {
+        let mut indices: Vec<usize> = positions.iter().map(|x| self.vertex(x)).collect();
+        indices.dedup();
+        indices
+    }
+
+    fn vertices(&mut self, positions: &[DirectPosition]) -> Value {
+        positions.iter().map(|x| json!(self.vertex(x))).collect()
+    }
+
+    /// The index of `position` in the vertex list, adding it if it is new.
+    fn vertex(&mut self, position: &DirectPosition) -> usize {
+        let vertex = match &self.transform {
+            Some(transform) => Vertex::Quantized(transform.quantize(position)),
+            None => Vertex::Coordinates(position.coords()),
+        };
+        self.mesh.push_vertex(vertex)
+    }
+}
+
+/// A vertex of the document, quantised if the encoder has a transform.
+#[derive(Debug, Clone, Copy)]
+enum Vertex {
+    Quantized([i64; 3]),
+    Coordinates([f64; 3]),
+}
+
+impl MeshVertex for Vertex {
+    /// An encoder only holds vertices of one kind, so their keys never mix.
+    type Key = [u64; 3];
+
+    fn weld(self) -> (Self, Self::Key) {
+        match self {
+            Self::Quantized(x) => (self, x.map(|x| x as u64)),
+            Self::Coordinates(x) => {
+                let (x, key) = x.weld();
+                (Self::Coordinates(x), key)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cityjson::CityJsonDecoder;
+    use crate::wkt::{read_wkt, write_wkt};
+    use egml_core::model::geometry::AbstractGeometryProperty;
+    use egml_core::model::geometry::primitives::Point;
+
+    const CUBE: &str = "POLYHEDRALSURFACE Z (\
+        ((0 0 0, 0 1 0, 1 1 0, 1 0 0, 0 0 0)), ((0 0 1, 1 0 1, 1 1 1, 0 1 1, 0 0 1)), \
+        ((0 0 0, 1 0 0, 1 0 1, 0 0 1, 0 0 0)), ((1 0 0, 1 1 0, 1 1 1, 1 0 1, 1 0 0)), \
+        ((1 1 0, 0 1 0, 0 1 1, 1 1 1, 1 1 0)), ((0 1 0, 0 0 0, 0 0 1, 0 1 1, 0 1 0)))";
+
+    fn multi_solid(members: Vec<AbstractGeometryKind>) -> AbstractGeometryKind {
+        let mut multi_geometry = MultiGeometry::new(None).unwrap();
+        multi_geometry.set_geometry_member(
+            members
+                .into_iter()
+                .map(AbstractGeometryProperty::from_object)
+                .collect(),
+        );
+        multi_geometry.into()
+    }
+
+    #[test]
+    fn geometries_share_deduplicated_vertices_and_round_trip() {
+        let geometries = [
+            read_wkt(CUBE).unwrap(),
+            read_wkt("MULTIPOINT Z ((0 0 0), (1 1 1))").unwrap(),
+            read_wkt("MULTILINESTRING Z ((0 0 0, 1 0 0), (1 0 1, 0 0 1))").unwrap(),
+            read_wkt("MULTIPOLYGON Z (((0 0 1, 1 0 1, 1 1 1, 0 0 1)))").unwrap(),
+        ];
+        let mut encoder = CityJsonEncoder::new();
+        let encoded: Vec<_> = geometries
+            .iter()
+            .map(|x| encoder.encode(x).unwrap())
+            .collect();
+
+        assert_eq!(encoder.vertices_len(), 8);
+        assert_eq!(encoded[0]["type"], "Solid");
+        assert_eq!(encoded[0]["boundaries"][0][0], json!([[0, 1, 2, 3]]));
+        assert_eq!(encoded[1]["boundaries"], json!([0, 6]));
+
+        let decoder = CityJsonDecoder::new(&encoder.into_vertices(), None).unwrap();
+        for (geometry, encoded) in geometries.iter().zip(&encoded) {
+            let decoded = decoder.decode(encoded).unwrap();
+            assert_eq!(write_wkt(&decoded).unwrap(), write_wkt(geometry).unwrap());
+        }
+    }
+
+    #[test]
+    fn quantised_vertices_merge_within_the_scale() {
+        let transform = CityJsonTransform::with_decimals(2, &DirectPosition::ORIGIN);
+        let mut encoder = CityJsonEncoder::with_transform(transform);
+        let points = [
+            read_wkt("POINT Z (1.001 2 3)").unwrap(),
+            read_wkt("POINT Z (0.999 2 3)").unwrap(),
+        ];
+
+        let encoded: Vec<_> = points.iter().map(|x| encoder.encode(x).unwrap()).collect();
+
+        assert_eq!(encoded[0]["boundaries"], json!([0]));
+        assert_eq!(encoded[1]["boundaries"], json!([0]));
+        assert_eq!(encoder.transform(), Some(&transform));
+        let decoder = CityJsonDecoder::new(&encoder.into_vertices(), Some(&transform)).unwrap();
+        assert_eq!(decoder.vertices()[0].coords(), [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn solids_with_cavities_and_multi_solids_round_trip() {
+        let outer = "((0 0 0, 0 4 0, 4 0 0, 0 0 0)), ((0 0 0, 4 0 0, 0 0 4, 0 0 0)), \
+                     ((0 0 0, 0 0 4, 0 4 0, 0 0 0)), ((4 0 0, 0 4 0, 0 0 4, 4 0 0))";
+        let inner = "((1 1 1, 2 1 1, 1 2 1, 1 1 1)), ((1 1 1, 1 1 2, 2 1 1, 1 1 1)), \
+                     ((1 1 1, 1 2 1, 1 1 2, 1 1 1)), ((2 1 1, 1 1 2, 1 2 1, 2 1 1))";
+        let mut solid =
+            Solid::try_from(read_wkt(&format!("POLYHEDRALSURFACE Z ({outer})")).unwrap()).unwrap();
+        let cavity =
+            Solid::try_from(read_wkt(&format!("POLYHEDRALSURFACE Z ({inner})")).unwrap()).unwrap();
+        solid.push_interior(cavity.exterior().unwrap().clone());
+        let geometry = multi_solid(vec![solid.clone().into(), read_wkt(CUBE).unwrap()]);
+
+        let mut encoder = CityJsonEncoder::new();
+        let encoded = encoder.encode(&geometry).unwrap();
+        assert_eq!(encoded["type"], "MultiSolid");
+        assert_eq!(encoded["boundaries"][0].as_array().unwrap().len(), 2);
+
+        let decoder = CityJsonDecoder::new(&encoder.into_vertices(), None).unwrap();
+        let AbstractGeometryKind::AbstractGeometricAggregateKind(
+            AbstractGeometricAggregateKind::MultiGeometry(decoded),
+        ) = decoder.decode(&encoded).unwrap()
+        else {
+            panic!("expected a MultiGeometry");
+        };
+        let first = decoded.geometry_member()[0].object().unwrap().clone();
+        assert_eq!(Solid::try_from(first).unwrap().points(), solid.points());
+    }
+
+    #[test]
+    fn shells_are_written_as_composite_surfaces() {
+        let shell = read_wkt(CUBE)
+            .map(|x| Solid::try_from(x).unwrap())
+            .unwrap()
+            .exterior()
+            .unwrap()
+            .object()
+            .unwrap()
+            .clone();
+        let mut encoder = CityJsonEncoder::new();
+
+        let encoded = encoder.encode(&shell.into()).unwrap();
+
+        assert_eq!(encoded["type"], "CompositeSurface");
+        let decoder = CityJsonDecoder::new(&encoder.into_vertices(), None).unwrap();
+        let decoded = decoder.decode(&encoded).unwrap();
+        assert!(matches!(
+            decoded,
+            AbstractGeometryKind::AbstractGeometricPrimitiveKind(
+                AbstractGeometricPrimitiveKind::AbstractSurfaceKind(
+                    AbstractSurfaceKind::CompositeSurface(_)
+                )
+            )
+        ));
+    }
+
+    #[test]
+    fn mixed_multi_geometries_are_unrepresentable() {
+        let geometry = multi_solid(vec![
+            read_wkt(CUBE).unwrap(),
+            Point::new(DirectPosition::ORIGIN).into(),
+        ]);
+
+        assert!(matches!(
+            CityJsonEncoder::new().encode(&geometry),
+            Err(Error::Unrepresentable { .. })
+        ));
+    }
+
+    #[test]
+    fn quantisation_drops_repeated_vertices_or_rejects_collapsed_rings() {
+        let transform = CityJsonTransform::with_decimals(2, &DirectPosition::ORIGIN);
+        let mut encoder = CityJsonEncoder::with_transform(transform);
+        let polygon = read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1.0001 0 0, 1 1 0, 0 0 0))").unwrap();
+        let collapsed = read_wkt("POLYGON Z ((0 0 0, 0.001 0 0, 0 0.001 0, 0 0 0))").unwrap();
+
+        let encoded = encoder.encode(&polygon).unwrap();
+
+        assert_eq!(encoded["boundaries"], json!([[[0, 1, 2]]]));
+        assert!(matches!(
+            encoder.encode(&collapsed),
+            Err(Error::Unrepresentable { .. })
+        ));
+        let decoder = CityJsonDecoder::new(&encoder.into_vertices(), Some(&transform)).unwrap();
+        assert!(decoder.decode(&encoded).is_ok());
+    }
+
+    #[test]
+    fn multi_geometries_of_solids_can_be_composite_solids() {
+        let geometry = multi_solid(vec![read_wkt(CUBE).unwrap()]);
+        let mut encoder = CityJsonEncoder::new();
+
+        let encoded = encoder.encode_composite_solid(&geometry).unwrap();
+
+        assert_eq!(encoded["type"], "CompositeSolid");
+        assert_eq!(
+            encoded["boundaries"],
+            encoder.encode(&geometry).unwrap()["boundaries"]
+        );
+        assert!(matches!(
+            encoder.encode_composite_solid(&read_wkt(CUBE).unwrap()),
+            Err(Error::Unrepresentable { .. })
+        ));
+    }
+}
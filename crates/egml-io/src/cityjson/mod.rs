@@ -0,0 +1,20 @@
+//! The geometry part of CityJSON: `boundaries` arrays that index into the
+//! shared, optionally quantised `vertices` of a document.
+//!
+//! A [`CityJsonEncoder`] collects the vertices of every geometry it encodes
+//! in one deduplicated list, which becomes the `vertices` of the document,
+//! and a [`CityJsonDecoder`] resolves the indices of `boundaries` against
+//! them. Rings are written without the closing vertex, as CityJSON and the
+//! egml model both store them.
+//!
+//! City objects, levels of detail, semantics and appearances are not
+//! covered; the encoder produces and the decoder reads only the `type` and
+//! `boundaries` of a geometry object.
+
+mod decoder;
+mod encoder;
+mod transform;
+
+pub use decoder::*;
+pub use encoder::*;
+pub use transform::*;
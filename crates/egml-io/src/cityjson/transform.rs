@@ -0,0 +1,54 @@
+use egml_core::model::geometry::DirectPosition;
+use serde::{Deserialize, Serialize};
+
+/// The `transform` of a CityJSON document, which quantises vertices to
+/// integers: a coordinate is `integer * scale + translate`.
+///
+/// It serializes to the `transform` member, e.g.
+/// `{"scale": [0.001, 0.001, 0.001], "translate": [...]}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CityJsonTransform {
+    pub scale: [f64; 3],
+    pub translate: [f64; 3],
+}
+
+impl CityJsonTransform {
+    pub fn new(scale: [f64; 3], translate: [f64; 3]) -> Self {
+        Self { scale, translate }
+    }
+
+    /// A transform that keeps `decimals` decimal places on every axis and
+    /// stores coordinates relative to `origin`, usually the lower corner of
+    /// the document's envelope.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use egml_core::model::geometry::DirectPosition;
+    /// use egml_io::cityjson::CityJsonTransform;
+    ///
+    /// let origin = DirectPosition::new(691000.0, 5336000.0, 400.0).unwrap();
+    /// let transform = CityJsonTransform::with_decimals(3, &origin);
+    ///
+    /// assert_eq!(transform.scale, [0.001; 3]);
+    /// assert_eq!(transform.translate, [691000.0, 5336000.0, 400.0]);
+    /// ```
+    pub fn with_decimals(decimals: u8, origin: &DirectPosition) -> Self {
+        let scale = 10f64.powi(-i32::from(decimals));
+        Self::new([scale; 3], origin.coords())
+    }
+
+    /// The integer vertex of `position`, rounded to the nearest multiple of
+    /// the scale.
+    pub fn quantize(&self, position: &DirectPosition) -> [i64; 3] {
+        let coords = position.coords();
+        std::array::from_fn(|axis| {
+            ((coords[axis] - self.translate[axis]) / self.scale[axis]).round() as i64
+        })
+    }
+
+    /// The coordinates of the integer `vertex`.
+    pub fn dequantize(&self, vertex: [i64; 3]) -> [f64; 3] {
+        std::array::from_fn(|axis| vertex[axis] as f64 * self.scale[axis] + self.translate[axis])
+    }
+}
@@ -38,6 +38,13 @@ pub fn serialize_abstract_gml(
             .attributes
             .push(("gml:id".to_string(), id.to_string()));
     }
+    let foreign_content = abstract_gml.foreign_content();
+    xml_node_parts
+        .attributes
+        .extend(foreign_content.attributes().iter().cloned());
+    xml_node_parts
+        .foreign_children
+        .extend(foreign_content.children().iter().cloned());
 
     if let Some(raw) = serialize_inner(GmlAbstractGml::from(abstract_gml), formatting)? {
         xml_node_parts.content.push(XmlNodeContent::Raw(raw));
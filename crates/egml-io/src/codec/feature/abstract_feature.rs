@@ -2,9 +2,10 @@ use crate::Error;
 use crate::codec::base::{deserialize_abstract_gml, serialize_abstract_gml};
 use crate::codec::feature::bounding_shape::GmlBoundingShape;
 use crate::util::{
-    Formatting, GmlElement, XmlElementSpans, XmlNodeContent, XmlNodeParts, serialize_inner,
+    Formatting, GmlElement, ParseContext, XmlElementSpans, XmlNodeContent, XmlNodeParts,
+    extract_foreign_content, serialize_inner,
 };
-use egml_core::model::base::AsAbstractGml;
+use egml_core::model::base::{AsAbstractGml, AsAbstractGmlMut};
 use egml_core::model::feature::{AbstractFeature, AsAbstractFeature, AsAbstractFeatureMut};
 use quick_xml::de;
 use serde::{Deserialize, Serialize};
@@ -19,6 +20,10 @@ pub fn deserialize_abstract_feature(
     let parsed: GmlAbstractFeature = de::from_reader(xml_document)?;
     abstract_feature.set_bounded_by(parsed.bounded_by.map(|x| x.try_into()).transpose()?);
 
+    if ParseContext::options().preserve_unknown {
+        abstract_feature.set_foreign_content(extract_foreign_content(xml_document)?);
+    }
+
     Ok(abstract_feature)
 }
 
@@ -54,8 +59,14 @@ impl From<&AbstractFeature> for GmlAbstractFeature {
 
 #[cfg(test)]
 mod tests {
-    use crate::codec::feature::abstract_feature::deserialize_abstract_feature;
-    use crate::util::{GmlElement, XmlElementSpans, extract_xml_element_spans};
+    use crate::codec::feature::abstract_feature::{
+        deserialize_abstract_feature, serialize_abstract_feature,
+    };
+    use crate::util::{
+        Formatting, GmlElement, ParseOptions, XmlElementSpans, XmlNode, deserialize_with_options,
+        extract_xml_element_spans,
+    };
+    use egml_core::model::base::AsAbstractGml;
     use egml_core::model::feature::{AbstractFeature, AsAbstractFeature};
     use egml_core::model::geometry::Envelope;
 
@@ -88,4 +99,31 @@ mod tests {
         assert_eq!(envelope.upper_corner().y(), 12.0);
         assert_eq!(envelope.upper_corner().z(), 13.0);
     }
+
+    #[test]
+    fn preserved_unknown_feature_content_is_written_in_place() {
+        let xml = "<ExampleFeature gml:id=\"f1\" app:source=\"survey\">\
+            <!-- imported -->\
+            <gml:name>house</gml:name>\
+            <app:height>12.5</app:height>\
+            <gml:boundedBy><gml:Envelope srsDimension=\"3\"><gml:lowerCorner srsDimension=\"3\">1 2 3</gml:lowerCorner><gml:upperCorner srsDimension=\"3\">11 12 13</gml:upperCorner></gml:Envelope></gml:boundedBy>\
+            <app:usage>residential</app:usage>\
+            </ExampleFeature>";
+        let options = ParseOptions::default().with_preserve_unknown(true);
+
+        let feature = deserialize_with_options(xml.as_bytes(), &options, |x| {
+            deserialize_abstract_feature(x, &extract_xml_element_spans(x)?)
+        })
+        .unwrap()
+        .into_value();
+
+        assert_eq!(feature.foreign_content().children().len(), 3);
+        let parts = serialize_abstract_feature(&feature, Formatting::Compact).unwrap();
+        assert_eq!(
+            XmlNode::new("ExampleFeature", parts)
+                .to_string(Formatting::Compact)
+                .unwrap(),
+            xml
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use crate::Error;
-use crate::codec::geometry::GmlEnvelope;
+use crate::codec::geometry::{GmlBox, GmlEnvelope};
 use egml_core::model::feature::BoundingShape;
 use egml_core::model::geometry::Envelope;
 use serde::{Deserialize, Serialize};
@@ -11,13 +11,20 @@ pub struct GmlBoundingShape {
         skip_serializing_if = "Option::is_none"
     )]
     pub envelope: Option<GmlEnvelope>,
+
+    /// The GML 2 equivalent of `gml:Envelope`, read but never written.
+    #[serde(rename = "Box", skip_serializing)]
+    pub r#box: Option<GmlBox>,
 }
 
 impl TryFrom<GmlBoundingShape> for BoundingShape {
     type Error = Error;
 
     fn try_from(item: GmlBoundingShape) -> Result<Self, Self::Error> {
-        let envelope: Option<Envelope> = item.envelope.map(|x| x.try_into()).transpose()?;
+        let envelope: Option<Envelope> = match item.envelope {
+            Some(x) => Some(x.try_into()?),
+            None => item.r#box.map(|x| x.try_into()).transpose()?,
+        };
 
         let bounding_shape = BoundingShape::new_unchecked(envelope, None);
         Ok(bounding_shape)
@@ -28,6 +35,7 @@ impl From<&BoundingShape> for GmlBoundingShape {
     fn from(item: &BoundingShape) -> Self {
         Self {
             envelope: item.envelope().map(|x| x.into()),
+            r#box: None,
         }
     }
 }
@@ -1,7 +1,10 @@
 use crate::Error;
 use crate::codec::base::{deserialize_abstract_gml, serialize_abstract_gml};
-use crate::util::{Formatting, GmlElement, XmlElementSpans, XmlNodeParts};
-use egml_core::model::base::AsAbstractGml;
+use crate::util::{
+    Formatting, GmlElement, ParseContext, XmlElementSpans, XmlNodeParts, check_gml_root,
+    extract_foreign_content,
+};
+use egml_core::model::base::{AsAbstractGml, AsAbstractGmlMut};
 use egml_core::model::geometry::{AbstractGeometry, AsAbstractGeometry, AsAbstractGeometryMut};
 use quick_xml::de;
 use serde::{Deserialize, Serialize};
@@ -10,6 +13,7 @@ pub fn deserialize_abstract_geometry(
     xml_document: &[u8],
     spans: &XmlElementSpans<GmlElement>,
 ) -> Result<AbstractGeometry, Error> {
+    check_gml_root(xml_document)?;
     let abstract_gml = deserialize_abstract_gml(xml_document, spans)?;
     let mut abstract_geometry = AbstractGeometry::from_abstract_gml(abstract_gml);
 
@@ -17,6 +21,10 @@ pub fn deserialize_abstract_geometry(
     abstract_geometry.set_srs_name_opt(parsed.srs_name);
     abstract_geometry.set_srs_dimension_opt(parsed.srs_dimension);
 
+    if ParseContext::options().preserve_unknown {
+        abstract_geometry.set_foreign_content(extract_foreign_content(xml_document)?);
+    }
+
     Ok(abstract_geometry)
 }
 
@@ -6,7 +6,9 @@ use crate::codec::base::{
 use crate::codec::geometry::{
     deserialize_abstract_geometry_kind, serialize_abstract_geometry_kind,
 };
-use crate::util::{Formatting, GmlElement, XmlElementSpans, XmlNode, XmlNodeContent, XmlNodeParts};
+use crate::util::{
+    Formatting, GmlElement, XmlElementSpans, XmlNode, XmlNodeParts, serialize_members,
+};
 use egml_core::model::base::{HasAssociationAttributes, HasOwnershipAttributes};
 use egml_core::model::geometry::{AbstractGeometryArrayProperty, AbstractGeometryKind};
 use quick_xml::de;
@@ -59,13 +61,11 @@ pub fn serialize_abstract_geometry_array_property(
         abstract_geometry_array_property.ownership(),
     ));
 
-    for object in abstract_geometry_array_property.objects() {
-        parts
-            .content
-            .push(XmlNodeContent::Child(serialize_abstract_geometry_kind(
-                object, formatting,
-            )?));
-    }
+    parts.content.extend(serialize_members(
+        abstract_geometry_array_property.objects(),
+        formatting,
+        serialize_abstract_geometry_kind,
+    )?);
 
     Ok(XmlNode::new(target_xml_element.into(), parts))
 }
@@ -245,12 +245,9 @@ mod tests {
             .unwrap();
 
         assert_eq!(property.href(), Some(&HRef::from_local("some-id")));
-        assert_eq!(property.title().as_deref(), Some("Some Title"));
-        assert_eq!(property.role().as_deref(), Some("http://example.com/role"));
-        assert_eq!(
-            property.arcrole().as_deref(),
-            Some("http://example.com/arcrole")
-        );
+        assert_eq!(property.title(), Some("Some Title"));
+        assert_eq!(property.role(), Some("http://example.com/role"));
+        assert_eq!(property.arcrole(), Some("http://example.com/arcrole"));
         assert_eq!(property.show(), Some(&ShowType::New));
         assert_eq!(property.actuate(), Some(&ActuateType::OnLoad));
         assert!(property.owns());
@@ -43,9 +43,7 @@ mod tests {
     use crate::util::{Formatting, extract_xml_element_spans};
     use egml_core::model::geometry::AbstractGeometryKind;
     use egml_core::model::geometry::DirectPosition;
-    use egml_core::model::geometry::aggregates::{
-        AbstractGeometricAggregateKind, MultiGeometry, MultiPoint,
-    };
+    use egml_core::model::geometry::aggregates::{AbstractGeometricAggregateKind, MultiPoint};
     use egml_core::model::geometry::primitives::{
         AbstractGeometricPrimitiveKind, AbstractRingKind, AbstractRingProperty,
         AbstractSurfaceKind, LinearRing, Point, PointProperty, Polygon,
@@ -1,7 +1,8 @@
 use crate::Error;
 use crate::codec::geometry::aggregates::{
-    deserialize_multi_curve, deserialize_multi_geometry, deserialize_multi_point,
-    deserialize_multi_surface, serialize_multi_curve, serialize_multi_geometry,
+    deserialize_multi_curve, deserialize_multi_geometry, deserialize_multi_line_string,
+    deserialize_multi_point, deserialize_multi_polygon, deserialize_multi_surface,
+    deserialize_simple_multi_point, serialize_multi_curve, serialize_multi_geometry,
     serialize_multi_point, serialize_multi_surface,
 };
 use crate::util::{Formatting, GmlElement, XmlElementSpans, XmlNode};
@@ -26,11 +27,26 @@ pub fn deserialize_abstract_geometric_aggregate_kind(
         return Ok(Some(multi_point.into()));
     }
 
+    if let Some(span) = spans.first(GmlElement::SimpleMultiPoint) {
+        let multi_point = deserialize_simple_multi_point(&xml_document[span.start..span.end])?;
+        return Ok(Some(multi_point.into()));
+    }
+
     if let Some(span) = spans.first(GmlElement::MultiSurface) {
         let multi_surface = deserialize_multi_surface(&xml_document[span.start..span.end])?;
         return Ok(Some(multi_surface.into()));
     }
 
+    if let Some(span) = spans.first(GmlElement::MultiPolygon) {
+        let multi_surface = deserialize_multi_polygon(&xml_document[span.start..span.end])?;
+        return Ok(Some(multi_surface.into()));
+    }
+
+    if let Some(span) = spans.first(GmlElement::MultiLineString) {
+        let multi_curve = deserialize_multi_line_string(&xml_document[span.start..span.end])?;
+        return Ok(Some(multi_curve.into()));
+    }
+
     Ok(None)
 }
 
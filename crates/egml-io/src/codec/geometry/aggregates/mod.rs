@@ -4,10 +4,13 @@ mod multi_curve;
 mod multi_curve_property;
 mod multi_geometry;
 mod multi_geometry_property;
+mod multi_line_string;
 mod multi_point;
 mod multi_point_property;
+mod multi_polygon;
 mod multi_surface;
 mod multi_surface_property;
+mod simple_multi_point;
 
 pub use abstract_geometric_aggregate::*;
 pub use abstract_geometric_aggregate_kind::*;
@@ -15,7 +18,10 @@ pub use multi_curve::*;
 pub use multi_curve_property::*;
 pub use multi_geometry::*;
 pub use multi_geometry_property::*;
+pub use multi_line_string::*;
 pub use multi_point::*;
 pub use multi_point_property::*;
+pub use multi_polygon::*;
 pub use multi_surface::*;
 pub use multi_surface_property::*;
+pub use simple_multi_point::*;
@@ -6,7 +6,7 @@ use crate::codec::geometry::primitives::{
     deserialize_abstract_curve_property, serialize_abstract_curve_property,
 };
 use crate::util::{
-    Formatting, GmlElement, XmlNode, XmlNodeContent, collect_children, extract_xml_element_spans,
+    Formatting, GmlElement, XmlNode, collect_children, extract_xml_element_spans, serialize_members,
 };
 use egml_core::model::geometry::aggregates::{AsAbstractGeometricAggregate, MultiCurve};
 
@@ -37,15 +37,13 @@ pub fn serialize_multi_curve(
         formatting,
     )?;
 
-    for member in multi_curve.curve_member() {
-        parts
-            .content
-            .push(XmlNodeContent::Child(serialize_abstract_curve_property(
-                member,
-                formatting,
-                GmlElement::CurveMemberProperty.into(),
-            )?));
-    }
+    parts.content.extend(serialize_members(
+        multi_curve.curve_member(),
+        formatting,
+        |x, formatting| {
+            serialize_abstract_curve_property(x, formatting, GmlElement::CurveMemberProperty.into())
+        },
+    )?);
 
     Ok(XmlNode::new(GmlElement::MultiCurve.into(), parts))
 }
@@ -8,7 +8,7 @@ use crate::codec::geometry::{
 };
 use crate::util::{
     Formatting, GmlElement, XmlNode, XmlNodeContent, collect_child, collect_children,
-    extract_xml_element_spans,
+    extract_xml_element_spans, serialize_members,
 };
 use egml_core::model::geometry::aggregates::{AsAbstractGeometricAggregate, MultiGeometry};
 
@@ -49,15 +49,17 @@ pub fn serialize_multi_geometry(
         formatting,
     )?;
 
-    for member in multi_geometry.geometry_member() {
-        xml_node_parts
-            .content
-            .push(XmlNodeContent::Child(serialize_abstract_geometry_property(
-                member,
+    xml_node_parts.content.extend(serialize_members(
+        multi_geometry.geometry_member(),
+        formatting,
+        |x, formatting| {
+            serialize_abstract_geometry_property(
+                x,
                 formatting,
                 GmlElement::GeometryMemberProperty.into(),
-            )?));
-    }
+            )
+        },
+    )?);
 
     if let Some(members) = multi_geometry.geometry_members() {
         xml_node_parts.content.push(XmlNodeContent::Child(
@@ -0,0 +1,51 @@
+use crate::Error;
+use crate::codec::geometry::aggregates::deserialize_abstract_geometric_aggregate;
+use crate::codec::geometry::primitives::deserialize_abstract_curve_property;
+use crate::util::{GmlElement, collect_children, extract_xml_element_spans};
+use egml_core::model::geometry::aggregates::MultiCurve;
+
+/// Reads the deprecated `gml:MultiLineString` of GML 2 and 3.1.1 as a
+/// [`MultiCurve`] whose members are the `gml:lineStringMember` line strings.
+pub fn deserialize_multi_line_string(xml_document: &[u8]) -> Result<MultiCurve, Error> {
+    let spans = extract_xml_element_spans(xml_document)?;
+    let abstract_geometric_aggregate =
+        deserialize_abstract_geometric_aggregate(xml_document, &spans)?;
+
+    let line_string_members = collect_children(
+        xml_document,
+        &spans,
+        GmlElement::LineStringMemberProperty,
+        deserialize_abstract_curve_property,
+    )?;
+
+    Ok(MultiCurve::from_abstract_geometric_aggregate(
+        abstract_geometric_aggregate,
+        line_string_members,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codec::geometry::aggregates::multi_line_string::deserialize_multi_line_string;
+
+    #[test]
+    fn deserialize_gml2_multi_line_string() {
+        let xml_document = b"<gml:MultiLineString>
+              <gml:lineStringMember>
+                <gml:LineString>
+                  <gml:coordinates>0,0,0 1,1,1</gml:coordinates>
+                </gml:LineString>
+              </gml:lineStringMember>
+              <gml:lineStringMember>
+                <gml:LineString>
+                  <gml:coord><gml:X>2</gml:X><gml:Y>2</gml:Y><gml:Z>2</gml:Z></gml:coord>
+                  <gml:coord><gml:X>3</gml:X><gml:Y>3</gml:Y><gml:Z>3</gml:Z></gml:coord>
+                </gml:LineString>
+              </gml:lineStringMember>
+            </gml:MultiLineString>";
+
+        let result = deserialize_multi_line_string(xml_document).unwrap();
+
+        assert_eq!(result.curve_member().len(), 2);
+    }
+}
@@ -1,14 +1,15 @@
 use crate::Error;
 use crate::codec::geometry::aggregates::{
     deserialize_abstract_geometric_aggregate, serialize_abstract_geometric_aggregate,
+    serialize_simple_multi_point,
 };
 use crate::codec::geometry::primitives::{
     deserialize_point_array_property, deserialize_point_property, serialize_point_array_property,
     serialize_point_property,
 };
 use crate::util::{
-    Formatting, GmlElement, XmlNode, XmlNodeContent, collect_child, collect_children,
-    extract_xml_element_spans,
+    Formatting, GmlElement, WriteOptions, XmlNode, XmlNodeContent, collect_child, collect_children,
+    extract_xml_element_spans, serialize_members,
 };
 use egml_core::model::geometry::aggregates::{AsAbstractGeometricAggregate, MultiPoint};
 
@@ -42,20 +43,24 @@ pub fn serialize_multi_point(
     multi_point: &MultiPoint,
     formatting: Formatting,
 ) -> Result<XmlNode, Error> {
+    if WriteOptions::current().compact_encodings
+        && let Some(node) = serialize_simple_multi_point(multi_point, formatting)?
+    {
+        return Ok(node);
+    }
+
     let mut parts = serialize_abstract_geometric_aggregate(
         multi_point.abstract_geometric_aggregate(),
         formatting,
     )?;
 
-    for member in multi_point.point_member() {
-        parts
-            .content
-            .push(XmlNodeContent::Child(serialize_point_property(
-                member,
-                formatting,
-                GmlElement::PointMemberProperty.into(),
-            )?));
-    }
+    parts.content.extend(serialize_members(
+        multi_point.point_member(),
+        formatting,
+        |x, formatting| {
+            serialize_point_property(x, formatting, GmlElement::PointMemberProperty.into())
+        },
+    )?);
 
     if let Some(members) = multi_point.point_members() {
         parts
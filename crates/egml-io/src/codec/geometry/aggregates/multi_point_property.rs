@@ -3,7 +3,9 @@ use crate::codec::base::{
     GmlAssociationAttributes, GmlOwnershipAttributes, serialize_association_attributes,
     serialize_ownership_attributes,
 };
-use crate::codec::geometry::aggregates::{deserialize_multi_point, serialize_multi_point};
+use crate::codec::geometry::aggregates::{
+    deserialize_multi_point, deserialize_simple_multi_point, serialize_multi_point,
+};
 use crate::util::{Formatting, GmlElement, XmlElementSpans, XmlNode, XmlNodeContent, XmlNodeParts};
 use egml_core::model::base::{HasAssociationAttributes, HasOwnershipAttributes};
 use egml_core::model::geometry::aggregates::MultiPointProperty;
@@ -16,10 +18,17 @@ pub fn deserialize_multi_point_property(
 ) -> Result<MultiPointProperty, Error> {
     let parsed: GmlMultiPointProperty = de::from_reader(xml_document)?;
 
-    let object = spans
-        .first(GmlElement::MultiPoint)
-        .map(|span| deserialize_multi_point(&xml_document[span.start..span.end]))
-        .transpose()?;
+    let object = if let Some(span) = spans.first(GmlElement::MultiPoint) {
+        Some(deserialize_multi_point(
+            &xml_document[span.start..span.end],
+        )?)
+    } else if let Some(span) = spans.first(GmlElement::SimpleMultiPoint) {
+        Some(deserialize_simple_multi_point(
+            &xml_document[span.start..span.end],
+        )?)
+    } else {
+        None
+    };
 
     Ok(MultiPointProperty::new(
         object,
@@ -176,12 +185,9 @@ mod tests {
         let property = deserialize_multi_point_property(xml_document, &spans).unwrap();
 
         assert_eq!(property.href(), Some(&HRef::from_local("some-id")));
-        assert_eq!(property.title().as_deref(), Some("Some Title"));
-        assert_eq!(property.role().as_deref(), Some("http://example.com/role"));
-        assert_eq!(
-            property.arcrole().as_deref(),
-            Some("http://example.com/arcrole")
-        );
+        assert_eq!(property.title(), Some("Some Title"));
+        assert_eq!(property.role(), Some("http://example.com/role"));
+        assert_eq!(property.arcrole(), Some("http://example.com/arcrole"));
         assert_eq!(property.show(), Some(&ShowType::New));
         assert_eq!(property.actuate(), Some(&ActuateType::OnLoad));
         assert!(property.owns());
@@ -0,0 +1,70 @@
+use crate::Error;
+use crate::codec::geometry::aggregates::deserialize_abstract_geometric_aggregate;
+use crate::codec::geometry::primitives::deserialize_abstract_surface_property;
+use crate::util::{GmlElement, collect_children_lenient, extract_xml_element_spans};
+use egml_core::model::geometry::aggregates::MultiSurface;
+use tracing::debug;
+
+/// Reads the deprecated `gml:MultiPolygon` of GML 2 and 3.1.1 as a
+/// [`MultiSurface`] whose members are the `gml:polygonMember` polygons.
+pub fn deserialize_multi_polygon(xml_document: &[u8]) -> Result<MultiSurface, Error> {
+    let spans = extract_xml_element_spans(xml_document)?;
+    let abstract_geometric_aggregate =
+        deserialize_abstract_geometric_aggregate(xml_document, &spans)?;
+
+    let (polygon_members, skipped) = collect_children_lenient(
+        xml_document,
+        &spans,
+        GmlElement::PolygonMemberProperty,
+        deserialize_abstract_surface_property,
+    );
+    if !skipped.is_empty() {
+        debug!(
+            count = skipped.len(),
+            "MultiPolygon: dropped invalid polygonMember(s)"
+        );
+    }
+
+    Ok(MultiSurface::from_abstract_geometric_aggregate(
+        abstract_geometric_aggregate,
+        polygon_members,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codec::geometry::aggregates::multi_polygon::deserialize_multi_polygon;
+    use egml_core::model::base::AsAbstractGml;
+
+    #[test]
+    fn deserialize_gml2_multi_polygon() {
+        let xml_document = b"<gml:MultiPolygon gml:id=\"mp1\">
+              <gml:polygonMember>
+                <gml:Polygon>
+                  <gml:outerBoundaryIs>
+                    <gml:LinearRing>
+                      <gml:coordinates>0,0,0 1,0,0 1,1,0 0,0,0</gml:coordinates>
+                    </gml:LinearRing>
+                  </gml:outerBoundaryIs>
+                </gml:Polygon>
+              </gml:polygonMember>
+              <gml:polygonMember>
+                <gml:Polygon>
+                  <gml:exterior>
+                    <gml:LinearRing>
+                      <gml:pos>0 0 1</gml:pos>
+                      <gml:pos>1 0 1</gml:pos>
+                      <gml:pos>1 1 1</gml:pos>
+                      <gml:pos>0 0 1</gml:pos>
+                    </gml:LinearRing>
+                  </gml:exterior>
+                </gml:Polygon>
+              </gml:polygonMember>
+            </gml:MultiPolygon>";
+
+        let result = deserialize_multi_polygon(xml_document).unwrap();
+
+        assert_eq!(result.id().map(|x| x.as_str()), Some("mp1"));
+        assert_eq!(result.surface_member().len(), 2);
+    }
+}
@@ -6,8 +6,8 @@ use crate::codec::geometry::primitives::{
     deserialize_abstract_surface_property, serialize_abstract_surface_property,
 };
 use crate::util::{
-    Formatting, GmlElement, XmlNode, XmlNodeContent, collect_children_lenient,
-    extract_xml_element_spans,
+    Formatting, GmlElement, XmlNode, collect_children_lenient, extract_xml_element_spans,
+    serialize_members,
 };
 use egml_core::model::geometry::aggregates::{AsAbstractGeometricAggregate, MultiSurface};
 use tracing::debug;
@@ -45,15 +45,17 @@ pub fn serialize_multi_surface(
         formatting,
     )?;
 
-    for member in multi_surface.surface_member() {
-        xml_node_parts
-            .content
-            .push(XmlNodeContent::Child(serialize_abstract_surface_property(
-                member,
+    xml_node_parts.content.extend(serialize_members(
+        multi_surface.surface_member(),
+        formatting,
+        |x, formatting| {
+            serialize_abstract_surface_property(
+                x,
                 formatting,
                 GmlElement::SurfaceMemberProperty.into(),
-            )?));
-    }
+            )
+        },
+    )?);
 
     Ok(XmlNode::new(
         GmlElement::MultiSurface.into(),
@@ -66,8 +68,13 @@ mod tests {
     use crate::codec::geometry::aggregates::multi_surface::{
         deserialize_multi_surface, serialize_multi_surface,
     };
-    use crate::util::Formatting;
+    use crate::codec::geometry::primitives::serialize_abstract_surface_property;
+    use crate::util::{
+        Formatting, GmlElement, GmlVersion, ParseOptions, Severity, WriteOptions, XmlNode,
+        XmlNodeContent, XmlNodeParts, deserialize_with_options, serialize_with_options,
+    };
     use egml_core::model::base::{AsAbstractGml, AsAbstractGmlMut};
+    use egml_core::model::common::CoordinatePrecision;
     use egml_core::model::geometry::DirectPosition;
     use egml_core::model::geometry::aggregates::MultiSurface;
     use egml_core::model::geometry::primitives::{
@@ -89,6 +96,51 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn many_members_are_serialized_in_order_with_the_callers_options() {
+        let mut member = make_multi_surface().surface_member()[0].clone();
+        let members: Vec<AbstractSurfaceProperty> = (0..100)
+            .map(|i| {
+                if let Some(AbstractSurfaceKind::Polygon(x)) = member.object_mut() {
+                    x.set_names(vec![format!("roof {i}").as_str().into()]);
+                }
+                member.clone()
+            })
+            .collect();
+        let multi_surface = MultiSurface::new(members.clone()).unwrap();
+        let options = WriteOptions::default().with_precision(CoordinatePrecision::uniform(1));
+
+        for formatting in [
+            Formatting::Compact,
+            Formatting::NewLine,
+            Formatting::Indent { char: ' ', size: 2 },
+        ] {
+            let (parallel, serial) = serialize_with_options(options, || {
+                let serial = members
+                    .iter()
+                    .map(|x| {
+                        serialize_abstract_surface_property(
+                            x,
+                            formatting,
+                            GmlElement::SurfaceMemberProperty.into(),
+                        )
+                        .map(XmlNodeContent::Child)
+                    })
+                    .collect::<Result<_, _>>()
+                    .unwrap();
+                (
+                    serialize_multi_surface(&multi_surface, formatting).unwrap(),
+                    XmlNode::new(GmlElement::MultiSurface.into(), XmlNodeParts::new(serial)),
+                )
+            });
+
+            let xml = parallel.to_string(formatting).unwrap();
+            assert_eq!(xml, serial.to_string(formatting).unwrap());
+            assert!(xml.contains("0.0 0.0 0.0"));
+            assert!(xml.find("roof 9<").unwrap() < xml.find("roof 10<").unwrap());
+        }
+    }
+
     #[test]
     fn deserialize_multi_surface_with_composite_surface_member() {
         let xml_document = b"
@@ -384,4 +436,222 @@ mod tests {
         );
         assert_eq!(recovered.id(), multi_surface.id());
     }
+
+    const RING: &str = "<gml:exterior><gml:LinearRing>\
+        <gml:posList>0 0 0 1 0 0 1 1 0 0 0 0</gml:posList>\
+        </gml:LinearRing></gml:exterior>";
+
+    #[test]
+    fn deserialize_multi_surface_skips_members_from_foreign_namespaces() {
+        let xml_document = format!(
+            "<gml:MultiSurface xmlns:gml=\"http://www.opengis.net/gml/3.2\" \
+             xmlns:ext=\"urn:example\">\
+             <gml:surfaceMember><gml:Polygon>{RING}</gml:Polygon></gml:surfaceMember>\
+             <ext:surfaceMember><gml:Polygon>{RING}</gml:Polygon></ext:surfaceMember>\
+             </gml:MultiSurface>"
+        );
+
+        let result = deserialize_multi_surface(xml_document.as_bytes()).unwrap();
+        assert_eq!(result.surface_member().len(), 1);
+    }
+
+    #[test]
+    fn deserialize_multi_surface_skips_undeclared_prefixes_only_with_options() {
+        let xml_document = format!(
+            "<gml:MultiSurface>\
+             <gml:surfaceMember><gml:Polygon>{RING}</gml:Polygon></gml:surfaceMember>\
+             <ext:surfaceMember><gml:Polygon>{RING}</gml:Polygon></ext:surfaceMember>\
+             </gml:MultiSurface>"
+        );
+
+        let plain = deserialize_multi_surface(xml_document.as_bytes()).unwrap();
+        let parsed = deserialize_with_options(
+            xml_document.as_bytes(),
+            &ParseOptions::default(),
+            deserialize_multi_surface,
+        )
+        .unwrap();
+
+        assert_eq!(plain.surface_member().len(), 2);
+        assert_eq!(parsed.value().surface_member().len(), 1);
+    }
+
+    #[test]
+    fn deserialize_multi_surface_resolves_prefixes_declared_on_the_root() {
+        let xml_document = format!(
+            "<root xmlns:g=\"http://www.opengis.net/gml\" xmlns:gml=\"urn:example\">\
+             <g:MultiSurface>\
+             <g:surfaceMember><g:Polygon>{}</g:Polygon></g:surfaceMember>\
+             <gml:surfaceMember><gml:Polygon>{RING}</gml:Polygon></gml:surfaceMember>\
+             </g:MultiSurface></root>",
+            RING.replace("gml:", "g:")
+        );
+        // Stands in for a CityGML reader that hands the geometry to egml-io.
+        fn deserialize_nested(xml: &[u8]) -> Result<MultiSurface, crate::Error> {
+            let start = xml
+                .windows(15)
+                .position(|w| w == b"<g:MultiSurface")
+                .unwrap();
+            deserialize_multi_surface(&xml[start..xml.len() - "</root>".len()])
+        }
+
+        let parsed = deserialize_with_options(
+            xml_document.as_bytes(),
+            &ParseOptions::default(),
+            deserialize_nested,
+        )
+        .unwrap();
+
+        assert_eq!(parsed.gml_version(), Some(GmlVersion::V3_1));
+        assert_eq!(parsed.value().surface_member().len(), 1);
+    }
+
+    #[test]
+    fn deserialize_multi_surface_resolves_foreign_prefixes_without_options() {
+        let xml_document = format!(
+            "<gml:MultiSurface xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:foo=\"urn:foo\">\
+             <gml:surfaceMember><gml:Polygon>{RING}</gml:Polygon></gml:surfaceMember>\
+             <gml:surfaceMember><foo:Polygon>{RING}</foo:Polygon></gml:surfaceMember>\
+             </gml:MultiSurface>"
+        );
+
+        let result = deserialize_multi_surface(xml_document.as_bytes()).unwrap();
+
+        assert_eq!(result.surface_member().len(), 1);
+    }
+
+    #[test]
+    fn deserialize_multi_surface_drops_members_with_only_foreign_content() {
+        let xml_document = format!(
+            "<gml:MultiSurface xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:foo=\"urn:foo\">\
+             <gml:surfaceMember><gml:Polygon>{RING}</gml:Polygon></gml:surfaceMember>\
+             <gml:surfaceMember><foo:Polygon gml:id=\"f1\">{RING}</foo:Polygon></gml:surfaceMember>\
+             </gml:MultiSurface>"
+        );
+
+        let parsed = deserialize_with_options(
+            xml_document.as_bytes(),
+            &ParseOptions::default(),
+            deserialize_multi_surface,
+        )
+        .unwrap();
+
+        assert_eq!(parsed.value().surface_member().len(), 1);
+        assert_eq!(parsed.diagnostics().len(), 1);
+        let diagnostic = &parsed.diagnostics()[0];
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.element, "gml:surfaceMember");
+        assert_eq!(diagnostic.id.as_ref().map(|x| x.as_str()), Some("f1"));
+        assert_eq!(
+            diagnostic.location.map(|x| x.offset),
+            xml_document.rfind("<gml:surfaceMember>")
+        );
+    }
+
+    /// Polygon member written back by the compact serializer exactly as read.
+    fn member(attributes: &str, content: &str) -> String {
+        format!(
+            "<gml:surfaceMember><gml:Polygon{attributes}>{content}\
+             <gml:exterior><gml:LinearRing><gml:posList srsDimension=\"3\">0 0 0 1 0 0 1 1 0 0 0 0</gml:posList></gml:LinearRing></gml:exterior>\
+             </gml:Polygon></gml:surfaceMember>"
+        )
+    }
+
+    fn round_trip(xml_document: &str) -> String {
+        let options = ParseOptions::default().with_preserve_unknown(true);
+        let multi_surface =
+            deserialize_with_options(xml_document.as_bytes(), &options, deserialize_multi_surface)
+                .unwrap()
+                .into_value();
+        serialize_multi_surface(&multi_surface, Formatting::Compact)
+            .unwrap()
+            .to_string(Formatting::Compact)
+            .unwrap()
+    }
+
+    #[test]
+    fn preserved_foreign_children_stay_between_repeated_members() {
+        let xml_document = format!(
+            "<gml:MultiSurface gml:id=\"ms\">\
+             <ext:first/>{}<ext:a>1</ext:a>{}<ext:b><ext:c/></ext:b><ext:d/>{}<ext:last/>\
+             </gml:MultiSurface>",
+            member("", ""),
+            member("", ""),
+            member("", "")
+        );
+
+        assert_eq!(round_trip(&xml_document), xml_document);
+    }
+
+    #[test]
+    fn preserved_comments_are_written_in_place() {
+        let xml_document = format!(
+            "<gml:MultiSurface>\
+             <!-- before -->{}<!-- between -->{}<!-- after -->\
+             </gml:MultiSurface>",
+            member("", "<!-- inside the first polygon -->"),
+            member("", "")
+        );
+
+        assert_eq!(round_trip(&xml_document), xml_document);
+    }
+
+    #[test]
+    fn preserved_foreign_attributes_are_kept_on_nested_objects() {
+        let xml_document = format!(
+            "<gml:MultiSurface ext:level=\"root\">{}{}</gml:MultiSurface>",
+            member(" gml:id=\"p1\" ext:quality=\"&lt;high&gt;\"", ""),
+            member(" ext:source=\"survey\"", "")
+        )
+        .replace("<gml:LinearRing>", "<gml:LinearRing ext:closed=\"yes\">");
+
+        assert_eq!(round_trip(&xml_document), xml_document);
+    }
+
+    #[test]
+    fn diagnostics_of_parallel_members_are_in_document_order() {
+        let invalid = [3, 17, 18, 42, 99];
+        let members: String = (0..100)
+            .map(|i| {
+                let pos_list = if invalid.contains(&i) {
+                    "0 0 0 1 0 0 0 0 0"
+                } else {
+                    "0 0 0 1 0 0 1 1 0 0 0 0"
+                };
+                format!(
+                    "\n  <gml:surfaceMember><gml:Polygon><gml:exterior><gml:LinearRing gml:id=\"r{i}\">\
+                     <gml:posList>{pos_list}</gml:posList></gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember>"
+                )
+            })
+            .collect();
+        let xml_document = format!("<gml:MultiSurface>{members}\n</gml:MultiSurface>");
+
+        let parsed = deserialize_with_options(
+            xml_document.as_bytes(),
+            &ParseOptions::lenient(),
+            deserialize_multi_surface,
+        )
+        .unwrap();
+
+        let found: Vec<_> = parsed
+            .diagnostics()
+            .iter()
+            .map(|x| {
+                let location = x.location.unwrap();
+                (
+                    x.element,
+                    x.id.as_ref().map(|id| id.as_str().to_string()),
+                    location.line,
+                    location.column,
+                )
+            })
+            .collect();
+        let column = "  <gml:surfaceMember><gml:Polygon>".len() + 1;
+        let expected: Vec<_> = invalid
+            .iter()
+            .map(|i| ("gml:exterior", Some(format!("r{i}")), i + 2, column))
+            .collect();
+        assert_eq!(parsed.value().surface_member().len(), 100);
+        assert_eq!(found, expected);
+    }
 }
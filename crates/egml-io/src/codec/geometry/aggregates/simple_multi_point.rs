@@ -0,0 +1,135 @@
+use crate::Error;
+use crate::codec::geometry::GmlSimplePositions;
+use crate::codec::geometry::aggregates::{
+    deserialize_abstract_geometric_aggregate, serialize_abstract_geometric_aggregate,
+};
+use crate::util::{
+    Formatting, GmlElement, XmlNode, XmlNodeContent, extract_xml_element_spans, serialize_inner,
+};
+use egml_core::model::base::{HasAssociationAttributes, HasOwnershipAttributes};
+use egml_core::model::geometry::DirectPosition;
+use egml_core::model::geometry::aggregates::{AsAbstractGeometricAggregate, MultiPoint};
+use egml_core::model::geometry::primitives::{
+    AbstractGeometricPrimitive, AsAbstractGeometricPrimitive, Point, PointArrayProperty,
+};
+use quick_xml::de;
+
+/// Reads a GML 3.3 `gmlce:SimpleMultiPoint` into a [`MultiPoint`] whose
+/// points are held by its `pointMembers`.
+pub fn deserialize_simple_multi_point(xml_document: &[u8]) -> Result<MultiPoint, Error> {
+    let spans = extract_xml_element_spans(xml_document)?;
+    let abstract_geometric_aggregate =
+        deserialize_abstract_geometric_aggregate(xml_document, &spans)?;
+
+    let parsed: GmlSimplePositions = de::from_reader(xml_document)?;
+    let points: Vec<DirectPosition> = parsed.try_into()?;
+    let point_members =
+        PointArrayProperty::from_objects(points.into_iter().map(Point::new).collect());
+
+    Ok(MultiPoint::from_abstract_geometric_aggregate(
+        abstract_geometric_aggregate,
+        Some(point_members),
+    ))
+}
+
+/// Writes `multi_point` as `gmlce:SimpleMultiPoint`, or returns `None` if it
+/// is empty or a member is a reference or carries attributes of its own.
+pub fn serialize_simple_multi_point(
+    multi_point: &MultiPoint,
+    formatting: Formatting,
+) -> Result<Option<XmlNode>, Error> {
+    let Some(points) = simple_points(multi_point) else {
+        return Ok(None);
+    };
+
+    let mut parts = serialize_abstract_geometric_aggregate(
+        multi_point.abstract_geometric_aggregate(),
+        formatting,
+    )?;
+    if let Some(raw) = serialize_inner(GmlSimplePositions::from(points.as_slice()), formatting)? {
+        parts.content.push(XmlNodeContent::Raw(raw));
+    }
+
+    Ok(Some(XmlNode::new(
+        GmlElement::SimpleMultiPoint.into(),
+        parts,
+    )))
+}
+
+fn simple_points(multi_point: &MultiPoint) -> Option<Vec<DirectPosition>> {
+    let mut points = Vec::new();
+    for member in multi_point.point_member() {
+        if member.association() != &Default::default() || member.ownership() != &Default::default()
+        {
+            return None;
+        }
+        points.push(member.object()?);
+    }
+    if let Some(members) = multi_point.point_members() {
+        if members.association() != &Default::default()
+            || members.ownership() != &Default::default()
+        {
+            return None;
+        }
+        points.extend(members.objects());
+    }
+
+    let simple =
+        |x: &&Point| x.abstract_geometric_primitive() == &AbstractGeometricPrimitive::default();
+    if points.is_empty() || !points.iter().all(simple) {
+        return None;
+    }
+    Some(points.into_iter().map(|x| *x.pos()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codec::geometry::aggregates::{
+        deserialize_simple_multi_point, serialize_simple_multi_point,
+    };
+    use crate::util::Formatting;
+    use egml_core::model::base::{AsAbstractGmlMut, Id};
+    use egml_core::model::geometry::DirectPosition;
+    use egml_core::model::geometry::aggregates::MultiPoint;
+    use egml_core::model::geometry::primitives::{Point, PointProperty};
+
+    #[test]
+    fn deserialize_simple_multi_point_reads_all_positions() {
+        let xml_document = b"<gmlce:SimpleMultiPoint gml:id=\"mp\">
+                <gml:posList>0 0 0 1 1 1 2 2 2</gml:posList>
+            </gmlce:SimpleMultiPoint>";
+
+        let multi_point = deserialize_simple_multi_point(xml_document).expect("should deserialize");
+
+        let points = multi_point.point_members().unwrap().objects();
+        assert_eq!(points.len(), 3);
+        assert_eq!(
+            points[2].pos(),
+            &DirectPosition::new(2.0, 2.0, 2.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn serialize_simple_multi_point_requires_plain_points() {
+        let point = |x: f64| Point::new(DirectPosition::new(x, 0.0, 0.0).unwrap());
+        let mut multi_point = MultiPoint::new(None).unwrap();
+        multi_point.push_point_member(PointProperty::from_object(point(1.0)));
+        multi_point.push_point_member(PointProperty::from_object(point(2.0)));
+
+        let xml = serialize_simple_multi_point(&multi_point, Formatting::Compact)
+            .unwrap()
+            .expect("should qualify")
+            .to_string(Formatting::Compact)
+            .unwrap();
+        let mut with_id = point(3.0);
+        with_id.set_id(Id::try_from("p3").unwrap());
+        multi_point.push_point_member(PointProperty::from_object(with_id));
+
+        assert!(xml.contains("<gml:posList srsDimension=\"3\">1 0 0 2 0 0</gml:posList>"));
+        assert!(
+            serialize_simple_multi_point(&multi_point, Formatting::Compact)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
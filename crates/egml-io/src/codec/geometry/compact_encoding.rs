@@ -0,0 +1,64 @@
+use crate::Error;
+use crate::codec::geometry::direct_position_list::GmlDirectPositionList;
+use crate::util::repair_ring_positions;
+use egml_core::model::base::{HasAssociationAttributes, HasOwnershipAttributes};
+use egml_core::model::geometry::DirectPosition;
+use egml_core::model::geometry::primitives::{
+    AbstractRing, AbstractRingKind, AbstractRingProperty, AsAbstractRing,
+};
+use quick_xml::de;
+use serde::{Deserialize, Serialize};
+
+/// Content shared by the GML 3.3 compact encodings: a single `gml:posList`
+/// that replaces the ring wrappers of the plain encoding.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct GmlSimplePositions {
+    #[serde(rename(serialize = "gml:posList", deserialize = "posList"))]
+    pub pos_list: GmlDirectPositionList,
+}
+
+impl TryFrom<GmlSimplePositions> for Vec<DirectPosition> {
+    type Error = Error;
+
+    fn try_from(item: GmlSimplePositions) -> Result<Self, Self::Error> {
+        item.pos_list.try_into()
+    }
+}
+
+impl From<&[DirectPosition]> for GmlSimplePositions {
+    fn from(points: &[DirectPosition]) -> Self {
+        Self {
+            pos_list: points.into(),
+        }
+    }
+}
+
+/// Reads the open ring encoded by a compact element such as
+/// `gmlce:SimplePolygon`. The closing vertex is implicit in these encodings,
+/// but a repeated one is tolerated and removed like in a `gml:LinearRing`.
+pub(crate) fn deserialize_simple_ring_positions(
+    xml_document: &[u8],
+    element: &'static str,
+) -> Result<Vec<DirectPosition>, Error> {
+    let parsed: GmlSimplePositions = de::from_reader(xml_document)?;
+    let mut points: Vec<DirectPosition> = parsed.try_into()?;
+    repair_ring_positions(&mut points, element, None, xml_document);
+    Ok(points)
+}
+
+/// Returns the points of `exterior` if the property holds nothing but an
+/// inline `gml:LinearRing` without attributes, so that it can be written as a
+/// bare `gml:posList`.
+pub(crate) fn simple_ring_points(exterior: &AbstractRingProperty) -> Option<&[DirectPosition]> {
+    if exterior.association() != &Default::default() || exterior.ownership() != &Default::default()
+    {
+        return None;
+    }
+
+    match exterior.object()? {
+        AbstractRingKind::LinearRing(x) if x.abstract_ring() == &AbstractRing::default() => {
+            Some(x.points())
+        }
+        _ => None,
+    }
+}
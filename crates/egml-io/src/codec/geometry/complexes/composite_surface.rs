@@ -4,7 +4,7 @@ use crate::codec::geometry::primitives::{
     serialize_abstract_surface, serialize_abstract_surface_property,
 };
 use crate::util::{
-    Formatting, GmlElement, XmlNode, XmlNodeContent, collect_children, extract_xml_element_spans,
+    Formatting, GmlElement, XmlNode, collect_children, extract_xml_element_spans, serialize_members,
 };
 use egml_core::model::geometry::aggregates::AggregationType;
 use egml_core::model::geometry::complexes::CompositeSurface;
@@ -34,15 +34,17 @@ pub fn serialize_composite_surface(
 ) -> Result<XmlNode, Error> {
     let mut parts = serialize_abstract_surface(surface.abstract_surface(), formatting)?;
 
-    for member in surface.surface_member() {
-        parts
-            .content
-            .push(XmlNodeContent::Child(serialize_abstract_surface_property(
-                member,
+    parts.content.extend(serialize_members(
+        surface.surface_member(),
+        formatting,
+        |x, formatting| {
+            serialize_abstract_surface_property(
+                x,
                 formatting,
                 GmlElement::SurfaceMemberProperty.into(),
-            )?));
-    }
+            )
+        },
+    )?);
 
     Ok(XmlNode::new(GmlElement::CompositeSurface.into(), parts))
 }
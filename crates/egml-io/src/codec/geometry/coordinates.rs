@@ -0,0 +1,155 @@
+use crate::Error;
+use egml_core::model::geometry::DirectPosition;
+use serde::{Deserialize, Serialize};
+
+/// The GML 2 `gml:coordinates` encoding: tuples separated by `ts`, whose
+/// values are separated by `cs` and use `decimal` as decimal point.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct GmlCoordinates {
+    #[serde(rename = "@cs", skip_serializing_if = "Option::is_none")]
+    cs: Option<String>,
+    #[serde(rename = "@ts", skip_serializing_if = "Option::is_none")]
+    ts: Option<String>,
+    #[serde(rename = "@decimal", skip_serializing_if = "Option::is_none")]
+    decimal: Option<String>,
+
+    #[serde(rename = "$value", default)]
+    value: String,
+}
+
+impl TryFrom<GmlCoordinates> for Vec<DirectPosition> {
+    type Error = Error;
+
+    fn try_from(item: GmlCoordinates) -> Result<Self, Self::Error> {
+        let cs = item.cs.as_deref().unwrap_or(",");
+        let ts = item.ts.as_deref().unwrap_or(" ");
+        let decimal = item.decimal.as_deref().unwrap_or(".");
+
+        split(&item.value, ts)
+            .map(|tuple| {
+                let values = split(tuple, cs)
+                    .map(|v| {
+                        let v = if decimal == "." {
+                            v.to_string()
+                        } else {
+                            v.replace(decimal, ".")
+                        };
+                        v.parse::<f64>()
+                            .map_err(|_| Error::InvalidCoordinates(tuple.to_string()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                match values[..] {
+                    [x, y, z] => Ok(DirectPosition::new(x, y, z)?),
+                    _ => Err(Error::UnsupportedDimension {
+                        found: values.len() as u32,
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Splits `value` at `separator`, treating a whitespace separator as any run
+/// of whitespace, and skips empty parts.
+fn split<'a>(value: &'a str, separator: &'a str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+    if separator.trim().is_empty() {
+        Box::new(value.split_whitespace())
+    } else {
+        Box::new(
+            value
+                .split(separator)
+                .map(str::trim)
+                .filter(|x| !x.is_empty()),
+        )
+    }
+}
+
+/// The GML 2 `gml:coord` encoding with one element per axis.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct GmlCoord {
+    #[serde(rename(serialize = "gml:X", deserialize = "X"))]
+    x: f64,
+    #[serde(
+        rename(serialize = "gml:Y", deserialize = "Y"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    y: Option<f64>,
+    #[serde(
+        rename(serialize = "gml:Z", deserialize = "Z"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    z: Option<f64>,
+}
+
+impl TryFrom<GmlCoord> for DirectPosition {
+    type Error = Error;
+
+    fn try_from(item: GmlCoord) -> Result<Self, Self::Error> {
+        match (item.y, item.z) {
+            (Some(y), Some(z)) => Ok(DirectPosition::new(item.x, y, z)?),
+            (y, _) => Err(Error::UnsupportedDimension {
+                found: 1 + y.is_some() as u32,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GmlCoord, GmlCoordinates};
+    use crate::Error;
+    use egml_core::model::geometry::DirectPosition;
+    use quick_xml::de;
+
+    #[test]
+    fn deserialize_coordinates_with_default_separators() {
+        let xml_document = b"<gml:coordinates>\n  0,0,0 1,0,0\n  1,1,0 </gml:coordinates>";
+
+        let parsed: GmlCoordinates = de::from_reader(xml_document.as_ref()).unwrap();
+        let positions: Vec<DirectPosition> = parsed.try_into().unwrap();
+
+        assert_eq!(positions.len(), 3);
+        assert_eq!(positions[2], DirectPosition::new(1.0, 1.0, 0.0).unwrap());
+    }
+
+    #[test]
+    fn deserialize_coordinates_with_custom_separators() {
+        let xml_document =
+            b"<gml:coordinates cs=\" \" ts=\";\" decimal=\",\">1,5 2 3;4 5,25 6</gml:coordinates>";
+
+        let parsed: GmlCoordinates = de::from_reader(xml_document.as_ref()).unwrap();
+        let positions: Vec<DirectPosition> = parsed.try_into().unwrap();
+
+        assert_eq!(positions[0], DirectPosition::new(1.5, 2.0, 3.0).unwrap());
+        assert_eq!(positions[1], DirectPosition::new(4.0, 5.25, 6.0).unwrap());
+    }
+
+    #[test]
+    fn deserialize_coordinates_rejects_2d_and_garbage() {
+        let two_d: GmlCoordinates =
+            de::from_reader(b"<gml:coordinates>0,0 1,1</gml:coordinates>".as_ref()).unwrap();
+        let garbage: GmlCoordinates =
+            de::from_reader(b"<gml:coordinates>0,a,0</gml:coordinates>".as_ref()).unwrap();
+
+        assert!(matches!(
+            Vec::<DirectPosition>::try_from(two_d),
+            Err(Error::UnsupportedDimension { found: 2 })
+        ));
+        assert!(matches!(
+            Vec::<DirectPosition>::try_from(garbage),
+            Err(Error::InvalidCoordinates(_))
+        ));
+    }
+
+    #[test]
+    fn deserialize_coord() {
+        let xml_document =
+            b"<gml:coord><gml:X>1</gml:X><gml:Y>2</gml:Y><gml:Z>3</gml:Z></gml:coord>";
+
+        let parsed: GmlCoord = de::from_reader(xml_document.as_ref()).unwrap();
+        let position: DirectPosition = parsed.try_into().unwrap();
+
+        assert_eq!(position, DirectPosition::new(1.0, 2.0, 3.0).unwrap());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::Error;
-use crate::codec::geometry::GmlDirectPosition;
+use crate::codec::geometry::{GmlCoord, GmlCoordinates, GmlDirectPosition};
 use egml_core::model::geometry::{DirectPosition, Envelope};
 use serde::{Deserialize, Serialize};
 
@@ -47,9 +47,51 @@ impl From<&Envelope> for GmlEnvelope {
     }
 }
 
+/// The GML 2 `gml:Box`, given by its two corners.
+#[derive(Debug, Deserialize, PartialEq, Clone)]
+pub struct GmlBox {
+    #[serde(rename = "$value")]
+    content: GmlBoxContent,
+
+    #[serde(rename = "@srsName")]
+    srs_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Clone)]
+enum GmlBoxContent {
+    #[serde(rename = "coordinates")]
+    Coordinates(GmlCoordinates),
+
+    #[serde(rename = "coord")]
+    Coord(Vec<GmlCoord>),
+}
+
+impl TryFrom<GmlBox> for Envelope {
+    type Error = Error;
+
+    fn try_from(item: GmlBox) -> Result<Self, Self::Error> {
+        let corners: Vec<DirectPosition> = match item.content {
+            GmlBoxContent::Coordinates(x) => x.try_into()?,
+            GmlBoxContent::Coord(x) => x
+                .into_iter()
+                .map(|p| p.try_into())
+                .collect::<Result<_, _>>()?,
+        };
+        let [lower_corner, upper_corner] = corners[..] else {
+            return Err(Error::InvalidCoordinateCount {
+                count: corners.len() * 3,
+            });
+        };
+
+        let mut envelope = Envelope::new(lower_corner, upper_corner)?;
+        envelope.set_srs_name_opt(item.srs_name);
+        Ok(envelope)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::codec::geometry::envelope::GmlEnvelope;
+    use crate::codec::geometry::envelope::{GmlBox, GmlEnvelope};
     use egml_core::model::geometry::Envelope;
     use quick_xml::{DeError, de};
 
@@ -71,4 +113,22 @@ mod tests {
         assert_eq!(envelope.upper_corner().y(), 12.0);
         assert_eq!(envelope.upper_corner().z(), 13.0);
     }
+
+    #[test]
+    fn deserialize_gml2_box() {
+        let coordinates = "<gml:Box srsName=\"EPSG:4979\">\
+            <gml:coordinates>1,2,3 11,12,13</gml:coordinates></gml:Box>";
+        let coord = "<gml:Box>\
+            <gml:coord><gml:X>1</gml:X><gml:Y>2</gml:Y><gml:Z>3</gml:Z></gml:coord>\
+            <gml:coord><gml:X>11</gml:X><gml:Y>12</gml:Y><gml:Z>13</gml:Z></gml:coord>\
+            </gml:Box>";
+
+        for xml_document in [coordinates, coord] {
+            let parsed: GmlBox = de::from_reader(xml_document.as_ref()).unwrap();
+            let envelope: Envelope = parsed.try_into().unwrap();
+
+            assert_eq!(envelope.lower_corner().z(), 3.0);
+            assert_eq!(envelope.upper_corner().x(), 11.0);
+        }
+    }
 }
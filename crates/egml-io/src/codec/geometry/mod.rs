@@ -6,6 +6,8 @@ mod abstract_geometry;
 mod abstract_geometry_array_property;
 mod abstract_geometry_kind;
 mod abstract_geometry_property;
+mod compact_encoding;
+mod coordinates;
 mod direct_position;
 mod direct_position_list;
 mod envelope;
@@ -13,6 +15,8 @@ mod envelope;
 pub use abstract_geometry_array_property::*;
 pub use abstract_geometry_kind::*;
 pub use abstract_geometry_property::*;
+pub use compact_encoding::*;
+pub use coordinates::*;
 pub use direct_position::*;
 pub use direct_position_list::*;
 pub use envelope::*;
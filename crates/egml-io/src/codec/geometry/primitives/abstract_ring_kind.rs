@@ -72,10 +72,7 @@ mod tests {
 
         let spans = extract_xml_element_spans(xml_document).expect("should work");
         let result = deserialize_abstract_ring_kind(xml_document, &spans).unwrap();
-        println!("{:?}", result);
-        //let gml_linear_ring = result.unwrap().linear_ring.expect("should work");
-        //let linear_ring: LinearRing = gml_linear_ring.try_into().unwrap();
-
-        //assert_eq!(linear_ring.points().len(), 3);
+        // gml:Ring is not read yet, so its curve members yield no ring kind.
+        assert!(result.is_none());
     }
 }
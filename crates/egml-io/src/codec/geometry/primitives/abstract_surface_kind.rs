@@ -2,8 +2,9 @@ use crate::Error;
 use crate::codec::geometry::complexes::deserialize_composite_surface;
 use crate::codec::geometry::complexes::serialize_composite_surface;
 use crate::codec::geometry::primitives::{
-    deserialize_polygon, deserialize_shell, deserialize_surface, deserialize_surface_kind,
-    serialize_polygon, serialize_shell, serialize_surface, serialize_surface_kind,
+    deserialize_polygon, deserialize_shell, deserialize_simple_polygon, deserialize_surface,
+    deserialize_surface_kind, serialize_polygon, serialize_shell, serialize_surface,
+    serialize_surface_kind,
 };
 use crate::util::{Formatting, GmlElement, XmlElementSpans, XmlNode};
 use egml_core::model::geometry::primitives::AbstractSurfaceKind;
@@ -27,6 +28,11 @@ pub fn deserialize_abstract_surface_kind(
         return Ok(Some(polygon.into()));
     }
 
+    if let Some(span) = spans.first(GmlElement::SimplePolygon) {
+        let polygon = deserialize_simple_polygon(&xml_document[span.start..span.end])?;
+        return Ok(Some(polygon.into()));
+    }
+
     if let Some(span) = spans.first(GmlElement::Surface) {
         let surface = deserialize_surface(&xml_document[span.start..span.end])?;
         return Ok(Some(surface.into()));
@@ -1,6 +1,6 @@
 use crate::Error;
 use crate::codec::abstract_object::{deserialize_abstract_object, serialize_abstract_object};
-use crate::util::{Formatting, GmlElement, XmlElementSpans, XmlNodeParts};
+use crate::util::{Formatting, GmlElement, XmlElementSpans, XmlNodeParts, check_gml_root};
 use egml_core::model::AsAbstractObject;
 use egml_core::model::geometry::primitives::AbstractSurfacePatch;
 
@@ -8,6 +8,7 @@ pub fn deserialize_abstract_surface_patch(
     xml_document: &[u8],
     spans: &XmlElementSpans<GmlElement>,
 ) -> Result<AbstractSurfacePatch, Error> {
+    check_gml_root(xml_document)?;
     let abstract_object = deserialize_abstract_object(xml_document, spans)?;
     let abstract_surface_patch = AbstractSurfacePatch::from_abstract_object(abstract_object);
 
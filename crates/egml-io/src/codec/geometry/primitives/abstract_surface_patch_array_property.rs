@@ -6,7 +6,10 @@ use crate::codec::base::{
 use crate::codec::geometry::primitives::{
     deserialize_abstract_surface_patch_kind, serialize_abstract_surface_patch_kind,
 };
-use crate::util::{Formatting, GmlElement, XmlElementSpans, XmlNode, XmlNodeContent, XmlNodeParts};
+use crate::util::{
+    Formatting, GmlElement, XmlElementSpans, XmlNode, XmlNodeParts, recover_child,
+    serialize_members,
+};
 use egml_core::model::base::{HasAssociationAttributes, HasOwnershipAttributes};
 use egml_core::model::geometry::primitives::AbstractSurfacePatchArrayProperty;
 use egml_core::model::geometry::primitives::AbstractSurfacePatchKind;
@@ -26,14 +29,15 @@ pub fn deserialize_abstract_surface_patch_array_property(
         .collect();
     all_spans.sort_by_key(|(_, r)| r.start);
 
-    let patches: Vec<AbstractSurfacePatchKind> = all_spans
-        .iter()
-        .filter_map(|(elem, span)| {
-            let slice = &xml_document[span.start..span.end];
-            let parent_spans = XmlElementSpans::single(*elem, slice.len());
-            deserialize_abstract_surface_patch_kind(slice, &parent_spans).transpose()
-        })
-        .collect::<Result<_, _>>()?;
+    let mut patches: Vec<AbstractSurfacePatchKind> = Vec::new();
+    for (elem, span) in &all_spans {
+        let slice = &xml_document[span.start..span.end];
+        let parent_spans = XmlElementSpans::single(*elem, slice.len());
+        match deserialize_abstract_surface_patch_kind(slice, &parent_spans) {
+            Ok(patch) => patches.extend(patch),
+            Err(error) => recover_child(slice, *elem, error)?,
+        }
+    }
 
     if patches.is_empty() {
         return Ok(None);
@@ -59,11 +63,11 @@ pub fn serialize_abstract_surface_patch_array_property(
         abstract_surface_patch_array_property.ownership(),
     ));
 
-    for patch in abstract_surface_patch_array_property.objects() {
-        parts.content.push(XmlNodeContent::Child(
-            serialize_abstract_surface_patch_kind(patch, formatting)?,
-        ));
-    }
+    parts.content.extend(serialize_members(
+        abstract_surface_patch_array_property.objects(),
+        formatting,
+        serialize_abstract_surface_patch_kind,
+    )?);
 
     Ok(XmlNode::new(target_xml_element, parts))
 }
@@ -195,12 +199,9 @@ mod tests {
             .unwrap();
 
         assert_eq!(property.href(), Some(&HRef::from_local("some-id")));
-        assert_eq!(property.title().as_deref(), Some("Some Title"));
-        assert_eq!(property.role().as_deref(), Some("http://example.com/role"));
-        assert_eq!(
-            property.arcrole().as_deref(),
-            Some("http://example.com/arcrole")
-        );
+        assert_eq!(property.title(), Some("Some Title"));
+        assert_eq!(property.role(), Some("http://example.com/role"));
+        assert_eq!(property.arcrole(), Some("http://example.com/arcrole"));
         assert_eq!(property.show(), Some(&ShowType::New));
         assert_eq!(property.actuate(), Some(&ActuateType::OnLoad));
         assert!(property.owns());
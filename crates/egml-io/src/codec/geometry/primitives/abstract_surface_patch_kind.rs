@@ -1,6 +1,7 @@
 use crate::Error;
 use crate::codec::geometry::primitives::{
-    deserialize_polygon_patch, deserialize_triangle, serialize_polygon_patch, serialize_triangle,
+    deserialize_polygon_patch, deserialize_simple_rectangle, deserialize_simple_triangle,
+    deserialize_triangle, serialize_polygon_patch, serialize_triangle,
 };
 use crate::util::{Formatting, GmlElement, XmlElementSpans, XmlNode};
 use egml_core::model::geometry::primitives::AbstractSurfacePatchKind;
@@ -19,6 +20,16 @@ pub fn deserialize_abstract_surface_patch_kind(
         return Ok(Some(triangle.into()));
     }
 
+    if let Some(span) = spans.first(GmlElement::SimpleTriangle) {
+        let triangle = deserialize_simple_triangle(&xml_document[span.start..span.end])?;
+        return Ok(Some(triangle.into()));
+    }
+
+    if let Some(span) = spans.first(GmlElement::SimpleRectangle) {
+        let polygon_patch = deserialize_simple_rectangle(&xml_document[span.start..span.end])?;
+        return Ok(Some(polygon_patch.into()));
+    }
+
     Ok(None)
 }
 
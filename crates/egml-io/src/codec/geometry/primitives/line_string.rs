@@ -1,13 +1,14 @@
 use crate::Error;
-use crate::codec::geometry::GmlDirectPosition;
 use crate::codec::geometry::direct_position_list::GmlDirectPositionList;
 use crate::codec::geometry::primitives::abstract_curve::{
     deserialize_abstract_curve, serialize_abstract_curve,
 };
+use crate::codec::geometry::{GmlCoord, GmlCoordinates, GmlDirectPosition};
 use crate::util::{
-    Formatting, GmlElement, XmlNode, XmlNodeContent, dedup_adjacent_positions,
-    extract_xml_element_spans, serialize_inner,
+    Formatting, GmlElement, XmlNode, XmlNodeContent, extract_xml_element_spans, repair_positions,
+    serialize_inner,
 };
+use egml_core::model::base::AsAbstractGml;
 use egml_core::model::geometry::DirectPosition;
 use egml_core::model::geometry::primitives::{AsAbstractCurve, LineString};
 use quick_xml::de;
@@ -19,7 +20,12 @@ pub fn deserialize_line_string(xml_document: &[u8]) -> Result<LineString, Error>
 
     let parsed: GmlLineString = de::from_reader(xml_document)?;
     let mut points: Vec<DirectPosition> = parsed.content.unwrap().try_into()?;
-    dedup_adjacent_positions(&mut points, "LineString");
+    repair_positions(
+        &mut points,
+        GmlElement::LineString.into(),
+        abstract_curve.id(),
+        xml_document,
+    );
 
     let line_string = LineString::from_abstract_curve(abstract_curve, points)?;
     Ok(line_string)
@@ -51,6 +57,12 @@ pub enum GmlLineStringContent {
 
     #[serde(rename(serialize = "gml:pos", deserialize = "pos"))]
     Pos(Vec<GmlDirectPosition>),
+
+    #[serde(rename(serialize = "gml:coordinates", deserialize = "coordinates"))]
+    Coordinates(GmlCoordinates),
+
+    #[serde(rename(serialize = "gml:coord", deserialize = "coord"))]
+    Coord(Vec<GmlCoord>),
 }
 
 impl TryFrom<GmlLineStringContent> for Vec<DirectPosition> {
@@ -67,6 +79,8 @@ impl TryFrom<GmlLineStringContent> for Vec<DirectPosition> {
 
                 Ok(points)
             }
+            GmlLineStringContent::Coordinates(x) => x.try_into(),
+            GmlLineStringContent::Coord(x) => x.into_iter().map(|p| p.try_into()).collect(),
         }
     }
 }
@@ -98,9 +112,9 @@ impl From<&LineString> for GmlLineString {
 #[cfg(test)]
 mod tests {
     use super::GmlLineString;
+    use crate::codec::geometry::primitives::deserialize_linear_ring;
     use crate::codec::geometry::primitives::line_string::serialize_line_string;
-    use crate::codec::geometry::primitives::{deserialize_linear_ring, serialize_linear_ring};
-    use crate::util::{Formatting, extract_xml_element_spans};
+    use crate::util::Formatting;
     use egml_core::model::geometry::DirectPosition;
     use egml_core::model::geometry::primitives::LineString;
     use quick_xml::de;
@@ -1,13 +1,14 @@
-use crate::codec::geometry::GmlDirectPosition;
 use crate::codec::geometry::direct_position_list::GmlDirectPositionList;
 use crate::codec::geometry::primitives::abstract_ring::{
     deserialize_abstract_ring, serialize_abstract_ring,
 };
+use crate::codec::geometry::{GmlCoord, GmlCoordinates, GmlDirectPosition};
 use crate::error::Error;
 use crate::util::{
-    Formatting, GmlElement, XmlNode, XmlNodeContent, dedup_adjacent_positions,
-    extract_xml_element_spans, serialize_inner,
+    Formatting, GmlElement, XmlNode, XmlNodeContent, extract_xml_element_spans,
+    repair_ring_positions, serialize_inner,
 };
+use egml_core::model::base::AsAbstractGml;
 use egml_core::model::geometry::DirectPosition;
 use egml_core::model::geometry::primitives::{AsAbstractRing, LinearRing};
 use quick_xml::de;
@@ -19,10 +20,12 @@ pub fn deserialize_linear_ring(xml_document: &[u8]) -> Result<LinearRing, Error>
 
     let parsed: GmlLinearRing = de::from_reader(xml_document)?;
     let mut points: Vec<DirectPosition> = parsed.content.unwrap().try_into()?;
-    dedup_adjacent_positions(&mut points, "LinearRing");
-    if points.first().unwrap() == points.last().unwrap() {
-        points.pop();
-    }
+    repair_ring_positions(
+        &mut points,
+        GmlElement::LinearRing.into(),
+        abstract_ring.id(),
+        xml_document,
+    );
 
     let linear_ring = LinearRing::from_abstract_ring(abstract_ring, points)?;
     Ok(linear_ring)
@@ -54,6 +57,12 @@ pub enum GmlLinearRingContent {
 
     #[serde(rename = "pos")]
     Pos(Vec<GmlDirectPosition>),
+
+    #[serde(rename(serialize = "gml:coordinates", deserialize = "coordinates"))]
+    Coordinates(GmlCoordinates),
+
+    #[serde(rename(serialize = "gml:coord", deserialize = "coord"))]
+    Coord(Vec<GmlCoord>),
 }
 
 impl TryFrom<GmlLinearRingContent> for Vec<DirectPosition> {
@@ -70,6 +79,8 @@ impl TryFrom<GmlLinearRingContent> for Vec<DirectPosition> {
 
                 Ok(points)
             }
+            GmlLinearRingContent::Coordinates(x) => x.try_into(),
+            GmlLinearRingContent::Coord(x) => x.into_iter().map(|p| p.try_into()).collect(),
         }
     }
 }
@@ -111,11 +122,10 @@ mod tests {
     use crate::codec::geometry::primitives::{
         GmlLinearRing, deserialize_linear_ring, serialize_linear_ring,
     };
-    use crate::util::{Formatting, extract_xml_element_spans};
+    use crate::util::Formatting;
     use egml_core::model::base::Id;
     use egml_core::model::geometry::DirectPosition;
     use egml_core::model::geometry::primitives::LinearRing;
-    use quick_xml::de;
 
     fn make_triangle() -> LinearRing {
         let points = vec![
@@ -192,6 +202,23 @@ mod tests {
         assert!(!xml.contains("id="));
     }
 
+    #[test]
+    fn deserialize_linear_ring_with_gml2_encodings() {
+        let coordinates = b"<gml:LinearRing>\
+            <gml:coordinates>0,0,0 1,0,0 0,1,0 0,0,0</gml:coordinates>\
+            </gml:LinearRing>";
+        let coord = b"<gml:LinearRing>\
+            <gml:coord><gml:X>0</gml:X><gml:Y>0</gml:Y><gml:Z>0</gml:Z></gml:coord>\
+            <gml:coord><gml:X>1</gml:X><gml:Y>0</gml:Y><gml:Z>0</gml:Z></gml:coord>\
+            <gml:coord><gml:X>0</gml:X><gml:Y>1</gml:Y><gml:Z>0</gml:Z></gml:coord>\
+            <gml:coord><gml:X>0</gml:X><gml:Y>0</gml:Y><gml:Z>0</gml:Z></gml:coord>\
+            </gml:LinearRing>";
+
+        let expected = make_triangle();
+        assert_eq!(deserialize_linear_ring(coordinates).unwrap(), expected);
+        assert_eq!(deserialize_linear_ring(coord).unwrap(), expected);
+    }
+
     #[test]
     fn serialize_linear_ring_appends_closing_vertex() {
         let ring = make_triangle(); // 3 open points
@@ -200,7 +227,7 @@ mod tests {
         let gml = GmlLinearRing::from(&ring);
         let positions: Vec<DirectPosition> = match gml.content.unwrap() {
             GmlLinearRingContent::PosList(pos_list) => pos_list.try_into().unwrap(),
-            GmlLinearRingContent::Pos(_) => panic!("expected PosList"),
+            _ => panic!("expected PosList"),
         };
         assert_eq!(positions.len(), 4); // 3 open points + closing vertex
         assert_eq!(positions.first(), positions.last()); // closing vertex equals first point
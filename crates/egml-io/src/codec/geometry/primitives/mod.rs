@@ -24,6 +24,9 @@ mod polygon;
 mod polygon_patch;
 mod shell;
 mod shell_property;
+mod simple_polygon;
+mod simple_rectangle;
+mod simple_triangle;
 mod solid;
 mod solid_property;
 mod surface;
@@ -53,6 +56,9 @@ pub use polygon::*;
 pub use polygon_patch::*;
 pub use shell::*;
 pub use shell_property::*;
+pub use simple_polygon::*;
+pub use simple_rectangle::*;
+pub use simple_triangle::*;
 pub use solid::*;
 pub use solid_property::*;
 pub use surface::*;
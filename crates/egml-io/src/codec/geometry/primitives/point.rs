@@ -1,7 +1,7 @@
-use crate::codec::geometry::GmlDirectPosition;
 use crate::codec::geometry::primitives::abstract_geometry_primitive::{
     deserialize_abstract_geometric_primitive, serialize_abstract_geometric_primitive,
 };
+use crate::codec::geometry::{GmlCoord, GmlCoordinates, GmlDirectPosition};
 use crate::error::Error;
 use crate::util::{
     Formatting, GmlElement, XmlNode, XmlNodeContent, extract_xml_element_spans, serialize_inner,
@@ -17,7 +17,7 @@ pub fn deserialize_point(xml_document: &[u8]) -> Result<Point, Error> {
         deserialize_abstract_geometric_primitive(xml_document, &spans)?;
 
     let parsed: GmlPoint = de::from_reader(xml_document)?;
-    let direct_position: DirectPosition = parsed.pos.try_into()?;
+    let direct_position: DirectPosition = parsed.content.try_into()?;
 
     let point =
         Point::from_abstract_geometric_primitive(abstract_geometric_primitive, direct_position);
@@ -37,15 +37,47 @@ pub fn serialize_point(point: &Point, formatting: Formatting) -> Result<XmlNode,
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct GmlPoint {
+    #[serde(rename = "$value")]
+    content: GmlPointContent,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum GmlPointContent {
     #[serde(rename(serialize = "gml:pos", deserialize = "pos"))]
-    pos: GmlDirectPosition,
+    Pos(GmlDirectPosition),
+
+    #[serde(rename(serialize = "gml:coordinates", deserialize = "coordinates"))]
+    Coordinates(GmlCoordinates),
+
+    #[serde(rename(serialize = "gml:coord", deserialize = "coord"))]
+    Coord(GmlCoord),
+}
+
+impl TryFrom<GmlPointContent> for DirectPosition {
+    type Error = Error;
+
+    fn try_from(value: GmlPointContent) -> Result<Self, Self::Error> {
+        match value {
+            GmlPointContent::Pos(x) => x.try_into(),
+            GmlPointContent::Coordinates(x) => {
+                let positions: Vec<DirectPosition> = x.try_into()?;
+                match positions[..] {
+                    [position] => Ok(position),
+                    _ => Err(Error::InvalidCoordinateCount {
+                        count: positions.len() * 3,
+                    }),
+                }
+            }
+            GmlPointContent::Coord(x) => x.try_into(),
+        }
+    }
 }
 
 impl TryFrom<GmlPoint> for Point {
     type Error = Error;
 
     fn try_from(item: GmlPoint) -> Result<Self, Self::Error> {
-        let point = Point::new(item.pos.try_into()?);
+        let point = Point::new(item.content.try_into()?);
         Ok(point)
     }
 }
@@ -53,7 +85,7 @@ impl TryFrom<GmlPoint> for Point {
 impl From<&Point> for GmlPoint {
     fn from(point: &Point) -> Self {
         Self {
-            pos: GmlDirectPosition::from(point.pos()),
+            content: GmlPointContent::Pos(GmlDirectPosition::from(point.pos())),
         }
     }
 }
@@ -61,7 +93,7 @@ impl From<&Point> for GmlPoint {
 #[cfg(test)]
 mod tests {
     use crate::codec::geometry::primitives::{deserialize_point, serialize_point};
-    use crate::util::{Formatting, extract_xml_element_spans};
+    use crate::util::Formatting;
     use egml_core::model::base::Id;
     use egml_core::model::geometry::DirectPosition;
     use egml_core::model::geometry::primitives::Point;
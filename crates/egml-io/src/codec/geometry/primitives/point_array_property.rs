@@ -5,8 +5,8 @@ use crate::codec::base::{
 };
 use crate::codec::geometry::primitives::{deserialize_point, serialize_point};
 use crate::util::{
-    Formatting, GmlElement, XmlElementSpans, XmlNode, XmlNodeContent, XmlNodeParts,
-    collect_children_simple,
+    Formatting, GmlElement, XmlElementSpans, XmlNode, XmlNodeParts, collect_children_simple,
+    serialize_members,
 };
 use egml_core::model::base::{HasAssociationAttributes, HasOwnershipAttributes};
 use egml_core::model::geometry::primitives::PointArrayProperty;
@@ -51,11 +51,11 @@ pub fn serialize_point_array_property(
             point_array_property.ownership(),
         ));
 
-    for point in point_array_property.objects() {
-        xml_node_parts
-            .content
-            .push(XmlNodeContent::Child(serialize_point(point, formatting)?));
-    }
+    xml_node_parts.content.extend(serialize_members(
+        point_array_property.objects(),
+        formatting,
+        serialize_point,
+    )?);
 
     Ok(XmlNode::new(target_xml_element, xml_node_parts))
 }
@@ -177,12 +177,9 @@ mod tests {
             .unwrap();
 
         assert_eq!(property.href(), Some(&HRef::from_local("some-id")));
-        assert_eq!(property.title().as_deref(), Some("Some Title"));
-        assert_eq!(property.role().as_deref(), Some("http://example.com/role"));
-        assert_eq!(
-            property.arcrole().as_deref(),
-            Some("http://example.com/arcrole")
-        );
+        assert_eq!(property.title(), Some("Some Title"));
+        assert_eq!(property.role(), Some("http://example.com/role"));
+        assert_eq!(property.arcrole(), Some("http://example.com/arcrole"));
         assert_eq!(property.show(), Some(&ShowType::New));
         assert_eq!(property.actuate(), Some(&ActuateType::OnLoad));
         assert!(property.owns());
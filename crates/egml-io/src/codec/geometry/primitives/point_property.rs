@@ -66,7 +66,7 @@ mod tests {
     use crate::codec::geometry::primitives::point_property::{
         deserialize_point_property, serialize_point_property,
     };
-    use crate::util::{Formatting, GmlElement, XmlNode, extract_xml_element_spans};
+    use crate::util::{Formatting, GmlElement, extract_xml_element_spans};
     use egml_core::model::base::{HasAssociationAttributes, HasOwnershipAttributes};
     use egml_core::model::geometry::DirectPosition;
     use egml_core::model::geometry::primitives::{Point, PointProperty};
@@ -228,12 +228,9 @@ mod tests {
         let property = deserialize_point_property(xml_document, &spans).unwrap();
 
         assert_eq!(property.href(), Some(&HRef::from_local("some-id")));
-        assert_eq!(property.title().as_deref(), Some("Some Title"));
-        assert_eq!(property.role().as_deref(), Some("http://example.com/role"));
-        assert_eq!(
-            property.arcrole().as_deref(),
-            Some("http://example.com/arcrole")
-        );
+        assert_eq!(property.title(), Some("Some Title"));
+        assert_eq!(property.role(), Some("http://example.com/role"));
+        assert_eq!(property.arcrole(), Some("http://example.com/arcrole"));
         assert_eq!(property.show(), Some(&ShowType::New));
         assert_eq!(property.actuate(), Some(&ActuateType::OnLoad));
         assert!(property.owns());
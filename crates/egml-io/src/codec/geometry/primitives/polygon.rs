@@ -3,10 +3,10 @@ use crate::codec::geometry::primitives::abstract_surface::{
     deserialize_abstract_surface, serialize_abstract_surface,
 };
 use crate::codec::geometry::primitives::{
-    deserialize_abstract_ring_property, serialize_abstract_ring_property,
+    deserialize_abstract_ring_property, serialize_abstract_ring_property, serialize_simple_polygon,
 };
 use crate::util::{
-    Formatting, GmlElement, XmlNode, XmlNodeContent, collect_child, collect_children,
+    Formatting, GmlElement, WriteOptions, XmlNode, XmlNodeContent, collect_child, collect_children,
     extract_xml_element_spans,
 };
 use egml_core::model::geometry::primitives::{AbstractRingProperty, AsAbstractSurface, Polygon};
@@ -21,18 +21,40 @@ pub fn deserialize_polygon(xml_document: &[u8]) -> Result<Polygon, Error> {
         GmlElement::ExteriorProperty,
         deserialize_abstract_ring_property,
     )?;
-    let interior: Vec<AbstractRingProperty> = collect_children(
+    // GML 2 names the boundaries outerBoundaryIs and innerBoundaryIs.
+    let exterior = match exterior {
+        Some(x) => Some(x),
+        None => collect_child(
+            xml_document,
+            &spans,
+            GmlElement::OuterBoundaryIsProperty,
+            deserialize_abstract_ring_property,
+        )?,
+    };
+    let mut interior: Vec<AbstractRingProperty> = collect_children(
         xml_document,
         &spans,
         GmlElement::InteriorProperty,
         deserialize_abstract_ring_property,
     )?;
+    interior.extend(collect_children(
+        xml_document,
+        &spans,
+        GmlElement::InnerBoundaryIsProperty,
+        deserialize_abstract_ring_property,
+    )?);
 
     let polygon = Polygon::from_abstract_surface(abstract_surface, exterior, interior);
     Ok(polygon)
 }
 
 pub fn serialize_polygon(polygon: &Polygon, formatting: Formatting) -> Result<XmlNode, Error> {
+    if WriteOptions::current().compact_encodings
+        && let Some(node) = serialize_simple_polygon(polygon, formatting)?
+    {
+        return Ok(node);
+    }
+
     let mut xml_node_parts = serialize_abstract_surface(polygon.abstract_surface(), formatting)?;
 
     if let Some(object) = &polygon.exterior() {
@@ -59,9 +81,13 @@ pub fn serialize_polygon(polygon: &Polygon, formatting: Formatting) -> Result<Xm
 
 #[cfg(test)]
 mod tests {
+    use crate::Error;
     use crate::codec::geometry::primitives::deserialize_polygon;
     use crate::codec::geometry::primitives::polygon::serialize_polygon;
-    use crate::util::Formatting;
+    use crate::util::{
+        DegeneratePolicy, Formatting, ParseOptions, RepairKind, Severity, deserialize_with_options,
+    };
+    use egml_core::model::base::AsAbstractGml;
     use egml_core::model::common::Triangulate;
     use egml_core::model::geometry::DirectPosition;
     use egml_core::model::geometry::primitives::{
@@ -231,6 +257,161 @@ mod tests {
         assert_eq!(input_xml, output_xml);
     }
 
+    #[test]
+    fn drop_policy_removes_degenerate_interior_ring_and_reports_it() {
+        let xml = b"<gml:Polygon gml:id=\"PG.1\">\
+            <gml:exterior><gml:LinearRing><gml:posList>0 0 0 4 0 0 4 4 0 0 4 0 0 0 0</gml:posList></gml:LinearRing></gml:exterior>\
+            <gml:interior><gml:LinearRing gml:id=\"R2\"><gml:posList>1 1 0 2 1 0 1 1 0</gml:posList></gml:LinearRing></gml:interior>\
+            </gml:Polygon>";
+
+        assert!(deserialize_polygon(xml).is_err());
+
+        let options = ParseOptions::default().with_degenerate(DegeneratePolicy::Drop);
+        let parsed =
+            deserialize_with_options(xml, &options, deserialize_polygon).expect("should repair");
+
+        assert!(parsed.value().interior().is_empty());
+        assert_eq!(parsed.diagnostics().len(), 1);
+        assert_eq!(parsed.diagnostics()[0].severity, Severity::Error);
+        assert_eq!(parsed.repairs().len(), 1);
+        let repair = &parsed.repairs().repairs()[0];
+        assert_eq!(repair.element, "gml:interior");
+        assert!(matches!(repair.kind, RepairKind::DroppedMember { .. }));
+        assert_eq!(repair.id.as_ref().map(|id| id.as_str()), Some("R2"));
+        assert_eq!(
+            repair.location.map(|l| l.offset),
+            std::str::from_utf8(xml).unwrap().find("<gml:interior>")
+        );
+    }
+
+    #[test]
+    fn lenient_mode_reports_repairs_as_warnings() {
+        let xml = b"<gml:Polygon>\
+            <gml:exterior><gml:LinearRing gml:id=\"R1\"><gml:posList>0 0 0 1 0 0 1 0 0 1 1 0 0 0 0</gml:posList></gml:LinearRing></gml:exterior>\
+            </gml:Polygon>";
+
+        let parsed = deserialize_with_options(xml, &ParseOptions::lenient(), deserialize_polygon)
+            .expect("should repair");
+
+        assert_eq!(parsed.repairs().len(), 1);
+        assert_eq!(parsed.diagnostics().len(), 1);
+        let diagnostic = &parsed.diagnostics()[0];
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(diagnostic.element, "gml:LinearRing");
+        assert_eq!(diagnostic.id.as_ref().map(|id| id.as_str()), Some("R1"));
+        assert!(!parsed.has_errors());
+    }
+
+    #[test]
+    fn lenient_drop_reports_dropped_members_as_errors() {
+        let xml = b"<gml:Polygon gml:id=\"PG.1\">
+  <gml:exterior><gml:LinearRing><gml:posList>0 0 0 4 0 0 4 4 0 0 4 0 0 0 0</gml:posList></gml:LinearRing></gml:exterior>
+  <gml:interior><gml:LinearRing gml:id=\"R2\"><gml:posList>1 1 0 2 1 0 1 1 0</gml:posList></gml:LinearRing></gml:interior>
+</gml:Polygon>";
+        let options = ParseOptions::lenient().with_degenerate(DegeneratePolicy::Drop);
+
+        let parsed =
+            deserialize_with_options(xml, &options, deserialize_polygon).expect("should repair");
+
+        assert!(parsed.value().interior().is_empty());
+        assert_eq!(parsed.repairs().len(), 1);
+        assert_eq!(parsed.diagnostics().len(), 1);
+        let diagnostic = &parsed.diagnostics()[0];
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.element, "gml:interior");
+        assert_eq!(diagnostic.id.as_ref().map(|id| id.as_str()), Some("R2"));
+        assert_eq!(
+            diagnostic.location.map(|l| (l.line, l.column)),
+            Some((3, 3))
+        );
+        assert!(parsed.has_errors());
+    }
+
+    #[test]
+    fn foreign_root_elements_are_rejected() {
+        let xml = b"<foo:Polygon xmlns:foo=\"urn:foo\" xmlns:gml=\"http://www.opengis.net/gml/3.2\">\
+            <gml:exterior><gml:LinearRing><gml:posList>0 0 0 1 0 0 1 1 0 0 0 0</gml:posList></gml:LinearRing></gml:exterior>\
+            </foo:Polygon>";
+
+        assert!(matches!(
+            deserialize_polygon(xml),
+            Err(Error::ForeignElement(x)) if x == "foo:Polygon"
+        ));
+        assert!(matches!(
+            deserialize_with_options(xml, &ParseOptions::default(), deserialize_polygon),
+            Err(Error::ForeignElement(_))
+        ));
+    }
+
+    #[test]
+    fn strict_options_reject_duplicate_positions() {
+        let xml = b"<gml:Polygon>\
+            <gml:exterior><gml:LinearRing><gml:posList>0 0 0 1 0 0 1 0 0 1 1 0 0 0 0</gml:posList></gml:LinearRing></gml:exterior>\
+            </gml:Polygon>";
+
+        let parsed = deserialize_with_options(xml, &ParseOptions::default(), deserialize_polygon)
+            .expect("default options repair duplicates");
+        assert_eq!(
+            parsed.repairs().repairs()[0].kind,
+            RepairKind::RemovedDuplicatePositions { removed: 1 }
+        );
+
+        let result = deserialize_with_options(xml, &ParseOptions::strict(), deserialize_polygon);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn preserved_unknown_content_is_written_in_place() {
+        let xml = "<gml:Polygon gml:id=\"p1\" ext:quality=\"a &amp; b\">\
+            <gml:name>roof</gml:name>\
+            <!-- checked -->\
+            <gml:exterior><gml:LinearRing><gml:posList srsDimension=\"3\">0 0 0 1 0 0 1 1 0 0 0 0</gml:posList></gml:LinearRing></gml:exterior>\
+            <ext:note><ext:by>survey</ext:by></ext:note>\
+            </gml:Polygon>";
+        let options = ParseOptions::default().with_preserve_unknown(true);
+
+        let polygon = deserialize_with_options(xml.as_bytes(), &options, deserialize_polygon)
+            .unwrap()
+            .into_value();
+        let ignored = deserialize_polygon(xml.as_bytes()).unwrap();
+
+        assert_eq!(
+            polygon.foreign_content().attributes(),
+            [("ext:quality".to_string(), "a & b".to_string())]
+        );
+        assert!(ignored.foreign_content().is_empty());
+        assert_eq!(
+            serialize_polygon(&polygon, Formatting::Compact)
+                .unwrap()
+                .to_string(Formatting::Compact)
+                .unwrap(),
+            xml
+        );
+    }
+
+    #[test]
+    fn preserved_unknown_content_follows_the_element_before_it() {
+        let xml = "<gml:Polygon>\
+            <gml:outerBoundaryIs><gml:LinearRing><gml:coordinates>0,0,0 4,0,0 4,4,0 0,0,0</gml:coordinates></gml:LinearRing></gml:outerBoundaryIs>\
+            <gml:innerBoundaryIs><gml:LinearRing><gml:coordinates>1,1,0 2,1,0 2,2,0 1,1,0</gml:coordinates></gml:LinearRing></gml:innerBoundaryIs>\
+            <!-- after the first hole -->\
+            <gml:innerBoundaryIs><gml:LinearRing><gml:coordinates>3,1,0 3.5,1,0 3.5,2,0 3,1,0</gml:coordinates></gml:LinearRing></gml:innerBoundaryIs>\
+            </gml:Polygon>";
+        let options = ParseOptions::default().with_preserve_unknown(true);
+
+        let polygon = deserialize_with_options(xml.as_bytes(), &options, deserialize_polygon)
+            .unwrap()
+            .into_value();
+        let written = serialize_polygon(&polygon, Formatting::Compact)
+            .unwrap()
+            .to_string(Formatting::Compact)
+            .unwrap();
+
+        assert!(written.contains(
+            "2 2 0 1 1 0</gml:posList></gml:LinearRing></gml:interior><!-- after the first hole --><gml:interior>"
+        ));
+    }
+
     #[test]
     fn serialize_polygon_with_interior_rings() {
         let exterior_pts = vec![
@@ -262,4 +443,21 @@ mod tests {
         let recovered: Polygon = deserialize_polygon(xml.as_ref()).expect("should deserialize");
         assert_eq!(recovered.interior().len(), 1);
     }
+
+    #[test]
+    fn deserialize_gml2_polygon_with_boundaries() {
+        let xml = b"<gml:Polygon>\
+            <gml:outerBoundaryIs><gml:LinearRing>\
+            <gml:coordinates>0,0,0 4,0,0 4,4,0 0,4,0 0,0,0</gml:coordinates>\
+            </gml:LinearRing></gml:outerBoundaryIs>\
+            <gml:innerBoundaryIs><gml:LinearRing>\
+            <gml:coordinates>1,1,0 2,1,0 2,2,0 1,1,0</gml:coordinates>\
+            </gml:LinearRing></gml:innerBoundaryIs>\
+            </gml:Polygon>";
+
+        let polygon = deserialize_polygon(xml).unwrap();
+
+        assert!(polygon.exterior().is_some());
+        assert_eq!(polygon.interior().len(), 1);
+    }
 }
@@ -4,9 +4,10 @@ use crate::codec::geometry::primitives::abstract_surface_patch::{
 };
 use crate::codec::geometry::primitives::{
     deserialize_abstract_ring_property, serialize_abstract_ring_property,
+    serialize_simple_rectangle,
 };
 use crate::util::{
-    Formatting, GmlElement, XmlNode, XmlNodeContent, collect_child, collect_children,
+    Formatting, GmlElement, WriteOptions, XmlNode, XmlNodeContent, collect_child, collect_children,
     extract_xml_element_spans,
 };
 use egml_core::model::geometry::primitives::{
@@ -39,6 +40,12 @@ pub fn serialize_polygon_patch(
     polygon_patch: &PolygonPatch,
     formatting: Formatting,
 ) -> Result<XmlNode, Error> {
+    if WriteOptions::current().compact_encodings
+        && let Some(node) = serialize_simple_rectangle(polygon_patch, formatting)?
+    {
+        return Ok(node);
+    }
+
     let mut xml_node_parts =
         serialize_abstract_surface_patch(polygon_patch.abstract_surface_patch(), formatting)?;
 
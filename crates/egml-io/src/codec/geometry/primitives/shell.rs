@@ -4,8 +4,8 @@ use crate::codec::geometry::primitives::{
     serialize_abstract_surface, serialize_abstract_surface_property,
 };
 use crate::util::{
-    Formatting, GmlElement, XmlNode, XmlNodeContent, collect_children_lenient,
-    extract_xml_element_spans,
+    Formatting, GmlElement, XmlNode, collect_children_lenient, extract_xml_element_spans,
+    serialize_members,
 };
 use egml_core::model::geometry::primitives::{AsAbstractSurface, Shell};
 use tracing::debug;
@@ -33,15 +33,17 @@ pub fn deserialize_shell(xml_document: &[u8]) -> Result<Shell, Error> {
 pub fn serialize_shell(shell: &Shell, formatting: Formatting) -> Result<XmlNode, Error> {
     let mut parts = serialize_abstract_surface(shell.abstract_surface(), formatting)?;
 
-    for member in shell.members() {
-        parts
-            .content
-            .push(XmlNodeContent::Child(serialize_abstract_surface_property(
-                member,
+    parts.content.extend(serialize_members(
+        shell.members(),
+        formatting,
+        |x, formatting| {
+            serialize_abstract_surface_property(
+                x,
                 formatting,
                 GmlElement::SurfaceMemberProperty.into(),
-            )?));
-    }
+            )
+        },
+    )?);
 
     Ok(XmlNode::new(GmlElement::Shell.into(), parts))
 }
@@ -16,8 +16,11 @@ pub fn deserialize_shell_property(
 ) -> Result<ShellProperty, Error> {
     let parsed: GmlShellProperty = de::from_reader(xml_document)?;
 
+    // GML 3.1.1 has no gml:Shell and bounds solids with a gml:CompositeSurface,
+    // whose surfaceMember content is the same.
     let object = spans
         .first(GmlElement::Shell)
+        .or_else(|| spans.first(GmlElement::CompositeSurface))
         .map(|span| deserialize_shell(&xml_document[span.start..span.end]))
         .transpose()?;
 
@@ -187,12 +190,9 @@ mod tests {
         let property = deserialize_shell_property(xml_document, &spans).unwrap();
 
         assert_eq!(property.href(), Some(&HRef::from_local("some-id")));
-        assert_eq!(property.title().as_deref(), Some("Some Title"));
-        assert_eq!(property.role().as_deref(), Some("http://example.com/role"));
-        assert_eq!(
-            property.arcrole().as_deref(),
-            Some("http://example.com/arcrole")
-        );
+        assert_eq!(property.title(), Some("Some Title"));
+        assert_eq!(property.role(), Some("http://example.com/role"));
+        assert_eq!(property.arcrole(), Some("http://example.com/arcrole"));
         assert_eq!(property.show(), Some(&ShowType::New));
         assert_eq!(property.actuate(), Some(&ActuateType::OnLoad));
         assert!(property.owns());
@@ -0,0 +1,85 @@
+use crate::Error;
+use crate::codec::geometry::primitives::abstract_surface::{
+    deserialize_abstract_surface, serialize_abstract_surface,
+};
+use crate::codec::geometry::{
+    GmlSimplePositions, deserialize_simple_ring_positions, simple_ring_points,
+};
+use crate::util::{
+    Formatting, GmlElement, XmlNode, XmlNodeContent, extract_xml_element_spans, serialize_inner,
+};
+use egml_core::model::geometry::primitives::{
+    AbstractRingProperty, AsAbstractSurface, LinearRing, Polygon,
+};
+
+/// Reads a GML 3.3 `gmlce:SimplePolygon` into a [`Polygon`] without interior
+/// rings.
+pub fn deserialize_simple_polygon(xml_document: &[u8]) -> Result<Polygon, Error> {
+    let spans = extract_xml_element_spans(xml_document)?;
+    let abstract_surface = deserialize_abstract_surface(xml_document, &spans)?;
+
+    let points = deserialize_simple_ring_positions(xml_document, GmlElement::SimplePolygon.into())?;
+    let exterior = AbstractRingProperty::from_object(LinearRing::new(points)?.into());
+
+    Ok(Polygon::from_abstract_surface(
+        abstract_surface,
+        Some(exterior),
+        Vec::new(),
+    ))
+}
+
+/// Writes `polygon` as `gmlce:SimplePolygon`, or returns `None` if it has
+/// interior rings or an exterior the compact encoding cannot express.
+pub fn serialize_simple_polygon(
+    polygon: &Polygon,
+    formatting: Formatting,
+) -> Result<Option<XmlNode>, Error> {
+    if !polygon.interior().is_empty() {
+        return Ok(None);
+    }
+    let Some(points) = polygon.exterior().and_then(simple_ring_points) else {
+        return Ok(None);
+    };
+
+    let mut xml_node_parts = serialize_abstract_surface(polygon.abstract_surface(), formatting)?;
+    if let Some(raw) = serialize_inner(GmlSimplePositions::from(points), formatting)? {
+        xml_node_parts.content.push(XmlNodeContent::Raw(raw));
+    }
+
+    Ok(Some(XmlNode::new(
+        GmlElement::SimplePolygon.into(),
+        xml_node_parts,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codec::geometry::primitives::{
+        deserialize_simple_polygon, serialize_simple_polygon,
+    };
+    use crate::util::Formatting;
+    use egml_core::model::base::{AsAbstractGml, Id};
+    use egml_core::model::geometry::primitives::AbstractRingKind;
+
+    #[test]
+    fn simple_polygon_round_trip_keeps_id() {
+        let xml_document = b"<gmlce:SimplePolygon gml:id=\"p1\">
+                <gml:posList srsDimension=\"3\">0 0 0 3 0 0 3 2 0 1 3 0 0 2 0</gml:posList>
+            </gmlce:SimplePolygon>";
+
+        let polygon = deserialize_simple_polygon(xml_document).expect("should deserialize");
+        let xml = serialize_simple_polygon(&polygon, Formatting::Compact)
+            .unwrap()
+            .expect("should qualify")
+            .to_string(Formatting::Compact)
+            .unwrap();
+
+        assert_eq!(polygon.id(), Some(&Id::try_from("p1").unwrap()));
+        let Some(AbstractRingKind::LinearRing(ring)) = polygon.exterior().unwrap().object() else {
+            panic!("exterior should be a linear ring");
+        };
+        assert_eq!(ring.points().len(), 5);
+        assert!(xml.starts_with("<gmlce:SimplePolygon gml:id=\"p1\"><gml:posList"));
+        assert_eq!(deserialize_simple_polygon(xml.as_bytes()).unwrap(), polygon);
+    }
+}
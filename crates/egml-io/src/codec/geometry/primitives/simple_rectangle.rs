@@ -0,0 +1,138 @@
+use crate::Error;
+use crate::codec::geometry::primitives::abstract_surface_patch::{
+    deserialize_abstract_surface_patch, serialize_abstract_surface_patch,
+};
+use crate::codec::geometry::{
+    GmlSimplePositions, deserialize_simple_ring_positions, simple_ring_points,
+};
+use crate::util::{
+    Formatting, GmlElement, XmlNode, XmlNodeContent, extract_xml_element_spans, serialize_inner,
+};
+use egml_core::model::geometry::primitives::{
+    AbstractRingProperty, AsAbstractSurfacePatch, LinearRing, PolygonPatch,
+};
+
+/// Reads a GML 3.3 `gmlce:SimpleRectangle` into a [`PolygonPatch`] whose
+/// exterior has four corners.
+pub fn deserialize_simple_rectangle(xml_document: &[u8]) -> Result<PolygonPatch, Error> {
+    let spans = extract_xml_element_spans(xml_document)?;
+    let abstract_surface_patch = deserialize_abstract_surface_patch(xml_document, &spans)?;
+
+    let points =
+        deserialize_simple_ring_positions(xml_document, GmlElement::SimpleRectangle.into())?;
+    if points.len() != 4 {
+        return Err(egml_core::Error::InvalidElementCount {
+            geometry: "SimpleRectangle",
+            expected: 4,
+            actual: points.len(),
+            spec: None,
+        }
+        .into());
+    }
+    let exterior = AbstractRingProperty::from_object(LinearRing::new(points)?.into());
+
+    Ok(PolygonPatch::from_abstract_surface_patch(
+        abstract_surface_patch,
+        Some(exterior),
+        Vec::new(),
+    ))
+}
+
+/// Writes `polygon_patch` as `gmlce:SimpleRectangle`, or returns `None` if it
+/// is not a single ring of four corners expressible by the compact encoding.
+pub fn serialize_simple_rectangle(
+    polygon_patch: &PolygonPatch,
+    formatting: Formatting,
+) -> Result<Option<XmlNode>, Error> {
+    if !polygon_patch.interior().is_empty() {
+        return Ok(None);
+    }
+    let Some(points) = polygon_patch
+        .exterior()
+        .and_then(simple_ring_points)
+        .filter(|x| x.len() == 4)
+    else {
+        return Ok(None);
+    };
+
+    let mut xml_node_parts =
+        serialize_abstract_surface_patch(polygon_patch.abstract_surface_patch(), formatting)?;
+    if let Some(raw) = serialize_inner(GmlSimplePositions::from(points), formatting)? {
+        xml_node_parts.content.push(XmlNodeContent::Raw(raw));
+    }
+
+    Ok(Some(XmlNode::new(
+        GmlElement::SimpleRectangle.into(),
+        xml_node_parts,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+    use crate::codec::geometry::primitives::{
+        deserialize_simple_rectangle, serialize_simple_rectangle,
+    };
+    use crate::util::Formatting;
+    use egml_core::model::geometry::DirectPosition;
+    use egml_core::model::geometry::primitives::{
+        AbstractRingKind, AbstractRingProperty, LinearRing, PolygonPatch,
+    };
+
+    #[test]
+    fn deserialize_simple_rectangle_requires_four_corners() {
+        let rectangle = b"<gmlce:SimpleRectangle>
+                <gml:posList>0 0 0 2 0 0 2 1 0 0 1 0</gml:posList>
+            </gmlce:SimpleRectangle>";
+        let triangle = b"<gmlce:SimpleRectangle>
+                <gml:posList>0 0 0 2 0 0 2 1 0</gml:posList>
+            </gmlce:SimpleRectangle>";
+
+        let patch = deserialize_simple_rectangle(rectangle).expect("should deserialize");
+
+        assert_eq!(
+            patch.exterior().unwrap().object().unwrap().points().len(),
+            4
+        );
+        assert!(matches!(
+            deserialize_simple_rectangle(triangle),
+            Err(Error::EgmlError(egml_core::Error::InvalidElementCount {
+                actual: 3,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn serialize_simple_rectangle_skips_other_patches() {
+        let ring = |n: usize| {
+            let points = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 2.0), (0.0, 1.0)]
+                .into_iter()
+                .take(n)
+                .map(|(x, y)| DirectPosition::new(x, y, 0.0).unwrap());
+            AbstractRingProperty::from_object(AbstractRingKind::LinearRing(
+                LinearRing::new(points).unwrap(),
+            ))
+        };
+        let rectangle = PolygonPatch::new(Some(ring(4)), []);
+        let pentagon = PolygonPatch::new(Some(ring(5)), []);
+
+        let node = serialize_simple_rectangle(&rectangle, Formatting::Compact).unwrap();
+
+        assert_eq!(
+            deserialize_simple_rectangle(
+                node.unwrap()
+                    .to_string(Formatting::Compact)
+                    .unwrap()
+                    .as_bytes()
+            )
+            .unwrap(),
+            rectangle
+        );
+        assert!(
+            serialize_simple_rectangle(&pentagon, Formatting::Compact)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::Error;
+use crate::codec::geometry::primitives::abstract_surface_patch::{
+    deserialize_abstract_surface_patch, serialize_abstract_surface_patch,
+};
+use crate::codec::geometry::{
+    GmlSimplePositions, deserialize_simple_ring_positions, simple_ring_points,
+};
+use crate::util::{
+    Formatting, GmlElement, XmlNode, XmlNodeContent, extract_xml_element_spans, serialize_inner,
+};
+use egml_core::model::geometry::primitives::{
+    AbstractRingProperty, AsAbstractSurfacePatch, LinearRing, Triangle,
+};
+
+/// Reads a GML 3.3 `gmlce:SimpleTriangle` into a [`Triangle`].
+pub fn deserialize_simple_triangle(xml_document: &[u8]) -> Result<Triangle, Error> {
+    let spans = extract_xml_element_spans(xml_document)?;
+    let abstract_surface_patch = deserialize_abstract_surface_patch(xml_document, &spans)?;
+
+    let points =
+        deserialize_simple_ring_positions(xml_document, GmlElement::SimpleTriangle.into())?;
+    let exterior = AbstractRingProperty::from_object(LinearRing::new(points)?.into());
+
+    let triangle = Triangle::from_abstract_surface_patch(abstract_surface_patch, exterior)?;
+    Ok(triangle)
+}
+
+/// Writes `triangle` as `gmlce:SimpleTriangle`, or returns `None` if its
+/// exterior cannot be expressed by the compact encoding.
+pub fn serialize_simple_triangle(
+    triangle: &Triangle,
+    formatting: Formatting,
+) -> Result<Option<XmlNode>, Error> {
+    let Some(points) = simple_ring_points(triangle.exterior()) else {
+        return Ok(None);
+    };
+
+    let mut xml_node_parts =
+        serialize_abstract_surface_patch(triangle.abstract_surface_patch(), formatting)?;
+    if let Some(raw) = serialize_inner(GmlSimplePositions::from(points), formatting)? {
+        xml_node_parts.content.push(XmlNodeContent::Raw(raw));
+    }
+
+    Ok(Some(XmlNode::new(
+        GmlElement::SimpleTriangle.into(),
+        xml_node_parts,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codec::geometry::primitives::{
+        deserialize_simple_triangle, serialize_simple_triangle,
+    };
+    use crate::util::Formatting;
+    use egml_core::model::geometry::DirectPosition;
+    use egml_core::model::geometry::primitives::Triangle;
+
+    #[test]
+    fn deserialize_simple_triangle_with_and_without_closing_vertex() {
+        let open = b"<gmlce:SimpleTriangle>
+                <gml:posList>0 0 0 1 0 0 0 1 0</gml:posList>
+            </gmlce:SimpleTriangle>";
+        let closed = b"<gmlce:SimpleTriangle>
+                <gml:posList>0 0 0 1 0 0 0 1 0 0 0 0</gml:posList>
+            </gmlce:SimpleTriangle>";
+
+        let open = deserialize_simple_triangle(open).expect("should deserialize");
+        let closed = deserialize_simple_triangle(closed).expect("should deserialize");
+
+        assert_eq!(open, closed);
+        assert_eq!(open.b(), &DirectPosition::new(1.0, 0.0, 0.0).unwrap());
+    }
+
+    #[test]
+    fn simple_triangle_round_trip() {
+        let triangle = Triangle::from_points(
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(0.0, 1.0, 0.0).unwrap(),
+        )
+        .unwrap();
+
+        let xml = serialize_simple_triangle(&triangle, Formatting::Compact)
+            .unwrap()
+            .expect("should qualify")
+            .to_string(Formatting::Compact)
+            .unwrap();
+        let parsed = deserialize_simple_triangle(xml.as_bytes()).unwrap();
+
+        assert!(!xml.contains("LinearRing"));
+        assert_eq!(parsed, triangle);
+    }
+}
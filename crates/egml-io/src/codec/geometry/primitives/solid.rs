@@ -59,7 +59,7 @@ pub fn serialize_solid(solid: &Solid, formatting: Formatting) -> Result<XmlNode,
 #[cfg(test)]
 mod tests {
     use crate::codec::geometry::primitives::solid::{deserialize_solid, serialize_solid};
-    use crate::util::Formatting;
+    use crate::util::{Formatting, ParseOptions, Severity, deserialize_with_options};
     use egml_core::model::base::HasAssociationAttributes;
     use egml_core::model::base::{AsAbstractGml, AsAbstractGmlMut};
     use egml_core::model::geometry::DirectPosition;
@@ -118,6 +118,40 @@ mod tests {
         assert_eq!(exterior_shell.members().len(), 2);
     }
 
+    #[test]
+    fn lenient_mode_keeps_solid_with_invalid_interior_ring() {
+        let xml_document = b"<gml:Solid gml:id=\"S1\">
+  <gml:exterior>
+    <gml:Shell>
+      <gml:surfaceMember>
+        <gml:Polygon gml:id=\"P1\">
+          <gml:exterior><gml:LinearRing><gml:posList>0 0 0 4 0 0 4 4 0 0 0 0</gml:posList></gml:LinearRing></gml:exterior>
+          <gml:interior><gml:LinearRing gml:id=\"R2\"><gml:posList>1 1 0 2 1 0 1 1 0</gml:posList></gml:LinearRing></gml:interior>
+        </gml:Polygon>
+      </gml:surfaceMember>
+    </gml:Shell>
+  </gml:exterior>
+</gml:Solid>";
+
+        assert!(deserialize_solid(xml_document).is_err());
+
+        let parsed =
+            deserialize_with_options(xml_document, &ParseOptions::lenient(), deserialize_solid)
+                .expect("lenient mode should recover");
+
+        let shell = parsed.value().exterior().unwrap().object().unwrap();
+        assert_eq!(shell.members().len(), 1);
+        assert_eq!(parsed.diagnostics().len(), 1);
+        let diagnostic = &parsed.diagnostics()[0];
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.element, "gml:interior");
+        assert_eq!(diagnostic.id.as_ref().map(|id| id.as_str()), Some("R2"));
+        assert_eq!(
+            diagnostic.location.map(|l| (l.line, l.column)),
+            Some((7, 11))
+        );
+    }
+
     #[test]
     fn deserialize_solid_with_xlink_members() {
         let xml_document = b"\
@@ -3,10 +3,11 @@ use crate::codec::geometry::primitives::abstract_surface_patch::{
     deserialize_abstract_surface_patch, serialize_abstract_surface_patch,
 };
 use crate::codec::geometry::primitives::{
-    deserialize_abstract_ring_property, serialize_abstract_ring_property,
+    deserialize_abstract_ring_property, serialize_abstract_ring_property, serialize_simple_triangle,
 };
 use crate::util::{
-    Formatting, GmlElement, XmlNode, XmlNodeContent, collect_child, extract_xml_element_spans,
+    Formatting, GmlElement, WriteOptions, XmlNode, XmlNodeContent, collect_child,
+    extract_xml_element_spans,
 };
 use egml_core::model::geometry::primitives::AsAbstractSurfacePatch;
 use egml_core::model::geometry::primitives::Triangle;
@@ -28,6 +29,12 @@ pub fn deserialize_triangle(xml_document: &[u8]) -> Result<Triangle, Error> {
 }
 
 pub fn serialize_triangle(triangle: &Triangle, formatting: Formatting) -> Result<XmlNode, Error> {
+    if WriteOptions::current().compact_encodings
+        && let Some(node) = serialize_simple_triangle(triangle, formatting)?
+    {
+        return Ok(node);
+    }
+
     let mut xml_node_parts =
         serialize_abstract_surface_patch(triangle.abstract_surface_patch(), formatting)?;
 
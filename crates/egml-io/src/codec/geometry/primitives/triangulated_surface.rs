@@ -1,7 +1,7 @@
 use crate::Error;
 use crate::codec::geometry::primitives::{deserialize_surface, serialize_surface};
 use crate::util::{Formatting, GmlElement, XmlNode};
-use egml_core::model::geometry::primitives::{AsSurface, TriangulatedSurface};
+use egml_core::model::geometry::primitives::TriangulatedSurface;
 
 pub fn deserialize_triangulated_surface(xml_document: &[u8]) -> Result<TriangulatedSurface, Error> {
     let surface = deserialize_surface(xml_document)?;
@@ -0,0 +1,9 @@
+//! COLLADA 1.4.1 export of triangulated geometries, e.g. for the models of
+//! KMZ files.
+//!
+//! COLLADA holds meshes only, so surfaces and solids are triangulated first
+//! and points and curves cannot be written.
+
+mod writer;
+
+pub use writer::*;
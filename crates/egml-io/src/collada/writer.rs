@@ -0,0 +1,325 @@
+use crate::Error;
+use crate::util::{
+    Formatting, LengthUnit, SceneTransform, UpAxis, WriteOptions, XmlNode, XmlNodeContent,
+    XmlNodeParts, element, lower_corner, text_element, utc_now,
+};
+use egml_core::model::base::AsAbstractGml;
+use egml_core::model::common::Triangulate;
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometry, DirectPosition};
+use quick_xml::Writer;
+use quick_xml::events::{BytesDecl, Event};
+use std::io::Write;
+
+/// Namespace of COLLADA 1.4.
+pub const COLLADA_NAMESPACE: &str = "http://www.collada.org/2005/11/COLLADASchema";
+
+/// Writes geometries as a COLLADA 1.4.1 document, triangulated with
+/// [`Triangulate`].
+///
+/// Each geometry becomes a `<geometry>` with an indexed mesh, instantiated
+/// by a `<node>` of the visual scene. Both are named after its `gml:id`, or
+/// `geometry_<n>` for the `n`-th geometry without one, while their XML ids
+/// are numbered since a `gml:id` may not be unique across files. The nodes
+/// are children of a root node translated by the local origin.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::collada::ColladaWriter;
+/// use egml_io::util::UpAxis;
+/// use egml_io::wkt::read_wkt;
+///
+/// let square = read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1 1 0, 0 1 0, 0 0 0))").unwrap();
+///
+/// let mut dae = Vec::new();
+/// ColladaWriter::new()
+///     .with_up_axis(UpAxis::Y)
+///     .write(&[square], &mut dae)
+///     .unwrap();
+///
+/// let dae = String::from_utf8(dae).unwrap();
+/// assert!(dae.contains("<up_axis>Y_UP</up_axis>"));
+/// assert!(dae.contains("<triangles count=\"2\">"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColladaWriter {
+    up_axis: UpAxis,
+    unit: LengthUnit,
+    origin: Option<DirectPosition>,
+    formatting: Formatting,
+}
+
+impl Default for ColladaWriter {
+    fn default() -> Self {
+        Self {
+            up_axis: UpAxis::Z,
+            unit: LengthUnit::meter(),
+            origin: None,
+            formatting: Formatting::default(),
+        }
+    }
+}
+
+impl ColladaWriter {
+    /// Creates a writer of Z-up coordinates in metres, relative to the lower
+    /// corner of the written geometries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes coordinates with `up_axis` up, declared in `<up_axis>`.
+    pub fn with_up_axis(mut self, up_axis: UpAxis) -> Self {
+        self.up_axis = up_axis;
+        self
+    }
+
+    /// Writes coordinates, which are taken to be metres, in `unit`, declared
+    /// in `<unit>`.
+    pub fn with_unit(mut self, unit: LengthUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Uses `origin` as the local origin instead, e.g. the point a KMZ
+    /// `<Model>` is placed at.
+    pub fn with_origin(mut self, origin: DirectPosition) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    pub fn with_formatting(mut self, formatting: Formatting) -> Self {
+        self.formatting = formatting;
+        self
+    }
+
+    /// Writes `geometries` to `out`. Coordinates are formatted according to
+    /// the current [`WriteOptions`](crate::util::WriteOptions).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a geometry cannot be triangulated at all, e.g. a
+    /// `MultiCurve`, and [`Error::Io`] if writing fails.
+    pub fn write<'a, W: Write>(
+        &self,
+        geometries: impl IntoIterator<Item = &'a AbstractGeometryKind>,
+        out: W,
+    ) -> Result<(), Error> {
+        let geometries = geometries
+            .into_iter()
+            .map(|x| Ok((x, x.triangulate()?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let origin = match self.origin {
+            Some(origin) => origin.coords(),
+            None => lower_corner(geometries.iter().map(|(_, x)| x)),
+        };
+        let transform = SceneTransform {
+            origin,
+            up_axis: self.up_axis,
+            meters: self.unit.meters(),
+        };
+        let options = WriteOptions::current();
+
+        let mut library = Vec::new();
+        let mut nodes = Vec::new();
+        for (index, (geometry, triangulation)) in geometries.iter().enumerate() {
+            let name = match geometry.abstract_geometry().abstract_gml.id() {
+                Some(id) => id.to_string(),
+                None => format!("geometry_{}", index + 1),
+            };
+            let id = format!("geometry-{}", index + 1);
+            let mesh = transform.mesh(triangulation)?;
+
+            let positions = mesh
+                .vertices
+                .iter()
+                .map(|x| transform.format(&options, x.coords()))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let indices = mesh
+                .triangles
+                .iter()
+                .flatten()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(" ");
+            let accessor = element(
+                "accessor",
+                &[
+                    ("source", format!("#{id}-positions-array")),
+                    ("count", mesh.vertices.len().to_string()),
+                    ("stride", "3".to_string()),
+                ],
+                ["X", "Y", "Z"]
+                    .map(|x| {
+                        element(
+                            "param",
+                            &[("name", x.to_string()), ("type", "float".to_string())],
+                            vec![],
+                        )
+                    })
+                    .into(),
+            );
+            let source = element(
+                "source",
+                &[("id", format!("{id}-positions"))],
+                vec![
+                    text_element(
+                        "float_array",
+                        &[
+                            ("id", format!("{id}-positions-array")),
+                            ("count", (3 * mesh.vertices.len()).to_string()),
+                        ],
+                        &positions,
+                    ),
+                    element("technique_common", &[], vec![accessor]),
+                ],
+            );
+            let vertices = element(
+                "vertices",
+                &[("id", format!("{id}-vertices"))],
+                vec![input("POSITION", format!("#{id}-positions"), None)],
+            );
+            let triangles = element(
+                "triangles",
+                &[("count", mesh.triangles.len().to_string())],
+                vec![
+                    input("VERTEX", format!("#{id}-vertices"), Some(0)),
+                    text_element("p", &[], &indices),
+                ],
+            );
+            library.push(element(
+                "geometry",
+                &[("id", id.clone()), ("name", name.clone())],
+                vec![element("mesh", &[], vec![source, vertices, triangles])],
+            ));
+            nodes.push(element(
+                "node",
+                &[("id", format!("node-{}", index + 1)), ("name", name)],
+                vec![element(
+                    "instance_geometry",
+                    &[("url", format!("#{id}"))],
+                    vec![],
+                )],
+            ));
+        }
+
+        let up_axis = match self.up_axis {
+            UpAxis::Y => "Y_UP",
+            UpAxis::Z => "Z_UP",
+        };
+        let created = utc_now();
+        let asset = element(
+            "asset",
+            &[],
+            vec![
+                element(
+                    "contributor",
+                    &[],
+                    vec![text_element("authoring_tool", &[], "egml")],
+                ),
+                text_element("created", &[], &created),
+                text_element("modified", &[], &created),
+                element(
+                    "unit",
+                    &[
+                        ("name", self.unit.name().to_string()),
+                        ("meter", self.unit.meters().to_string()),
+                    ],
+                    vec![],
+                ),
+                text_element("up_axis", &[], up_axis),
+            ],
+        );
+        let mut root_content = vec![text_element(
+            "translate",
+            &[],
+            &transform.format(&options, transform.translation()),
+        )];
+        root_content.extend(nodes);
+        let scene = element(
+            "visual_scene",
+            &[("id", "scene".to_string())],
+            vec![element(
+                "node",
+                &[("id", "root".to_string()), ("name", "root".to_string())],
+                root_content,
+            )],
+        );
+        let mut content = vec![asset];
+        if !library.is_empty() {
+            content.push(element("library_geometries", &[], library));
+        }
+        content.extend([
+            element("library_visual_scenes", &[], vec![scene]),
+            element(
+                "scene",
+                &[],
+                vec![element(
+                    "instance_visual_scene",
+                    &[("url", "#scene".to_string())],
+                    vec![],
+                )],
+            ),
+        ]);
+        let document = XmlNode::new(
+            "COLLADA",
+            XmlNodeParts::new(content).with_attributes(vec![
+                ("xmlns".to_string(), COLLADA_NAMESPACE.to_string()),
+                ("version".to_string(), "1.4.1".to_string()),
+            ]),
+        );
+
+        let mut writer = Writer::new(out);
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        if self.formatting != Formatting::Compact {
+            writer.get_mut().write_all(b"\n")?;
+        }
+        document.write_to(&mut writer, self.formatting)?;
+        writer.get_mut().write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+fn input(semantic: &str, source: String, offset: Option<usize>) -> XmlNodeContent {
+    let mut attributes = vec![("semantic", semantic.to_string()), ("source", source)];
+    if let Some(offset) = offset {
+        attributes.push(("offset", offset.to_string()));
+    }
+    element("input", &attributes, vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt::read_wkt;
+    use egml_core::model::base::{AsAbstractGmlMut, Id};
+    use egml_core::model::geometry::AsAbstractGeometryMut;
+
+    #[test]
+    fn geometries_are_instantiated_by_named_nodes() {
+        let mut wall =
+            read_wkt("POLYGON Z ((10 20 0, 11 20 0, 11 20 1, 10 20 1, 10 20 0))").unwrap();
+        wall.abstract_geometry_mut()
+            .abstract_gml
+            .set_id(Id::try_from("wall & roof").unwrap());
+        let roof = read_wkt("POLYGON Z ((10 20 1, 11 20 1, 11 21 1, 10 20 1))").unwrap();
+
+        let mut dae = Vec::new();
+        ColladaWriter::new()
+            .with_unit(LengthUnit::new("centimeter", 0.01))
+            .write(&[wall, roof], &mut dae)
+            .unwrap();
+        let dae = String::from_utf8(dae).unwrap();
+
+        assert!(dae.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<COLLADA "));
+        assert!(dae.contains("<unit name=\"centimeter\" meter=\"0.01\"/>"));
+        assert!(dae.contains("<up_axis>Z_UP</up_axis>"));
+        assert!(dae.contains("<geometry id=\"geometry-1\" name=\"wall &amp; roof\">"));
+        assert!(dae.contains("<node id=\"node-2\" name=\"geometry_2\">"));
+        assert!(dae.contains("<instance_geometry url=\"#geometry-2\"/>"));
+        // The root node restores the lower corner, in centimetres.
+        assert!(dae.contains("<translate>1000 2000 0</translate>"));
+        assert!(dae.contains("<float_array id=\"geometry-1-positions-array\" count=\"12\">"));
+        assert!(dae.contains(" 100 0 100 "));
+    }
+}
@@ -1,3 +1,4 @@
+use egml_core::profile::{ProfileViolation, SimpleFeaturesLevel};
 use thiserror::Error;
 
 /// Errors returned by `egml-io` parsing operations.
@@ -12,6 +13,14 @@ pub enum Error {
     #[error(transparent)]
     Xml(#[from] quick_xml::Error),
 
+    /// Wraps an I/O error raised while writing to the output.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Wraps an error reading or writing a zip archive.
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+
     /// Wraps a serde deserialization error from `quick-xml`.
     #[error(transparent)]
     XmlDe(#[from] quick_xml::DeError),
@@ -20,6 +29,21 @@ pub enum Error {
     #[error(transparent)]
     XmlSe(#[from] quick_xml::SeError),
 
+    /// Wraps a JSON syntax error from `serde_json`.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// The XML declaration names a character encoding that is not supported.
+    ///
+    /// The inner string is the declared encoding label.
+    #[error("unsupported character encoding '{0}'")]
+    UnsupportedEncoding(String),
+
+    /// The input contains byte sequences that are invalid in its character
+    /// encoding, whose name is given.
+    #[error("input is not valid {0}")]
+    MalformedEncoding(&'static str),
+
     /// A required XML element with the given name was not found in the parsed GML fragment.
     ///
     /// The inner string names the missing element (e.g. `"gml:exterior"`,
@@ -43,6 +67,12 @@ pub enum Error {
     )]
     InvalidCoordinateCount { count: usize },
 
+    /// A `gml:coordinates` tuple contains a value that is not a number.
+    ///
+    /// The inner string is the offending tuple.
+    #[error("invalid coordinate tuple '{0}' in gml:coordinates")]
+    InvalidCoordinates(String),
+
     /// One or more required child elements are absent from the GML fragment.
     ///
     /// The inner string names the parent element or context where children are
@@ -50,6 +80,12 @@ pub enum Error {
     #[error("required child elements are absent from '{0}'")]
     MissingElements(String),
 
+    /// An element that is read as a GML object is in a foreign namespace.
+    ///
+    /// The inner string is the qualified name of the element.
+    #[error("element '{0}' is not in a GML namespace")]
+    ForeignElement(String),
+
     /// A `gml:LinearRing` element was expected but not found.
     #[error("missing gml:LinearRing element")]
     MissingLinearRing,
@@ -60,7 +96,76 @@ pub enum Error {
     #[error("missing surface kind for '{0}'")]
     MissingSurfaceKind(String),
 
-    /// The GML input uses XLink references (`xlink:href`), which are not yet resolved.
-    #[error("XLinks are not supported yet")]
+    /// A document referenced via `xlink:href` could not be loaded.
+    ///
+    /// `document` is the name the document was requested under.
+    #[error("cannot load referenced document '{document}': {reason}")]
+    ReferenceLoad { document: String, reason: String },
+
+    /// A namespace prefix configured for writing is empty or not a valid name.
+    #[error("invalid namespace prefix '{0}'")]
+    InvalidNamespacePrefix(String),
+
+    /// A geometry uses constructs outside of the Simple Features level a
+    /// [`DocumentWriter`](crate::util::DocumentWriter) enforces.
+    ///
+    /// `violations` holds every violation that applies to `level`; the
+    /// message names the first one, if any.
+    #[error(
+        "geometry does not conform to GML Simple Features {level}{}",
+        .violations.first().map_or(String::new(), |x| format!(": {x}"))
+    )]
+    SimpleFeatures {
+        level: SimpleFeaturesLevel,
+        violations: Vec<ProfileViolation>,
+    },
+
+    /// A geometry cannot be expressed in the named output format.
+    ///
+    /// `reason` describes the construct, e.g. `"a solid with interior shells"`.
+    #[error("{format} cannot represent {reason}")]
+    Unrepresentable {
+        format: &'static str,
+        reason: String,
+    },
+
+    /// The input is not valid Well-Known Text.
+    ///
+    /// `position` is the byte offset at which reading failed.
+    #[error("invalid WKT at byte {position}: {message}")]
+    Wkt { position: usize, message: String },
+
+    /// The input is not valid Well-Known Binary.
+    ///
+    /// `position` is the byte offset at which reading failed.
+    #[error("invalid WKB at byte {position}: {message}")]
+    Wkb { position: usize, message: String },
+
+    /// The input is valid JSON but not a valid GeoJSON or JSON-FG object.
+    #[error("invalid GeoJSON: {0}")]
+    GeoJson(String),
+
+    /// The input is valid JSON but not a valid CityJSON geometry or vertex
+    /// list.
+    #[error("invalid CityJSON: {0}")]
+    CityJson(String),
+
+    /// The input is not a valid STL file.
+    #[error("invalid STL: {0}")]
+    Stl(String),
+
+    /// The input is not a valid PLY file.
+    #[error("invalid PLY: {0}")]
+    Ply(String),
+
+    /// The input is not a valid Wavefront OBJ file.
+    #[error("invalid OBJ: {0}")]
+    Obj(String),
+
+    /// The GML input uses an XLink reference (`xlink:href`) that cannot be handled.
+    ///
+    /// Local references are kept on the parsed property and can be resolved with
+    /// [`resolve_local_references`](egml_core::resolver::resolve_local_references).
+    #[error("unsupported XLink reference")]
     UnsupportedXLink,
 }
@@ -0,0 +1,76 @@
+use egml_core::model::base::Id;
+use egml_core::model::basic_types::Code;
+use egml_core::model::feature::AsAbstractFeature;
+use egml_core::model::geometry::AbstractGeometryKind;
+use serde_json::{Map, Value};
+
+/// A GeoJSON `Feature`: the identity of a GML feature, its attribute values
+/// and its geometry.
+///
+/// The names are written as the `names` member of the `properties`, each as
+/// its value; their code spaces are not written.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GeoJsonFeature {
+    /// The `id` of the feature. Numeric ids are read as their decimal text.
+    pub id: Option<Id>,
+    pub names: Vec<Code>,
+    /// The `properties` of the feature, without `names`.
+    pub properties: Map<String, Value>,
+    /// The geometry, or `None` for an unlocated feature.
+    pub geometry: Option<AbstractGeometryKind>,
+}
+
+impl GeoJsonFeature {
+    /// Creates a feature without id, names or properties.
+    pub fn new(geometry: Option<AbstractGeometryKind>) -> Self {
+        Self {
+            geometry,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a feature with the `gml:id` and names of `feature`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use egml_core::model::base::{AbstractGml, AsAbstractGmlMut, Id};
+    /// use egml_core::model::basic_types::Code;
+    /// use egml_core::model::feature::AbstractFeature;
+    /// use egml_io::geojson::GeoJsonFeature;
+    ///
+    /// let mut gml = AbstractGml::with_id(Id::try_from("building-1").unwrap());
+    /// gml.push_name(Code::new("Town hall"));
+    /// let feature = GeoJsonFeature::from_feature(&AbstractFeature::from_abstract_gml(gml), None)
+    ///     .with_property("storeys", 3);
+    ///
+    /// assert_eq!(feature.id.unwrap().as_str(), "building-1");
+    /// assert_eq!(feature.names[0].value(), "Town hall");
+    /// ```
+    pub fn from_feature(
+        feature: &impl AsAbstractFeature,
+        geometry: Option<AbstractGeometryKind>,
+    ) -> Self {
+        Self {
+            id: feature.id().cloned(),
+            names: feature.names().to_vec(),
+            properties: Map::new(),
+            geometry,
+        }
+    }
+
+    pub fn with_id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn with_names(mut self, names: Vec<Code>) -> Self {
+        self.names = names;
+        self
+    }
+
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.properties.insert(key.into(), value.into());
+        self
+    }
+}
@@ -0,0 +1,46 @@
+//! GeoJSON (RFC 7946) and OGC Features and Geometries JSON (JSON-FG) for
+//! egml features and geometries.
+//!
+//! A [`GeoJsonFeature`] carries the `gml:id` and names of a feature, its
+//! properties and its geometry. [`GeoJsonWriter`] writes geometries as
+//! follows:
+//!
+//! | egml geometry | GeoJSON | JSON-FG |
+//! |---------------|---------|---------|
+//! | `Point` | `Point` | `Point` |
+//! | `LineString`, `LinearRing` | `LineString` | `LineString` |
+//! | `Polygon`, `Surface` with one patch | `Polygon` | `Polygon` |
+//! | `CompositeSurface`, `Shell`, `TriangulatedSurface`, other `Surface`s | `MultiPolygon` | `MultiPolygon` |
+//! | `Solid` | `MultiPolygon` per [`SolidFlattening`] | `Polyhedron` |
+//! | `MultiPoint` | `MultiPoint` | `MultiPoint` |
+//! | `MultiCurve` | `MultiLineString` | `MultiLineString` |
+//! | `MultiSurface` | `MultiPolygon`, with composite members flattened | `MultiPolygon` |
+//! | `MultiGeometry` | `GeometryCollection` | `GeometryCollection` |
+//!
+//! [`read_geojson`] and [`read_geojson_geometry`] read both encodings, a
+//! `Polyhedron` as a `Solid`. The closing vertex GeoJSON requires is added to
+//! every ring when writing and removed when reading.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use egml_io::geojson::{GeoJsonWriter, read_geojson};
+//!
+//! let json = r#"{"type": "FeatureCollection", "features": [{"type": "Feature",
+//!     "geometry": {"type": "LineString", "coordinates": [[0, 0, 0], [1, 1, 0]]},
+//!     "properties": {"lanes": 2}}]}"#;
+//! let features = read_geojson(json).unwrap();
+//!
+//! let written = GeoJsonWriter::new()
+//!     .write_feature_collection(&features)
+//!     .unwrap();
+//! assert_eq!(written["features"][0]["properties"]["lanes"], 2);
+//! ```
+
+mod feature;
+mod reader;
+mod writer;
+
+pub use feature::*;
+pub use reader::*;
+pub use writer::*;
@@ -0,0 +1,328 @@
+use crate::Error;
+use crate::geojson::GeoJsonFeature;
+use crate::util::{Ring, SimpleGeometry, epsg_code, epsg_srs_name, to_solid};
+use egml_core::model::base::Id;
+use egml_core::model::basic_types::Code;
+use egml_core::model::geometry::aggregates::MultiGeometry;
+use egml_core::model::geometry::{
+    AbstractGeometryKind, AbstractGeometryProperty, AsAbstractGeometryMut, DirectPosition,
+};
+use serde_json::{Map, Value};
+
+/// Reads the features of a GeoJSON or JSON-FG document: the members of a
+/// `FeatureCollection`, a single `Feature`, or a bare geometry as a feature
+/// without properties.
+///
+/// The `place` of a JSON-FG feature takes precedence over its `geometry`,
+/// and its `coordRefSys` becomes the `srsName` of the geometry. Positions
+/// must have three coordinates.
+///
+/// # Errors
+///
+/// Returns [`Error::Json`] for malformed JSON, [`Error::GeoJson`] for JSON
+/// that is not GeoJSON, such as an unknown geometry type or an unclosed
+/// ring, and [`Error::UnsupportedDimension`] for 2-D positions.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::AsAbstractGeometry;
+/// use egml_io::geojson::read_geojson;
+///
+/// let features = read_geojson(
+///     r#"{"type": "Feature", "id": 7, "coordRefSys": "http://www.opengis.net/def/crs/EPSG/0/25832",
+///         "geometry": null, "place": {"type": "Point", "coordinates": [1, 2, 3]},
+///         "properties": {"names": ["Well"], "depth": 12.5}}"#,
+/// )
+/// .unwrap();
+///
+/// let feature = &features[0];
+/// assert_eq!(feature.id.as_ref().unwrap().as_str(), "7");
+/// assert_eq!(feature.names[0].value(), "Well");
+/// assert_eq!(feature.properties["depth"], 12.5);
+/// assert_eq!(
+///     feature.geometry.as_ref().unwrap().srs_name().unwrap(),
+///     "urn:ogc:def:crs:EPSG::25832"
+/// );
+/// ```
+pub fn read_geojson(json: &str) -> Result<Vec<GeoJsonFeature>, Error> {
+    let value: Value = serde_json::from_str(json)?;
+    let object = as_object(&value, "document")?;
+    match member_type(object)? {
+        "FeatureCollection" => {
+            let coord_ref_sys = object.get("coordRefSys");
+            as_array(member(object, "features")?, "features")?
+                .iter()
+                .map(|x| read_feature(x, coord_ref_sys))
+                .collect()
+        }
+        "Feature" => Ok(vec![read_feature(&value, None)?]),
+        _ => Ok(vec![GeoJsonFeature::new(Some(read_geometry(&value)?))]),
+    }
+}
+
+/// Reads a GeoJSON or JSON-FG geometry object. A `Polyhedron` becomes a
+/// `Solid`, a `MultiPolyhedron` a `MultiGeometry` of `Solid`s.
+///
+/// # Errors
+///
+/// Returns the errors of [`read_geojson`].
+pub fn read_geojson_geometry(json: &str) -> Result<AbstractGeometryKind, Error> {
+    read_geometry(&serde_json::from_str(json)?)
+}
+
+fn read_feature(value: &Value, coord_ref_sys: Option<&Value>) -> Result<GeoJsonFeature, Error> {
+    let object = as_object(value, "feature")?;
+    if member_type(object)? != "Feature" {
+        return Err(Error::GeoJson("expected a Feature".to_string()));
+    }
+
+    let id = match object.get("id") {
+        None | Some(Value::Null) => None,
+        Some(Value::String(x)) => Some(Id::try_from(x.as_str())?),
+        Some(Value::Number(x)) => Some(Id::try_from(x.to_string())?),
+        Some(_) => {
+            return Err(Error::GeoJson(
+                "id is neither a string nor a number".to_string(),
+            ));
+        }
+    };
+
+    let mut properties = match object.get("properties") {
+        None | Some(Value::Null) => Map::new(),
+        Some(x) => as_object(x, "properties")?.clone(),
+    };
+    let names = match properties.remove("names") {
+        None => Vec::new(),
+        Some(Value::Array(x)) => x
+            .iter()
+            .map(|x| {
+                x.as_str()
+                    .map(Code::new)
+                    .ok_or_else(|| Error::GeoJson("names must be strings".to_string()))
+            })
+            .collect::<Result<_, _>>()?,
+        Some(x) => {
+            properties.insert("names".to_string(), x);
+            Vec::new()
+        }
+    };
+
+    let place = object.get("place").filter(|x| !x.is_null());
+    let mut geometry = match place.or(object.get("geometry")) {
+        None | Some(Value::Null) => None,
+        Some(x) => Some(read_geometry(x)?),
+    };
+    if let Some(geometry) = &mut geometry
+        && let Some(srs_name) = object.get("coordRefSys").or(coord_ref_sys)
+    {
+        let srs_name = srs_name
+            .as_str()
+            .ok_or_else(|| Error::GeoJson("only CRS references are supported".to_string()))?;
+        match epsg_code(srs_name) {
+            Some(code) => geometry.set_srs_name(epsg_srs_name(code)),
+            None => geometry.set_srs_name(srs_name),
+        }
+    }
+
+    Ok(GeoJsonFeature {
+        id,
+        names,
+        properties,
+        geometry,
+    })
+}
+
+fn read_geometry(value: &Value) -> Result<AbstractGeometryKind, Error> {
+    let object = as_object(value, "geometry")?;
+    let kind = member_type(object)?;
+    if kind == "GeometryCollection" {
+        let members = as_array(member(object, "geometries")?, "geometries")?
+            .iter()
+            .map(|x| Ok(AbstractGeometryProperty::from_object(read_geometry(x)?)))
+            .collect::<Result<_, Error>>()?;
+        let mut multi_geometry = MultiGeometry::new(None)?;
+        multi_geometry.set_geometry_member(members);
+        return Ok(multi_geometry.into());
+    }
+
+    let coordinates = member(object, "coordinates")?;
+    let simple = match kind {
+        "Point" => SimpleGeometry::Point(position(coordinates)?),
+        "LineString" => SimpleGeometry::LineString(positions(coordinates)?),
+        "Polygon" => SimpleGeometry::Polygon(rings(coordinates)?),
+        "MultiPoint" => SimpleGeometry::MultiPoint(positions(coordinates)?),
+        "MultiLineString" => SimpleGeometry::MultiLineString(list(coordinates, positions)?),
+        "MultiPolygon" => SimpleGeometry::MultiPolygon(list(coordinates, rings)?),
+        "Polyhedron" => return Ok(to_solid(list(coordinates, polygons)?)?.into()),
+        "MultiPolyhedron" => {
+            let mut multi_geometry = MultiGeometry::new(None)?;
+            multi_geometry.set_geometry_member(
+                list(coordinates, |x| list(x, polygons))?
+                    .into_iter()
+                    .map(|x| Ok(AbstractGeometryProperty::from_object(to_solid(x)?.into())))
+                    .collect::<Result<_, Error>>()?,
+            );
+            return Ok(multi_geometry.into());
+        }
+        _ => {
+            return Err(Error::GeoJson(format!(
+                "unsupported geometry type '{kind}'"
+            )));
+        }
+    };
+    simple.into_geometry()
+}
+
+fn member<'a>(object: &'a Map<String, Value>, name: &str) -> Result<&'a Value, Error> {
+    object
+        .get(name)
+        .ok_or_else(|| Error::GeoJson(format!("missing member '{name}'")))
+}
+
+fn member_type(object: &Map<String, Value>) -> Result<&str, Error> {
+    member(object, "type")?
+        .as_str()
+        .ok_or_else(|| Error::GeoJson("type is not a string".to_string()))
+}
+
+fn as_object<'a>(value: &'a Value, what: &str) -> Result<&'a Map<String, Value>, Error> {
+    value
+        .as_object()
+        .ok_or_else(|| Error::GeoJson(format!("{what} is not an object")))
+}
+
+fn as_array<'a>(value: &'a Value, what: &str) -> Result<&'a Vec<Value>, Error> {
+    value
+        .as_array()
+        .ok_or_else(|| Error::GeoJson(format!("{what} is not an array")))
+}
+
+/// Reads each element of the coordinate array `value` with `item`.
+fn list<T>(value: &Value, item: impl Fn(&Value) -> Result<T, Error>) -> Result<Vec<T>, Error> {
+    as_array(value, "coordinates")?.iter().map(item).collect()
+}
+
+fn polygons(value: &Value) -> Result<Vec<Vec<Ring>>, Error> {
+    list(value, rings)
+}
+
+fn rings(value: &Value) -> Result<Vec<Ring>, Error> {
+    list(value, |x| {
+        let ring = positions(x)?;
+        if ring.len() < 4 || ring.first() != ring.last() {
+            return Err(Error::GeoJson("ring is not closed".to_string()));
+        }
+        Ok(ring)
+    })
+}
+
+fn positions(value: &Value) -> Result<Vec<DirectPosition>, Error> {
+    list(value, position)
+}
+
+fn position(value: &Value) -> Result<DirectPosition, Error> {
+    let coordinates = list(value, |x| {
+        x.as_f64()
+            .ok_or_else(|| Error::GeoJson("coordinate is not a number".to_string()))
+    })?;
+    let [x, y, z] = coordinates[..] else {
+        return Err(Error::UnsupportedDimension {
+            found: coordinates.len() as u32,
+        });
+    };
+    Ok(DirectPosition::new(x, y, z)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use egml_core::model::geometry::AsAbstractGeometry;
+    use egml_core::model::geometry::primitives::Solid;
+
+    #[test]
+    fn geometries_round_trip() {
+        let geometries = [
+            r#"{"type":"Point","coordinates":[1.0,2.0,3.0]}"#,
+            r#"{"type":"LineString","coordinates":[[0.0,0.0,0.0],[1.0,0.0,0.0]]}"#,
+            r#"{"type":"Polygon","coordinates":[[[0.0,0.0,0.0],[4.0,0.0,0.0],[4.0,4.0,0.0],[0.0,0.0,0.0]],[[1.0,1.0,0.0],[2.0,2.0,0.0],[2.0,1.0,0.0],[1.0,1.0,0.0]]]}"#,
+            r#"{"type":"MultiPoint","coordinates":[[0.0,0.0,0.0],[1.0,1.0,1.0]]}"#,
+            r#"{"type":"MultiLineString","coordinates":[[[0.0,0.0,0.0],[1.0,0.0,0.0]]]}"#,
+            r#"{"type":"MultiPolygon","coordinates":[[[[0.0,0.0,0.0],[1.0,0.0,0.0],[1.0,1.0,0.0],[0.0,0.0,0.0]]]]}"#,
+            r#"{"type":"GeometryCollection","geometries":[{"type":"Point","coordinates":[1.0,2.0,3.0]}]}"#,
+        ];
+
+        for json in geometries {
+            let geometry = read_geojson_geometry(json).unwrap();
+            let written = GeoJsonWriter::new().write_geometry(&geometry).unwrap();
+            assert_eq!(written.to_string(), json);
+        }
+    }
+
+    #[test]
+    fn polyhedra_are_read_as_solids_with_interior_shells() {
+        let tetrahedron = |o: f64| {
+            format!(
+                "[[[[{o},{o},{o}],[{o},1,{o}],[1,{o},{o}],[{o},{o},{o}]]],\
+                  [[[{o},{o},{o}],[1,{o},{o}],[{o},{o},1],[{o},{o},{o}]]],\
+                  [[[{o},{o},{o}],[{o},{o},1],[{o},1,{o}],[{o},{o},{o}]]],\
+                  [[[1,{o},{o}],[{o},1,{o}],[{o},{o},1],[1,{o},{o}]]]]"
+            )
+        };
+        let json = format!(
+            r#"{{"type":"Polyhedron","coordinates":[{},{}]}}"#,
+            tetrahedron(-1.0),
+            tetrahedron(0.0)
+        );
+
+        let solid = Solid::try_from(read_geojson_geometry(&json).unwrap()).unwrap();
+
+        assert_eq!(
+            solid.exterior().unwrap().object().unwrap().members().len(),
+            4
+        );
+        assert_eq!(solid.interior().len(), 1);
+    }
+
+    #[test]
+    fn collections_pass_their_crs_to_features() {
+        let features = read_geojson(
+            r#"{"type":"FeatureCollection","coordRefSys":"http://www.opengis.net/def/crs/EPSG/0/25832",
+                "features":[{"type":"Feature","id":"a","geometry":{"type":"Point","coordinates":[1,2,3]},"properties":null},
+                            {"type":"Feature","geometry":null,"properties":{"names":"not a list"}}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(features.len(), 2);
+        assert_eq!(
+            features[0].geometry.as_ref().unwrap().srs_name().unwrap(),
+            "urn:ogc:def:crs:EPSG::25832"
+        );
+        assert!(features[1].geometry.is_none());
+        assert!(features[1].names.is_empty());
+        assert_eq!(features[1].properties["names"], "not a list");
+    }
+
+    #[test]
+    fn invalid_geojson_is_rejected() {
+        assert!(matches!(
+            read_geojson_geometry(r#"{"type":"Point","coordinates":[1,2]}"#),
+            Err(Error::UnsupportedDimension { found: 2 })
+        ));
+        assert!(matches!(
+            read_geojson_geometry(
+                r#"{"type":"Polygon","coordinates":[[[0,0,0],[1,0,0],[1,1,0],[0,1,0]]]}"#
+            ),
+            Err(Error::GeoJson(_))
+        ));
+        assert!(matches!(
+            read_geojson_geometry(r#"{"type":"Prism","coordinates":[]}"#),
+            Err(Error::GeoJson(_))
+        ));
+        assert!(matches!(
+            read_geojson(r#"{"type":"Feature""#),
+            Err(Error::Json(_))
+        ));
+    }
+}
@@ -0,0 +1,591 @@
+use crate::Error;
+use crate::geojson::GeoJsonFeature;
+use crate::util::{PlacementCrs, Ring, SimpleGeometry, WriteOptions, epsg_code, solid_shells};
+use egml_core::model::geometry::aggregates::AbstractGeometricAggregateKind;
+use egml_core::model::geometry::primitives::{AbstractGeometricPrimitiveKind, AbstractSolidKind};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometry, DirectPosition};
+use serde_json::{Map, Value, json};
+
+/// The JSON-FG conformance classes a document written with
+/// [`GeoJsonWriter::with_json_fg`] declares in `conformsTo`. Every egml
+/// geometry is 3-D.
+pub const JSON_FG_CONFORMANCE: [&str; 2] = [
+    "http://www.opengis.net/spec/json-fg-1/0.2/conf/core",
+    "http://www.opengis.net/spec/json-fg-1/0.2/conf/3d",
+];
+
+/// How a `Solid` is flattened into a GeoJSON `MultiPolygon`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SolidFlattening {
+    /// The faces of the exterior shell. Cavities are dropped, which suits
+    /// maps that only show the outside of a solid.
+    #[default]
+    ExteriorShell,
+    /// The faces of the exterior shell followed by those of the interior
+    /// shells.
+    AllShells,
+}
+
+/// Writes geometries and features as GeoJSON (RFC 7946) or JSON-FG.
+///
+/// GeoJSON has no solids, so a `Solid` is flattened into a `MultiPolygon`
+/// according to [`with_solid_flattening`](Self::with_solid_flattening), and
+/// composite surfaces, shells and triangulated surfaces into a
+/// `MultiPolygon` of all their faces. With
+/// [`with_json_fg`](Self::with_json_fg), solids are written as JSON-FG
+/// `Polyhedron`s in the `place` of a feature instead.
+///
+/// GeoJSON coordinates are longitude, latitude and height in OGC CRS84.
+/// Geometries in EPSG:4326 or EPSG:4979 have their axes swapped, and those in
+/// a CRS that [`PlacementCrs`] supports are reprojected; other CRSs can only be
+/// written as a JSON-FG `place`. Polygon rings are oriented as RFC 7946
+/// §3.1.6 requires, the exterior counter-clockwise and the interiors
+/// clockwise. Ordinates are formatted according to the current
+/// [`WriteOptions`](crate::util::WriteOptions), except that reprojected
+/// longitudes and latitudes keep their full precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GeoJsonWriter {
+    solid_flattening: SolidFlattening,
+    json_fg: bool,
+}
+
+impl GeoJsonWriter {
+    /// Creates a writer for plain GeoJSON that keeps the exterior shell of
+    /// solids.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_solid_flattening(mut self, solid_flattening: SolidFlattening) -> Self {
+        self.solid_flattening = solid_flattening;
+        self
+    }
+
+    /// Writes JSON-FG: features get the `conformsTo`, `time`, `coordRefSys`
+    /// and `place` members, and a `place` holds solids as `Polyhedron`s or
+    /// coordinates in a CRS other than WGS 84. The `geometry` remains plain
+    /// GeoJSON for clients that do not know JSON-FG; it is `null` if the CRS
+    /// is not WGS 84.
+    pub fn with_json_fg(mut self, json_fg: bool) -> Self {
+        self.json_fg = json_fg;
+        self
+    }
+
+    /// Returns `geometry` as a GeoJSON geometry object in OGC CRS84, or when
+    /// writing JSON-FG, in its own CRS with solids as `Polyhedron`s.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Unrepresentable`] for a polygon with interior rings
+    /// but no exterior ring or, unless writing JSON-FG, for a CRS that cannot
+    /// be reprojected to OGC CRS84, and an error if a member is an unresolved
+    /// `xlink:href` reference.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use egml_core::model::geometry::DirectPosition;
+    /// use egml_core::model::geometry::primitives::Point;
+    /// use egml_io::geojson::GeoJsonWriter;
+    ///
+    /// let point = Point::new(DirectPosition::new(7.5, 47.5, 260.0).unwrap());
+    /// let json = GeoJsonWriter::new().write_geometry(&point.into()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     json.to_string(),
+    ///     r#"{"type":"Point","coordinates":[7.5,47.5,260.0]}"#
+    /// );
+    /// ```
+    pub fn write_geometry(&self, geometry: &AbstractGeometryKind) -> Result<Value, Error> {
+        let options = WriteOptions::current();
+        let coordinates = if self.json_fg {
+            CoordinateWriter::new(&options, None)
+        } else {
+            CoordinateWriter::crs84(&options, geometry.srs_name().map(String::as_str))?
+        };
+        self.geometry_value(geometry, self.json_fg, &coordinates)
+    }
+
+    /// Returns `feature` as a GeoJSON `Feature`, with `conformsTo` when
+    /// writing JSON-FG. A JSON-FG feature whose CRS cannot be reprojected to
+    /// OGC CRS84 has a `null` `geometry` and only a `place`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`write_geometry`](Self::write_geometry).
+    pub fn write_feature(&self, feature: &GeoJsonFeature) -> Result<Value, Error> {
+        self.feature_value(feature, self.json_fg, &WriteOptions::current())
+    }
+
+    /// Returns `features` as a GeoJSON `FeatureCollection`, with `conformsTo`
+    /// when writing JSON-FG.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`write_geometry`](Self::write_geometry).
+    pub fn write_feature_collection(&self, features: &[GeoJsonFeature]) -> Result<Value, Error> {
+        let options = WriteOptions::current();
+        let mut collection = Map::new();
+        collection.insert("type".to_string(), json!("FeatureCollection"));
+        if self.json_fg {
+            collection.insert("conformsTo".to_string(), json!(JSON_FG_CONFORMANCE));
+        }
+        collection.insert(
+            "features".to_string(),
+            features
+                .iter()
+                .map(|x| self.feature_value(x, false, &options))
+                .collect::<Result<_, _>>()?,
+        );
+        Ok(Value::Object(collection))
+    }
+
+    fn feature_value(
+        &self,
+        feature: &GeoJsonFeature,
+        conforms_to: bool,
+        options: &WriteOptions,
+    ) -> Result<Value, Error> {
+        let mut value = Map::new();
+        value.insert("type".to_string(), json!("Feature"));
+        if conforms_to {
+            value.insert("conformsTo".to_string(), json!(JSON_FG_CONFORMANCE));
+        }
+        if let Some(id) = &feature.id {
+            value.insert("id".to_string(), json!(id.as_str()));
+        }
+
+        let srs_name = feature
+            .geometry
+            .as_ref()
+            .and_then(|x| x.srs_name())
+            .map(String::as_str);
+        let geometry = match (
+            &feature.geometry,
+            CoordinateWriter::crs84(options, srs_name),
+        ) {
+            (None, _) => Value::Null,
+            (Some(x), Ok(coordinates)) => self.geometry_value(x, false, &coordinates)?,
+            (Some(_), Err(_)) if self.json_fg => Value::Null,
+            (Some(_), Err(e)) => return Err(e),
+        };
+        if self.json_fg {
+            let place = match &feature.geometry {
+                Some(x) => self.geometry_value(x, true, &CoordinateWriter::new(options, None))?,
+                None => Value::Null,
+            };
+            let crs84 = srs_name.is_none_or(is_crs84);
+            value.insert("time".to_string(), Value::Null);
+            value.insert(
+                "coordRefSys".to_string(),
+                srs_name.map_or(Value::Null, |x| json!(coord_ref_sys(x))),
+            );
+            let place = if crs84 && place == geometry {
+                Value::Null
+            } else {
+                place
+            };
+            value.insert("place".to_string(), place);
+        }
+        value.insert("geometry".to_string(), geometry);
+
+        let mut properties = feature.properties.clone();
+        if !feature.names.is_empty() {
+            properties.insert(
+                "names".to_string(),
+                feature.names.iter().map(|x| json!(x.value())).collect(),
+            );
+        }
+        value.insert("properties".to_string(), Value::Object(properties));
+        Ok(Value::Object(value))
+    }
+
+    fn geometry_value(
+        &self,
+        geometry: &AbstractGeometryKind,
+        polyhedra: bool,
+        coordinates: &CoordinateWriter,
+    ) -> Result<Value, Error> {
+        let format = if polyhedra { "JSON-FG" } else { "GeoJSON" };
+        match geometry {
+            AbstractGeometryKind::AbstractGeometricPrimitiveKind(
+                AbstractGeometricPrimitiveKind::AbstractSolidKind(AbstractSolidKind::Solid(x)),
+            ) => {
+                let mut shells = solid_shells(x, format)?;
+                if polyhedra {
+                    return Ok(typed(
+                        "Polyhedron",
+                        shells.iter().map(|x| coordinates.faces(x)).collect(),
+                    ));
+                }
+                if self.solid_flattening == SolidFlattening::ExteriorShell {
+                    shells.truncate(1);
+                }
+                let faces: Vec<_> = shells.into_iter().flatten().collect();
+                Ok(typed("MultiPolygon", coordinates.polygons(&faces)))
+            }
+            AbstractGeometryKind::AbstractGeometricAggregateKind(
+                AbstractGeometricAggregateKind::MultiGeometry(x),
+            ) => {
+                let mut members = Vec::new();
+                for member in x.geometry_member() {
+                    let object = member.object().ok_or(Error::UnsupportedXLink)?;
+                    members.push(self.geometry_value(object, polyhedra, coordinates)?);
+                }
+                for object in x.geometry_members().map(|x| x.objects()).unwrap_or(&[]) {
+                    members.push(self.geometry_value(object, polyhedra, coordinates)?);
+                }
+                Ok(json!({"type": "GeometryCollection", "geometries": members}))
+            }
+            _ => Ok(simple_value(
+                &SimpleGeometry::from_geometry(geometry, format)?,
+                coordinates,
+            )),
+        }
+    }
+}
+
+/// Returns whether `srs_name` denotes OGC CRS84, the only CRS of RFC 7946.
+fn is_crs84(srs_name: &str) -> bool {
+    srs_name.to_ascii_uppercase().contains("CRS84")
+}
+
+/// The OGC URI of the CRS named `srs_name`, which JSON-FG expects in
+/// `coordRefSys`.
+fn coord_ref_sys(srs_name: &str) -> String {
+    match epsg_code(srs_name) {
+        Some(code) => format!("http://www.opengis.net/def/crs/EPSG/0/{code}"),
+        None => srs_name.to_string(),
+    }
+}
+
+fn typed(kind: &str, coordinates: Value) -> Value {
+    json!({"type": kind, "coordinates": coordinates})
+}
+
+fn simple_value(geometry: &SimpleGeometry, coordinates: &CoordinateWriter) -> Value {
+    match geometry {
+        SimpleGeometry::Point(x) => typed("Point", coordinates.position(x)),
+        SimpleGeometry::LineString(x) => typed("LineString", coordinates.positions(x)),
+        SimpleGeometry::Polygon(x) | SimpleGeometry::Triangle(x) => {
+            typed("Polygon", coordinates.rings(x))
+        }
+        SimpleGeometry::MultiPoint(x) => typed("MultiPoint", coordinates.positions(x)),
+        SimpleGeometry::MultiLineString(x) => typed(
+            "MultiLineString",
+            x.iter().map(|x| coordinates.positions(x)).collect(),
+        ),
+        SimpleGeometry::MultiPolygon(x)
+        | SimpleGeometry::PolyhedralSurface(x)
+        | SimpleGeometry::Tin(x) => typed("MultiPolygon", coordinates.polygons(x)),
+        SimpleGeometry::GeometryCollection(x) => json!({
+            "type": "GeometryCollection",
+            "geometries": x.iter().map(|x| simple_value(x, coordinates)).collect::<Vec<_>>(),
+        }),
+    }
+}
+
+/// Writes positions as JSON arrays, reprojected to OGC CRS84 if needed and
+/// rounded to the precision of the [`WriteOptions`].
+struct CoordinateWriter<'a> {
+    options: &'a WriteOptions,
+    reprojection: Option<PlacementCrs>,
+}
+
+impl<'a> CoordinateWriter<'a> {
+    fn new(options: &'a WriteOptions, reprojection: Option<PlacementCrs>) -> Self {
+        Self {
+            options,
+            reprojection,
+        }
+    }
+
+    /// A writer that reprojects from `srs_name` to OGC CRS84, or keeps the
+    /// coordinates if they have no CRS or already are in OGC CRS84.
+    fn crs84(options: &'a WriteOptions, srs_name: Option<&str>) -> Result<Self, Error> {
+        let reprojection =
+            match srs_name {
+                Some(x) if !is_crs84(x) => Some(PlacementCrs::from_srs_name(x).ok_or_else(
+                    || Error::Unrepresentable {
+                        format: "GeoJSON",
+                        reason: format!(
+                            "coordinates in {x}, which are not reprojected to OGC CRS84"
+                        ),
+                    },
+                )?),
+                _ => None,
+            };
+        Ok(Self::new(options, reprojection))
+    }
+
+    fn coords(&self, position: &DirectPosition) -> [f64; 3] {
+        match self.reprojection {
+            Some(crs) => crs.to_crs84(position.coords()),
+            None => position.coords(),
+        }
+    }
+
+    /// The coordinates of `coords`, rounded to the precision of the options.
+    fn coords_value(&self, coords: [f64; 3]) -> Value {
+        coords
+            .into_iter()
+            .enumerate()
+            .map(|(axis, value)| {
+                // Precisions are given in the units of the source CRS.
+                if self.reprojection.is_some() && axis < 2 {
+                    return json!(value);
+                }
+                let rounded = self
+                    .options
+                    .format_ordinate(axis, value)
+                    .parse()
+                    .unwrap_or(value);
+                json!(rounded)
+            })
+            .collect()
+    }
+
+    fn position(&self, position: &DirectPosition) -> Value {
+        self.coords_value(self.coords(position))
+    }
+
+    fn positions(&self, positions: &[DirectPosition]) -> Value {
+        positions.iter().map(|x| self.position(x)).collect()
+    }
+
+    /// The rings of a polygon, the exterior counter-clockwise and the
+    /// interiors clockwise in the x/y plane.
+    fn rings(&self, rings: &[Ring]) -> Value {
+        rings
+            .iter()
+            .enumerate()
+            .map(|(index, ring)| {
+                let mut coords: Vec<[f64; 3]> = ring.iter().map(|x| self.coords(x)).collect();
+                let area = signed_area(&coords);
+                if (index == 0 && area < 0.0) || (index > 0 && area > 0.0) {
+                    coords.reverse();
+                }
+                coords
+                    .into_iter()
+                    .map(|x| self.coords_value(x))
+                    .collect::<Value>()
+            })
+            .collect()
+    }
+
+    fn polygons(&self, polygons: &[Vec<Ring>]) -> Value {
+        polygons.iter().map(|x| self.rings(x)).collect()
+    }
+
+    /// The faces of a `Polyhedron` shell, whose orientation is kept since it
+    /// defines the outside of the solid.
+    fn faces(&self, faces: &[Vec<Ring>]) -> Value {
+        faces
+            .iter()
+            .map(|x| x.iter().map(|x| self.positions(x)).collect::<Value>())
+            .collect()
+    }
+}
+
+/// Twice the signed area of `ring` projected onto the x/y plane, positive if
+/// it runs counter-clockwise.
+fn signed_area(ring: &[[f64; 3]]) -> f64 {
+    ring.iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(a, b)| a[0] * b[1] - b[0] * a[1])
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{serialize_with_options, to_solid};
+    use egml_core::model::base::Id;
+    use egml_core::model::basic_types::Code;
+    use egml_core::model::common::CoordinatePrecision;
+    use egml_core::model::geometry::AsAbstractGeometryMut;
+    use egml_core::model::geometry::primitives::Point;
+
+    /// The four closed triangles of a tetrahedron with its corner at
+    /// `(origin, origin, origin)`.
+    fn tetrahedron(origin: f64, size: f64) -> Vec<Vec<Ring>> {
+        let corner = |x: f64, y: f64, z: f64| {
+            DirectPosition::new(origin + x * size, origin + y * size, origin + z * size).unwrap()
+        };
+        let [a, b, c, d] = [
+            corner(0.0, 0.0, 0.0),
+            corner(1.0, 0.0, 0.0),
+            corner(0.0, 1.0, 0.0),
+            corner(0.0, 0.0, 1.0),
+        ];
+        [[a, c, b], [a, b, d], [a, d, c], [b, c, d]]
+            .into_iter()
+            .map(|[p, q, r]| vec![vec![p, q, r, p]])
+            .collect()
+    }
+
+    fn solid_with_cavity() -> AbstractGeometryKind {
+        to_solid(vec![tetrahedron(0.0, 4.0), tetrahedron(1.0, 1.0)])
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn solids_are_flattened_per_rule_or_kept_as_polyhedra() {
+        let solid = solid_with_cavity();
+
+        let exterior = GeoJsonWriter::new().write_geometry(&solid).unwrap();
+        assert_eq!(exterior["type"], "MultiPolygon");
+        assert_eq!(exterior["coordinates"].as_array().unwrap().len(), 4);
+
+        let all = GeoJsonWriter::new()
+            .with_solid_flattening(SolidFlattening::AllShells)
+            .write_geometry(&solid)
+            .unwrap();
+        assert_eq!(all["coordinates"].as_array().unwrap().len(), 8);
+        assert_eq!(all["coordinates"][4][0][0], json!([1.0, 1.0, 1.0]));
+
+        let polyhedron = GeoJsonWriter::new()
+            .with_json_fg(true)
+            .write_geometry(&solid)
+            .unwrap();
+        assert_eq!(polyhedron["type"], "Polyhedron");
+        assert_eq!(polyhedron["coordinates"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            polyhedron["coordinates"][0][0][0],
+            json!([
+                [0.0, 0.0, 0.0],
+                [0.0, 4.0, 0.0],
+                [4.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0]
+            ])
+        );
+    }
+
+    #[test]
+    fn json_fg_features_place_solids_and_projected_coordinates() {
+        let mut projected = solid_with_cavity();
+        projected.set_srs_name("urn:ogc:def:crs:EPSG::25832");
+        let mut geographic = solid_with_cavity();
+        geographic.set_srs_name("urn:ogc:def:crs:EPSG::4979");
+
+        let mut unsupported = solid_with_cavity();
+        unsupported.set_srs_name("EPSG:31467");
+        let writer = GeoJsonWriter::new().with_json_fg(true);
+
+        let feature = writer
+            .write_feature(&GeoJsonFeature::new(Some(projected)))
+            .unwrap();
+        assert_eq!(feature["conformsTo"], json!(JSON_FG_CONFORMANCE));
+        assert_eq!(
+            feature["coordRefSys"],
+            "http://www.opengis.net/def/crs/EPSG/0/25832"
+        );
+        assert_eq!(feature["place"]["type"], "Polyhedron");
+        assert_eq!(feature["geometry"]["type"], "MultiPolygon");
+        assert!(feature["time"].is_null());
+
+        let feature = writer
+            .write_feature(&GeoJsonFeature::new(Some(unsupported.clone())))
+            .unwrap();
+        assert_eq!(feature["place"]["type"], "Polyhedron");
+        assert!(feature["geometry"].is_null());
+        assert!(matches!(
+            GeoJsonWriter::new().write_feature(&GeoJsonFeature::new(Some(unsupported))),
+            Err(Error::Unrepresentable { .. })
+        ));
+
+        let feature = writer
+            .write_feature(&GeoJsonFeature::new(Some(geographic)))
+            .unwrap();
+        assert_eq!(feature["place"]["type"], "Polyhedron");
+        assert_eq!(feature["geometry"]["type"], "MultiPolygon");
+
+        let point = Point::new(DirectPosition::ORIGIN).into();
+        let collection = writer
+            .write_feature_collection(&[GeoJsonFeature::new(Some(point))])
+            .unwrap();
+        let feature = &collection["features"][0];
+        assert!(feature.get("conformsTo").is_none());
+        assert!(feature["place"].is_null());
+        assert_eq!(feature["geometry"]["type"], "Point");
+    }
+
+    #[test]
+    fn features_carry_id_names_properties_and_rounded_coordinates() {
+        let point = Point::new(DirectPosition::new(7.123456, 47.654321, 260.55).unwrap());
+        let feature = GeoJsonFeature::new(Some(point.into()))
+            .with_id(Id::try_from("b1").unwrap())
+            .with_names(vec![Code::with_code_space("urn:names", "Town hall")])
+            .with_property("storeys", 3);
+        let options = WriteOptions::default()
+            .with_precision(CoordinatePrecision::horizontal_vertical(3, 1))
+            .with_trim_trailing_zeros(true);
+
+        let value = serialize_with_options(options, || {
+            GeoJsonWriter::new().write_feature(&feature).unwrap()
+        });
+
+        assert_eq!(
+            value.to_string(),
+            r#"{"type":"Feature","id":"b1","geometry":{"type":"Point","coordinates":[7.123,47.654,260.6]},"properties":{"storeys":3,"names":["Town hall"]}}"#
+        );
+    }
+
+    #[test]
+    fn coordinates_are_written_in_crs84_with_rfc_7946_winding() {
+        let square = |corners: [(f64, f64); 4]| {
+            let mut ring: Ring = corners
+                .into_iter()
+                .map(|(x, y)| DirectPosition::new(x, y, 0.0).unwrap())
+                .collect();
+            ring.push(ring[0]);
+            ring
+        };
+        // Exterior clockwise, interior counter-clockwise.
+        let polygon: AbstractGeometryKind = SimpleGeometry::Polygon(vec![
+            square([(0.0, 0.0), (0.0, 4.0), (4.0, 4.0), (4.0, 0.0)]),
+            square([(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)]),
+        ])
+        .into_geometry()
+        .unwrap();
+
+        let written = GeoJsonWriter::new().write_geometry(&polygon).unwrap();
+        assert_eq!(
+            written["coordinates"],
+            json!([
+                [
+                    [0.0, 0.0, 0.0],
+                    [4.0, 0.0, 0.0],
+                    [4.0, 4.0, 0.0],
+                    [0.0, 4.0, 0.0],
+                    [0.0, 0.0, 0.0]
+                ],
+                [
+                    [1.0, 1.0, 0.0],
+                    [1.0, 2.0, 0.0],
+                    [2.0, 2.0, 0.0],
+                    [2.0, 1.0, 0.0],
+                    [1.0, 1.0, 0.0]
+                ]
+            ])
+        );
+
+        let mut utm: AbstractGeometryKind =
+            Point::new(DirectPosition::new(500_000.0, 4_649_776.224_82, 5.0).unwrap()).into();
+        utm.set_srs_name("EPSG:32631");
+        let written = GeoJsonWriter::new().write_geometry(&utm).unwrap();
+        let coordinates = written["coordinates"].as_array().unwrap();
+        assert!((coordinates[0].as_f64().unwrap() - 3.0).abs() < 1e-8);
+        assert!((coordinates[1].as_f64().unwrap() - 42.0).abs() < 1e-8);
+        assert_eq!(coordinates[2], 5.0);
+
+        let mut lat_lon: AbstractGeometryKind =
+            Point::new(DirectPosition::new(47.5, 7.5, 260.0).unwrap()).into();
+        lat_lon.set_srs_name("EPSG:4979");
+        assert_eq!(
+            GeoJsonWriter::new().write_geometry(&lat_lon).unwrap()["coordinates"],
+            json!([7.5, 47.5, 260.0])
+        );
+    }
+}
@@ -0,0 +1,9 @@
+//! Binary glTF 2.0 (GLB) export of triangulated geometries for web 3D
+//! viewers.
+//!
+//! glTF holds triangle meshes only, so surfaces and solids are triangulated
+//! first and points and curves cannot be written.
+
+mod writer;
+
+pub use writer::*;
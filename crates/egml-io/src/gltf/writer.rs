@@ -0,0 +1,391 @@
+use crate::Error;
+use crate::util::{IndexedMesh, lower_corner};
+use egml_core::model::base::{AsAbstractGml, Id};
+use egml_core::model::common::{Triangulate, Triangulation};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometry, DirectPosition};
+use serde_json::{Value, json};
+use std::io::Write;
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_VERSION: u32 = 2;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+/// Writes geometries as a binary glTF 2.0 (GLB) file for web 3D viewers.
+///
+/// Each geometry is triangulated with [`Triangulate`] into an indexed mesh
+/// with flat normals and becomes a node named after its `gml:id`, or
+/// `geometry_<n>` for the `n`-th geometry without one. The `extras` of a
+/// node hold the `gml:id` as `id` and the members [`Triangulate`] skipped as
+/// `skipped`.
+///
+/// glTF stores `f32` positions, so all nodes are children of a root node
+/// whose `translation` is the local origin, by default the lower corner of
+/// all geometries. Coordinates are converted from the Z-up axes of GIS data
+/// to the Y-up axes of glTF: `(x, y, z)` becomes `(x, z, -y)`.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::gltf::GlbWriter;
+/// use egml_io::wkt::read_wkt;
+///
+/// let square = read_wkt("POLYGON Z ((691000 5336000 400, 691001 5336000 400, \
+///     691001 5336001 400, 691000 5336001 400, 691000 5336000 400))")
+/// .unwrap();
+///
+/// let mut glb = Vec::new();
+/// GlbWriter::new().write(&[square], &mut glb).unwrap();
+///
+/// assert_eq!(&glb[..4], b"glTF");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GlbWriter {
+    origin: Option<DirectPosition>,
+}
+
+impl GlbWriter {
+    /// Creates a writer that places the local origin at the lower corner of
+    /// the written geometries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses `origin` as the local origin instead, e.g. to share one origin
+    /// among several files.
+    pub fn with_origin(mut self, origin: DirectPosition) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Writes `geometries` to `out` as a GLB file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a geometry cannot be triangulated at all, e.g. a
+    /// `MultiCurve`, and [`Error::Io`] if writing fails.
+    pub fn write<'a, W: Write>(
+        &self,
+        geometries: impl IntoIterator<Item = &'a AbstractGeometryKind>,
+        out: W,
+    ) -> Result<(), Error> {
+        let triangulations = geometries
+            .into_iter()
+            .map(|x| Ok((x.abstract_geometry().abstract_gml.id(), x.triangulate()?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        self.write_triangulations(&triangulations, out)
+    }
+
+    /// Writes a node for each triangulation, named after the paired id.
+    pub(crate) fn write_triangulations<W: Write>(
+        &self,
+        triangulations: &[(Option<&Id>, Triangulation)],
+        mut out: W,
+    ) -> Result<(), Error> {
+        let origin = match self.origin {
+            Some(origin) => origin.coords(),
+            None => lower_corner(triangulations.iter().map(|(_, x)| x)),
+        };
+
+        let mut binary = Vec::new();
+        let mut document = Document::default();
+        for (index, (id, triangulation)) in triangulations.iter().enumerate() {
+            let mesh = Mesh::from_triangulation(triangulation, origin);
+
+            let mut extras = serde_json::Map::new();
+            if let Some(id) = id {
+                extras.insert("id".to_string(), json!(id.as_str()));
+            }
+            if triangulation.has_skipped() {
+                extras.insert(
+                    "skipped".to_string(),
+                    triangulation
+                        .skipped()
+                        .iter()
+                        .map(|x| json!(x.to_string()))
+                        .collect(),
+                );
+            }
+            let mut node = json!({
+                "name": id.map_or_else(|| format!("geometry_{}", index + 1), |x| x.to_string()),
+            });
+            // glTF does not allow empty accessors, so a geometry whose
+            // triangles all lack area gets a node without a mesh.
+            if !mesh.0.triangles.is_empty() {
+                node["mesh"] = json!(document.push_mesh(&mesh, &mut binary));
+            }
+            if !extras.is_empty() {
+                node["extras"] = Value::Object(extras);
+            }
+            document.nodes.push(node);
+        }
+
+        let children: Vec<usize> = (1..=document.nodes.len()).collect();
+        let root = json!({
+            "name": "root",
+            "translation": gltf_axes(origin),
+            "children": children,
+        });
+        let nodes: Vec<Value> = std::iter::once(root).chain(document.nodes).collect();
+        let mut gltf = json!({
+            "asset": {"version": "2.0", "generator": "egml"},
+            "scene": 0,
+            "scenes": [{"nodes": [0]}],
+            "nodes": nodes,
+        });
+        // glTF does not allow empty arrays, which a file without triangles
+        // would have.
+        if !binary.is_empty() {
+            gltf["meshes"] = json!(document.meshes);
+            gltf["accessors"] = json!(document.accessors);
+            gltf["bufferViews"] = json!(document.buffer_views);
+            gltf["buffers"] = json!([{"byteLength": binary.len()}]);
+        }
+
+        let mut json = serde_json::to_vec(&gltf)?;
+        pad(&mut json, b' ');
+        pad(&mut binary, 0);
+        let mut length = 12 + 8 + json.len();
+        if !binary.is_empty() {
+            length += 8 + binary.len();
+        }
+
+        out.write_all(&GLB_MAGIC.to_le_bytes())?;
+        out.write_all(&GLB_VERSION.to_le_bytes())?;
+        out.write_all(&(length as u32).to_le_bytes())?;
+        out.write_all(&(json.len() as u32).to_le_bytes())?;
+        out.write_all(&CHUNK_JSON.to_le_bytes())?;
+        out.write_all(&json)?;
+        if !binary.is_empty() {
+            out.write_all(&(binary.len() as u32).to_le_bytes())?;
+            out.write_all(&CHUNK_BIN.to_le_bytes())?;
+            out.write_all(&binary)?;
+        }
+        Ok(())
+    }
+}
+
+/// The meshes, accessors, buffer views and nodes of the glTF document,
+/// without the root node.
+#[derive(Default)]
+struct Document {
+    meshes: Vec<Value>,
+    accessors: Vec<Value>,
+    buffer_views: Vec<Value>,
+    nodes: Vec<Value>,
+}
+
+impl Document {
+    /// Appends the buffers of `mesh` to `binary` and returns the index of
+    /// the mesh.
+    fn push_mesh(&mut self, mesh: &Mesh, binary: &mut Vec<u8>) -> usize {
+        let (min, max) = mesh.bounds();
+        let position = self.push_accessor(
+            binary,
+            mesh.positions().flatten().flat_map(|x| x.to_le_bytes()),
+            json!({"componentType": FLOAT, "count": mesh.0.vertices.len(), "type": "VEC3",
+                "min": min, "max": max}),
+            ARRAY_BUFFER,
+        );
+        let normal = self.push_accessor(
+            binary,
+            mesh.normals().flatten().flat_map(|x| x.to_le_bytes()),
+            json!({"componentType": FLOAT, "count": mesh.0.vertices.len(), "type": "VEC3"}),
+            ARRAY_BUFFER,
+        );
+        let indices = self.push_accessor(
+            binary,
+            mesh.indices().flat_map(|x| x.to_le_bytes()),
+            json!({"componentType": UNSIGNED_INT, "count": 3 * mesh.0.triangles.len(), "type": "SCALAR"}),
+            ELEMENT_ARRAY_BUFFER,
+        );
+        self.meshes.push(json!({"primitives": [{
+            "attributes": {"POSITION": position, "NORMAL": normal},
+            "indices": indices,
+        }]}));
+        self.meshes.len() - 1
+    }
+
+    /// Appends `bytes` as a buffer view with an `accessor` on it.
+    fn push_accessor(
+        &mut self,
+        binary: &mut Vec<u8>,
+        bytes: impl Iterator<Item = u8>,
+        mut accessor: Value,
+        target: u32,
+    ) -> usize {
+        let offset = binary.len();
+        binary.extend(bytes);
+        self.buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": offset,
+            "byteLength": binary.len() - offset,
+            "target": target,
+        }));
+        accessor["bufferView"] = json!(self.buffer_views.len() - 1);
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+}
+
+/// An indexed triangle mesh in glTF axes, with a position and a normal per
+/// vertex. Vertices are shared by triangles with the same normal.
+struct Mesh(IndexedMesh<([f32; 3], [f32; 3])>);
+
+impl Mesh {
+    fn from_triangulation(triangulation: &Triangulation, origin: [f64; 3]) -> Self {
+        let mut mesh = IndexedMesh::default();
+        for triangle in triangulation.surface().triangles() {
+            let corners = [triangle.a(), triangle.b(), triangle.c()].map(|x| {
+                let coords = x.coords();
+                gltf_axes(std::array::from_fn(|axis| coords[axis] - origin[axis]))
+            });
+            let Some(normal) = normal(&corners) else {
+                continue;
+            };
+            let indices = corners.map(|x| mesh.push_vertex((x.map(|x| x as f32), normal)));
+            mesh.push_polygon(&indices);
+        }
+        Self(mesh)
+    }
+
+    fn positions(&self) -> impl Iterator<Item = &[f32; 3]> {
+        self.0.vertices.iter().map(|(position, _)| position)
+    }
+
+    fn normals(&self) -> impl Iterator<Item = &[f32; 3]> {
+        self.0.vertices.iter().map(|(_, normal)| normal)
+    }
+
+    /// The vertex indices of the triangles.
+    fn indices(&self) -> impl Iterator<Item = u32> {
+        self.0.triangles.iter().flatten().map(|x| *x as u32)
+    }
+
+    /// The `min` and `max` of the positions, which glTF requires.
+    fn bounds(&self) -> ([f32; 3], [f32; 3]) {
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for position in self.positions() {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(position[axis]);
+                max[axis] = max[axis].max(position[axis]);
+            }
+        }
+        (min, max)
+    }
+}
+
+/// The unit normal of the counter-clockwise triangle `corners`, or `None`
+/// if it has no area.
+fn normal(corners: &[[f64; 3]; 3]) -> Option<[f32; 3]> {
+    let [a, b, c] = corners;
+    let u: [f64; 3] = std::array::from_fn(|axis| b[axis] - a[axis]);
+    let v: [f64; 3] = std::array::from_fn(|axis| c[axis] - a[axis]);
+    let n = [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ];
+    let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    (length > 0.0).then(|| n.map(|x| (x / length) as f32))
+}
+
+/// `(x, y, z)` of Z-up coordinates as `(x, z, -y)` of glTF's Y-up axes.
+fn gltf_axes([x, y, z]: [f64; 3]) -> [f64; 3] {
+    [x, z, -y]
+}
+
+/// Pads `chunk` to a multiple of four bytes, as GLB requires.
+fn pad(chunk: &mut Vec<u8>, byte: u8) {
+    while !chunk.len().is_multiple_of(4) {
+        chunk.push(byte);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt::read_wkt;
+    use egml_core::model::base::{AsAbstractGmlMut, Id};
+    use egml_core::model::geometry::AsAbstractGeometryMut;
+
+    /// The JSON and binary chunk of `glb`, checking the header on the way.
+    fn chunks(glb: &[u8]) -> (Value, &[u8]) {
+        let word = |offset: usize| u32::from_le_bytes(glb[offset..offset + 4].try_into().unwrap());
+        assert_eq!(word(0), GLB_MAGIC);
+        assert_eq!(word(4), GLB_VERSION);
+        assert_eq!(word(8) as usize, glb.len());
+        let json_length = word(12) as usize;
+        assert_eq!(word(16), CHUNK_JSON);
+        assert_eq!(json_length % 4, 0);
+        let json = serde_json::from_slice(&glb[20..20 + json_length]).unwrap();
+        let binary = &glb[20 + json_length..];
+        assert_eq!(word(20 + json_length + 4), CHUNK_BIN);
+        (json, &binary[8..])
+    }
+
+    fn write(writer: &GlbWriter, geometries: &[AbstractGeometryKind]) -> Vec<u8> {
+        let mut glb = Vec::new();
+        writer.write(geometries, &mut glb).unwrap();
+        glb
+    }
+
+    #[test]
+    fn nodes_carry_ids_and_the_root_the_origin() {
+        let mut cube = read_wkt(
+            "POLYHEDRALSURFACE Z (\
+            ((0 0 0, 0 1 0, 1 1 0, 1 0 0, 0 0 0)), ((0 0 1, 1 0 1, 1 1 1, 0 1 1, 0 0 1)), \
+            ((0 0 0, 1 0 0, 1 0 1, 0 0 1, 0 0 0)), ((1 0 0, 1 1 0, 1 1 1, 1 0 1, 1 0 0)), \
+            ((1 1 0, 0 1 0, 0 1 1, 1 1 1, 1 1 0)), ((0 1 0, 0 0 0, 0 0 1, 0 1 1, 0 1 0)))",
+        )
+        .unwrap();
+        cube.abstract_geometry_mut()
+            .abstract_gml
+            .set_id(Id::try_from("building_1").unwrap());
+        let roof = read_wkt("POLYGON Z ((0 0 2, 1 0 2, 1 1 2, 0 0 2))").unwrap();
+        let origin = DirectPosition::new(-10.0, -20.0, -30.0).unwrap();
+
+        let glb = write(&GlbWriter::new().with_origin(origin), &[cube, roof]);
+        let (json, binary) = chunks(&glb);
+
+        assert_eq!(json["nodes"][0]["translation"], json!([-10.0, -30.0, 20.0]));
+        assert_eq!(json["nodes"][0]["children"], json!([1, 2]));
+        assert_eq!(json["nodes"][1]["name"], "building_1");
+        assert_eq!(json["nodes"][1]["extras"]["id"], "building_1");
+        assert_eq!(json["nodes"][2]["name"], "geometry_2");
+        assert!(json["nodes"][2].get("extras").is_none());
+
+        // Faces of the cube share vertices only with faces of equal normal.
+        let cube = &json["meshes"][0]["primitives"][0];
+        let positions =
+            &json["accessors"][cube["attributes"]["POSITION"].as_u64().unwrap() as usize];
+        assert_eq!(positions["count"], 24);
+        assert_eq!(positions["min"], json!([10.0, 30.0, -21.0]));
+        assert_eq!(positions["max"], json!([11.0, 31.0, -20.0]));
+        let indices = &json["accessors"][cube["indices"].as_u64().unwrap() as usize];
+        assert_eq!(indices["count"], 36);
+        assert_eq!(json["buffers"][0]["byteLength"], binary.len());
+    }
+
+    #[test]
+    fn normals_point_up_in_gltf_axes() {
+        let floor = read_wkt("POLYGON Z ((5 5 1, 6 5 1, 6 6 1, 5 5 1))").unwrap();
+
+        let glb = write(&GlbWriter::new(), &[floor]);
+        let (json, binary) = chunks(&glb);
+
+        assert_eq!(json["nodes"][0]["translation"], json!([5.0, 1.0, -5.0]));
+        let view = &json["bufferViews"][1];
+        let offset = view["byteOffset"].as_u64().unwrap() as usize;
+        let normal: Vec<f32> = binary[offset..offset + 12]
+            .chunks(4)
+            .map(|x| f32::from_le_bytes(x.try_into().unwrap()))
+            .collect();
+        assert_eq!(normal, [0.0, 1.0, 0.0]);
+    }
+}
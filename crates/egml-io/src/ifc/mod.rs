@@ -0,0 +1,13 @@
+//! IFC4 export of geometries as STEP physical files (`.ifc`), e.g. to hand
+//! city models to BIM tools as context.
+//!
+//! | egml | IFC |
+//! |------|-----|
+//! | `Solid` | `IfcFacetedBrep`, or `IfcFacetedBrepWithVoids` with interior shells |
+//! | Surfaces and aggregates of them | `IfcTriangulatedFaceSet` |
+//!
+//! Points and curves cannot be written.
+
+mod writer;
+
+pub use writer::*;
@@ -0,0 +1,594 @@
+use crate::Error;
+use crate::util::{
+    IndexedMesh, PlacementCrs, Ring, SceneTransform, UpAxis, WriteOptions, epsg_code, lower_corner,
+    solid_shells, unsigned_zero, utc_now,
+};
+use egml_core::model::base::AsAbstractGml;
+use egml_core::model::common::{Triangulate, Triangulation};
+use egml_core::model::geometry::primitives::{
+    AbstractGeometricPrimitiveKind, AbstractSolidKind, Solid,
+};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometry, DirectPosition};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::io::Write;
+
+const FORMAT: &str = "IFC";
+
+/// The 64 characters of IFC's compressed `GlobalId`s.
+const GLOBAL_ID_CHARACTERS: &[u8; 64] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz_$";
+
+/// Writes geometries as an IFC4 STEP file.
+///
+/// The file has an `IfcProject` with an `IfcSite` and an `IfcBuilding`,
+/// which contains an `IfcBuildingElementProxy` per geometry. A proxy is
+/// named after the `gml:id` of its geometry, or `geometry_<n>` for the
+/// `n`-th geometry without one, and its `GlobalId` is derived from the
+/// project name and its own, so exporting the same geometries again yields
+/// the same `GlobalId`s. A proxy whose name is taken by an earlier one
+/// derives its `GlobalId` from the name with the first free suffix `/2`,
+/// `/3`, … instead.
+///
+/// Coordinates are written relative to a local origin. If the CRS is known,
+/// an `IfcMapConversion` from the model context to an `IfcProjectedCRS`
+/// holds the origin, otherwise the placement of the site does. The CRS must
+/// be projected, as the map conversion takes the model's metres as eastings
+/// and northings.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::ifc::IfcWriter;
+/// use egml_io::wkt::read_wkt;
+///
+/// let roof = read_wkt("SRID=25832;POLYGON Z ((691000 5336000 400, \
+///     691010 5336000 400, 691010 5336010 405, 691000 5336000 400))")
+/// .unwrap();
+///
+/// let mut ifc = Vec::new();
+/// IfcWriter::new().write(&[roof], &mut ifc).unwrap();
+///
+/// let ifc = String::from_utf8(ifc).unwrap();
+/// assert!(ifc.starts_with("ISO-10303-21;\n"));
+/// assert!(ifc.contains("FILE_SCHEMA(('IFC4'));"));
+/// assert!(ifc.contains("=IFCMAPCONVERSION(#"));
+/// assert!(ifc.contains(",691000.,5336000.,400.,1.,0.,1.);"));
+/// assert!(ifc.contains("=IFCTRIANGULATEDFACESET(#"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct IfcWriter {
+    project_name: String,
+    srs_name: Option<String>,
+    origin: Option<DirectPosition>,
+}
+
+impl Default for IfcWriter {
+    fn default() -> Self {
+        Self {
+            project_name: "egml".to_string(),
+            srs_name: None,
+            origin: None,
+        }
+    }
+}
+
+impl IfcWriter {
+    /// Creates a writer of a project named `egml`, which takes the CRS from
+    /// the `srsName` of the first geometry and places the local origin at
+    /// the lower corner of the written geometries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names the project, from which the `GlobalId`s of the project, site,
+    /// building and proxies are derived.
+    pub fn with_project_name(mut self, project_name: impl Into<String>) -> Self {
+        self.project_name = project_name.into();
+        self
+    }
+
+    /// Uses the CRS `srs_name` for the map conversion, e.g. for geometries
+    /// without an `srsName`.
+    pub fn with_srs_name(mut self, srs_name: impl Into<String>) -> Self {
+        self.srs_name = Some(srs_name.into());
+        self
+    }
+
+    /// Uses `origin` as the local origin instead.
+    pub fn with_origin(mut self, origin: DirectPosition) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Writes `geometries` to `out`. Coordinates are formatted according to
+    /// the current [`WriteOptions`](crate::util::WriteOptions).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a geometry cannot be triangulated at all, e.g. a
+    /// `MultiCurve`, or has an unresolved reference,
+    /// [`Error::Unrepresentable`] if the CRS is geographic or geocentric,
+    /// e.g. EPSG:4326, and [`Error::Io`] if writing fails.
+    pub fn write<'a, W: Write>(
+        &self,
+        geometries: impl IntoIterator<Item = &'a AbstractGeometryKind>,
+        mut out: W,
+    ) -> Result<(), Error> {
+        let geometries = geometries
+            .into_iter()
+            .map(|x| Ok((x, x.triangulate()?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let origin = match self.origin {
+            Some(origin) => origin.coords(),
+            None => lower_corner(geometries.iter().map(|(_, x)| x)),
+        };
+        let srs_name = self.srs_name.as_deref().or_else(|| {
+            geometries
+                .first()
+                .and_then(|(x, _)| x.abstract_geometry().srs_name())
+                .map(String::as_str)
+        });
+        if let Some(srs_name) = srs_name
+            && PlacementCrs::from_srs_name(srs_name)
+                .is_some_and(|x| !matches!(x, PlacementCrs::Utm { .. }))
+        {
+            return Err(Error::Unrepresentable {
+                format: FORMAT,
+                reason: format!("the CRS {srs_name}, which is not projected"),
+            });
+        }
+
+        let mut step = Step::new(origin);
+        let length_unit = step.add("IFCSIUNIT(*,.LENGTHUNIT.,$,.METRE.)".to_string());
+        let angle_unit = step.add("IFCSIUNIT(*,.PLANEANGLEUNIT.,$,.RADIAN.)".to_string());
+        let units = step.add(format!("IFCUNITASSIGNMENT((#{length_unit},#{angle_unit}))"));
+        let world_origin = step.add("IFCCARTESIANPOINT((0.,0.,0.))".to_string());
+        let identity = step.add(format!("IFCAXIS2PLACEMENT3D(#{world_origin},$,$)"));
+        let context = step.add(format!(
+            "IFCGEOMETRICREPRESENTATIONCONTEXT($,'Model',3,1.E-05,#{identity},$)"
+        ));
+        let body = step.add(format!(
+            "IFCGEOMETRICREPRESENTATIONSUBCONTEXT('Body','Model',*,*,*,*,#{context},$,\
+             .MODEL_VIEW.,$)"
+        ));
+        let project = step.add(format!(
+            "IFCPROJECT('{}',$,{},$,$,$,$,(#{context}),#{units})",
+            global_id(&self.project_name),
+            string(&self.project_name)
+        ));
+
+        let site_location = match srs_name {
+            Some(srs_name) => {
+                let name =
+                    epsg_code(srs_name).map_or(srs_name.to_string(), |x| format!("EPSG:{x}"));
+                let crs = step.add(format!("IFCPROJECTEDCRS({},$,$,$,$,$,$)", string(&name)));
+                let [eastings, northings, height] =
+                    std::array::from_fn(|axis| step.real(axis, origin[axis]));
+                step.add(format!(
+                    "IFCMAPCONVERSION(#{context},#{crs},{eastings},{northings},{height},1.,0.,1.)"
+                ));
+                identity
+            }
+            None => {
+                let [x, y, z] = std::array::from_fn(|axis| step.real(axis, origin[axis]));
+                let point = step.add(format!("IFCCARTESIANPOINT(({x},{y},{z}))"));
+                step.add(format!("IFCAXIS2PLACEMENT3D(#{point},$,$)"))
+            }
+        };
+        let site_placement = step.add(format!("IFCLOCALPLACEMENT($,#{site_location})"));
+        let site = step.add(format!(
+            "IFCSITE('{}',$,'Site',$,$,#{site_placement},$,$,.ELEMENT.,$,$,$,$,$)",
+            global_id(&format!("{}/site", self.project_name))
+        ));
+        let building_placement =
+            step.add(format!("IFCLOCALPLACEMENT(#{site_placement},#{identity})"));
+        let building = step.add(format!(
+            "IFCBUILDING('{}',$,'Building',$,$,#{building_placement},$,$,.ELEMENT.,$,$,$)",
+            global_id(&format!("{}/building", self.project_name))
+        ));
+        step.add(format!(
+            "IFCRELAGGREGATES('{}',$,$,$,#{project},(#{site}))",
+            global_id(&format!("{}/project-site", self.project_name))
+        ));
+        step.add(format!(
+            "IFCRELAGGREGATES('{}',$,$,$,#{site},(#{building}))",
+            global_id(&format!("{}/site-building", self.project_name))
+        ));
+
+        let element_placement = step.add(format!(
+            "IFCLOCALPLACEMENT(#{building_placement},#{identity})"
+        ));
+        let mut elements = Vec::new();
+        let mut keys = HashSet::new();
+        for (index, (geometry, triangulation)) in geometries.iter().enumerate() {
+            let name = match geometry.abstract_geometry().abstract_gml.id() {
+                Some(id) => id.to_string(),
+                None => format!("geometry_{}", index + 1),
+            };
+            let key = format!("{}/element/{name}", self.project_name);
+            let key = if keys.contains(&key) {
+                (2..)
+                    .map(|k| format!("{key}/{k}"))
+                    .find(|x| !keys.contains(x))
+                    .expect("a free suffix")
+            } else {
+                key
+            };
+            keys.insert(key.clone());
+            let (kind, item) = match geometry {
+                AbstractGeometryKind::AbstractGeometricPrimitiveKind(
+                    AbstractGeometricPrimitiveKind::AbstractSolidKind(AbstractSolidKind::Solid(x)),
+                ) if x.exterior().is_some() => ("Brep", step.faceted_brep(x)?),
+                _ => ("Tessellation", step.triangulated_face_set(triangulation)?),
+            };
+            let representation = step.add(format!(
+                "IFCSHAPEREPRESENTATION(#{body},'Body','{kind}',(#{item}))"
+            ));
+            let shape = step.add(format!(
+                "IFCPRODUCTDEFINITIONSHAPE($,$,(#{representation}))"
+            ));
+            elements.push(step.add(format!(
+                "IFCBUILDINGELEMENTPROXY('{}',$,{},$,$,#{element_placement},#{shape},$,\
+                 .NOTDEFINED.)",
+                global_id(&key),
+                string(&name)
+            )));
+        }
+        if !elements.is_empty() {
+            let elements = elements
+                .iter()
+                .map(|x| format!("#{x}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            step.add(format!(
+                "IFCRELCONTAINEDINSPATIALSTRUCTURE('{}',$,$,$,({elements}),#{building})",
+                global_id(&format!("{}/building-elements", self.project_name))
+            ));
+        }
+
+        writeln!(out, "ISO-10303-21;")?;
+        writeln!(out, "HEADER;")?;
+        writeln!(out, "FILE_DESCRIPTION((''),'2;1');")?;
+        writeln!(
+            out,
+            "FILE_NAME('','{}',(''),(''),'egml','egml','');",
+            utc_now()
+        )?;
+        writeln!(out, "FILE_SCHEMA(('IFC4'));")?;
+        writeln!(out, "ENDSEC;")?;
+        writeln!(out, "DATA;")?;
+        for (index, entity) in step.entities.iter().enumerate() {
+            writeln!(out, "#{}={entity};", index + 1)?;
+        }
+        writeln!(out, "ENDSEC;")?;
+        writeln!(out, "END-ISO-10303-21;")?;
+        Ok(())
+    }
+}
+
+/// The entity instances of the data section, numbered from one.
+struct Step {
+    entities: Vec<String>,
+    /// The local positions of the `IfcCartesianPoint`s, shared by all faces.
+    positions: IndexedMesh<[f64; 3]>,
+    /// The instance number of the `IfcCartesianPoint` of each position.
+    points: Vec<usize>,
+    transform: SceneTransform,
+    options: WriteOptions,
+}
+
+impl Step {
+    fn new(origin: [f64; 3]) -> Self {
+        Self {
+            entities: Vec::new(),
+            positions: IndexedMesh::default(),
+            points: Vec::new(),
+            transform: SceneTransform {
+                origin,
+                up_axis: UpAxis::Z,
+                meters: 1.0,
+            },
+            options: WriteOptions::current(),
+        }
+    }
+
+    /// Adds `entity` and returns its instance number.
+    fn add(&mut self, entity: String) -> usize {
+        self.entities.push(entity);
+        self.entities.len()
+    }
+
+    /// Adds an `IfcFacetedBrep` of the exterior shell of `solid`, with the
+    /// interior shells as voids.
+    fn faceted_brep(&mut self, solid: &Solid) -> Result<usize, Error> {
+        let mut shells = solid_shells(solid, FORMAT)?.into_iter();
+        let outer = match shells.next() {
+            Some(faces) => self.closed_shell(&faces),
+            None => unreachable!("the solid has an exterior shell"),
+        };
+        let voids: Vec<String> = shells
+            .map(|x| format!("#{}", self.closed_shell(&x)))
+            .collect();
+        Ok(if voids.is_empty() {
+            self.add(format!("IFCFACETEDBREP(#{outer})"))
+        } else {
+            self.add(format!(
+                "IFCFACETEDBREPWITHVOIDS(#{outer},({}))",
+                voids.join(",")
+            ))
+        })
+    }
+
+    fn closed_shell(&mut self, faces: &[Vec<Ring>]) -> usize {
+        let faces: Vec<String> = faces
+            .iter()
+            .map(|rings| {
+                let bounds: Vec<String> = rings
+                    .iter()
+                    .enumerate()
+                    .map(|(index, ring)| {
+                        // Rings are closed, poly loops are not.
+                        let points: Vec<String> = ring[..ring.len() - 1]
+                            .iter()
+                            .map(|x| format!("#{}", self.point(x)))
+                            .collect();
+                        let poly_loop = self.add(format!("IFCPOLYLOOP(({}))", points.join(",")));
+                        let bound = if index == 0 {
+                            "IFCFACEOUTERBOUND"
+                        } else {
+                            "IFCFACEBOUND"
+                        };
+                        format!("#{}", self.add(format!("{bound}(#{poly_loop},.T.)")))
+                    })
+                    .collect();
+                format!("#{}", self.add(format!("IFCFACE(({}))", bounds.join(","))))
+            })
+            .collect();
+        self.add(format!("IFCCLOSEDSHELL(({}))", faces.join(",")))
+    }
+
+    /// Returns the `IfcCartesianPoint` of `position`, adding it unless it
+    /// was added before.
+    fn point(&mut self, position: &DirectPosition) -> usize {
+        let index = self
+            .positions
+            .push_vertex(self.transform.apply(position.coords()));
+        if index == self.points.len() {
+            let coords = self.positions.vertices[index];
+            let point = self.add(format!("IFCCARTESIANPOINT(({}))", self.coordinates(coords)));
+            self.points.push(point);
+        }
+        self.points[index]
+    }
+
+    fn triangulated_face_set(&mut self, triangulation: &Triangulation) -> Result<usize, Error> {
+        let mesh = self.transform.mesh(triangulation)?;
+        let coordinates: Vec<String> = mesh
+            .vertices
+            .iter()
+            .map(|x| format!("({})", self.coordinates(x.coords())))
+            .collect();
+        let list = self.add(format!(
+            "IFCCARTESIANPOINTLIST3D(({}))",
+            coordinates.join(",")
+        ));
+        // Indices are one-based.
+        let indices: Vec<String> = mesh
+            .triangles
+            .iter()
+            .map(|[a, b, c]| format!("({},{},{})", a + 1, b + 1, c + 1))
+            .collect();
+        Ok(self.add(format!(
+            "IFCTRIANGULATEDFACESET(#{list},$,$,({}),$)",
+            indices.join(",")
+        )))
+    }
+
+    fn coordinates(&self, coords: [f64; 3]) -> String {
+        let [x, y, z] = std::array::from_fn(|axis| self.real(axis, coords[axis]));
+        format!("{x},{y},{z}")
+    }
+
+    /// `value` as a STEP real, which needs a decimal point, with the
+    /// precision of `axis` (0 = x, 1 = y, 2 = z).
+    fn real(&self, axis: usize, value: f64) -> String {
+        with_point(self.options.format_ordinate(axis, unsigned_zero(value)))
+    }
+}
+
+fn with_point(mut number: String) -> String {
+    if !number.contains(['.', 'e', 'E']) {
+        number.push('.');
+    }
+    number
+}
+
+/// `value` as a quoted STEP string, with non-ASCII characters encoded as
+/// UTF-16 in `\X2\` control directives.
+fn string(value: &str) -> String {
+    let mut encoded = String::from("'");
+    for x in value.chars() {
+        match x {
+            '\'' => encoded.push_str("''"),
+            '\\' => encoded.push_str("\\\\"),
+            ' '..='~' => encoded.push(x),
+            _ => {
+                encoded.push_str("\\X2\\");
+                for unit in x.encode_utf16(&mut [0; 2]) {
+                    encoded.push_str(&format!("{unit:04X}"));
+                }
+                encoded.push_str("\\X0\\");
+            }
+        }
+    }
+    encoded.push('\'');
+    encoded
+}
+
+/// A `GlobalId` derived from `name`: the first 128 bits of its SHA-256 hash
+/// in IFC's 22-character base-64 encoding.
+fn global_id(name: &str) -> String {
+    let hash = Sha256::digest(name.as_bytes());
+    let value = u128::from_be_bytes(hash[..16].try_into().expect("16 bytes"));
+    // The first character holds the two highest bits, the others six each.
+    std::iter::once((value >> 126) as usize)
+        .chain((0..21).rev().map(|x| (value >> (6 * x)) as usize & 0x3f))
+        .map(|x| char::from(GLOBAL_ID_CHARACTERS[x]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::serialize_with_options;
+    use crate::wkt::read_wkt;
+    use egml_core::model::base::{AsAbstractGmlMut, Id};
+    use egml_core::model::common::CoordinatePrecision;
+    use egml_core::model::geometry::AsAbstractGeometryMut;
+
+    fn write(writer: &IfcWriter, geometries: &[AbstractGeometryKind]) -> String {
+        let mut ifc = Vec::new();
+        writer.write(geometries, &mut ifc).unwrap();
+        String::from_utf8(ifc).unwrap()
+    }
+
+    #[test]
+    fn solids_are_written_as_faceted_breps() {
+        let mut cube = read_wkt(
+            "POLYHEDRALSURFACE Z (\
+            ((0 0 0, 0 1 0, 1 1 0, 1 0 0, 0 0 0)), ((0 0 1, 1 0 1, 1 1 1, 0 1 1, 0 0 1)), \
+            ((0 0 0, 1 0 0, 1 0 1, 0 0 1, 0 0 0)), ((1 0 0, 1 1 0, 1 1 1, 1 0 1, 1 0 0)), \
+            ((1 1 0, 0 1 0, 0 1 1, 1 1 1, 1 1 0)), ((0 1 0, 0 0 0, 0 0 1, 0 1 1, 0 1 0)))",
+        )
+        .unwrap();
+        cube.abstract_geometry_mut()
+            .abstract_gml
+            .set_id(Id::try_from("Gebäude_1").unwrap());
+
+        let ifc = write(&IfcWriter::new(), &[cube]);
+
+        let count = |entity: &str| ifc.matches(&format!("={entity}(")).count();
+        assert_eq!(count("IFCFACETEDBREP"), 1);
+        assert_eq!(count("IFCCLOSEDSHELL"), 1);
+        assert_eq!(count("IFCFACE"), 6);
+        assert_eq!(count("IFCPOLYLOOP"), 6);
+        // The world origin, the site location and the eight corners.
+        assert_eq!(count("IFCCARTESIANPOINT"), 10);
+        assert!(ifc.contains("'Brep'"));
+        assert!(ifc.contains(&format!(
+            "=IFCBUILDINGELEMENTPROXY('{}',$,'Geb\\X2\\00E4\\X0\\ude_1',",
+            global_id("egml/element/Gebäude_1")
+        )));
+        // Without a CRS, the site is placed at the origin.
+        assert_eq!(count("IFCMAPCONVERSION"), 0);
+        assert!(ifc.ends_with("ENDSEC;\nEND-ISO-10303-21;\n"));
+    }
+
+    #[test]
+    fn surfaces_are_written_as_triangulated_face_sets() {
+        let square =
+            read_wkt("SRID=25832;POLYGON Z ((10 20 30, 11 20 30, 11 21 30, 10 21 30, 10 20 30))")
+                .unwrap();
+
+        let ifc = write(&IfcWriter::new().with_project_name("Quarter"), &[square]);
+
+        assert!(ifc.contains("=IFCPROJECTEDCRS('EPSG:25832',$,$,$,$,$,$);"));
+        assert!(ifc.contains(",10.,20.,30.,1.,0.,1.);"));
+        assert!(ifc.contains("=IFCPROJECT('"));
+        assert!(ifc.contains(",'Quarter',$,$,$,$,(#"));
+        let face_set = ifc
+            .lines()
+            .find(|x| x.contains("=IFCTRIANGULATEDFACESET("))
+            .unwrap();
+        assert_eq!(face_set.matches('(').count(), 1 + 1 + 2);
+        let point_list = ifc
+            .lines()
+            .find(|x| x.contains("=IFCCARTESIANPOINTLIST3D("))
+            .unwrap();
+        assert!(point_list.contains("(0.,0.,0.)"));
+        assert!(point_list.contains("(1.,1.,0.)"));
+        assert!(ifc.contains("'Tessellation'"));
+    }
+
+    #[test]
+    fn geographic_crss_are_rejected() {
+        let square =
+            read_wkt("SRID=4326;POLYGON Z ((48 11 0, 48 11.1 0, 48.1 11.1 0, 48 11 0))").unwrap();
+
+        let mut ifc = Vec::new();
+        let result = IfcWriter::new().write(&[square], &mut ifc);
+
+        assert!(matches!(
+            result,
+            Err(Error::Unrepresentable { format: "IFC", .. })
+        ));
+    }
+
+    #[test]
+    fn global_ids_have_22_characters_of_the_ifc_alphabet() {
+        let id = global_id("wall_1");
+
+        assert_eq!(id.len(), 22);
+        assert!(matches!(id.as_bytes()[0], b'0'..=b'3'));
+        assert!(id.bytes().all(|x| GLOBAL_ID_CHARACTERS.contains(&x)));
+        assert_eq!(id, global_id("wall_1"));
+        assert_ne!(id, global_id("wall_2"));
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(string("it's a\\b"), "'it''s a\\\\b'");
+        assert_eq!(string("€"), "'\\X2\\20AC\\X0\\'");
+    }
+
+    #[test]
+    fn proxy_global_ids_are_scoped_and_unique() {
+        let square = |id: &str| {
+            let mut square = read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1 1 0, 0 1 0, 0 0 0))").unwrap();
+            square
+                .abstract_geometry_mut()
+                .abstract_gml
+                .set_id(Id::try_from(id).unwrap());
+            square
+        };
+        let geometries = [square("wall"), square("wall"), square("site")];
+
+        let ifc = write(&IfcWriter::new().with_project_name("Quarter"), &geometries);
+        let other = write(&IfcWriter::new().with_project_name("Block"), &geometries);
+
+        let global_ids = |ifc: &str| -> Vec<String> {
+            ifc.lines()
+                .filter_map(|x| x.split("=IFCBUILDINGELEMENTPROXY('").nth(1))
+                .map(|x| x[..22].to_string())
+                .collect()
+        };
+        let ids = global_ids(&ifc);
+        assert_eq!(
+            ids,
+            [
+                global_id("Quarter/element/wall"),
+                global_id("Quarter/element/wall/2"),
+                global_id("Quarter/element/site"),
+            ]
+        );
+        assert_ne!(ids[2], global_id("Quarter/site"));
+        assert!(global_ids(&other).iter().all(|x| !ids.contains(x)));
+    }
+
+    #[test]
+    fn map_conversions_round_each_axis_with_its_precision() {
+        let square = read_wkt(
+            "SRID=25832;POLYGON Z ((691000.1234 5336000.5678 400.26, 691001 5336000.5678 400.26, \
+             691001 5336001 400.26, 691000.1234 5336000.5678 400.26))",
+        )
+        .unwrap();
+        let options = WriteOptions::default()
+            .with_precision(CoordinatePrecision::horizontal_vertical(3, 1))
+            .with_trim_trailing_zeros(true);
+
+        let ifc = serialize_with_options(options, || write(&IfcWriter::new(), &[square]));
+
+        assert!(ifc.contains(",691000.123,5336000.568,400.3,1.,0.,1.);"));
+    }
+}
@@ -20,18 +20,221 @@
 //! | `gml:MultiSurface` | [`egml_core::model::geometry::aggregates::MultiSurface`] |
 //! | `gml:CompositeSurface` | [`egml_core::model::geometry::complexes::CompositeSurface`] |
 //!
+//! Legacy encodings from GML 2 and GML 3.1.1 are read into the same types:
+//! `gml:coordinates` (with its `cs`, `ts` and `decimal` separators) and
+//! `gml:coord` wherever `gml:pos`/`gml:posList` are accepted,
+//! `gml:outerBoundaryIs`/`gml:innerBoundaryIs` in polygons, `gml:Box` as an
+//! envelope, `gml:MultiPolygon` as a `MultiSurface`, `gml:MultiLineString` as
+//! a `MultiCurve`, and a `gml:CompositeSurface` bounding a `gml:Solid`.
+//!
+//! The GML 3.3 compact encodings (`http://www.opengis.net/gml/3.3/ce`) replace
+//! the ring wrappers by a bare `gml:posList`: `gmlce:SimpleTriangle` is read as
+//! a `Triangle`, `gmlce:SimpleRectangle` as a `PolygonPatch`,
+//! `gmlce:SimplePolygon` as a `Polygon` and `gmlce:SimpleMultiPoint` as a
+//! `MultiPoint`.
+//!
+//! # Namespaces
+//!
+//! Elements are matched by namespace as well as local name, so a `Polygon`
+//! from a declared foreign namespace is never taken for `gml:Polygon`. Both
+//! GML 3.1.1 (`http://www.opengis.net/gml`) and GML 3.2 are accepted; the
+//! version of a document is available from [`util::detect_gml_version`] and
+//! [`util::Parsed::gml_version`]. Prefixes are resolved against the
+//! declarations of the whole document passed to a deserializer, and a GML
+//! object whose root element is in a foreign namespace is rejected with
+//! [`Error::ForeignElement`]. A property whose only content is from a foreign
+//! namespace is dropped and reported as a [`util::Diagnostic`].
+//!
+//! Fragments without namespace declarations are read as before, matching by
+//! local name. Only [`util::deserialize_with_options`] also skips elements
+//! whose prefix is declared nowhere in the document, unless it is `gml:` or
+//! `gmlce:`.
+//!
+//! # Character encodings
+//!
+//! The deserializers read UTF-8. [`util::decode_document`] transcodes documents
+//! in other encodings, selected by a byte order mark or the `encoding` of the
+//! XML declaration, e.g. ISO-8859-1, Windows-1252 or UTF-16.
+//! [`util::deserialize_with_options`] and [`resolver::DocumentResolver`] apply
+//! it to every document they read, and
+//! [`util::DocumentWriter::with_encoding`] selects the encoding of the output.
+//!
+//! # Compressed documents and archives
+//!
+//! Gzip streams are recognized by their magic bytes and decompressed in memory
+//! by [`util::deserialize_with_options`], [`util::read_document`] and
+//! [`resolver::DocumentResolver`]. [`util::ZipDocuments`] iterates over the GML
+//! documents of a zip archive without unpacking it to disk.
+//! [`util::DocumentWriter::with_compression`] writes `.gml.gz` files, and
+//! [`util::ZipDocumentWriter`] collects written documents in a zip archive.
+//!
 //! # Error handling
 //!
 //! All parse errors are reported via [`Error`].  The most common variants are
 //! [`Error::XmlDe`] (malformed XML) and [`Error::MissingElements`]
 //! (required child elements absent from the GML fragment).
 //!
-//! XLink references (`xlink:href`) are not yet resolved; attempting to parse a
-//! document that uses them returns [`Error::UnsupportedXLink`].
+//! # Repairing real-world input
+//!
+//! Common defects such as duplicate positions or a repeated closing vertex are
+//! repaired while parsing. [`util::ParseOptions`] selects a policy per defect and
+//! [`util::deserialize_with_options`] reports every applied repair in a
+//! [`util::RepairReport`].
+//!
+//! With [`util::ParseOptions::lenient`], a child element that cannot be
+//! deserialized is dropped from its parent instead of failing the document. The
+//! returned [`util::Parsed`] lists a [`util::Diagnostic`] with severity, element
+//! name, `gml:id` and location for every dropped element. Elements dropped
+//! under [`util::DegeneratePolicy::Drop`] are reported as errors the same way.
+//!
+//! # Preserving unknown content
+//!
+//! With [`util::ParseOptions::preserve_unknown`], attributes, child elements and
+//! comments of a geometry or feature that the model does not cover, such as
+//! vendor extensions, are kept as raw XML in its
+//! [`ForeignContent`](egml_core::model::base::ForeignContent). The serializers
+//! write them again after the child element they followed. A
+//! prefix used by preserved XML but declared on an ancestor must be declared on
+//! the written document, e.g. via [`util::DocumentWriter::with_namespace`].
+//!
+//! # XLink references
+//!
+//! A property that references its object via `xlink:href` is parsed with the
+//! `href` and without an object. After parsing,
+//! [`resolve_local_references`](egml_core::resolver::resolve_local_references)
+//! replaces every local `#id` reference with a clone of the geometry it points
+//! at and reports the references it could not resolve:
+//!
+//! ```rust
+//! use egml_core::resolver::resolve_local_references;
+//! use egml_io::codec::geometry::aggregates::deserialize_multi_surface;
+//!
+//! let xml = b"<gml:MultiSurface>\
+//!     <gml:surfaceMember><gml:Polygon gml:id=\"p1\"><gml:exterior><gml:LinearRing>\
+//!     <gml:posList>0 0 0 1 0 0 1 1 0 0 0 0</gml:posList>\
+//!     </gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember>\
+//!     <gml:surfaceMember xlink:href=\"#p1\"/>\
+//!     </gml:MultiSurface>";
+//!
+//! let mut multi_surface = deserialize_multi_surface(xml).unwrap();
+//! let report = resolve_local_references(&mut multi_surface);
+//!
+//! assert!(report.is_complete());
+//! assert!(multi_surface.surface_member()[1].object().is_some());
+//! ```
+//!
+//! References into other documents (`tile_2.gml#p1`) are followed by a
+//! [`resolver::DocumentResolver`], which fetches each document once through a
+//! [`resolver::ReferenceLoader`] — [`resolver::FileSystemLoader`] for files
+//! below a root directory, [`resolver::InMemoryLoader`] for tests — and
+//! reports reference cycles instead of following them.
+//!
+//! # Writing documents
+//!
+//! Serializers such as [`codec::geometry::serialize_abstract_geometry_kind`]
+//! produce fragments. [`util::DocumentWriter`] turns geometries into a
+//! standalone GML 3.2 document with an XML declaration, namespace
+//! declarations, `xsi:schemaLocation` and configurable prefixes, written to any
+//! [`std::io::Write`]. It can also write GML 3.1.1 for older consumers.
+//!
+//! [`util::WriteOptions::compact_encodings`] writes qualifying geometries in
+//! the GML 3.3 compact encodings, either for a whole document via
+//! [`util::DocumentWriter::with_write_options`] or for individual serializer
+//! calls via [`util::serialize_with_options`].
+//!
+//! Coordinates are written at full `f64` precision unless
+//! [`util::WriteOptions::precision`] fixes the decimal places per axis.
+//! [`util::DocumentWriter::with_snap_to_grid`] additionally snaps each
+//! geometry to that grid before writing, so vertices that merge on disk are
+//! dropped instead of written as adjacent duplicates.
+//!
+//! Aggregates, composites and collections with many members serialize them in
+//! parallel and keep their order, so the output does not depend on the number
+//! of threads. [`util::DocumentWriter::streaming`] appends members to a
+//! document as they are produced, without building the whole document first.
+//!
+//! # Well-Known Text
+//!
+//! [`wkt::write_wkt`] and [`wkt::write_ewkt`] write geometries as WKT and
+//! PostGIS EWKT for exchange with PostGIS or Shapely, and [`wkt::read_wkt`]
+//! reads either. The EWKT `SRID` corresponds to the EPSG code of the
+//! `srsName`, see [`util::epsg_code`].
+//!
+//! # Well-Known Binary
+//!
+//! [`wkb::write_wkb`] and [`wkb::write_ewkb`] produce ISO WKB and PostGIS
+//! EWKB in either byte order, e.g. for `COPY … BINARY` or GeoParquet, and
+//! [`wkb::read_wkb`] reads both. Output matches PostGIS byte for byte.
+//!
+//! # GeoJSON and JSON-FG
+//!
+//! [`geojson::GeoJsonWriter`] writes features and geometries as GeoJSON for
+//! web maps, flattening solids into `MultiPolygon`s, or as JSON-FG with solids
+//! kept as `Polyhedron`s. [`geojson::read_geojson`] reads either back.
+//!
+//! # CityJSON
+//!
+//! [`cityjson::CityJsonEncoder`] converts geometries into CityJSON
+//! `boundaries` over a deduplicated, optionally quantised vertex list shared
+//! by a whole document, and [`cityjson::CityJsonDecoder`] converts them back.
+//!
+//! # Wavefront OBJ
+//!
+//! [`obj::ObjWriter`] triangulates geometries into an OBJ file for viewers
+//! such as Blender, with one object per `gml:id` and an optional local origin.
+//! [`obj::read_obj`] reads each object back as a `TriangulatedSurface`, or a
+//! `Solid` if it is closed.
+//!
+//! # STL and PLY
+//!
+//! [`stl::StlWriter`] writes ASCII or binary STL with facet normals from the
+//! triangle winding, and [`ply::PlyWriter`] writes a PLY mesh, optionally
+//! with the geometry of each face as an `id_index`. [`stl::read_stl`] and
+//! [`ply::read_ply`] read meshes back like [`obj::read_obj`].
+//!
+//! # glTF
+//!
+//! [`gltf::GlbWriter`] writes geometries as GLB with an indexed mesh and a
+//! node per `gml:id`, relative to a local origin kept in the root node.
+//!
+//! # 3D Tiles
+//!
+//! [`tiles3d::TilesetWriter`] generates a 3D Tiles 1.1 tileset with a GLB
+//! content per tile of a quadtree, placed on the globe from a UTM or
+//! geographic CRS.
+//!
+//! # COLLADA and X3D
+//!
+//! [`collada::ColladaWriter`] and [`x3d::X3dWriter`] write triangulated
+//! geometries for legacy viewers, with a node per `gml:id` and a
+//! configurable [`util::UpAxis`] and [`util::LengthUnit`].
+//!
+//! # IFC
+//!
+//! [`ifc::IfcWriter`] writes solids as `IfcFacetedBrep`s and surfaces as
+//! `IfcTriangulatedFaceSet`s into an IFC4 STEP file with a project, site and
+//! building, and a map conversion from the source CRS.
 
+pub mod cityjson;
 pub mod codec;
+pub mod collada;
 mod error;
+pub mod geojson;
+pub mod gltf;
+pub mod ifc;
+pub mod obj;
+pub mod ply;
+pub mod resolver;
+pub mod stl;
+pub mod tiles3d;
 pub mod util;
+pub mod wkb;
+pub mod wkt;
+pub mod x3d;
 
 #[doc(inline)]
 pub use crate::error::Error;
+/// The character encodings accepted by [`util::DocumentWriter::with_encoding`].
+pub use encoding_rs;
+/// The JSON values produced and consumed by [`geojson`].
+pub use serde_json;
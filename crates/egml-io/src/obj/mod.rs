@@ -0,0 +1,11 @@
+//! Wavefront OBJ export of triangulated geometries, e.g. for visual checks
+//! in Blender, and import of OBJ meshes.
+//!
+//! OBJ holds triangles only, so surfaces and solids are triangulated first
+//! and points and curves cannot be written.
+
+mod reader;
+mod writer;
+
+pub use reader::*;
+pub use writer::*;
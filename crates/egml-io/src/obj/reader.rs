@@ -0,0 +1,207 @@
+use crate::Error;
+use crate::util::IndexedMesh;
+use egml_core::model::base::{AsAbstractGmlMut, Id};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometryMut, DirectPosition};
+
+/// Reads the meshes of a Wavefront OBJ file.
+///
+/// Each object (`o`) or group (`g`) with faces becomes a geometry whose
+/// `gml:id` is its name; faces before the first name form a geometry
+/// without one. Faces with more than three vertices are split into fans of
+/// triangles. A mesh is read as a `Solid` if it is closed and consistently
+/// oriented, and as a `TriangulatedSurface` otherwise. An `# origin x y z`
+/// comment, as written by [`ObjWriter`](crate::obj::ObjWriter), is added to
+/// every vertex. Texture coordinates, normals, lines and materials are
+/// ignored.
+///
+/// # Errors
+///
+/// Returns [`Error::Obj`] if the input is malformed or has no faces.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::base::AsAbstractGml;
+/// use egml_core::model::geometry::AsAbstractGeometry;
+/// use egml_io::obj::read_obj;
+///
+/// let obj = "# origin 691000 5336000 400\no roof\n\
+///     v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\n";
+/// let geometries = read_obj(obj).unwrap();
+///
+/// let id = geometries[0].abstract_geometry().abstract_gml.id().unwrap();
+/// assert_eq!(id.as_str(), "roof");
+/// ```
+pub fn read_obj(text: &str) -> Result<Vec<AbstractGeometryKind>, Error> {
+    let mut origin = [0.0; 3];
+    let mut vertices = Vec::new();
+    let mut groups: Vec<(Option<&str>, Vec<Vec<usize>>)> = vec![(None, Vec::new())];
+
+    for line in text.lines() {
+        let line = line.trim();
+        let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        match keyword {
+            "#" if rest.starts_with("origin ") => {
+                origin = coordinates(&rest["origin ".len()..], line)?;
+            }
+            "v" => vertices.push(coordinates(rest, line)?),
+            "f" => {
+                let face = rest
+                    .split_whitespace()
+                    .map(|x| {
+                        // Only the vertex of `v/vt/vn` is used.
+                        let index: i64 = x
+                            .split('/')
+                            .next()
+                            .and_then(|x| x.parse().ok())
+                            .ok_or_else(|| Error::Obj(format!("invalid face '{line}'")))?;
+                        // Negative indices count back from the latest vertex.
+                        let index = if index < 0 {
+                            vertices.len() as i64 + index
+                        } else {
+                            index - 1
+                        };
+                        usize::try_from(index)
+                            .ok()
+                            .filter(|x| *x < vertices.len())
+                            .ok_or_else(|| Error::Obj(format!("invalid vertex in '{line}'")))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                groups.last_mut().expect("a group").1.push(face);
+            }
+            "o" | "g" => {
+                let name = (!rest.is_empty()).then_some(rest);
+                match groups.last_mut() {
+                    Some((current, faces)) if faces.is_empty() => *current = name,
+                    _ => groups.push((name, Vec::new())),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let vertices = vertices
+        .into_iter()
+        .map(|x| {
+            let [x, y, z] = std::array::from_fn(|axis| x[axis] + origin[axis]);
+            DirectPosition::new(x, y, z)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let geometries = groups
+        .into_iter()
+        .filter(|(_, faces)| !faces.is_empty())
+        .map(|(name, faces)| {
+            let mut mesh = IndexedMesh::default();
+            for face in faces {
+                let indices: Vec<usize> = face
+                    .iter()
+                    .map(|x| mesh.push_vertex(vertices[*x]))
+                    .collect();
+                mesh.push_polygon(&indices);
+            }
+            let mut geometry = mesh.into_geometry()?;
+            if let Some(name) = name {
+                geometry
+                    .abstract_geometry_mut()
+                    .abstract_gml
+                    .set_id(Id::try_from(name)?);
+            }
+            Ok(geometry)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    if geometries.is_empty() {
+        return Err(Error::Obj("the file has no faces".to_string()));
+    }
+    Ok(geometries)
+}
+
+/// The first three numbers of `values`, ignoring an optional fourth.
+fn coordinates(values: &str, line: &str) -> Result<[f64; 3], Error> {
+    let values = values
+        .split_whitespace()
+        .map(|x| x.parse::<f64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| Error::Obj(format!("invalid coordinates in '{line}'")))?;
+    match values.as_slice() {
+        [x, y, z] | [x, y, z, _] => Ok([*x, *y, *z]),
+        _ => Err(Error::UnsupportedDimension {
+            found: values.len() as u32,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::obj::ObjWriter;
+    use crate::wkt::read_wkt;
+    use egml_core::model::base::AsAbstractGml;
+    use egml_core::model::common::{GeometryType, HasGeometryType};
+    use egml_core::model::geometry::AsAbstractGeometry;
+    use egml_core::model::geometry::primitives::Solid;
+
+    #[test]
+    fn written_objects_are_read_back() {
+        let mut cube = read_wkt(
+            "POLYHEDRALSURFACE Z (\
+            ((0 0 0, 0 1 0, 1 1 0, 1 0 0, 0 0 0)), ((0 0 1, 1 0 1, 1 1 1, 0 1 1, 0 0 1)), \
+            ((0 0 0, 1 0 0, 1 0 1, 0 0 1, 0 0 0)), ((1 0 0, 1 1 0, 1 1 1, 1 0 1, 1 0 0)), \
+            ((1 1 0, 0 1 0, 0 1 1, 1 1 1, 1 1 0)), ((0 1 0, 0 0 0, 0 0 1, 0 1 1, 0 1 0)))",
+        )
+        .unwrap();
+        cube.abstract_geometry_mut()
+            .abstract_gml
+            .set_id(Id::try_from("building_1").unwrap());
+        let roof = read_wkt("POLYGON Z ((0 0 1, 1 0 1, 1 1 2, 0 0 1))").unwrap();
+        let origin = DirectPosition::new(1.0, 2.0, 3.0).unwrap();
+
+        let mut obj = Vec::new();
+        ObjWriter::new()
+            .with_origin(origin)
+            .write([&cube, &roof], &mut obj)
+            .unwrap();
+        let geometries = read_obj(&String::from_utf8(obj).unwrap()).unwrap();
+
+        assert_eq!(geometries.len(), 2);
+        let id = geometries[0].abstract_geometry().abstract_gml.id().unwrap();
+        assert_eq!(id.as_str(), "building_1");
+        let solid = Solid::try_from(geometries[0].clone()).unwrap();
+        // The six faces are read as twelve triangles.
+        assert_eq!(solid.points().len(), 36);
+        assert!(solid.points().iter().any(|x| x.coords() == [1.0, 1.0, 1.0]));
+        assert_eq!(
+            geometries[1].geometry_type(),
+            GeometryType::TriangulatedSurface
+        );
+    }
+
+    #[test]
+    fn face_vertices_may_be_relative_or_carry_attributes() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 1 1 0 1.0\nvt 0 0\nvn 0 0 1\n\
+                   usemtl roof\nf 1/1/1 2//1 -1\n";
+
+        let geometries = read_obj(obj).unwrap();
+
+        assert_eq!(geometries.len(), 1);
+        assert!(
+            geometries[0]
+                .abstract_geometry()
+                .abstract_gml
+                .id()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        for obj in [
+            "v 0 0 0\n",
+            "v 0 0\nf 1 1 1\n",
+            "v 0 0 0\nf 1 2 3\n",
+            "f a b c\n",
+        ] {
+            assert!(read_obj(obj).is_err(), "{obj}");
+        }
+    }
+}
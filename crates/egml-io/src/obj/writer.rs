@@ -0,0 +1,197 @@
+use crate::Error;
+use crate::util::{IndexedMesh, WriteOptions};
+use egml_core::model::base::AsAbstractGml;
+use egml_core::model::common::Triangulate;
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometry, DirectPosition};
+use std::io::Write;
+
+/// The statement that starts the group of each geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ObjGrouping {
+    /// `o`, imported by Blender as one object per geometry.
+    #[default]
+    Object,
+    /// `g`, imported by Blender as one object per geometry only when
+    /// splitting by group.
+    Group,
+}
+
+/// Writes geometries as Wavefront OBJ, triangulated with [`Triangulate`].
+///
+/// Each geometry becomes a group named after its `gml:id`, or `geometry_<n>`
+/// for the `n`-th geometry without one. Equal vertices are written once and
+/// shared by all faces of the file. Members that [`Triangulate`] skipped are
+/// listed in `#` comments of their group.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::DirectPosition;
+/// use egml_io::obj::ObjWriter;
+/// use egml_io::wkt::read_wkt;
+///
+/// let square = read_wkt("POLYGON Z ((691000 5336000 400, 691001 5336000 400, \
+///     691001 5336001 400, 691000 5336001 400, 691000 5336000 400))")
+/// .unwrap();
+/// let origin = DirectPosition::new(691000.0, 5336000.0, 400.0).unwrap();
+///
+/// let mut obj = Vec::new();
+/// ObjWriter::new()
+///     .with_origin(origin)
+///     .write(&[square], &mut obj)
+///     .unwrap();
+///
+/// let obj = String::from_utf8(obj).unwrap();
+/// assert!(obj.contains("# origin 691000 5336000 400\no geometry_1\n"));
+/// assert!(obj.contains("v 1 1 0\n"));
+/// assert_eq!(obj.lines().filter(|x| x.starts_with("v ")).count(), 4);
+/// assert_eq!(obj.lines().filter(|x| x.starts_with("f ")).count(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ObjWriter {
+    grouping: ObjGrouping,
+    origin: Option<DirectPosition>,
+}
+
+impl ObjWriter {
+    /// Creates a writer that starts each group with `o` and writes
+    /// coordinates unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_grouping(mut self, grouping: ObjGrouping) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    /// Subtracts `origin` from every vertex and records it in a comment.
+    /// Viewers store vertices as `f32`, which keeps only centimetres of UTM
+    /// coordinates, so models should be moved close to the origin.
+    pub fn with_origin(mut self, origin: DirectPosition) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Writes `geometries` to `out`. Coordinates are formatted according to
+    /// the current [`WriteOptions`](crate::util::WriteOptions).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a geometry cannot be triangulated at all, e.g. a
+    /// `MultiCurve`, and [`Error::Io`] if writing fails.
+    pub fn write<'a, W: Write>(
+        &self,
+        geometries: impl IntoIterator<Item = &'a AbstractGeometryKind>,
+        mut out: W,
+    ) -> Result<(), Error> {
+        let options = WriteOptions::current();
+        let offset = self.origin.map_or([0.0; 3], |x| x.coords());
+        let mut mesh = IndexedMesh::<[f64; 3]>::default();
+
+        writeln!(out, "# Written by egml")?;
+        if let Some(origin) = &self.origin {
+            let [x, y, z] = origin.coords();
+            writeln!(out, "# origin {x} {y} {z}")?;
+        }
+
+        for (index, geometry) in geometries.into_iter().enumerate() {
+            let triangulation = geometry.triangulate()?;
+            let name = match geometry.abstract_geometry().abstract_gml.id() {
+                Some(id) => id.as_str().split_whitespace().collect::<Vec<_>>().join("_"),
+                None => format!("geometry_{}", index + 1),
+            };
+            let statement = match self.grouping {
+                ObjGrouping::Object => "o",
+                ObjGrouping::Group => "g",
+            };
+            writeln!(out, "{statement} {name}")?;
+            for error in triangulation.skipped() {
+                writeln!(out, "# skipped: {error}")?;
+            }
+
+            for triangle in triangulation.surface().triangles() {
+                let written = mesh.vertices.len();
+                // Indices are one-based.
+                let [a, b, c] = [triangle.a(), triangle.b(), triangle.c()].map(|x| {
+                    let coords = x.coords();
+                    mesh.push_vertex(std::array::from_fn(|axis| coords[axis] - offset[axis])) + 1
+                });
+                for coords in &mesh.vertices[written..] {
+                    let [x, y, z] =
+                        std::array::from_fn(|axis| options.format_ordinate(axis, coords[axis]));
+                    writeln!(out, "v {x} {y} {z}")?;
+                }
+                writeln!(out, "f {a} {b} {c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt::read_wkt;
+    use egml_core::model::base::{AsAbstractGmlMut, Id};
+    use egml_core::model::geometry::AsAbstractGeometryMut;
+
+    fn write(writer: &ObjWriter, geometries: &[AbstractGeometryKind]) -> String {
+        let mut obj = Vec::new();
+        writer.write(geometries, &mut obj).unwrap();
+        String::from_utf8(obj).unwrap()
+    }
+
+    #[test]
+    fn groups_are_named_by_id_and_share_vertices() {
+        let mut left = read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1 1 0, 0 1 0, 0 0 0))").unwrap();
+        left.abstract_geometry_mut()
+            .abstract_gml
+            .set_id(Id::try_from("wall 1").unwrap());
+        let right = read_wkt("POLYGON Z ((1 0 0, 2 0 0, 2 1 0, 1 1 0, 1 0 0))").unwrap();
+
+        let obj = write(
+            &ObjWriter::new().with_grouping(ObjGrouping::Group),
+            &[left, right],
+        );
+
+        assert!(obj.contains("g wall_1\n"));
+        assert!(obj.contains("g geometry_2\n"));
+        assert_eq!(obj.lines().filter(|x| x.starts_with("v ")).count(), 6);
+        assert_eq!(obj.lines().filter(|x| x.starts_with("f ")).count(), 4);
+        for face in obj.lines().filter(|x| x.starts_with("f ")) {
+            assert!(
+                face[2..]
+                    .split(' ')
+                    .all(|x| (1..=6).contains(&x.parse().unwrap()))
+            );
+        }
+    }
+
+    #[test]
+    fn skipped_members_are_listed_in_comments() {
+        // The second ring doubles back on itself, which the triangulation
+        // cannot handle.
+        let surfaces = read_wkt(
+            "MULTIPOLYGON Z (((0 0 0, 1 0 0, 1 1 0, 0 0 0)), \
+             ((2.063 3.375 9.274, 2.057 3.377 9.269, 2.063 3.375 9.274, \
+               2.067 3.373 9.277, 2.073 3.371 9.282, 2.067 3.373 9.277, 2.063 3.375 9.274)))",
+        )
+        .unwrap();
+
+        let obj = write(&ObjWriter::new(), &[surfaces]);
+
+        assert_eq!(
+            obj.lines().filter(|x| x.starts_with("# skipped: ")).count(),
+            1
+        );
+        assert_eq!(obj.lines().filter(|x| x.starts_with("f ")).count(), 1);
+    }
+
+    #[test]
+    fn curves_cannot_be_written() {
+        let curves = read_wkt("MULTILINESTRING Z ((0 0 0, 1 0 0))").unwrap();
+
+        assert!(ObjWriter::new().write(&[curves], Vec::new()).is_err());
+    }
+}
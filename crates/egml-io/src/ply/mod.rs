@@ -0,0 +1,12 @@
+//! PLY export and import of triangle meshes, e.g. for point-cloud tooling.
+//!
+//! The writer triangulates surfaces and solids into one mesh with shared
+//! vertices, optionally recording the geometry of each face in an
+//! `id_index` property. The reader turns meshes back into
+//! `TriangulatedSurface`s, or `Solid`s if they close a volume.
+
+mod reader;
+mod writer;
+
+pub use reader::*;
+pub use writer::*;
@@ -0,0 +1,443 @@
+use crate::Error;
+use crate::util::IndexedMesh;
+use egml_core::model::base::{AsAbstractGmlMut, Id};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometryMut, DirectPosition};
+use std::collections::{BTreeMap, HashMap};
+
+/// Reads the meshes of an ASCII or binary PLY file.
+///
+/// Faces are read from the `vertex_indices` (or `vertex_index`) list of
+/// the `face` element, and polygons with more than three vertices are split
+/// into fans of triangles. If faces have an `id_index` property, as written
+/// by [`PlyWriter`](crate::ply::PlyWriter), each index becomes a geometry
+/// with the `gml:id` of its `comment id_index`, in the order of the indices.
+/// Otherwise all faces form one geometry. A mesh is read as a `Solid` if it
+/// is closed and consistently oriented, and as a `TriangulatedSurface`
+/// otherwise.
+///
+/// # Errors
+///
+/// Returns [`Error::Ply`] if the input is malformed or has no faces, e.g. a
+/// point cloud.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::common::{GeometryType, HasGeometryType};
+/// use egml_io::ply::read_ply;
+///
+/// let ply = "ply\nformat ascii 1.0\n\
+///     element vertex 3\nproperty float x\nproperty float y\nproperty float z\n\
+///     element face 1\nproperty list uchar int vertex_indices\nend_header\n\
+///     0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n";
+/// let geometries = read_ply(ply.as_bytes()).unwrap();
+///
+/// assert_eq!(geometries[0].geometry_type(), GeometryType::TriangulatedSurface);
+/// ```
+pub fn read_ply(bytes: &[u8]) -> Result<Vec<AbstractGeometryKind>, Error> {
+    let end = b"end_header";
+    let header_end = bytes
+        .windows(end.len())
+        .position(|x| x == end)
+        .ok_or_else(|| Error::Ply("missing end_header".to_string()))?;
+    let header = std::str::from_utf8(&bytes[..header_end])
+        .map_err(|_| Error::Ply("the header is not valid UTF-8".to_string()))?;
+    // The data starts after the line break that ends the header.
+    let mut data = &bytes[header_end + end.len()..];
+    if let Some(rest) = data.strip_prefix(b"\r") {
+        data = rest;
+    }
+    data = data.strip_prefix(b"\n").unwrap_or(data);
+
+    let header = Header::parse(header)?;
+    let mut source = match header.format {
+        Format::Ascii => Source::Ascii(
+            std::str::from_utf8(data)
+                .map_err(|_| Error::Ply("ASCII data is not valid UTF-8".to_string()))?
+                .split_whitespace(),
+        ),
+        Format::Binary { big_endian } => Source::Binary { data, big_endian },
+    };
+
+    let mut vertices = Vec::new();
+    let mut faces = Vec::new();
+    for element in &header.elements {
+        for _ in 0..element.count {
+            let mut coords = [None; 3];
+            let mut indices = None;
+            let mut id_index = None;
+            for property in &element.properties {
+                match property {
+                    Property::Scalar { name, kind } => {
+                        let value = source.read(*kind)?;
+                        match (element.name.as_str(), name.as_str()) {
+                            ("vertex", "x") => coords[0] = Some(value),
+                            ("vertex", "y") => coords[1] = Some(value),
+                            ("vertex", "z") => coords[2] = Some(value),
+                            ("face", "id_index") => id_index = Some(index(value)?),
+                            _ => {}
+                        }
+                    }
+                    Property::List { name, count, item } => {
+                        let count = index(source.read(*count)?)?;
+                        let values = (0..count)
+                            .map(|_| source.read(*item).and_then(index))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        if element.name == "face"
+                            && matches!(name.as_str(), "vertex_indices" | "vertex_index")
+                        {
+                            indices = Some(values);
+                        }
+                    }
+                }
+            }
+            match element.name.as_str() {
+                "vertex" => {
+                    let [Some(x), Some(y), Some(z)] = coords else {
+                        return Err(Error::Ply("a vertex lacks x, y or z".to_string()));
+                    };
+                    vertices.push(DirectPosition::new(x, y, z)?);
+                }
+                "face" => {
+                    let indices = indices
+                        .ok_or_else(|| Error::Ply("a face lacks vertex_indices".to_string()))?;
+                    faces.push((id_index, indices));
+                }
+                _ => {}
+            }
+        }
+    }
+    if faces.is_empty() {
+        return Err(Error::Ply("the file has no faces".to_string()));
+    }
+
+    let mut meshes = BTreeMap::new();
+    for (id_index, indices) in faces {
+        if let Some(index) = indices.iter().find(|x| **x >= vertices.len()) {
+            return Err(Error::Ply(format!("invalid vertex index {index}")));
+        }
+        let mesh: &mut IndexedMesh = meshes.entry(id_index).or_default();
+        let indices: Vec<usize> = indices
+            .iter()
+            .map(|x| mesh.push_vertex(vertices[*x]))
+            .collect();
+        mesh.push_polygon(&indices);
+    }
+    meshes
+        .into_iter()
+        .map(|(id_index, mesh)| {
+            let mut geometry = mesh.into_geometry()?;
+            if let Some(id) = id_index.and_then(|x| header.ids.get(&x)) {
+                geometry
+                    .abstract_geometry_mut()
+                    .abstract_gml
+                    .set_id(Id::try_from(id.as_str())?);
+            }
+            Ok(geometry)
+        })
+        .collect()
+}
+
+/// A non-negative integer read as `f64`, as an index or count.
+fn index(value: f64) -> Result<usize, Error> {
+    if value >= 0.0 && value.fract() == 0.0 {
+        Ok(value as usize)
+    } else {
+        Err(Error::Ply(format!("{value} is not an index")))
+    }
+}
+
+enum Format {
+    Ascii,
+    Binary { big_endian: bool },
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl Kind {
+    fn parse(name: &str) -> Result<Self, Error> {
+        Ok(match name {
+            "char" | "int8" => Self::I8,
+            "uchar" | "uint8" => Self::U8,
+            "short" | "int16" => Self::I16,
+            "ushort" | "uint16" => Self::U16,
+            "int" | "int32" => Self::I32,
+            "uint" | "uint32" => Self::U32,
+            "float" | "float32" => Self::F32,
+            "double" | "float64" => Self::F64,
+            _ => return Err(Error::Ply(format!("unknown property type '{name}'"))),
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+}
+
+enum Property {
+    Scalar {
+        name: String,
+        kind: Kind,
+    },
+    List {
+        name: String,
+        count: Kind,
+        item: Kind,
+    },
+}
+
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+struct Header {
+    format: Format,
+    elements: Vec<Element>,
+    /// The `gml:id` of each `id_index`, from the comments.
+    ids: HashMap<usize, String>,
+}
+
+impl Header {
+    fn parse(header: &str) -> Result<Self, Error> {
+        let mut lines = header.lines().map(str::trim);
+        if lines.next() != Some("ply") {
+            return Err(Error::Ply("missing magic number 'ply'".to_string()));
+        }
+        let mut format = None;
+        let mut elements: Vec<Element> = Vec::new();
+        let mut ids = HashMap::new();
+        for line in lines {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                ["format", "ascii", _] => format = Some(Format::Ascii),
+                ["format", "binary_little_endian", _] => {
+                    format = Some(Format::Binary { big_endian: false })
+                }
+                ["format", "binary_big_endian", _] => {
+                    format = Some(Format::Binary { big_endian: true })
+                }
+                ["comment", "id_index", index, ..] => {
+                    // The id is the rest of the line, which may hold spaces.
+                    let id = line.splitn(4, ' ').nth(3).unwrap_or_default().trim();
+                    if let Ok(index) = index.parse()
+                        && !id.is_empty()
+                    {
+                        ids.insert(index, id.to_string());
+                    }
+                }
+                ["element", name, count] => elements.push(Element {
+                    name: name.to_string(),
+                    count: count
+                        .parse()
+                        .map_err(|_| Error::Ply(format!("invalid element '{line}'")))?,
+                    properties: Vec::new(),
+                }),
+                ["property", "list", count, item, name] => elements
+                    .last_mut()
+                    .ok_or_else(|| Error::Ply("a property precedes all elements".to_string()))?
+                    .properties
+                    .push(Property::List {
+                        name: name.to_string(),
+                        count: Kind::parse(count)?,
+                        item: Kind::parse(item)?,
+                    }),
+                ["property", kind, name] => elements
+                    .last_mut()
+                    .ok_or_else(|| Error::Ply("a property precedes all elements".to_string()))?
+                    .properties
+                    .push(Property::Scalar {
+                        name: name.to_string(),
+                        kind: Kind::parse(kind)?,
+                    }),
+                ["comment", ..] | ["obj_info", ..] | [] => {}
+                _ => return Err(Error::Ply(format!("unexpected header line '{line}'"))),
+            }
+        }
+        Ok(Self {
+            format: format.ok_or_else(|| Error::Ply("missing format".to_string()))?,
+            elements,
+            ids,
+        })
+    }
+}
+
+/// The data after the header.
+enum Source<'a> {
+    Ascii(std::str::SplitWhitespace<'a>),
+    Binary { data: &'a [u8], big_endian: bool },
+}
+
+impl Source<'_> {
+    fn read(&mut self, kind: Kind) -> Result<f64, Error> {
+        match self {
+            Self::Ascii(words) => words
+                .next()
+                .ok_or_else(|| Error::Ply("the data ends early".to_string()))?
+                .parse()
+                .map_err(|_| Error::Ply("invalid number in the data".to_string())),
+            Self::Binary { data, big_endian } => {
+                let (bytes, rest) = data
+                    .split_at_checked(kind.size())
+                    .ok_or_else(|| Error::Ply("the data ends early".to_string()))?;
+                *data = rest;
+                let mut buffer = [0; 8];
+                buffer[..bytes.len()].copy_from_slice(bytes);
+                if *big_endian {
+                    buffer[..bytes.len()].reverse();
+                }
+                let [b0, b1, b2, b3, ..] = buffer;
+                Ok(match kind {
+                    Kind::I8 => f64::from(b0 as i8),
+                    Kind::U8 => f64::from(b0),
+                    Kind::I16 => f64::from(i16::from_le_bytes([b0, b1])),
+                    Kind::U16 => f64::from(u16::from_le_bytes([b0, b1])),
+                    Kind::I32 => f64::from(i32::from_le_bytes([b0, b1, b2, b3])),
+                    Kind::U32 => f64::from(u32::from_le_bytes([b0, b1, b2, b3])),
+                    Kind::F32 => f64::from(f32::from_le_bytes([b0, b1, b2, b3])),
+                    Kind::F64 => f64::from_le_bytes(buffer),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ply::{PlyEncoding, PlyWriter};
+    use crate::wkt::read_wkt;
+    use egml_core::model::base::AsAbstractGml;
+    use egml_core::model::common::{GeometryType, HasGeometryType};
+    use egml_core::model::geometry::AsAbstractGeometry;
+
+    #[test]
+    fn geometries_are_split_by_id_index() {
+        let mut cube = read_wkt(
+            "POLYHEDRALSURFACE Z (\
+            ((0 0 0, 0 1 0, 1 1 0, 1 0 0, 0 0 0)), ((0 0 1, 1 0 1, 1 1 1, 0 1 1, 0 0 1)), \
+            ((0 0 0, 1 0 0, 1 0 1, 0 0 1, 0 0 0)), ((1 0 0, 1 1 0, 1 1 1, 1 0 1, 1 0 0)), \
+            ((1 1 0, 0 1 0, 0 1 1, 1 1 1, 1 1 0)), ((0 1 0, 0 0 0, 0 0 1, 0 1 1, 0 1 0)))",
+        )
+        .unwrap();
+        cube.abstract_geometry_mut()
+            .abstract_gml
+            .set_id(Id::try_from("building 1").unwrap());
+        let roof = read_wkt("POLYGON Z ((0 0 1, 1 0 1, 1 1 2, 0 0 1))").unwrap();
+
+        for encoding in [PlyEncoding::Ascii, PlyEncoding::BinaryLittleEndian] {
+            let mut ply = Vec::new();
+            PlyWriter::new()
+                .with_encoding(encoding)
+                .with_id_index(true)
+                .write([&cube, &roof], &mut ply)
+                .unwrap();
+
+            let geometries = read_ply(&ply).unwrap();
+
+            assert_eq!(geometries.len(), 2);
+            assert_eq!(geometries[0].geometry_type(), GeometryType::Solid);
+            assert_eq!(
+                geometries[0]
+                    .abstract_geometry()
+                    .abstract_gml
+                    .id()
+                    .unwrap()
+                    .as_str(),
+                "building 1"
+            );
+            assert_eq!(
+                geometries[1].geometry_type(),
+                GeometryType::TriangulatedSurface
+            );
+            assert!(
+                geometries[1]
+                    .abstract_geometry()
+                    .abstract_gml
+                    .id()
+                    .is_none()
+            );
+        }
+    }
+
+    #[test]
+    fn big_endian_polygons_are_split_into_triangles() {
+        let mut ply = b"ply\nformat binary_big_endian 1.0\nelement vertex 4\n\
+            property float x\nproperty float y\nproperty float z\nproperty uchar red\n\
+            element face 1\nproperty list uchar ushort vertex_indices\nend_header\n"
+            .to_vec();
+        for [x, y] in [[0.0f32, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]] {
+            for value in [x, y, 5.0] {
+                ply.extend(value.to_be_bytes());
+            }
+            ply.push(255);
+        }
+        ply.push(4);
+        for index in 0u16..4 {
+            ply.extend(index.to_be_bytes());
+        }
+
+        let geometries = read_ply(&ply).unwrap();
+
+        assert_eq!(
+            geometries[0].geometry_type(),
+            GeometryType::TriangulatedSurface
+        );
+        let triangulated = egml_core::model::geometry::primitives::TriangulatedSurface::try_from(
+            geometries[0].clone(),
+        )
+        .unwrap();
+        assert_eq!(triangulated.triangles().len(), 2);
+        assert_eq!(triangulated.triangles()[1].b().coords(), [1.0, 1.0, 5.0]);
+    }
+
+    #[test]
+    fn vertices_at_equal_positions_are_welded() {
+        let ply = "ply\nformat ascii 1.0\nelement vertex 4\nproperty float x\n\
+            property float y\nproperty float z\nelement face 2\n\
+            property list uchar int vertex_indices\nend_header\n\
+            0 0 0\n1 0 0\n0 1 0\n1 0 0\n3 0 1 3\n3 0 1 2\n";
+
+        let geometries = read_ply(ply.as_bytes()).unwrap();
+
+        let triangulated = egml_core::model::geometry::primitives::TriangulatedSurface::try_from(
+            geometries[0].clone(),
+        )
+        .unwrap();
+        assert_eq!(triangulated.triangles().len(), 1);
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        for ply in [
+            "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n0\n",
+            "ply\nformat ascii 1.0\nelement face 1\nproperty list uchar int vertex_indices\n\
+             end_header\n3 0 1 2\n",
+            "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\n\
+             property float z\nelement face 1\nproperty list uchar int vertex_indices\n\
+             end_header\n0 0 0\n1 0 0\n",
+            "plx\nformat ascii 1.0\nend_header\n",
+        ] {
+            assert!(
+                matches!(read_ply(ply.as_bytes()), Err(Error::Ply(_))),
+                "{ply}"
+            );
+        }
+    }
+}
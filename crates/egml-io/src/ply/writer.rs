@@ -0,0 +1,197 @@
+use crate::Error;
+use crate::util::{IndexedMesh, WriteOptions};
+use egml_core::model::base::AsAbstractGml;
+use egml_core::model::common::Triangulate;
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometry};
+use std::io::Write;
+
+/// The encoding of the data of a PLY file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PlyEncoding {
+    /// `binary_little_endian`.
+    #[default]
+    BinaryLittleEndian,
+    /// `ascii`.
+    Ascii,
+}
+
+/// Writes geometries as a PLY mesh, triangulated with [`Triangulate`].
+///
+/// Vertices are written as `double`s and shared by all faces of the file.
+/// With [`with_id_index`](Self::with_id_index), each face has an `id_index`
+/// property holding the position of its geometry among the written ones,
+/// and the header has a `comment id_index <index> <gml:id>` for each
+/// geometry with an id.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::ply::{PlyEncoding, PlyWriter};
+/// use egml_io::wkt::read_wkt;
+///
+/// let square = read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1 1 0, 0 1 0, 0 0 0))").unwrap();
+///
+/// let mut ply = Vec::new();
+/// PlyWriter::new()
+///     .with_encoding(PlyEncoding::Ascii)
+///     .write(&[square], &mut ply)
+///     .unwrap();
+///
+/// let ply = String::from_utf8(ply).unwrap();
+/// assert!(ply.contains("element vertex 4\n"));
+/// assert!(ply.contains("element face 2\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlyWriter {
+    encoding: PlyEncoding,
+    id_index: bool,
+}
+
+impl PlyWriter {
+    /// Creates a writer of binary PLY without `id_index` properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_encoding(mut self, encoding: PlyEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Whether to write the `id_index` property of each face.
+    pub fn with_id_index(mut self, id_index: bool) -> Self {
+        self.id_index = id_index;
+        self
+    }
+
+    /// Writes `geometries` to `out`. ASCII coordinates are formatted
+    /// according to the current [`WriteOptions`](crate::util::WriteOptions).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a geometry cannot be triangulated at all, e.g. a
+    /// `MultiCurve`, and [`Error::Io`] if writing fails.
+    pub fn write<'a, W: Write>(
+        &self,
+        geometries: impl IntoIterator<Item = &'a AbstractGeometryKind>,
+        mut out: W,
+    ) -> Result<(), Error> {
+        let mut mesh = IndexedMesh::<[f64; 3]>::default();
+        let mut faces = Vec::new();
+        let mut ids = Vec::new();
+        for (index, geometry) in geometries.into_iter().enumerate() {
+            let index = index as u32;
+            if let Some(id) = geometry.abstract_geometry().abstract_gml.id() {
+                ids.push((index, id.as_str().replace(['\r', '\n'], " ")));
+            }
+            for triangle in geometry.triangulate()?.surface().triangles() {
+                let face = [triangle.a(), triangle.b(), triangle.c()]
+                    .map(|x| mesh.push_vertex(x.coords()) as u32);
+                faces.push((face, index));
+            }
+        }
+
+        let format = match self.encoding {
+            PlyEncoding::BinaryLittleEndian => "binary_little_endian",
+            PlyEncoding::Ascii => "ascii",
+        };
+        writeln!(out, "ply")?;
+        writeln!(out, "format {format} 1.0")?;
+        writeln!(out, "comment Written by egml")?;
+        if self.id_index {
+            for (index, id) in &ids {
+                writeln!(out, "comment id_index {index} {id}")?;
+            }
+        }
+        writeln!(out, "element vertex {}", mesh.vertices.len())?;
+        for axis in ["x", "y", "z"] {
+            writeln!(out, "property double {axis}")?;
+        }
+        writeln!(out, "element face {}", faces.len())?;
+        writeln!(out, "property list uchar uint vertex_indices")?;
+        if self.id_index {
+            writeln!(out, "property uint id_index")?;
+        }
+        writeln!(out, "end_header")?;
+
+        match self.encoding {
+            PlyEncoding::Ascii => {
+                let options = WriteOptions::current();
+                for coords in &mesh.vertices {
+                    let [x, y, z] =
+                        std::array::from_fn(|axis| options.format_ordinate(axis, coords[axis]));
+                    writeln!(out, "{x} {y} {z}")?;
+                }
+                for ([a, b, c], index) in &faces {
+                    write!(out, "3 {a} {b} {c}")?;
+                    if self.id_index {
+                        write!(out, " {index}")?;
+                    }
+                    writeln!(out)?;
+                }
+            }
+            PlyEncoding::BinaryLittleEndian => {
+                for value in mesh.vertices.iter().flatten() {
+                    out.write_all(&value.to_le_bytes())?;
+                }
+                for (face, index) in &faces {
+                    out.write_all(&[3])?;
+                    for value in face {
+                        out.write_all(&value.to_le_bytes())?;
+                    }
+                    if self.id_index {
+                        out.write_all(&index.to_le_bytes())?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt::read_wkt;
+    use egml_core::model::base::{AsAbstractGmlMut, Id};
+    use egml_core::model::geometry::AsAbstractGeometryMut;
+
+    #[test]
+    fn faces_carry_the_index_of_their_geometry() {
+        let left = read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1 1 0, 0 1 0, 0 0 0))").unwrap();
+        let mut right = read_wkt("POLYGON Z ((1 0 0, 2 0 0, 2 1 0, 1 1 0, 1 0 0))").unwrap();
+        right
+            .abstract_geometry_mut()
+            .abstract_gml
+            .set_id(Id::try_from("wall_2").unwrap());
+
+        let mut ply = Vec::new();
+        PlyWriter::new()
+            .with_encoding(PlyEncoding::Ascii)
+            .with_id_index(true)
+            .write(&[left, right], &mut ply)
+            .unwrap();
+        let ply = String::from_utf8(ply).unwrap();
+
+        let (header, data) = ply.split_once("end_header\n").unwrap();
+        assert!(header.contains("comment id_index 1 wall_2\n"));
+        assert!(!header.contains("comment id_index 0"));
+        assert!(header.contains("element vertex 6\nproperty double x\n"));
+        assert!(header.ends_with("property uint id_index\n"));
+        let faces: Vec<&str> = data.lines().skip(6).collect();
+        assert_eq!(faces.len(), 4);
+        assert_eq!(faces.iter().filter(|x| x.ends_with(" 1")).count(), 2);
+    }
+
+    #[test]
+    fn binary_data_follows_the_header() {
+        let square = read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1 1 0, 0 1 0, 0 0 0))").unwrap();
+
+        let mut ply = Vec::new();
+        PlyWriter::new().write(&[square], &mut ply).unwrap();
+
+        let header = b"end_header\n";
+        let start = ply.windows(header.len()).position(|x| x == header).unwrap() + header.len();
+        assert_eq!(ply.len() - start, 4 * 3 * 8 + 2 * (1 + 3 * 4));
+    }
+}
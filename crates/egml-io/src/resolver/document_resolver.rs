@@ -0,0 +1,346 @@
+use crate::Error;
+use crate::codec::geometry::deserialize_abstract_geometry_kind;
+use crate::resolver::ReferenceLoader;
+use crate::util::{GmlElement, XmlElement, XmlElementSpans, decode_document, decompress_document};
+use egml_core::model::common::VisitPropertiesMut;
+use egml_core::model::geometry::AbstractGeometryKind;
+use egml_core::model::xlink::HRef;
+use egml_core::resolver::{
+    ReferenceTarget, ReferenceTargets, ResolutionReport, UnresolvedReason, resolve_references,
+};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Resolves `xlink:href` references across documents, fetching each
+/// referenced document through a [`ReferenceLoader`].
+///
+/// A reference such as `tile_2.gml#p1` is resolved relative to the document
+/// it occurs in; a plain `#p1` refers to that same document. Every document is
+/// loaded and indexed by `gml:id` once, and every parsed target is kept, so
+/// a resolver can be reused for all tiles of a dataset. A chain of references
+/// that leads back to a target that is currently being inlined is reported
+/// as [`UnresolvedReason::Cycle`] instead of being followed.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::aggregates::MultiSurface;
+/// use egml_core::model::geometry::primitives::AbstractSurfaceProperty;
+/// use egml_core::model::xlink::HRef;
+/// use egml_io::resolver::{DocumentResolver, InMemoryLoader};
+///
+/// let loader = InMemoryLoader::new().with_document(
+///     "tiles/b.gml",
+///     b"<core:CityModel><gml:Polygon gml:id=\"p1\"><gml:exterior><gml:LinearRing>\
+///       <gml:posList>0 0 0 1 0 0 1 1 0 0 0 0</gml:posList>\
+///       </gml:LinearRing></gml:exterior></gml:Polygon></core:CityModel>"
+///         .to_vec(),
+/// );
+/// let mut resolver = DocumentResolver::new(loader);
+///
+/// let mut multi_surface = MultiSurface::new(vec![AbstractSurfaceProperty::from_href(
+///     HRef::from_remote("b.gml#p1"),
+/// )])
+/// .unwrap();
+/// let report = resolver.resolve(&mut multi_surface, "tiles/a.gml");
+///
+/// assert!(report.is_complete());
+/// assert!(multi_surface.surface_member()[0].object().is_some());
+/// ```
+#[derive(Debug)]
+pub struct DocumentResolver<L> {
+    loader: L,
+    documents: HashMap<String, Result<IndexedDocument, String>>,
+    targets: HashMap<String, AbstractGeometryKind>,
+}
+
+/// A loaded document and the span of every element with a `gml:id`.
+#[derive(Debug)]
+struct IndexedDocument {
+    bytes: Vec<u8>,
+    elements: HashMap<String, IdentifiedElement>,
+}
+
+/// Element name (if it is a GML element) and byte span of an element with a
+/// `gml:id`.
+type IdentifiedElement = (Option<GmlElement>, Range<usize>);
+
+impl<L: ReferenceLoader> DocumentResolver<L> {
+    pub fn new(loader: L) -> Self {
+        Self {
+            loader,
+            documents: HashMap::new(),
+            targets: HashMap::new(),
+        }
+    }
+
+    pub fn loader(&self) -> &L {
+        &self.loader
+    }
+
+    /// Inlines the target of every reference within `root`, which was read
+    /// from `document`.
+    ///
+    /// Local `#id` references are looked up in `document` itself, which is
+    /// loaded through the loader like any other document.
+    pub fn resolve<T: VisitPropertiesMut + ?Sized>(
+        &mut self,
+        root: &mut T,
+        document: &str,
+    ) -> ResolutionReport {
+        resolve_references(root, Some(document), self)
+    }
+
+    /// Returns the indexed `document`, loading it on first use. Failures are
+    /// cached as well, so a missing document is only requested once.
+    fn document(&mut self, document: &str) -> Result<&IndexedDocument, String> {
+        let loader = &self.loader;
+        self.documents
+            .entry(document.to_string())
+            .or_insert_with(|| {
+                let bytes = loader.load(document).map_err(|e| e.to_string())?;
+                let bytes = decompress_document(&bytes).map_err(|e| e.to_string())?;
+                let bytes = decode_document(&bytes)
+                    .map_err(|e| e.to_string())?
+                    .into_owned();
+                let elements = index_ids(&bytes).map_err(|e| e.to_string())?;
+                Ok(IndexedDocument { bytes, elements })
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+}
+
+impl<L: ReferenceLoader> ReferenceTargets for DocumentResolver<L> {
+    fn target(
+        &mut self,
+        href: &HRef,
+        base: Option<&str>,
+    ) -> Result<ReferenceTarget, UnresolvedReason> {
+        let (path, id) = match href {
+            HRef::Local(id) => ("", id.as_str()),
+            HRef::Remote(uri) => uri.split_once('#').ok_or(UnresolvedReason::NotFound)?,
+        };
+        let document = join(base, path)?;
+        let key = format!("{document}#{id}");
+
+        if let Some(geometry) = self.targets.get(&key) {
+            return Ok(ReferenceTarget {
+                key,
+                base: Some(document),
+                geometry: geometry.clone(),
+            });
+        }
+
+        let indexed = self
+            .document(&document)
+            .map_err(|message| UnresolvedReason::LoadFailed { message })?;
+        let (element, span) = indexed.elements.get(id).ok_or(UnresolvedReason::NotFound)?;
+        let geometry = match element {
+            Some(element) => {
+                let xml_document = &indexed.bytes[span.clone()];
+                let spans = XmlElementSpans::single(*element, xml_document.len());
+                deserialize_abstract_geometry_kind(xml_document, &spans).map_err(|e| {
+                    UnresolvedReason::LoadFailed {
+                        message: e.to_string(),
+                    }
+                })?
+            }
+            None => None,
+        }
+        .ok_or(UnresolvedReason::TypeMismatch {
+            expected: "geometry",
+        })?;
+
+        self.targets.insert(key.clone(), geometry.clone());
+        Ok(ReferenceTarget {
+            key,
+            base: Some(document),
+            geometry,
+        })
+    }
+}
+
+/// Resolves `path` against the document `base`, collapsing `.` and `..`
+/// segments. An empty path refers to `base` itself; URIs with a scheme are
+/// left to the loader.
+fn join(base: Option<&str>, path: &str) -> Result<String, UnresolvedReason> {
+    if path.is_empty() {
+        return base.map(str::to_owned).ok_or(UnresolvedReason::NotFound);
+    }
+    if path.contains("://") || path.starts_with('/') {
+        return Ok(path.to_string());
+    }
+
+    let directory = base
+        .and_then(|b| b.rsplit_once('/'))
+        .map_or("", |(directory, _)| directory);
+    if directory.contains("://") {
+        return Ok(format!("{directory}/{path}"));
+    }
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in directory.split('/').chain(path.split('/')) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop().ok_or_else(|| UnresolvedReason::LoadFailed {
+                    message: format!("'{path}' leaves the document root"),
+                })?;
+            }
+            _ => segments.push(segment),
+        }
+    }
+    Ok(segments.join("/"))
+}
+
+/// Maps every `gml:id` in `xml_document` to its element and byte span. The
+/// first element wins if an id occurs twice.
+fn index_ids(xml_document: &[u8]) -> Result<HashMap<String, IdentifiedElement>, Error> {
+    let mut reader = Reader::from_reader(xml_document);
+    let mut open: Vec<Option<(String, Option<GmlElement>, usize)>> = Vec::new();
+    let mut elements = HashMap::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                // buffer_position() is right after `>`, so `<` is e.len() + 2 bytes back.
+                let start = reader.buffer_position() as usize - e.len() - 2;
+                open.push(gml_id(&e).map(|id| (id, element(&e), start)));
+            }
+            Event::End(_) => {
+                if let Some((id, element, start)) = open.pop().flatten() {
+                    let end = reader.buffer_position() as usize;
+                    elements.entry(id).or_insert((element, start..end));
+                }
+            }
+            Event::Empty(e) => {
+                if let Some(id) = gml_id(&e) {
+                    let end = reader.buffer_position() as usize;
+                    let start = end - e.len() - 3;
+                    elements.entry(id).or_insert((element(&e), start..end));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(elements)
+}
+
+fn gml_id(e: &BytesStart<'_>) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == b"id")
+        .map(|a| String::from_utf8_lossy(&a.value).into_owned())
+}
+
+fn element(e: &BytesStart<'_>) -> Option<GmlElement> {
+    GmlElement::from_local_name(e.local_name().as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolver::InMemoryLoader;
+    use egml_core::model::geometry::primitives::{AbstractSurfaceProperty, Shell};
+
+    const POLYGON: &str = "<gml:exterior><gml:LinearRing>\
+        <gml:posList>0 0 0 1 0 0 1 1 0 0 0 0</gml:posList>\
+        </gml:LinearRing></gml:exterior>";
+
+    fn loader() -> InMemoryLoader {
+        InMemoryLoader::new()
+            .with_document(
+                "tiles/a.gml",
+                format!(
+                    "<root><gml:Polygon gml:id=\"a1\">{POLYGON}</gml:Polygon>\
+                     <gml:Shell gml:id=\"s1\">\
+                     <gml:surfaceMember xlink:href=\"#a1\"/>\
+                     <gml:surfaceMember xlink:href=\"../shared/b.gml#b1\"/>\
+                     </gml:Shell></root>"
+                )
+                .into_bytes(),
+            )
+            .with_document(
+                "shared/b.gml",
+                format!(
+                    "<root><gml:Polygon gml:id=\"b1\">{POLYGON}</gml:Polygon>\
+                     <gml:Shell gml:id=\"loop\">\
+                     <gml:surfaceMember xlink:href=\"../tiles/c.gml#loop\"/>\
+                     </gml:Shell></root>"
+                )
+                .into_bytes(),
+            )
+            .with_document(
+                "tiles/c.gml",
+                "<root><gml:Shell gml:id=\"loop\">\
+                 <gml:surfaceMember xlink:href=\"../shared/b.gml#loop\"/>\
+                 </gml:Shell></root>"
+                    .as_bytes()
+                    .to_vec(),
+            )
+    }
+
+    #[test]
+    fn references_are_followed_across_documents() {
+        let mut resolver = DocumentResolver::new(loader());
+        let mut shell = Shell::new(vec![
+            AbstractSurfaceProperty::from_href(HRef::from_local("s1")),
+            AbstractSurfaceProperty::from_href(HRef::from_remote("../shared/b.gml#b1")),
+        ])
+        .unwrap();
+
+        let report = resolver.resolve(&mut shell, "tiles/a.gml");
+
+        assert!(report.is_complete(), "{:?}", report.unresolved());
+        // s1, its two members, and b1 from the second document.
+        assert_eq!(report.resolved(), 4);
+        assert_eq!(resolver.documents.len(), 2);
+    }
+
+    #[test]
+    fn cycles_across_documents_are_reported() {
+        let mut resolver = DocumentResolver::new(loader());
+        let mut shell = Shell::new(vec![AbstractSurfaceProperty::from_href(HRef::from_remote(
+            "c.gml#loop",
+        ))])
+        .unwrap();
+
+        let report = resolver.resolve(&mut shell, "tiles/a.gml");
+
+        assert_eq!(report.unresolved().len(), 1);
+        assert_eq!(report.unresolved()[0].reason, UnresolvedReason::Cycle);
+    }
+
+    #[test]
+    fn missing_documents_and_ids_are_reported() {
+        let mut resolver = DocumentResolver::new(loader());
+        let mut shell = Shell::new(vec![
+            AbstractSurfaceProperty::from_href(HRef::from_remote("missing.gml#x")),
+            AbstractSurfaceProperty::from_href(HRef::from_local("nope")),
+        ])
+        .unwrap();
+
+        let report = resolver.resolve(&mut shell, "tiles/a.gml");
+
+        assert!(matches!(
+            report.unresolved()[0].reason,
+            UnresolvedReason::LoadFailed { .. }
+        ));
+        assert_eq!(report.unresolved()[1].reason, UnresolvedReason::NotFound);
+    }
+
+    #[test]
+    fn join_collapses_relative_segments() {
+        assert_eq!(
+            join(Some("tiles/a.gml"), "../shared/./b.gml").unwrap(),
+            "shared/b.gml"
+        );
+        assert_eq!(join(Some("a.gml"), "").unwrap(), "a.gml");
+        assert!(join(Some("a.gml"), "../b.gml").is_err());
+    }
+}
@@ -0,0 +1,7 @@
+//! Resolution of `xlink:href` references that point into other documents.
+
+mod document_resolver;
+mod reference_loader;
+
+pub use document_resolver::*;
+pub use reference_loader::*;
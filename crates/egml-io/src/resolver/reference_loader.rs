@@ -0,0 +1,138 @@
+use crate::Error;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// Fetches the raw bytes of documents referenced via `xlink:href`.
+///
+/// `document` is the normalized name of the document as produced by
+/// [`DocumentResolver`](crate::resolver::DocumentResolver): a `/`-separated
+/// path relative to the document that contained the first reference, without
+/// the `#id` fragment. Parsing and caching are left to the resolver, so a
+/// loader is called at most once per document.
+pub trait ReferenceLoader {
+    /// # Errors
+    ///
+    /// Returns [`Error::ReferenceLoad`] if the document does not exist or may
+    /// not be read by this loader.
+    fn load(&self, document: &str) -> Result<Vec<u8>, Error>;
+}
+
+/// Loads documents from files below a root directory.
+///
+/// Document names are resolved against the root. Names that are absolute,
+/// contain a URI scheme or climb out of the root with `..` are rejected, as
+/// are files whose canonical path lies outside the root (e.g. via a
+/// symlink). Nothing is ever fetched over the network.
+#[derive(Debug, Clone)]
+pub struct FileSystemLoader {
+    root: PathBuf,
+}
+
+impl FileSystemLoader {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, document: &str) -> Result<PathBuf, String> {
+        if document.contains("://") {
+            return Err("only local files can be loaded".to_string());
+        }
+        let relative = Path::new(document);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return Err("path leaves the root directory".to_string());
+        }
+
+        let root = self.root.canonicalize().map_err(|e| e.to_string())?;
+        let path = root
+            .join(relative)
+            .canonicalize()
+            .map_err(|e| e.to_string())?;
+        if !path.starts_with(&root) {
+            return Err("path leaves the root directory".to_string());
+        }
+        Ok(path)
+    }
+}
+
+impl ReferenceLoader for FileSystemLoader {
+    fn load(&self, document: &str) -> Result<Vec<u8>, Error> {
+        self.path(document)
+            .and_then(|path| std::fs::read(path).map_err(|e| e.to_string()))
+            .map_err(|reason| Error::ReferenceLoad {
+                document: document.to_string(),
+                reason,
+            })
+    }
+}
+
+/// Serves documents from memory, keyed by their normalized name.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::resolver::{InMemoryLoader, ReferenceLoader};
+///
+/// let loader = InMemoryLoader::new().with_document("tiles/a.gml", b"<gml:Point/>".to_vec());
+///
+/// assert!(loader.load("tiles/a.gml").is_ok());
+/// assert!(loader.load("tiles/b.gml").is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryLoader {
+    documents: HashMap<String, Vec<u8>>,
+}
+
+impl InMemoryLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_document(mut self, document: impl Into<String>, bytes: Vec<u8>) -> Self {
+        self.insert(document, bytes);
+        self
+    }
+
+    /// Stores `bytes` under `document`, replacing any earlier content.
+    pub fn insert(&mut self, document: impl Into<String>, bytes: Vec<u8>) {
+        self.documents.insert(document.into(), bytes);
+    }
+}
+
+impl ReferenceLoader for InMemoryLoader {
+    fn load(&self, document: &str) -> Result<Vec<u8>, Error> {
+        self.documents
+            .get(document)
+            .cloned()
+            .ok_or_else(|| Error::ReferenceLoad {
+                document: document.to_string(),
+                reason: "no such document".to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_system_loader_stays_within_its_root() {
+        let root = std::env::temp_dir().join(format!("egml-loader-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("tiles")).unwrap();
+        std::fs::write(root.join("tiles/a.gml"), b"<gml:Point/>").unwrap();
+        let loader = FileSystemLoader::new(&root);
+
+        assert_eq!(loader.load("tiles/a.gml").unwrap(), b"<gml:Point/>");
+        assert!(loader.load("tiles/../tiles/a.gml").is_err());
+        assert!(loader.load("/etc/hosts").is_err());
+        assert!(loader.load("https://example.com/a.gml").is_err());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,13 @@
+//! STL export and import of triangle meshes, e.g. for 3D printing and
+//! facility management tools.
+//!
+//! STL holds unconnected triangles with a facet normal each. The writer
+//! triangulates surfaces and solids first, and the reader joins triangles
+//! at equal vertices into a `TriangulatedSurface`, or a `Solid` if they
+//! close a volume.
+
+mod reader;
+mod writer;
+
+pub use reader::*;
+pub use writer::*;
@@ -0,0 +1,243 @@
+use crate::Error;
+use crate::util::{IndexedMesh, xml_id};
+use egml_core::model::base::{AsAbstractGmlMut, Id};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometryMut, DirectPosition};
+
+/// Reads the meshes of an ASCII or binary STL file, telling them apart by
+/// the size that binary STL declares.
+///
+/// Each ASCII `solid` becomes a geometry whose `gml:id` is the name of the
+/// solid, if any; binary STL holds a single one. Triangles are joined at
+/// equal vertices, and a mesh is read as a `Solid` if it is closed and
+/// consistently oriented, and as a `TriangulatedSurface` otherwise. Facet
+/// normals are ignored in favour of the winding, and facets with repeated
+/// vertices are dropped.
+///
+/// # Errors
+///
+/// Returns [`Error::Stl`] if the input is malformed, and an error if a mesh
+/// has no triangles.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::common::{GeometryType, HasGeometryType};
+/// use egml_io::stl::read_stl;
+///
+/// let stl = "solid roof\n\
+///     facet normal 0 0 1\n outer loop\n\
+///       vertex 0 0 0\n vertex 1 0 0\n vertex 0 1 0\n\
+///     endloop\n endfacet\n\
+///     endsolid roof\n";
+/// let geometries = read_stl(stl.as_bytes()).unwrap();
+///
+/// assert_eq!(geometries.len(), 1);
+/// assert_eq!(geometries[0].geometry_type(), GeometryType::TriangulatedSurface);
+/// ```
+pub fn read_stl(bytes: &[u8]) -> Result<Vec<AbstractGeometryKind>, Error> {
+    if let Some(count) = bytes.get(80..84) {
+        let count = u32::from_le_bytes(count.try_into().expect("four bytes")) as usize;
+        if count.checked_mul(50).and_then(|x| x.checked_add(84)) == Some(bytes.len()) {
+            return read_binary(&bytes[84..]);
+        }
+    }
+    if bytes.trim_ascii_start().starts_with(b"solid") {
+        let text = std::str::from_utf8(bytes)
+            .map_err(|_| Error::Stl("ASCII STL is not valid UTF-8".to_string()))?;
+        return read_ascii(text);
+    }
+    Err(Error::Stl(
+        "the input is neither ASCII STL nor binary STL of the declared size".to_string(),
+    ))
+}
+
+fn read_binary(facets: &[u8]) -> Result<Vec<AbstractGeometryKind>, Error> {
+    let mut mesh = IndexedMesh::default();
+    for facet in facets.chunks_exact(50) {
+        // The facet normal in the first twelve bytes is ignored.
+        let values: Vec<f64> = facet[12..48]
+            .chunks_exact(4)
+            .map(|x| f64::from(f32::from_le_bytes(x.try_into().expect("four bytes"))))
+            .collect();
+        let mut indices = [0; 3];
+        for (corner, coords) in values.chunks_exact(3).enumerate() {
+            indices[corner] =
+                mesh.push_vertex(DirectPosition::new(coords[0], coords[1], coords[2])?);
+        }
+        mesh.push_polygon(&indices);
+    }
+    Ok(vec![mesh.into_geometry()?])
+}
+
+fn read_ascii(text: &str) -> Result<Vec<AbstractGeometryKind>, Error> {
+    let mut geometries = Vec::new();
+    let mut solid: Option<(&str, IndexedMesh)> = None;
+    let mut facet = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        match keyword {
+            "solid" => {
+                if solid.is_some() {
+                    return Err(Error::Stl("a solid starts inside another".to_string()));
+                }
+                solid = Some((rest.trim(), IndexedMesh::default()));
+            }
+            "vertex" => {
+                let Some((_, mesh)) = &mut solid else {
+                    return Err(Error::Stl("a vertex lies outside of a solid".to_string()));
+                };
+                let coords = rest
+                    .split_whitespace()
+                    .map(|x| {
+                        x.parse::<f64>()
+                            .map_err(|_| Error::Stl(format!("invalid vertex '{line}'")))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let [x, y, z] =
+                    <[f64; 3]>::try_from(coords).map_err(|x| Error::UnsupportedDimension {
+                        found: x.len() as u32,
+                    })?;
+                facet.push(mesh.push_vertex(DirectPosition::new(x, y, z)?));
+            }
+            "endloop" => {
+                let Some((_, mesh)) = &mut solid else {
+                    return Err(Error::Stl("a loop lies outside of a solid".to_string()));
+                };
+                if facet.len() < 3 {
+                    return Err(Error::Stl(format!(
+                        "a loop has {} vertices instead of three",
+                        facet.len()
+                    )));
+                }
+                mesh.push_polygon(&facet);
+                facet.clear();
+            }
+            "endsolid" => {
+                let (name, mesh) = solid
+                    .take()
+                    .ok_or_else(|| Error::Stl("endsolid without solid".to_string()))?;
+                geometries.push(named_geometry(name, mesh)?);
+            }
+            "facet" | "endfacet" | "outer" | "" => {}
+            _ => return Err(Error::Stl(format!("unexpected line '{line}'"))),
+        }
+    }
+    // Some writers omit the final endsolid.
+    if let Some((name, mesh)) = solid {
+        geometries.push(named_geometry(name, mesh)?);
+    }
+    Ok(geometries)
+}
+
+/// The geometry of `mesh`, with the `solid` name made a valid `gml:id` as
+/// the id.
+fn named_geometry(name: &str, mesh: IndexedMesh) -> Result<AbstractGeometryKind, Error> {
+    let mut geometry = mesh.into_geometry()?;
+    if !name.is_empty() {
+        geometry
+            .abstract_geometry_mut()
+            .abstract_gml
+            .set_id(Id::try_from(xml_id(name))?);
+    }
+    Ok(geometry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stl::{StlEncoding, StlWriter};
+    use crate::wkt::read_wkt;
+    use egml_core::model::base::AsAbstractGml;
+    use egml_core::model::common::{GeometryType, HasGeometryType};
+    use egml_core::model::geometry::AsAbstractGeometry;
+    use egml_core::model::geometry::primitives::Solid;
+
+    fn cube() -> AbstractGeometryKind {
+        read_wkt(
+            "POLYHEDRALSURFACE Z (\
+            ((0 0 0, 0 1 0, 1 1 0, 1 0 0, 0 0 0)), ((0 0 1, 1 0 1, 1 1 1, 0 1 1, 0 0 1)), \
+            ((0 0 0, 1 0 0, 1 0 1, 0 0 1, 0 0 0)), ((1 0 0, 1 1 0, 1 1 1, 1 0 1, 1 0 0)), \
+            ((1 1 0, 0 1 0, 0 1 1, 1 1 1, 1 1 0)), ((0 1 0, 0 0 0, 0 0 1, 0 1 1, 0 1 0)))",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn written_cubes_are_read_as_solids() {
+        for encoding in [StlEncoding::Ascii, StlEncoding::Binary] {
+            let mut stl = Vec::new();
+            StlWriter::new()
+                .with_encoding(encoding)
+                .write(&[cube()], &mut stl)
+                .unwrap();
+
+            let geometries = read_stl(&stl).unwrap();
+
+            assert_eq!(geometries.len(), 1);
+            let solid = Solid::try_from(geometries[0].clone()).unwrap();
+            assert_eq!(solid.points().len(), 12 * 3, "{encoding:?}");
+        }
+    }
+
+    #[test]
+    fn ascii_solids_are_named_and_open_meshes_are_surfaces() {
+        let stl = "solid a\nfacet normal 0 0 0\nouter loop\nvertex 0 0 0\nvertex 1 0 0\n\
+                   vertex 0 1 0\nendloop\nendfacet\nendsolid a\n\
+                   solid\nfacet normal 0 0 0\nouter loop\nvertex 0 0 0\nvertex 0 0 0\n\
+                   vertex 0 1 0\nendloop\nendfacet\nfacet normal 0 0 0\nouter loop\n\
+                   vertex 0 0 1\nvertex 1 0 1\nvertex 0 1 1\nendloop\nendfacet\n";
+
+        let geometries = read_stl(stl.as_bytes()).unwrap();
+
+        assert_eq!(geometries.len(), 2);
+        assert_eq!(
+            geometries[0]
+                .abstract_geometry()
+                .abstract_gml
+                .id()
+                .unwrap()
+                .as_str(),
+            "a"
+        );
+        assert!(
+            geometries[1]
+                .abstract_geometry()
+                .abstract_gml
+                .id()
+                .is_none()
+        );
+        for geometry in &geometries {
+            assert_eq!(geometry.geometry_type(), GeometryType::TriangulatedSurface);
+        }
+    }
+
+    #[test]
+    fn solid_names_are_made_valid_ids() {
+        let facet = "facet normal 0 0 0\nouter loop\nvertex 0 0 0\nvertex 1 0 0\n\
+                     vertex 0 1 0\nendloop\nendfacet\n";
+        let stl = format!("solid my part\n{facet}endsolid my part\nsolid 3d/print\n{facet}");
+
+        let ids: Vec<_> = read_stl(stl.as_bytes())
+            .unwrap()
+            .iter()
+            .map(|x| x.abstract_geometry().abstract_gml.id().unwrap().to_string())
+            .collect();
+
+        assert_eq!(ids, ["my_part", "_3d_print"]);
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        for stl in [
+            "not stl",
+            "solid\nvertex 0 0\n",
+            "solid\nouter loop\nvertex 0 0 0\nendloop\n",
+            "solid\nsolid\n",
+            "endsolid\n",
+        ] {
+            assert!(read_stl(stl.as_bytes()).is_err(), "{stl}");
+        }
+    }
+}
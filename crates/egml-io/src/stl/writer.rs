@@ -0,0 +1,244 @@
+use crate::Error;
+use crate::util::{WriteOptions, unsigned_zero};
+use egml_core::model::base::AsAbstractGml;
+use egml_core::model::common::{Triangulate, Triangulation};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometry, DirectPosition};
+use std::io::Write;
+
+/// The 80 bytes of the binary header, which must not start with `solid`.
+const BINARY_HEADER: &[u8] = b"Written by egml";
+
+/// The encoding of an STL file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StlEncoding {
+    /// Binary STL, with coordinates as `f32`, a fifth of the size of ASCII.
+    #[default]
+    Binary,
+    /// ASCII STL, with a `solid` per geometry.
+    Ascii,
+}
+
+/// Writes geometries as STL, usually the `Solid`s or `Shell`s of a
+/// building, triangulated with [`Triangulate`].
+///
+/// Each facet normal is derived from the counter-clockwise winding of its
+/// triangle, which for a GML solid points outwards. In ASCII, each geometry
+/// is a `solid` named after its `gml:id`, or `geometry_<n>` for the `n`-th
+/// geometry without one. Binary STL has no names and holds all triangles
+/// in one mesh.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::stl::{StlEncoding, StlWriter};
+/// use egml_io::wkt::read_wkt;
+///
+/// let triangle = read_wkt("TIN Z (((0 0 0, 1 0 0, 0 1 0, 0 0 0)))").unwrap();
+///
+/// let mut stl = Vec::new();
+/// StlWriter::new()
+///     .with_encoding(StlEncoding::Ascii)
+///     .write(&[triangle], &mut stl)
+///     .unwrap();
+///
+/// let stl = String::from_utf8(stl).unwrap();
+/// assert!(stl.starts_with("solid geometry_1\n  facet normal 0 0 1\n"));
+/// assert!(stl.ends_with("endsolid geometry_1\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StlWriter {
+    encoding: StlEncoding,
+    origin: Option<DirectPosition>,
+}
+
+impl StlWriter {
+    /// Creates a writer of binary STL that writes coordinates unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_encoding(mut self, encoding: StlEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Subtracts `origin` from every vertex. Binary STL stores vertices as
+    /// `f32`, which keeps only centimetres of UTM coordinates, so models
+    /// should be moved close to the origin. STL has no place to record it.
+    pub fn with_origin(mut self, origin: DirectPosition) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Writes `geometries` to `out`. ASCII coordinates are formatted
+    /// according to the current [`WriteOptions`](crate::util::WriteOptions).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a geometry cannot be triangulated at all, e.g. a
+    /// `MultiCurve`, [`Error::Unrepresentable`] if binary STL would hold more
+    /// than `u32::MAX` triangles, and [`Error::Io`] if writing fails.
+    pub fn write<'a, W: Write>(
+        &self,
+        geometries: impl IntoIterator<Item = &'a AbstractGeometryKind>,
+        out: W,
+    ) -> Result<(), Error> {
+        let offset = self.origin.map_or([0.0; 3], |x| x.coords());
+        let solids = geometries
+            .into_iter()
+            .enumerate()
+            .map(|(index, geometry)| {
+                let name = match geometry.abstract_geometry().abstract_gml.id() {
+                    Some(id) => id.as_str().split_whitespace().collect::<Vec<_>>().join("_"),
+                    None => format!("geometry_{}", index + 1),
+                };
+                Ok((name, facets(&geometry.triangulate()?, offset)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        match self.encoding {
+            StlEncoding::Ascii => write_ascii(&solids, out),
+            StlEncoding::Binary => write_binary(&solids, out),
+        }
+    }
+}
+
+/// A facet normal and the three corners of a triangle.
+type Facet = ([f64; 3], [[f64; 3]; 3]);
+
+fn facets(triangulation: &Triangulation, offset: [f64; 3]) -> Vec<Facet> {
+    triangulation
+        .surface()
+        .triangles()
+        .into_iter()
+        .map(|x| {
+            let corners = [x.a(), x.b(), x.c()].map(|x| {
+                let coords = x.coords();
+                std::array::from_fn(|axis| coords[axis] - offset[axis])
+            });
+            (normal(&corners), corners)
+        })
+        .collect()
+}
+
+fn write_ascii<W: Write>(solids: &[(String, Vec<Facet>)], mut out: W) -> Result<(), Error> {
+    let options = WriteOptions::current();
+    for (name, facets) in solids {
+        writeln!(out, "solid {name}")?;
+        for (normal, corners) in facets {
+            let [x, y, z] = normal;
+            writeln!(out, "  facet normal {x} {y} {z}")?;
+            writeln!(out, "    outer loop")?;
+            for corner in corners {
+                let [x, y, z] =
+                    std::array::from_fn(|axis| options.format_ordinate(axis, corner[axis]));
+                writeln!(out, "      vertex {x} {y} {z}")?;
+            }
+            writeln!(out, "    endloop")?;
+            writeln!(out, "  endfacet")?;
+        }
+        writeln!(out, "endsolid {name}")?;
+    }
+    Ok(())
+}
+
+fn write_binary<W: Write>(solids: &[(String, Vec<Facet>)], mut out: W) -> Result<(), Error> {
+    let count = solids.iter().map(|(_, x)| x.len()).sum::<usize>();
+    let count = u32::try_from(count).map_err(|_| Error::Unrepresentable {
+        format: "binary STL",
+        reason: format!("{count} triangles"),
+    })?;
+
+    let mut header = [b' '; 80];
+    header[..BINARY_HEADER.len()].copy_from_slice(BINARY_HEADER);
+    out.write_all(&header)?;
+    out.write_all(&count.to_le_bytes())?;
+    for (normal, corners) in solids.iter().flat_map(|(_, x)| x) {
+        for value in std::iter::once(normal).chain(corners).flatten() {
+            out.write_all(&(*value as f32).to_le_bytes())?;
+        }
+        // The attribute byte count, which is unused.
+        out.write_all(&0u16.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// The unit normal of the counter-clockwise triangle `corners`, or zero if
+/// it has no area, which STL readers take as a request to compute it.
+fn normal([a, b, c]: &[[f64; 3]; 3]) -> [f64; 3] {
+    let u: [f64; 3] = std::array::from_fn(|axis| b[axis] - a[axis]);
+    let v: [f64; 3] = std::array::from_fn(|axis| c[axis] - a[axis]);
+    let n = [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ];
+    let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    if length > 0.0 {
+        n.map(|x| unsigned_zero(x / length))
+    } else {
+        [0.0; 3]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt::read_wkt;
+    use egml_core::model::base::{AsAbstractGmlMut, Id};
+    use egml_core::model::geometry::AsAbstractGeometryMut;
+
+    fn cube() -> AbstractGeometryKind {
+        read_wkt(
+            "POLYHEDRALSURFACE Z (\
+            ((0 0 0, 0 1 0, 1 1 0, 1 0 0, 0 0 0)), ((0 0 1, 1 0 1, 1 1 1, 0 1 1, 0 0 1)), \
+            ((0 0 0, 1 0 0, 1 0 1, 0 0 1, 0 0 0)), ((1 0 0, 1 1 0, 1 1 1, 1 0 1, 1 0 0)), \
+            ((1 1 0, 0 1 0, 0 1 1, 1 1 1, 1 1 0)), ((0 1 0, 0 0 0, 0 0 1, 0 1 1, 0 1 0)))",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn ascii_solids_have_outward_normals() {
+        let mut cube = cube();
+        cube.abstract_geometry_mut()
+            .abstract_gml
+            .set_id(Id::try_from("building 1").unwrap());
+
+        let mut stl = Vec::new();
+        StlWriter::new()
+            .with_encoding(StlEncoding::Ascii)
+            .write(&[cube], &mut stl)
+            .unwrap();
+        let stl = String::from_utf8(stl).unwrap();
+
+        assert!(stl.starts_with("solid building_1\n"));
+        let normals: Vec<&str> = stl
+            .lines()
+            .filter_map(|x| x.trim().strip_prefix("facet normal "))
+            .collect();
+        assert_eq!(normals.len(), 12);
+        for normal in ["0 0 -1", "0 0 1", "0 -1 0", "1 0 0", "0 1 0", "-1 0 0"] {
+            assert_eq!(normals.iter().filter(|x| **x == normal).count(), 2);
+        }
+    }
+
+    #[test]
+    fn binary_files_have_a_header_count_and_facets() {
+        let origin = DirectPosition::new(1.0, 1.0, 1.0).unwrap();
+
+        let mut stl = Vec::new();
+        StlWriter::new()
+            .with_origin(origin)
+            .write(&[cube(), cube()], &mut stl)
+            .unwrap();
+
+        assert_eq!(stl.len(), 84 + 24 * 50);
+        assert!(stl.starts_with(BINARY_HEADER));
+        assert_eq!(u32::from_le_bytes(stl[80..84].try_into().unwrap()), 24);
+        let value = |offset: usize| f32::from_le_bytes(stl[offset..offset + 4].try_into().unwrap());
+        // The first facet lies on the bottom face, moved by the origin.
+        assert_eq!([value(84), value(88), value(92)], [0.0, 0.0, -1.0]);
+        assert_eq!(value(96 + 8), -1.0);
+    }
+}
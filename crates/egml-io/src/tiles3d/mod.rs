@@ -0,0 +1,11 @@
+//! OGC 3D Tiles 1.1 tilesets of triangulated geometries, e.g. for CesiumJS.
+//!
+//! The geometries are split into a quadtree of tiles with a GLB content per
+//! leaf, written by [`GlbWriter`](crate::gltf::GlbWriter). The tileset is
+//! placed on the globe in earth-centred, earth-fixed coordinates, which
+//! requires the source CRS to be one of [`PlacementCrs`].
+
+mod tileset;
+
+pub use crate::util::PlacementCrs;
+pub use tileset::*;
@@ -0,0 +1,490 @@
+use crate::Error;
+use crate::gltf::GlbWriter;
+use crate::util::{PlacementCrs, ecef_to_geodetic};
+use egml_core::model::base::{AsAbstractGml, Id};
+use egml_core::model::common::{Triangulate, Triangulation};
+use egml_core::model::geometry::primitives::{Triangle, TriangulatedSurface};
+use egml_core::model::geometry::{
+    AbstractGeometryKind, AsAbstractGeometry, DirectPosition, Envelope,
+};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+
+const FORMAT: &str = "3D Tiles";
+
+/// Generates a 3D Tiles 1.1 tileset with a glTF content per leaf tile, e.g.
+/// for CesiumJS.
+///
+/// The geometries are triangulated and moved into a local east-north-up
+/// frame at the centre of the dataset, which the root tile's `transform`
+/// places on the globe. Tiles are split into quadrants on the horizontal
+/// plane until no tile holds more than
+/// [`with_max_features_per_tile`](Self::with_max_features_per_tile)
+/// geometries. Each geometry lies in the tile holding the centre of its
+/// envelope, as a node named after its `gml:id`, and parent tiles have no
+/// content of their own. A geometry all of whose triangles degenerate in the
+/// local frame, e.g. one much smaller than the precision of ECEF
+/// coordinates, is left out.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::tiles3d::TilesetWriter;
+/// use egml_io::wkt::read_wkt;
+///
+/// let square = read_wkt("SRID=25832;POLYGON Z ((691000 5336000 400, \
+///     691001 5336000 400, 691001 5336001 400, 691000 5336001 400, \
+///     691000 5336000 400))")
+/// .unwrap();
+///
+/// let tileset = TilesetWriter::new().generate(&[square]).unwrap();
+///
+/// assert_eq!(tileset.tileset["asset"]["version"], "1.1");
+/// assert_eq!(tileset.tileset["root"]["content"]["uri"], "tiles/0.glb");
+/// assert_eq!(tileset.contents[0].0, "tiles/0.glb");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TilesetWriter {
+    max_features_per_tile: usize,
+    max_depth: u32,
+    srs_name: Option<String>,
+    height_offset: f64,
+}
+
+impl Default for TilesetWriter {
+    fn default() -> Self {
+        Self {
+            max_features_per_tile: 64,
+            max_depth: 8,
+            srs_name: None,
+            height_offset: 0.0,
+        }
+    }
+}
+
+impl TilesetWriter {
+    /// Creates a writer that splits tiles holding more than 64 geometries,
+    /// at most eight levels deep, and takes the CRS from the `srsName` of the
+    /// first geometry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_features_per_tile(mut self, max_features_per_tile: usize) -> Self {
+        self.max_features_per_tile = max_features_per_tile.max(1);
+        self
+    }
+
+    /// Limits the depth of the tile tree, the root being at depth zero.
+    /// Tiles at `max_depth` are not split however many geometries they hold.
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Uses the CRS `srs_name` for all geometries, e.g. for geometries
+    /// without an `srsName`. See [`PlacementCrs`] for the supported CRSs.
+    pub fn with_srs_name(mut self, srs_name: impl Into<String>) -> Self {
+        self.srs_name = Some(srs_name.into());
+        self
+    }
+
+    /// Adds `height_offset` to every height before placement. Heights are
+    /// placed as ellipsoidal heights, so for heights above sea level this
+    /// should be the geoid undulation at the dataset, e.g. about 48 metres
+    /// in southern Germany. It is ignored for ECEF coordinates.
+    pub fn with_height_offset(mut self, height_offset: f64) -> Self {
+        self.height_offset = height_offset;
+        self
+    }
+
+    /// Returns the tileset of `geometries`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Unrepresentable`] if there is no CRS or it is not
+    /// supported, or if there are no geometries, and an error if a geometry
+    /// cannot be triangulated at all, e.g. a `MultiCurve`.
+    pub fn generate<'a>(
+        &self,
+        geometries: impl IntoIterator<Item = &'a AbstractGeometryKind>,
+    ) -> Result<Tileset, Error> {
+        let geometries: Vec<&AbstractGeometryKind> = geometries.into_iter().collect();
+        let crs = self.placement_crs(&geometries)?;
+
+        let mut ecef_triangulations = Vec::with_capacity(geometries.len());
+        let mut lower = [f64::INFINITY; 3];
+        let mut upper = [f64::NEG_INFINITY; 3];
+        for geometry in &geometries {
+            let triangulation = geometry.triangulate()?;
+            let triangles: Vec<[[f64; 3]; 3]> = triangulation
+                .surface()
+                .triangles()
+                .into_iter()
+                .map(|x| {
+                    [x.a(), x.b(), x.c()].map(|x| {
+                        let mut coords = x.coords();
+                        if crs != PlacementCrs::Ecef {
+                            coords[2] += self.height_offset;
+                        }
+                        crs.to_ecef(coords)
+                    })
+                })
+                .collect();
+            for position in triangles.iter().flatten() {
+                for axis in 0..3 {
+                    lower[axis] = lower[axis].min(position[axis]);
+                    upper[axis] = upper[axis].max(position[axis]);
+                }
+            }
+            let id = geometry.abstract_geometry().abstract_gml.id();
+            ecef_triangulations.push((id, triangles, triangulation.into_parts().1));
+        }
+        let center: [f64; 3] = std::array::from_fn(|axis| (lower[axis] + upper[axis]) / 2.0);
+        let frame = EastNorthUp::new(center);
+        let mut features = Vec::with_capacity(ecef_triangulations.len());
+        for (id, triangles, mut skipped) in ecef_triangulations {
+            let mut local = Vec::with_capacity(triangles.len());
+            let mut positions = Vec::with_capacity(3 * triangles.len());
+            for corners in triangles {
+                let [a, b, c] = corners.map(|x| {
+                    let [x, y, z] = frame.to_local(x);
+                    DirectPosition::new(x, y, z)
+                });
+                let (a, b, c) = (a?, b?, c?);
+                match Triangle::from_points(a, b, c) {
+                    Ok(triangle) => {
+                        positions.extend([a, b, c]);
+                        local.push(triangle);
+                    }
+                    Err(error) => skipped.push(error),
+                }
+            }
+            if local.is_empty() {
+                continue;
+            }
+            let envelope = Envelope::from_points(&positions)?;
+            let triangulation =
+                Triangulation::new(TriangulatedSurface::from_triangles(local)?, skipped);
+            features.push(Feature {
+                id,
+                triangulation,
+                envelope,
+            });
+        }
+
+        if features.is_empty() {
+            return Err(Error::Unrepresentable {
+                format: FORMAT,
+                reason: "there are no geometries to tile".to_string(),
+            });
+        }
+
+        let indices: Vec<usize> = (0..features.len()).collect();
+        let geometric_error = diagonal(&tile_envelope(&features, &indices));
+        let mut contents = Vec::new();
+        let mut root = self.tile(&features, indices, 0, "0", &mut contents)?;
+        root["transform"] = json!(frame.transform());
+        root["refine"] = json!("ADD");
+
+        Ok(Tileset {
+            tileset: json!({
+                "asset": {"version": "1.1", "generator": "egml"},
+                "geometricError": geometric_error,
+                "root": root,
+            }),
+            contents,
+        })
+    }
+
+    fn placement_crs(&self, geometries: &[&AbstractGeometryKind]) -> Result<PlacementCrs, Error> {
+        let srs_name = self
+            .srs_name
+            .as_deref()
+            .or_else(|| {
+                geometries
+                    .first()
+                    .and_then(|x| x.abstract_geometry().srs_name())
+                    .map(String::as_str)
+            })
+            .ok_or_else(|| Error::Unrepresentable {
+                format: FORMAT,
+                reason: "the CRS is unknown without an srsName".to_string(),
+            })?;
+        PlacementCrs::from_srs_name(srs_name).ok_or_else(|| Error::Unrepresentable {
+            format: FORMAT,
+            reason: format!("the CRS '{srs_name}' cannot be placed on the globe"),
+        })
+    }
+
+    /// Returns the tile of the features at `indices`, appending the
+    /// contents of it and its descendants to `contents`.
+    fn tile(
+        &self,
+        features: &[Feature],
+        indices: Vec<usize>,
+        depth: u32,
+        address: &str,
+        contents: &mut Vec<(String, Vec<u8>)>,
+    ) -> Result<Value, Error> {
+        let envelope = tile_envelope(features, &indices);
+        let center = envelope.center().coords();
+        let [hx, hy, hz] = [
+            envelope.size_x() / 2.0,
+            envelope.size_y() / 2.0,
+            envelope.size_z() / 2.0,
+        ];
+        let mut tile = json!({
+            "boundingVolume": {"box": [
+                center[0], center[1], center[2],
+                hx, 0.0, 0.0,
+                0.0, hy, 0.0,
+                0.0, 0.0, hz,
+            ]},
+        });
+
+        if indices.len() > self.max_features_per_tile && depth < self.max_depth {
+            let mut quadrants: [Vec<usize>; 4] = Default::default();
+            for index in indices.iter().copied() {
+                let [x, y, _] = features[index].envelope.center().coords();
+                let quadrant = usize::from(x >= center[0]) + 2 * usize::from(y >= center[1]);
+                quadrants[quadrant].push(index);
+            }
+            // Features whose centres coincide cannot be separated, so their
+            // tile stays a leaf.
+            if quadrants.iter().filter(|x| !x.is_empty()).count() > 1 {
+                let mut children = Vec::new();
+                for (quadrant, indices) in quadrants.into_iter().enumerate() {
+                    if !indices.is_empty() {
+                        let address = format!("{address}_{quadrant}");
+                        children.push(self.tile(
+                            features,
+                            indices,
+                            depth + 1,
+                            &address,
+                            contents,
+                        )?);
+                    }
+                }
+                tile["geometricError"] = json!(diagonal(&envelope));
+                tile["children"] = json!(children);
+                return Ok(tile);
+            }
+        }
+
+        let triangulations: Vec<(Option<&Id>, Triangulation)> = indices
+            .iter()
+            .map(|x| (features[*x].id, features[*x].triangulation.clone()))
+            .collect();
+        let mut glb = Vec::new();
+        GlbWriter::new()
+            .with_origin(DirectPosition::ORIGIN)
+            .write_triangulations(&triangulations, &mut glb)?;
+        let uri = format!("tiles/{address}.glb");
+        tile["geometricError"] = json!(0.0);
+        tile["content"] = json!({"uri": uri});
+        contents.push((uri, glb));
+        Ok(tile)
+    }
+}
+
+/// A generated tileset: the `tileset.json` document and the GLB content
+/// of each leaf tile, keyed by its URI relative to `tileset.json`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tileset {
+    pub tileset: Value,
+    pub contents: Vec<(String, Vec<u8>)>,
+}
+
+impl Tileset {
+    /// Writes `tileset.json` and the contents below `directory`, creating
+    /// the directories as needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if writing fails.
+    pub fn write_to_directory(&self, directory: impl AsRef<Path>) -> Result<(), Error> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+        fs::write(
+            directory.join("tileset.json"),
+            serde_json::to_vec_pretty(&self.tileset)?,
+        )?;
+        for (uri, content) in &self.contents {
+            let path = directory.join(uri);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, content)?;
+        }
+        Ok(())
+    }
+}
+
+/// A triangulated geometry in the local frame.
+struct Feature<'a> {
+    id: Option<&'a Id>,
+    triangulation: Triangulation,
+    envelope: Envelope,
+}
+
+/// The east-north-up frame tangent to the ellipsoid below an ECEF origin.
+struct EastNorthUp {
+    origin: [f64; 3],
+    east: [f64; 3],
+    north: [f64; 3],
+    up: [f64; 3],
+}
+
+impl EastNorthUp {
+    fn new(origin: [f64; 3]) -> Self {
+        let [latitude, longitude, _] = ecef_to_geodetic(origin);
+        let (sin_lat, cos_lat) = latitude.sin_cos();
+        let (sin_lon, cos_lon) = longitude.sin_cos();
+        Self {
+            origin,
+            east: [-sin_lon, cos_lon, 0.0],
+            north: [-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat],
+            up: [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat],
+        }
+    }
+
+    fn to_local(&self, ecef: [f64; 3]) -> [f64; 3] {
+        let d: [f64; 3] = std::array::from_fn(|axis| ecef[axis] - self.origin[axis]);
+        [self.east, self.north, self.up].map(|x| x[0] * d[0] + x[1] * d[1] + x[2] * d[2])
+    }
+
+    /// The column-major matrix from the local frame to ECEF.
+    fn transform(&self) -> [f64; 16] {
+        let [e, n, u, o] = [self.east, self.north, self.up, self.origin];
+        [
+            e[0], e[1], e[2], 0.0, //
+            n[0], n[1], n[2], 0.0, //
+            u[0], u[1], u[2], 0.0, //
+            o[0], o[1], o[2], 1.0,
+        ]
+    }
+}
+
+fn tile_envelope(features: &[Feature], indices: &[usize]) -> Envelope {
+    let envelopes: Vec<Envelope> = indices
+        .iter()
+        .map(|x| features[*x].envelope.clone())
+        .collect();
+    Envelope::from_envelopes(&envelopes).expect("tiles hold at least one feature")
+}
+
+/// The length of the diagonal of `envelope`, used as the geometric error of
+/// a tile whose content is only shown by its children.
+fn diagonal(envelope: &Envelope) -> f64 {
+    let [x, y, z] = [envelope.size_x(), envelope.size_y(), envelope.size_z()];
+    (x * x + y * y + z * z).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::PlacementCrs;
+    use crate::wkt::read_wkt;
+
+    fn squares(count: usize) -> Vec<AbstractGeometryKind> {
+        (0..count)
+            .map(|i| {
+                let (x, y) = (691_000 + 100 * (i % 4), 5_336_000 + 100 * (i / 4));
+                read_wkt(&format!(
+                    "SRID=25832;POLYGON Z (({x} {y} 400, {} {y} 400, {} {} 400, {x} {} 400, \
+                     {x} {y} 400))",
+                    x + 10,
+                    x + 10,
+                    y + 10,
+                    y + 10
+                ))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn leaves(tile: &Value) -> Vec<&Value> {
+        match tile["children"].as_array() {
+            Some(children) => children.iter().flat_map(leaves).collect(),
+            None => vec![tile],
+        }
+    }
+
+    #[test]
+    fn crowded_tiles_are_split() {
+        let tileset = TilesetWriter::new()
+            .with_max_features_per_tile(2)
+            .generate(&squares(8))
+            .unwrap();
+        let root = &tileset.tileset["root"];
+
+        assert!(root.get("content").is_none());
+        assert!(root["geometricError"].as_f64().unwrap() > 0.0);
+        let leaves = leaves(root);
+        assert!(leaves.len() > 1);
+        assert_eq!(leaves.len(), tileset.contents.len());
+        for (leaf, (uri, glb)) in leaves.iter().zip(&tileset.contents) {
+            assert_eq!(leaf["content"]["uri"], uri.as_str());
+            assert_eq!(leaf["geometricError"], 0.0);
+            assert_eq!(&glb[..4], b"glTF");
+        }
+    }
+
+    #[test]
+    fn the_root_is_placed_at_the_centre_in_ecef() {
+        let tileset = TilesetWriter::new().generate(&squares(1)).unwrap();
+        let transform = tileset.tileset["root"]["transform"].as_array().unwrap();
+
+        let center = PlacementCrs::Utm {
+            zone: 32,
+            south: false,
+        }
+        .to_ecef([691_005.0, 5_336_005.0, 400.0]);
+        for axis in 0..3 {
+            assert!((transform[12 + axis].as_f64().unwrap() - center[axis]).abs() < 1e-3);
+        }
+        // The local frame is centred on the square, which is level. Grid
+        // north deviates from true north by about two degrees there, so the
+        // box is slightly wider than the square.
+        let bounding_box = tileset.tileset["root"]["boundingVolume"]["box"]
+            .as_array()
+            .unwrap();
+        assert!(bounding_box[0].as_f64().unwrap().abs() < 1e-6);
+        assert!((5.0..5.5).contains(&bounding_box[3].as_f64().unwrap()));
+        assert!(bounding_box[11].as_f64().unwrap() < 0.1);
+    }
+
+    #[test]
+    fn unknown_crs_are_rejected() {
+        let square = read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1 1 0, 0 0 0))").unwrap();
+
+        assert!(matches!(
+            TilesetWriter::new().generate([&square]),
+            Err(Error::Unrepresentable { .. })
+        ));
+        assert!(matches!(
+            TilesetWriter::new()
+                .with_srs_name("EPSG:31467")
+                .generate([&square]),
+            Err(Error::Unrepresentable { .. })
+        ));
+    }
+
+    #[test]
+    fn geometries_that_degenerate_in_the_local_frame_are_left_out() {
+        let mut geometries = squares(1);
+        geometries.push(
+            read_wkt(
+                "SRID=25832;POLYGON Z ((691000 5336000 400, 691000.0000000002 5336000 400, \
+                 691000 5336000.000000001 400, 691000 5336000 400))",
+            )
+            .unwrap(),
+        );
+
+        let tileset = TilesetWriter::new().generate(&geometries).unwrap();
+
+        assert_eq!(tileset.contents.len(), 1);
+    }
+}
@@ -0,0 +1,330 @@
+use crate::Error;
+use flate2::Compression as GzipLevel;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::borrow::Cow;
+use std::io::{Read, Seek, Write};
+use zip::ZipArchive;
+use zip::write::SimpleFileOptions;
+
+/// The two bytes every gzip stream starts with.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Compression applied to a written document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Plain XML.
+    #[default]
+    None,
+    /// A gzip stream, as in `.gml.gz` files.
+    Gzip,
+}
+
+/// Returns `bytes` decompressed if they are a gzip stream, and borrowed
+/// otherwise. Concatenated gzip members are read as one document.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] if the gzip stream is corrupt or truncated.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::util::decompress_document;
+///
+/// assert_eq!(decompress_document(b"<gml:Point/>").unwrap().as_ref(), b"<gml:Point/>");
+/// ```
+pub fn decompress_document(bytes: &[u8]) -> Result<Cow<'_, [u8]>, Error> {
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return Ok(Cow::Borrowed(bytes));
+    }
+    let mut decompressed = Vec::new();
+    MultiGzDecoder::new(bytes).read_to_end(&mut decompressed)?;
+    Ok(Cow::Owned(decompressed))
+}
+
+/// Reads a whole document from `reader`, decompressing it on the fly if it is
+/// a gzip stream.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] if `reader` fails or the gzip stream is corrupt.
+pub fn read_document<R: Read>(mut reader: R) -> Result<Vec<u8>, Error> {
+    let mut magic = Vec::with_capacity(GZIP_MAGIC.len());
+    (&mut reader)
+        .take(GZIP_MAGIC.len() as u64)
+        .read_to_end(&mut magic)?;
+
+    let mut bytes = Vec::new();
+    let mut reader = std::io::Cursor::new(&magic).chain(reader);
+    if magic == GZIP_MAGIC {
+        MultiGzDecoder::new(reader).read_to_end(&mut bytes)?;
+    } else {
+        reader.read_to_end(&mut bytes)?;
+    }
+    Ok(bytes)
+}
+
+/// A GML document read from a zip archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZipDocument {
+    /// Path of the entry within the archive.
+    pub name: String,
+    /// The document, decompressed.
+    pub bytes: Vec<u8>,
+}
+
+/// Iterates over the `.gml` and `.xml` entries of a zip archive, in archive
+/// order.
+///
+/// Entries are decompressed in memory one at a time; gzipped entries
+/// (`.gml.gz`, `.xml.gz`) are decompressed as well. Directories and other
+/// files are skipped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use egml_io::codec::geometry::aggregates::deserialize_multi_surface;
+/// use egml_io::util::{ParseOptions, ZipDocuments, deserialize_with_options};
+///
+/// let archive = std::fs::File::open("tiles.zip").unwrap();
+/// for document in ZipDocuments::new(archive).unwrap() {
+///     let document = document.unwrap();
+///     let parsed = deserialize_with_options(
+///         &document.bytes,
+///         &ParseOptions::default(),
+///         deserialize_multi_surface,
+///     );
+///     println!("{}: {}", document.name, parsed.is_ok());
+/// }
+/// ```
+pub struct ZipDocuments<R: Read + Seek> {
+    archive: ZipArchive<R>,
+    index: usize,
+}
+
+impl<R: Read + Seek> ZipDocuments<R> {
+    /// # Errors
+    ///
+    /// Returns [`Error::Zip`] if `reader` is not a zip archive.
+    pub fn new(reader: R) -> Result<Self, Error> {
+        Ok(Self {
+            archive: ZipArchive::new(reader)?,
+            index: 0,
+        })
+    }
+
+    /// Number of entries in the archive, including the skipped ones.
+    pub fn entry_count(&self) -> usize {
+        self.archive.len()
+    }
+
+    fn read_entry(&mut self, index: usize) -> Result<Option<ZipDocument>, Error> {
+        let mut entry = self.archive.by_index(index)?;
+        let name = entry.name()?.into_owned();
+        if entry.is_dir() || !is_document_name(&name) {
+            return Ok(None);
+        }
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        let bytes = match decompress_document(&bytes)? {
+            Cow::Borrowed(_) => bytes,
+            Cow::Owned(x) => x,
+        };
+        Ok(Some(ZipDocument { name, bytes }))
+    }
+}
+
+impl<R: Read + Seek> Iterator for ZipDocuments<R> {
+    type Item = Result<ZipDocument, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.archive.len() {
+            let index = self.index;
+            self.index += 1;
+            match self.read_entry(index) {
+                Ok(Some(document)) => return Some(Ok(document)),
+                Ok(None) => {}
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        None
+    }
+}
+
+fn is_document_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    let name = name.strip_suffix(".gz").unwrap_or(&name);
+    name.ends_with(".gml") || name.ends_with(".xml")
+}
+
+/// Writes documents as deflated entries of a zip archive.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::DirectPosition;
+/// use egml_core::model::geometry::primitives::Point;
+/// use egml_io::util::{DocumentWriter, ZipDocumentWriter, ZipDocuments};
+///
+/// let point = Point::new(DirectPosition::new(1.0, 2.0, 3.0).unwrap()).into();
+///
+/// let mut archive = ZipDocumentWriter::new(std::io::Cursor::new(Vec::new()));
+/// DocumentWriter::new()
+///     .write_geometry(archive.start_document("tiles/a.gml").unwrap(), &point)
+///     .unwrap();
+/// let zip = archive.finish().unwrap();
+///
+/// let documents: Vec<_> = ZipDocuments::new(zip).unwrap().collect();
+/// assert_eq!(documents[0].as_ref().unwrap().name, "tiles/a.gml");
+/// ```
+pub struct ZipDocumentWriter<W: Write + Seek> {
+    archive: zip::ZipWriter<W>,
+}
+
+impl<W: Write + Seek> ZipDocumentWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            archive: zip::ZipWriter::new(out),
+        }
+    }
+
+    /// Starts the entry `name` and returns the output to write it to. The
+    /// entry ends when the next one starts or the archive is finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Zip`] if the entry cannot be started, e.g. because
+    /// `name` was already used.
+    pub fn start_document(&mut self, name: &str) -> Result<&mut (impl Write + use<W>), Error> {
+        self.archive
+            .start_file(name, SimpleFileOptions::default())?;
+        Ok(&mut self.archive)
+    }
+
+    /// Writes the central directory and returns the output.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Zip`] if the output fails.
+    pub fn finish(self) -> Result<W, Error> {
+        Ok(self.archive.finish()?)
+    }
+}
+
+/// The output of a [`DocumentWriter`](crate::util::DocumentWriter), compressed
+/// according to its [`Compression`].
+pub(crate) enum CompressedWriter<W: Write> {
+    None(W),
+    Gzip(Box<GzEncoder<W>>),
+}
+
+impl<W: Write> CompressedWriter<W> {
+    pub(crate) fn new(out: W, compression: Compression) -> Self {
+        match compression {
+            Compression::None => Self::None(out),
+            Compression::Gzip => Self::Gzip(Box::new(GzEncoder::new(out, GzipLevel::default()))),
+        }
+    }
+
+    /// Ends the compressed stream and flushes the output.
+    pub(crate) fn finish(self) -> std::io::Result<()> {
+        let mut out = match self {
+            Self::None(x) => x,
+            Self::Gzip(x) => x.finish()?,
+        };
+        out.flush()
+    }
+}
+
+impl<W: Write> Write for CompressedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::None(x) => x.write(buf),
+            Self::Gzip(x) => x.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::None(x) => x.flush(),
+            Self::Gzip(x) => x.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut writer = CompressedWriter::new(Vec::new(), Compression::Gzip);
+        writer.write_all(bytes).unwrap();
+        let CompressedWriter::Gzip(x) = writer else {
+            unreachable!()
+        };
+        x.finish().unwrap()
+    }
+
+    #[test]
+    fn gzip_streams_are_detected_and_decompressed() {
+        let compressed = gzip(b"<gml:Point/>");
+
+        assert_eq!(
+            decompress_document(&compressed).unwrap().as_ref(),
+            b"<gml:Point/>"
+        );
+        assert_eq!(read_document(&compressed[..]).unwrap(), b"<gml:Point/>");
+        assert_eq!(read_document(&b"<a/>"[..]).unwrap(), b"<a/>");
+        assert_eq!(read_document(&b"<"[..]).unwrap(), b"<");
+    }
+
+    #[test]
+    fn truncated_gzip_streams_are_rejected() {
+        let compressed = gzip(b"<gml:Point gml:id=\"p1\"><gml:pos>1 2 3</gml:pos></gml:Point>");
+        let truncated = &compressed[..compressed.len() - 10];
+
+        assert!(matches!(decompress_document(truncated), Err(Error::Io(_))));
+        assert!(matches!(read_document(truncated), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn zip_documents_skip_other_entries_and_unpack_gzip() {
+        let mut archive = ZipDocumentWriter::new(std::io::Cursor::new(Vec::new()));
+        archive
+            .start_document("a.gml")
+            .unwrap()
+            .write_all(b"<a/>")
+            .unwrap();
+        archive
+            .start_document("readme.txt")
+            .unwrap()
+            .write_all(b"tiles")
+            .unwrap();
+        archive
+            .start_document("b/c.XML.gz")
+            .unwrap()
+            .write_all(&gzip(b"<c/>"))
+            .unwrap();
+        let zip = archive.finish().unwrap();
+
+        let documents: Vec<ZipDocument> = ZipDocuments::new(zip)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            documents,
+            [
+                ZipDocument {
+                    name: "a.gml".to_string(),
+                    bytes: b"<a/>".to_vec()
+                },
+                ZipDocument {
+                    name: "b/c.XML.gz".to_string(),
+                    bytes: b"<c/>".to_vec()
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use crate::util::epsg_code;
+
+/// The semi-major axis of the WGS 84 ellipsoid, in metres.
+const A: f64 = 6_378_137.0;
+/// The flattening of the WGS 84 ellipsoid.
+const F: f64 = 1.0 / 298.257_223_563;
+/// The squared first eccentricity of the WGS 84 ellipsoid.
+const E2: f64 = F * (2.0 - F);
+
+/// The scale factor on the central meridian of UTM zones.
+const UTM_SCALE: f64 = 0.9996;
+const UTM_FALSE_EASTING: f64 = 500_000.0;
+const UTM_FALSE_NORTHING_SOUTH: f64 = 10_000_000.0;
+
+/// A source CRS that can be placed on the globe without a CRS library.
+///
+/// ETRS89 and WGS 84 differ by less than a metre, which viewers cannot show
+/// at building scale, so both are placed on the WGS 84 ellipsoid. Heights are
+/// taken as ellipsoidal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementCrs {
+    /// Earth-centred, earth-fixed coordinates (EPSG:4978).
+    Ecef,
+    /// Geographic coordinates in degrees with an ellipsoidal height, in
+    /// latitude, longitude order (EPSG:4326, EPSG:4979) or longitude,
+    /// latitude order (OGC CRS84).
+    Geographic { lon_lat_order: bool },
+    /// A UTM zone of WGS 84 (EPSG:326xx, EPSG:327xx) or ETRS89
+    /// (EPSG:25828 to EPSG:25838).
+    Utm { zone: u8, south: bool },
+}
+
+impl PlacementCrs {
+    /// Returns the CRS named by `srs_name`, or `None` if it is not supported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use egml_io::util::PlacementCrs;
+    ///
+    /// assert_eq!(
+    ///     PlacementCrs::from_srs_name("urn:ogc:def:crs:EPSG::25832"),
+    ///     Some(PlacementCrs::Utm { zone: 32, south: false })
+    /// );
+    /// assert_eq!(PlacementCrs::from_srs_name("EPSG:31467"), None);
+    /// ```
+    pub fn from_srs_name(srs_name: &str) -> Option<Self> {
+        if srs_name.to_ascii_uppercase().contains("CRS84") {
+            return Some(Self::Geographic {
+                lon_lat_order: true,
+            });
+        }
+        match epsg_code(srs_name)? {
+            4978 => Some(Self::Ecef),
+            4326 | 4979 => Some(Self::Geographic {
+                lon_lat_order: false,
+            }),
+            code @ 32601..=32660 => Some(Self::Utm {
+                zone: (code - 32600) as u8,
+                south: false,
+            }),
+            code @ 32701..=32760 => Some(Self::Utm {
+                zone: (code - 32700) as u8,
+                south: true,
+            }),
+            code @ 25828..=25838 => Some(Self::Utm {
+                zone: (code - 25800) as u8,
+                south: false,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the earth-centred, earth-fixed coordinates of `coords`.
+    pub fn to_ecef(&self, coords: [f64; 3]) -> [f64; 3] {
+        match self {
+            Self::Ecef => coords,
+            _ => {
+                let [latitude, longitude, height] = self.to_geodetic(coords);
+                geodetic_to_ecef(latitude, longitude, height)
+            }
+        }
+    }
+
+    /// Returns the longitude and latitude in degrees and the ellipsoidal
+    /// height of `coords`, the axis order of OGC CRS84.
+    pub fn to_crs84(&self, coords: [f64; 3]) -> [f64; 3] {
+        let [x, y, z] = coords;
+        match self {
+            Self::Geographic {
+                lon_lat_order: true,
+            } => coords,
+            Self::Geographic {
+                lon_lat_order: false,
+            } => [y, x, z],
+            _ => {
+                let [latitude, longitude, height] = self.to_geodetic(coords);
+                [longitude.to_degrees(), latitude.to_degrees(), height]
+            }
+        }
+    }
+
+    /// Returns the latitude and longitude in radians and the ellipsoidal
+    /// height of `coords`.
+    fn to_geodetic(self, coords: [f64; 3]) -> [f64; 3] {
+        let [x, y, z] = coords;
+        match self {
+            Self::Ecef => ecef_to_geodetic(coords),
+            Self::Geographic {
+                lon_lat_order: false,
+            } => [x.to_radians(), y.to_radians(), z],
+            Self::Geographic {
+                lon_lat_order: true,
+            } => [y.to_radians(), x.to_radians(), z],
+            Self::Utm { zone, south } => {
+                let northing = if south {
+                    y - UTM_FALSE_NORTHING_SOUTH
+                } else {
+                    y
+                };
+                let central_meridian = (f64::from(zone) * 6.0 - 183.0).to_radians();
+                let [latitude, longitude] =
+                    inverse_utm(x - UTM_FALSE_EASTING, northing, central_meridian);
+                [latitude, longitude, z]
+            }
+        }
+    }
+}
+
+/// Converts geodetic coordinates on the WGS 84 ellipsoid to ECEF.
+pub(crate) fn geodetic_to_ecef(latitude: f64, longitude: f64, height: f64) -> [f64; 3] {
+    let (sin_lat, cos_lat) = latitude.sin_cos();
+    let (sin_lon, cos_lon) = longitude.sin_cos();
+    let n = A / (1.0 - E2 * sin_lat * sin_lat).sqrt();
+    [
+        (n + height) * cos_lat * cos_lon,
+        (n + height) * cos_lat * sin_lon,
+        (n * (1.0 - E2) + height) * sin_lat,
+    ]
+}
+
+/// Converts ECEF coordinates to latitude and longitude in radians and the
+/// height above the WGS 84 ellipsoid, by fixed-point iteration, which
+/// converges to below a millimetre within a few steps near the surface.
+pub(crate) fn ecef_to_geodetic([x, y, z]: [f64; 3]) -> [f64; 3] {
+    let p = x.hypot(y);
+    let longitude = y.atan2(x);
+    let mut latitude = z.atan2(p * (1.0 - E2));
+    let mut height = 0.0;
+    for _ in 0..10 {
+        let sin_lat = latitude.sin();
+        let n = A / (1.0 - E2 * sin_lat * sin_lat).sqrt();
+        height = if latitude.cos().abs() > 1e-10 {
+            p / latitude.cos() - n
+        } else {
+            z.abs() - n * (1.0 - E2)
+        };
+        latitude = z.atan2(p * (1.0 - E2 * n / (n + height)));
+    }
+    [latitude, longitude, height]
+}
+
+/// Converts transverse Mercator coordinates relative to the false origin
+/// to latitude and longitude in radians, with the series of Krüger, which
+/// is accurate to well below a millimetre within a UTM zone.
+fn inverse_utm(easting: f64, northing: f64, central_meridian: f64) -> [f64; 2] {
+    let n = F / (2.0 - F);
+    let n2 = n * n;
+    let n3 = n2 * n;
+    let rectifying_radius = A / (1.0 + n) * (1.0 + n2 / 4.0 + n2 * n2 / 64.0);
+    let beta = [
+        n / 2.0 - 2.0 * n2 / 3.0 + 37.0 * n3 / 96.0,
+        n2 / 48.0 + n3 / 15.0,
+        17.0 * n3 / 480.0,
+    ];
+    let delta = [
+        2.0 * n - 2.0 * n2 / 3.0 - 2.0 * n3,
+        7.0 * n2 / 3.0 - 8.0 * n3 / 5.0,
+        56.0 * n3 / 15.0,
+    ];
+
+    let xi = northing / (UTM_SCALE * rectifying_radius);
+    let eta = easting / (UTM_SCALE * rectifying_radius);
+    let mut xi_prime = xi;
+    let mut eta_prime = eta;
+    for (j, beta) in (1..=3).zip(beta) {
+        let j = 2.0 * f64::from(j);
+        xi_prime -= beta * (j * xi).sin() * (j * eta).cosh();
+        eta_prime -= beta * (j * xi).cos() * (j * eta).sinh();
+    }
+
+    let chi = (xi_prime.sin() / eta_prime.cosh()).asin();
+    let mut latitude = chi;
+    for (j, delta) in (1..=3).zip(delta) {
+        latitude += delta * (2.0 * f64::from(j) * chi).sin();
+    }
+    let longitude = central_meridian + eta_prime.sinh().atan2(xi_prime.cos());
+    [latitude, longitude]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: [f64; 3], expected: [f64; 3], tolerance: f64) {
+        for axis in 0..3 {
+            assert!(
+                (actual[axis] - expected[axis]).abs() < tolerance,
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn geographic_coordinates_are_placed_on_the_ellipsoid() {
+        let wgs84 = PlacementCrs::from_srs_name("EPSG:4979").unwrap();
+        let crs84 = PlacementCrs::from_srs_name("urn:ogc:def:crs:OGC:1.3:CRS84").unwrap();
+        let b = A * (1.0 - F);
+
+        assert_close(wgs84.to_ecef([0.0, 0.0, 0.0]), [A, 0.0, 0.0], 1e-6);
+        assert_close(crs84.to_ecef([90.0, 0.0, 10.0]), [0.0, A + 10.0, 0.0], 1e-6);
+        assert_close(wgs84.to_ecef([90.0, 0.0, 0.0]), [0.0, 0.0, b], 1e-6);
+    }
+
+    #[test]
+    fn utm_coordinates_are_converted_to_geodetic() {
+        let utm = PlacementCrs::from_srs_name("EPSG:32631").unwrap();
+        let [latitude, longitude, height] = utm.to_geodetic([500_000.0, 4_649_776.224_82, 5.0]);
+        assert!((latitude.to_degrees() - 42.0).abs() < 1e-8);
+        assert!((longitude.to_degrees() - 3.0).abs() < 1e-8);
+        assert_eq!(height, 5.0);
+
+        let south = PlacementCrs::from_srs_name("EPSG:32733").unwrap();
+        let [latitude, longitude, _] = south.to_geodetic([500_000.0, 10_000_000.0, 0.0]);
+        assert!(latitude.abs() < 1e-12);
+        assert!((longitude.to_degrees() - 15.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn ecef_round_trips_through_geodetic() {
+        let ecef = geodetic_to_ecef(48.1_f64.to_radians(), 11.6_f64.to_radians(), 520.0);
+        let [latitude, longitude, height] = ecef_to_geodetic(ecef);
+
+        assert!((latitude.to_degrees() - 48.1).abs() < 1e-10);
+        assert!((longitude.to_degrees() - 11.6).abs() < 1e-10);
+        assert!((height - 520.0).abs() < 1e-6);
+    }
+}
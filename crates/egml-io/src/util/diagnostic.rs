@@ -0,0 +1,128 @@
+use egml_core::model::base::Id;
+use std::fmt;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The input was defective and has been repaired, see
+    /// [`Repair`](crate::util::Repair). Only reported in lenient mode.
+    Warning,
+    /// An element could not be deserialized and was dropped from its parent.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// Position of an element in the input document.
+///
+/// `offset` is the zero-based byte offset of the element's start tag; `line`
+/// and `column` are one-based, with the column counted in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    /// Computes the location of `offset` within `xml_document`.
+    pub fn from_offset(xml_document: &[u8], offset: usize) -> Self {
+        LineIndex::new(xml_document).location(offset)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Start offsets of all lines in a document, for repeated offset lookups.
+pub(crate) struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub(crate) fn new(xml_document: &[u8]) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(
+                xml_document
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, b)| **b == b'\n')
+                    .map(|(i, _)| i + 1),
+            )
+            .collect();
+        Self { line_starts }
+    }
+
+    pub(crate) fn location(&self, offset: usize) -> Location {
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        Location {
+            offset,
+            line,
+            column: offset - self.line_starts[line - 1] + 1,
+        }
+    }
+}
+
+/// A problem encountered while deserializing a document in lenient mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Qualified name of the affected element (e.g. `"gml:interior"`).
+    pub element: &'static str,
+    /// First `gml:id` found on or directly inside the affected element.
+    pub id: Option<Id>,
+    /// Position of the affected element in the input, if known.
+    pub location: Option<Location>,
+    /// Rendered error message.
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.element)?;
+        if let Some(id) = &self.id {
+            write!(f, " [id={id}]")?;
+        }
+        if let Some(location) = &self.location {
+            write!(f, " at {location}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Location;
+
+    #[test]
+    fn location_counts_lines_and_columns_from_one() {
+        let xml = b"<a>\n  <b/>\n</a>";
+
+        assert_eq!(
+            Location::from_offset(xml, 0),
+            Location {
+                offset: 0,
+                line: 1,
+                column: 1
+            }
+        );
+        assert_eq!(
+            Location::from_offset(xml, 6),
+            Location {
+                offset: 6,
+                line: 2,
+                column: 3
+            }
+        );
+    }
+}
@@ -0,0 +1,1268 @@
+use crate::Error;
+use crate::codec::geometry::serialize_abstract_geometry_kind;
+use crate::util::xml_element_writer::write_prefix;
+use crate::util::{
+    CompressedWriter, Compression, EncodingWriter, Formatting, GML_CE_NAMESPACE,
+    GML_CE_SCHEMA_LOCATION, GmlVersion, WriteOptions, XmlNode, declared_encoding_name,
+    serialize_with_options,
+};
+use egml_core::model::base::{AsAbstractGml, AsAbstractGmlMut, Id};
+use egml_core::model::common::{
+    IterGeometries, PropertyVisitorMut, SnapToGrid, VisitPropertiesMut,
+};
+use egml_core::model::geometry::primitives::{
+    AbstractCurveProperty, AbstractSurfaceProperty, PointProperty,
+};
+use egml_core::model::geometry::{AbstractGeometryKind, AbstractGeometryProperty};
+use egml_core::profile::{
+    SimpleFeaturesLevel, check_simple_features, downgrade_to_simple_features,
+};
+use encoding_rs::{Encoding, UTF_8};
+use quick_xml::Reader;
+use quick_xml::Writer;
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
+use quick_xml::name::QName;
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Namespace of GML 3.2.
+pub const GML_NAMESPACE: &str = "http://www.opengis.net/gml/3.2";
+/// Namespace of XLink, used for `xlink:href` and friends.
+pub const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+/// Namespace of XML Schema instance attributes such as `xsi:schemaLocation`.
+pub const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+/// Official location of the GML 3.2.1 schema.
+pub const GML_SCHEMA_LOCATION: &str = "http://schemas.opengis.net/gml/3.2.1/gml.xsd";
+
+/// Writes geometries as standalone GML 3.2 documents.
+///
+/// Unlike [`XmlNode::to_string`], which produces a fragment with hard-coded
+/// `gml:` and `xlink:` prefixes, the writer emits the XML declaration, declares
+/// every namespace on the root element, adds `xsi:schemaLocation` and renames
+/// the prefixes if others were configured.
+///
+/// For consumers that only understand GML 3.1.1, select
+/// [`GmlVersion::V3_1`] with [`with_gml_version`](Self::with_gml_version): the
+/// 3.1.1 namespace and schema are declared, `gml:Shell` is written as
+/// `gml:CompositeSurface` and the patches of a `gml:TriangulatedSurface` as
+/// `gml:trianglePatches`.
+///
+/// With [`WriteOptions::compact_encodings`] set through
+/// [`with_write_options`](Self::with_write_options), qualifying geometries are
+/// written in the GML 3.3 compact encodings and the `gmlce` namespace is
+/// declared. The compact encodings extend GML 3.2, so the option has no
+/// effect on GML 3.1.1 output.
+///
+/// [`with_simple_features`](Self::with_simple_features) restricts the output
+/// to a GML Simple Features level: geometries outside of it are either
+/// rejected or rewritten with [`downgrade_to_simple_features`] first.
+///
+/// Documents are written in UTF-8 unless [`with_encoding`](Self::with_encoding)
+/// selects another encoding, which is then declared in the XML declaration.
+///
+/// GML 3.2 requires a `gml:id` on every geometry, so geometries without one
+/// are written with a generated id, see
+/// [`with_generated_ids`](Self::with_generated_ids).
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::DirectPosition;
+/// use egml_core::model::geometry::primitives::Point;
+/// use egml_io::util::DocumentWriter;
+///
+/// let point = Point::new(DirectPosition::new(1.0, 2.0, 3.0).unwrap()).into();
+///
+/// let mut out = Vec::new();
+/// DocumentWriter::new()
+///     .with_gml_prefix("g")
+///     .write_collection(&mut out, [&point])
+///     .unwrap();
+///
+/// let xml = String::from_utf8(out).unwrap();
+/// assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+/// assert!(xml.contains("<g:MultiGeometry xmlns:g=\"http://www.opengis.net/gml/3.2\""));
+/// assert!(xml.contains("<g:pos srsDimension=\"3\">1 2 3</g:pos>"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentWriter {
+    formatting: Formatting,
+    xml_declaration: bool,
+    encoding: &'static Encoding,
+    compression: Compression,
+    gml_version: GmlVersion,
+    write_options: WriteOptions,
+    simple_features: Option<(SimpleFeaturesLevel, ProfileEnforcement)>,
+    snap_to_grid: bool,
+    gml_schema_location: bool,
+    gml_prefix: String,
+    xlink_prefix: String,
+    namespaces: Vec<(String, String)>,
+    schema_locations: Vec<(String, String)>,
+    collection_element: String,
+    member_element: String,
+    collection_id: Option<String>,
+    generated_ids: bool,
+}
+
+/// How a [`DocumentWriter`] handles geometries outside of the Simple Features
+/// level it enforces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProfileEnforcement {
+    /// Fails with [`Error::SimpleFeatures`].
+    #[default]
+    Refuse,
+    /// Rewrites the geometry with [`downgrade_to_simple_features`], e.g. a
+    /// `CompositeSurface` as a `MultiSurface` of `Polygon`s, and fails only
+    /// if that is not enough.
+    Downgrade,
+}
+
+impl Default for DocumentWriter {
+    fn default() -> Self {
+        Self {
+            formatting: Formatting::default(),
+            xml_declaration: true,
+            encoding: UTF_8,
+            compression: Compression::None,
+            gml_version: GmlVersion::V3_2,
+            write_options: WriteOptions::default(),
+            simple_features: None,
+            snap_to_grid: false,
+            gml_schema_location: true,
+            gml_prefix: "gml".to_string(),
+            xlink_prefix: "xlink".to_string(),
+            namespaces: Vec::new(),
+            schema_locations: Vec::new(),
+            collection_element: "gml:MultiGeometry".to_string(),
+            member_element: "gml:geometryMember".to_string(),
+            collection_id: None,
+            generated_ids: true,
+        }
+    }
+}
+
+impl DocumentWriter {
+    /// Creates a writer that emits an XML declaration, uses the `gml` and
+    /// `xlink` prefixes, points `xsi:schemaLocation` at the official GML 3.2.1
+    /// schema and wraps collections in `gml:MultiGeometry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_formatting(mut self, formatting: Formatting) -> Self {
+        self.formatting = formatting;
+        self
+    }
+
+    pub fn with_xml_declaration(mut self, xml_declaration: bool) -> Self {
+        self.xml_declaration = xml_declaration;
+        self
+    }
+
+    /// Selects the character encoding of the output, e.g. `encoding_rs::WINDOWS_1252`.
+    ///
+    /// Characters the encoding cannot represent are written as numeric
+    /// character references, and UTF-16 output starts with a byte order mark.
+    /// Without an XML declaration, readers will assume UTF-8 for anything but
+    /// UTF-16.
+    pub fn with_encoding(mut self, encoding: &'static Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Compresses the output, e.g. with [`Compression::Gzip`] for `.gml.gz`
+    /// files. To write into a zip archive, pass the output of
+    /// [`ZipDocumentWriter::start_document`](crate::util::ZipDocumentWriter::start_document)
+    /// instead.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Selects the GML version of the output. Defaults to [`GmlVersion::V3_2`].
+    pub fn with_gml_version(mut self, gml_version: GmlVersion) -> Self {
+        self.gml_version = gml_version;
+        self
+    }
+
+    /// Sets the options applied while serializing the geometries, see
+    /// [`WriteOptions`].
+    pub fn with_write_options(mut self, write_options: WriteOptions) -> Self {
+        self.write_options = write_options;
+        self
+    }
+
+    /// Snaps a copy of each geometry to [`WriteOptions::precision`] before
+    /// writing it, see [`SnapToGrid`]. Vertices that merge on the grid are
+    /// written once instead of as adjacent duplicates, and a ring that
+    /// collapses is reported as an error rather than written as invalid GML.
+    /// Has no effect without a precision.
+    pub fn with_snap_to_grid(mut self, snap_to_grid: bool) -> Self {
+        self.snap_to_grid = snap_to_grid;
+        self
+    }
+
+    /// Restricts the geometries to the given GML Simple Features level, see
+    /// [`ProfileEnforcement`] for how geometries outside of it are handled.
+    pub fn with_simple_features(
+        mut self,
+        level: SimpleFeaturesLevel,
+        enforcement: ProfileEnforcement,
+    ) -> Self {
+        self.simple_features = Some((level, enforcement));
+        self
+    }
+
+    /// Sets the prefix bound to the GML namespace. It must not be empty, since
+    /// `gml:id` is a qualified attribute.
+    pub fn with_gml_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.gml_prefix = prefix.into();
+        self
+    }
+
+    /// Sets the prefix bound to [`XLINK_NAMESPACE`]. It must not be empty.
+    pub fn with_xlink_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.xlink_prefix = prefix.into();
+        self
+    }
+
+    /// Declares an additional namespace on the root element, e.g. the one of
+    /// a custom collection element.
+    pub fn with_namespace(mut self, prefix: impl Into<String>, uri: impl Into<String>) -> Self {
+        self.namespaces.push((prefix.into(), uri.into()));
+        self
+    }
+
+    /// Adds a `namespace location` pair to `xsi:schemaLocation`.
+    pub fn with_schema_location(
+        mut self,
+        namespace: impl Into<String>,
+        location: impl Into<String>,
+    ) -> Self {
+        self.schema_locations
+            .push((namespace.into(), location.into()));
+        self
+    }
+
+    /// Removes all schema locations, including the default GML one, so that
+    /// no `xsi:schemaLocation` is written.
+    pub fn without_schema_locations(mut self) -> Self {
+        self.gml_schema_location = false;
+        self.schema_locations.clear();
+        self
+    }
+
+    /// Sets the root element and member property used by
+    /// [`write_collection`](Self::write_collection), e.g.
+    /// `("core:CityModel", "core:cityObjectMember")`. Names with the `gml:`
+    /// prefix are renamed along with the geometries.
+    pub fn with_collection_element(
+        mut self,
+        collection: impl Into<String>,
+        member: impl Into<String>,
+    ) -> Self {
+        self.collection_element = collection.into();
+        self.member_element = member.into();
+        self
+    }
+
+    /// Sets the `gml:id` of the collection element. Without one, a collection
+    /// element in the GML namespace gets the generated id `egml` and other
+    /// elements get none.
+    pub fn with_collection_id(mut self, id: impl Into<String>) -> Self {
+        self.collection_id = Some(id.into());
+        self
+    }
+
+    /// Gives every geometry without a `gml:id` a deterministic one, which GML
+    /// 3.2 requires. Defaults to `true`, and has no effect on GML 3.1.1
+    /// output, where ids are optional.
+    ///
+    /// A geometry without an id is named after its position, `egml.1` for
+    /// the first member of a collection or the root geometry of a document,
+    /// and geometries nested within a member after the member, in document
+    /// order: `egml.1.1`, or `building_7.1` within a member with the id
+    /// `building_7`. Rings and shells are not GML objects and stay without
+    /// an id. A generated id that is already taken is skipped: nested
+    /// geometries take the next free number and a member takes a suffix,
+    /// `egml.1_2`. [`write_collection`](Self::write_collection) considers the
+    /// ids of all members, while a [`StreamingWriter`] only knows those of the
+    /// members written so far and of the current batch.
+    ///
+    /// Without generated ids, geometries without an id are written as they
+    /// are, which some consumers accept even though it is not schema-valid.
+    pub fn with_generated_ids(mut self, generated_ids: bool) -> Self {
+        self.generated_ids = generated_ids;
+        self
+    }
+
+    /// Writes `geometry` as the root element of a document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidNamespacePrefix`] for an invalid prefix (see
+    /// [`streaming`](Self::streaming)), [`Error::SimpleFeatures`] if the
+    /// geometry is outside of the enforced profile level, or an error if it
+    /// cannot be serialized or `out` fails.
+    pub fn write_geometry<W: Write>(
+        &self,
+        out: W,
+        geometry: &AbstractGeometryKind,
+    ) -> Result<(), Error> {
+        self.validate()?;
+        let taken = self.collection_id().into_iter().map(str::to_string);
+        let taken = taken.chain(existing_ids(geometry)).collect();
+        let mut node = self.serialize(geometry, 0, &taken)?;
+        let mut attributes = self.root_attributes();
+        attributes.append(&mut node.parts.attributes);
+        node.parts.attributes = attributes;
+
+        let mut writer = self.output(out)?;
+        self.write_declaration(&mut writer)?;
+        let mut buffer = Vec::new();
+        node.write_to(&mut Writer::new(&mut buffer), self.formatting)?;
+        self.write_renamed(&mut writer, &buffer)?;
+        Ok(writer.into_inner().finish()?.finish()?)
+    }
+
+    /// Writes `geometries` as members of the collection element, one member
+    /// property per geometry. Each member is serialized and written on its
+    /// own, so the whole document is never held in memory at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidNamespacePrefix`] for an invalid prefix (see
+    /// [`streaming`](Self::streaming)), [`Error::SimpleFeatures`] if a geometry
+    /// is outside of the enforced profile level, or an error if it cannot be
+    /// serialized or `out` fails.
+    pub fn write_collection<'g, W: Write>(
+        &self,
+        out: W,
+        geometries: impl IntoIterator<Item = &'g AbstractGeometryKind>,
+    ) -> Result<(), Error> {
+        let geometries: Vec<_> = geometries.into_iter().collect();
+        let mut stream = self.streaming(out)?;
+        stream
+            .taken
+            .extend(geometries.iter().flat_map(|x| existing_ids(x)));
+        stream.write_geometries(geometries)?;
+        stream.finish()
+    }
+
+    /// Starts a collection document on `out` and returns a writer that
+    /// appends members to it as they are produced, e.g. while reading or
+    /// generating geometries. Only one batch of members is held in memory at
+    /// a time.
+    ///
+    /// To stream a large aggregate rather than a collection, set its element
+    /// and member property with
+    /// [`with_collection_element`](Self::with_collection_element), e.g.
+    /// `("gml:MultiSurface", "gml:surfaceMember")`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidNamespacePrefix`] if a prefix is empty or
+    /// contains a colon, if two prefixes are bound to different namespaces
+    /// (including the reserved `xml`, `xmlns`, `xsi` and, with compact
+    /// encodings, `gmlce`), or if the collection element or member property
+    /// uses a prefix that is not declared. Returns an error if `out` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use egml_core::model::geometry::DirectPosition;
+    /// use egml_core::model::geometry::primitives::Point;
+    /// use egml_io::util::DocumentWriter;
+    ///
+    /// let mut out = Vec::new();
+    /// let writer = DocumentWriter::new()
+    ///     .with_collection_element("gml:MultiPoint", "gml:pointMember");
+    /// let mut stream = writer.streaming(&mut out).unwrap();
+    /// for x in 0..3 {
+    ///     let point = Point::new(DirectPosition::new(x as f64, 0.0, 0.0).unwrap());
+    ///     stream.write_geometry(&point.into()).unwrap();
+    /// }
+    /// stream.finish().unwrap();
+    ///
+    /// let xml = String::from_utf8(out).unwrap();
+    /// assert_eq!(xml.matches("<gml:pointMember>").count(), 3);
+    /// assert!(xml.ends_with("</gml:MultiPoint>"));
+    /// ```
+    pub fn streaming<W: Write>(&self, out: W) -> Result<StreamingWriter<'_, W>, Error> {
+        self.validate()?;
+        let mut writer = self.output(out)?;
+        self.write_declaration(&mut writer)?;
+
+        let mut buffer = Vec::new();
+        let mut attributes = self.root_attributes();
+        if let Some(id) = self.collection_id() {
+            attributes.push(("gml:id".to_string(), id.to_string()));
+        }
+        let root = BytesStart::new(self.collection_element.as_str())
+            .with_attributes(attributes.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        Writer::new(&mut buffer).write_event(Event::Start(root))?;
+        self.write_renamed(&mut writer, &buffer)?;
+
+        Ok(StreamingWriter {
+            document: self,
+            writer,
+            buffer,
+            members: 0,
+            taken: self
+                .collection_id()
+                .map(str::to_string)
+                .into_iter()
+                .collect(),
+        })
+    }
+
+    /// Wraps `out` so that it receives the document compressed and encoded.
+    fn output<W: Write>(
+        &self,
+        out: W,
+    ) -> Result<Writer<EncodingWriter<CompressedWriter<W>>>, Error> {
+        let out = CompressedWriter::new(out, self.compression);
+        Ok(Writer::new(EncodingWriter::new(out, self.encoding)?))
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let mut reserved = vec!["xml", "xmlns", "xsi"];
+        if self.effective_write_options().compact_encodings {
+            reserved.push("gmlce");
+        }
+        let mut bound: Vec<&str> = Vec::new();
+        let prefixes = [&self.gml_prefix, &self.xlink_prefix]
+            .into_iter()
+            .chain(self.namespaces.iter().map(|(prefix, _)| prefix));
+        for prefix in prefixes {
+            if prefix.is_empty()
+                || prefix.contains(':')
+                || reserved.contains(&prefix.as_str())
+                || bound.contains(&prefix.as_str())
+            {
+                return Err(Error::InvalidNamespacePrefix(prefix.clone()));
+            }
+            bound.push(prefix);
+        }
+
+        // Names with the `gml:` prefix are renamed to the configured one.
+        bound.push("gml");
+        for name in [&self.collection_element, &self.member_element] {
+            match name.split_once(':') {
+                Some((prefix, _)) if bound.contains(&prefix) => {}
+                Some((prefix, _)) => return Err(Error::InvalidNamespacePrefix(prefix.to_string())),
+                None => return Err(Error::InvalidNamespacePrefix(String::new())),
+            }
+        }
+        Ok(())
+    }
+
+    /// The `gml:id` of the collection element, if it gets one.
+    fn collection_id(&self) -> Option<&str> {
+        match &self.collection_id {
+            Some(id) => Some(id),
+            None if self.generates_ids()
+                && (self.collection_element.starts_with("gml:")
+                    || self
+                        .collection_element
+                        .strip_prefix(self.gml_prefix.as_str())
+                        .is_some_and(|x| x.starts_with(':'))) =>
+            {
+                Some("egml")
+            }
+            None => None,
+        }
+    }
+
+    fn generates_ids(&self) -> bool {
+        self.generated_ids && self.gml_version == GmlVersion::V3_2
+    }
+
+    /// Serializes `geometry`, the `index`-th member of a collection or the
+    /// root geometry for index 0. Generated ids avoid the ids in `taken`.
+    fn serialize(
+        &self,
+        geometry: &AbstractGeometryKind,
+        index: usize,
+        taken: &HashSet<String>,
+    ) -> Result<XmlNode, Error> {
+        let mut prepared = None;
+        if let Some((level, enforcement)) = self.simple_features
+            && !check_simple_features(geometry).conforms_to(level)
+        {
+            let downgraded = match enforcement {
+                ProfileEnforcement::Refuse => geometry.clone(),
+                ProfileEnforcement::Downgrade => downgrade_to_simple_features(geometry.clone())?,
+            };
+            let violations: Vec<_> = check_simple_features(&downgraded)
+                .violations_at(level)
+                .cloned()
+                .collect();
+            if !violations.is_empty() {
+                return Err(Error::SimpleFeatures { level, violations });
+            }
+            prepared = Some(downgraded);
+        }
+        if self.snap_to_grid
+            && let Some(precision) = self.write_options.precision
+        {
+            let mut snapped = prepared.take().unwrap_or_else(|| geometry.clone());
+            snapped.snap_to_grid(precision)?;
+            prepared = Some(snapped);
+        }
+
+        if self.generates_ids()
+            && (geometry.id().is_none() || geometry.iter_geometries().any(|x| x.id().is_none()))
+        {
+            let mut identified = prepared.take().unwrap_or_else(|| geometry.clone());
+            assign_missing_ids(&mut identified, index + 1, taken)?;
+            prepared = Some(identified);
+        }
+
+        let geometry = prepared.as_ref().unwrap_or(geometry);
+        serialize_with_options(self.effective_write_options(), || {
+            serialize_abstract_geometry_kind(geometry, self.formatting)
+        })
+    }
+
+    fn effective_write_options(&self) -> WriteOptions {
+        WriteOptions {
+            compact_encodings: self.write_options.compact_encodings
+                && self.gml_version == GmlVersion::V3_2,
+            ..self.write_options
+        }
+    }
+
+    fn write_declaration<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        if self.xml_declaration {
+            writer.write_event(Event::Decl(BytesDecl::new(
+                "1.0",
+                Some(declared_encoding_name(self.encoding)),
+                None,
+            )))?;
+            // The root element follows on its own line unless everything is compact.
+            if self.formatting != Formatting::Compact {
+                writer.get_mut().write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Namespace declarations and schema locations, with the final prefixes.
+    fn root_attributes(&self) -> Vec<(String, String)> {
+        let mut attributes = vec![
+            (
+                format!("xmlns:{}", self.gml_prefix),
+                self.gml_version.namespace().to_string(),
+            ),
+            (
+                format!("xmlns:{}", self.xlink_prefix),
+                XLINK_NAMESPACE.to_string(),
+            ),
+        ];
+        let compact_encodings = self.effective_write_options().compact_encodings;
+        if compact_encodings {
+            attributes.push(("xmlns:gmlce".to_string(), GML_CE_NAMESPACE.to_string()));
+        }
+        attributes.extend(
+            self.namespaces
+                .iter()
+                .map(|(prefix, uri)| (format!("xmlns:{prefix}"), uri.clone())),
+        );
+        let mut gml_schema_locations = Vec::new();
+        if self.gml_schema_location {
+            gml_schema_locations.push((
+                self.gml_version.namespace(),
+                self.gml_version.schema_location(),
+            ));
+            if compact_encodings {
+                gml_schema_locations.push((GML_CE_NAMESPACE, GML_CE_SCHEMA_LOCATION));
+            }
+        }
+        let schema_locations: Vec<(&str, &str)> = gml_schema_locations
+            .into_iter()
+            .chain(
+                self.schema_locations
+                    .iter()
+                    .map(|(n, l)| (n.as_str(), l.as_str())),
+            )
+            .collect();
+        if !schema_locations.is_empty() {
+            attributes.push(("xmlns:xsi".to_string(), XSI_NAMESPACE.to_string()));
+            let locations = schema_locations
+                .iter()
+                .map(|(namespace, location)| format!("{namespace} {location}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            attributes.push(("xsi:schemaLocation".to_string(), locations));
+        }
+        attributes
+    }
+
+    fn write_member<W: Write>(&self, writer: &mut Writer<W>, node: &XmlNode) -> Result<(), Error> {
+        write_prefix(writer.get_mut(), self.formatting, 1)?;
+        writer.write_event(Event::Start(BytesStart::new(self.member_element.as_str())))?;
+        node.write_to_at_depth(writer, self.formatting, 2)?;
+        write_prefix(writer.get_mut(), self.formatting, 1)?;
+        writer.write_event(Event::End(BytesEnd::new(self.member_element.as_str())))?;
+        Ok(())
+    }
+
+    /// Copies `fragment` to `writer`, replacing the `gml:` and `xlink:`
+    /// prefixes of element and attribute names with the configured ones and
+    /// renaming elements that differ in GML 3.1.1.
+    fn write_renamed<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        fragment: &[u8],
+    ) -> Result<(), Error> {
+        if self.gml_prefix == "gml"
+            && self.xlink_prefix == "xlink"
+            && self.gml_version == GmlVersion::V3_2
+        {
+            writer.get_mut().write_all(fragment)?;
+            return Ok(());
+        }
+
+        let mut reader = Reader::from_reader(fragment);
+        // Fragments are renamed piecewise, so ends may close elements of earlier fragments.
+        reader.config_mut().check_end_names = false;
+        reader.config_mut().allow_unmatched_ends = true;
+        // Original and renamed names of the open elements.
+        let mut open: Vec<(String, String)> = Vec::new();
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => {
+                    let renamed = self.rename_start(&e, open.last().map(|x| x.0.as_str()));
+                    open.push((
+                        String::from_utf8_lossy(e.name().as_ref()).into_owned(),
+                        String::from_utf8_lossy(renamed.name().as_ref()).into_owned(),
+                    ));
+                    writer.write_event(Event::Start(renamed))?
+                }
+                Event::Empty(e) => {
+                    let renamed = self.rename_start(&e, open.last().map(|x| x.0.as_str()));
+                    writer.write_event(Event::Empty(renamed))?
+                }
+                Event::End(e) => {
+                    let name = match open.pop() {
+                        Some((_, renamed)) => renamed,
+                        None => self.rename(e.name().as_ref(), None),
+                    };
+                    writer.write_event(Event::End(BytesEnd::new(name)))?
+                }
+                Event::Eof => break,
+                event => writer.write_event(event)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn rename_start(&self, element: &BytesStart<'_>, parent: Option<&str>) -> BytesStart<'static> {
+        let mut renamed = BytesStart::new(self.rename(element.name().as_ref(), parent));
+        for attribute in element.attributes().flatten() {
+            let key = self.rename(attribute.key.as_ref(), None);
+            renamed.push_attribute(Attribute {
+                key: QName(key.as_bytes()),
+                value: attribute.value,
+            });
+        }
+        renamed
+    }
+
+    /// Renames an element or attribute name as written by the serializers.
+    /// `parent` is the serializer's name of the enclosing element, if known.
+    fn rename(&self, name: &[u8], parent: Option<&str>) -> String {
+        let name = String::from_utf8_lossy(name);
+        if let Some(local) = name.strip_prefix("gml:") {
+            let local = match (self.gml_version, local, parent) {
+                (GmlVersion::V3_1, "Shell", _) => "CompositeSurface",
+                (GmlVersion::V3_1, "patches", Some("gml:TriangulatedSurface")) => "trianglePatches",
+                _ => local,
+            };
+            format!("{}:{local}", self.gml_prefix)
+        } else if let Some(local) = name.strip_prefix("xlink:") {
+            format!("{}:{local}", self.xlink_prefix)
+        } else {
+            name.into_owned()
+        }
+    }
+}
+
+/// Returns the `gml:id`s of `geometry` and every geometry within it.
+fn existing_ids(geometry: &AbstractGeometryKind) -> impl Iterator<Item = String> + '_ {
+    geometry
+        .iter_geometries()
+        .filter_map(|x| x.id().map(|id| id.as_str().to_string()))
+}
+
+/// Gives `geometry` and every geometry within it that has no `gml:id` one
+/// that is not in `taken`, see [`DocumentWriter::with_generated_ids`].
+fn assign_missing_ids(
+    geometry: &mut AbstractGeometryKind,
+    position: usize,
+    taken: &HashSet<String>,
+) -> Result<(), Error> {
+    let prefix = match geometry.id() {
+        Some(id) => id.as_str().to_string(),
+        None => {
+            let base = format!("egml.{position}");
+            let id = std::iter::once(base.clone())
+                .chain((2..).map(|n| format!("{base}_{n}")))
+                .find(|x| !taken.contains(x))
+                .expect("an unbounded sequence has a free id");
+            geometry.set_id(Id::try_from(id.as_str())?);
+            id
+        }
+    };
+    let mut assigner = IdAssigner {
+        prefix,
+        next: 1,
+        taken,
+        error: None,
+    };
+    geometry.visit_properties_mut(&mut assigner);
+    assigner.error.map_or(Ok(()), Err)
+}
+
+/// Names the inline geometries without a `gml:id` after the geometry they
+/// are nested in. Rings and shells are not GML objects in GML 3.2 and have no
+/// `gml:id`.
+struct IdAssigner<'t> {
+    prefix: String,
+    next: usize,
+    taken: &'t HashSet<String>,
+    error: Option<Error>,
+}
+
+impl IdAssigner<'_> {
+    fn assign(&mut self, object: Option<&mut impl AsAbstractGmlMut>) {
+        let Some(object) = object else {
+            return;
+        };
+        if object.id().is_some() {
+            return;
+        }
+        let mut id = format!("{}.{}", self.prefix, self.next);
+        while self.taken.contains(&id) {
+            self.next += 1;
+            id = format!("{}.{}", self.prefix, self.next);
+        }
+        match Id::try_from(id.as_str()) {
+            Ok(id) => object.set_id(id),
+            Err(error) => self.error = Some(error.into()),
+        }
+        self.next += 1;
+    }
+}
+
+/// Implements a [`PropertyVisitorMut`] method that names the inline object of
+/// the property and descends into it.
+macro_rules! assign_property_id {
+    ($method:ident, $property:ty) => {
+        fn $method(&mut self, property: &mut $property) {
+            self.assign(property.object_mut());
+            property.visit_properties_mut(self);
+        }
+    };
+}
+
+impl PropertyVisitorMut for IdAssigner<'_> {
+    assign_property_id!(visit_curve_property, AbstractCurveProperty);
+    assign_property_id!(visit_surface_property, AbstractSurfaceProperty);
+    assign_property_id!(visit_point_property, PointProperty);
+    assign_property_id!(visit_geometry_property, AbstractGeometryProperty);
+}
+
+/// Number of members a [`StreamingWriter`] serializes at once.
+const STREAMING_BATCH: usize = 256;
+
+/// Writes the members of a collection document one after another, created by
+/// [`DocumentWriter::streaming`].
+///
+/// Members are serialized and written immediately, so the document never
+/// exists as an [`XmlNode`] tree. [`write_geometries`](Self::write_geometries)
+/// serializes batches of members in parallel and writes them in input order,
+/// so the output is the same as when writing them one by one.
+///
+/// The document is incomplete until [`finish`](Self::finish) is called.
+pub struct StreamingWriter<'d, W: Write> {
+    document: &'d DocumentWriter,
+    writer: Writer<EncodingWriter<CompressedWriter<W>>>,
+    buffer: Vec<u8>,
+    /// Number of members written so far.
+    members: usize,
+    /// Ids that generated ids must avoid.
+    taken: HashSet<String>,
+}
+
+impl<W: Write> StreamingWriter<'_, W> {
+    /// Appends `geometry` as the next member.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SimpleFeatures`] if the geometry is outside of the
+    /// enforced profile level, or an error if it cannot be serialized or the
+    /// output fails.
+    pub fn write_geometry(&mut self, geometry: &AbstractGeometryKind) -> Result<(), Error> {
+        self.taken.extend(existing_ids(geometry));
+        let node = self
+            .document
+            .serialize(geometry, self.members, &self.taken)?;
+        self.members += 1;
+        self.buffer.clear();
+        self.document
+            .write_member(&mut Writer::new(&mut self.buffer), &node)?;
+        self.document.write_renamed(&mut self.writer, &self.buffer)
+    }
+
+    /// Appends `geometries` as the next members, in order. Batches of members
+    /// are serialized in parallel.
+    ///
+    /// # Errors
+    ///
+    /// Fails on the first geometry that [`write_geometry`](Self::write_geometry)
+    /// would fail on; the members before it have been written.
+    pub fn write_geometries<'g>(
+        &mut self,
+        geometries: impl IntoIterator<Item = &'g AbstractGeometryKind>,
+    ) -> Result<(), Error> {
+        let mut geometries = geometries.into_iter().peekable();
+        while geometries.peek().is_some() {
+            let batch: Vec<&AbstractGeometryKind> =
+                geometries.by_ref().take(STREAMING_BATCH).collect();
+            let first = self.members;
+            self.members += batch.len();
+            self.taken
+                .extend(batch.iter().flat_map(|x| existing_ids(x)));
+            let taken = &self.taken;
+            let fragments: Vec<Result<Vec<u8>, Error>> = batch
+                .into_par_iter()
+                .enumerate()
+                .map(|(index, geometry)| {
+                    let node = self.document.serialize(geometry, first + index, taken)?;
+                    let mut fragment = Vec::new();
+                    self.document
+                        .write_member(&mut Writer::new(&mut fragment), &node)?;
+                    Ok(fragment)
+                })
+                .collect();
+            for fragment in fragments {
+                self.document.write_renamed(&mut self.writer, &fragment?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Closes the collection element, ends the compressed stream if any and
+    /// flushes the output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output fails.
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.buffer.clear();
+        let mut end = Writer::new(&mut self.buffer);
+        write_prefix(end.get_mut(), self.document.formatting, 0)?;
+        end.write_event(Event::End(BytesEnd::new(
+            self.document.collection_element.as_str(),
+        )))?;
+        self.document
+            .write_renamed(&mut self.writer, &self.buffer)?;
+        Ok(self.writer.into_inner().finish()?.finish()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::geometry::aggregates::deserialize_multi_geometry;
+    use crate::codec::geometry::primitives::{deserialize_polygon, deserialize_solid};
+    use crate::util::{ParseOptions, deserialize_with_options};
+    use egml_core::model::base::{AsAbstractGml, AsAbstractGmlMut};
+    use egml_core::model::common::{CoordinatePrecision, GeometryType};
+    use egml_core::model::geometry::DirectPosition;
+    use egml_core::model::geometry::primitives::{
+        AbstractRingKind, AbstractRingProperty, AbstractSurfaceKind, AbstractSurfaceProperty,
+        LineString, LinearRing, Point, Polygon, Shell, ShellProperty, Solid,
+    };
+    use egml_core::profile::ProfileViolationKind;
+
+    fn geometries() -> Vec<AbstractGeometryKind> {
+        let ring = LinearRing::new([
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(0.0, 1.0, 0.0).unwrap(),
+        ])
+        .unwrap();
+        let polygon = Polygon::new(
+            Some(AbstractRingProperty::from_object(
+                AbstractRingKind::LinearRing(ring),
+            )),
+            [],
+        )
+        .unwrap();
+        vec![
+            polygon.into(),
+            Point::new(DirectPosition::new(1.0, 2.0, 3.0).unwrap()).into(),
+        ]
+    }
+
+    fn solid() -> AbstractGeometryKind {
+        let AbstractGeometryKind::AbstractGeometricPrimitiveKind(polygon) = &geometries()[0] else {
+            unreachable!()
+        };
+        let polygon: Polygon = polygon.clone().try_into().unwrap();
+        let shell = Shell::new(vec![AbstractSurfaceProperty::from_object(
+            AbstractSurfaceKind::Polygon(polygon),
+        )])
+        .unwrap();
+        Solid::new(Some(ShellProperty::from_object(shell)))
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn documents_are_written_and_read_in_other_encodings() {
+        let AbstractGeometryKind::AbstractGeometricPrimitiveKind(polygon) = &geometries()[0] else {
+            unreachable!()
+        };
+        let mut polygon: Polygon = polygon.clone().try_into().unwrap();
+        polygon.push_name("Höhe €".into());
+        let geometry: AbstractGeometryKind = polygon.into();
+
+        for encoding in [encoding_rs::ISO_8859_2, encoding_rs::UTF_16BE] {
+            let mut out = Vec::new();
+            DocumentWriter::new()
+                .with_encoding(encoding)
+                .write_geometry(&mut out, &geometry)
+                .unwrap();
+
+            let parsed =
+                deserialize_with_options(&out, &ParseOptions::default(), deserialize_polygon)
+                    .unwrap();
+            assert_eq!(parsed.value().names(), ["Höhe €".into()]);
+        }
+
+        let mut out = Vec::new();
+        DocumentWriter::new()
+            .with_encoding(encoding_rs::ISO_8859_2)
+            .write_geometry(&mut out, &geometry)
+            .unwrap();
+        assert!(out.starts_with(b"<?xml version=\"1.0\" encoding=\"ISO-8859-2\"?>"));
+        let name = b"<gml:name>H\xf6he &#8364;</gml:name>";
+        assert!(out.windows(name.len()).any(|x| x == name));
+    }
+
+    #[test]
+    fn streamed_members_match_the_collection_document() {
+        let geometries: Vec<AbstractGeometryKind> =
+            geometries().into_iter().cycle().take(600).collect();
+        let writer = DocumentWriter::new()
+            .with_gml_prefix("g")
+            .with_formatting(Formatting::Indent { char: ' ', size: 2 });
+
+        let mut streamed = Vec::new();
+        let mut stream = writer.streaming(&mut streamed).unwrap();
+        stream.write_geometry(&geometries[0]).unwrap();
+        stream.write_geometries(&geometries[1..]).unwrap();
+        stream.finish().unwrap();
+
+        let mut serial = Vec::new();
+        let mut stream = writer.streaming(&mut serial).unwrap();
+        for geometry in &geometries {
+            stream.write_geometry(geometry).unwrap();
+        }
+        stream.finish().unwrap();
+
+        assert_eq!(streamed, serial);
+        let xml = String::from_utf8(streamed).unwrap();
+        assert_eq!(xml.matches("<g:geometryMember>").count(), 600);
+        assert!(xml.ends_with("\n</g:MultiGeometry>"));
+    }
+
+    #[test]
+    fn gzip_documents_are_read_transparently() {
+        let mut out = Vec::new();
+        DocumentWriter::new()
+            .with_compression(Compression::Gzip)
+            .with_generated_ids(false)
+            .write_geometry(&mut out, &geometries()[0])
+            .unwrap();
+
+        let parsed =
+            deserialize_with_options(&out, &ParseOptions::default(), deserialize_polygon).unwrap();
+
+        assert_eq!(&out[..2], [0x1f, 0x8b]);
+        assert_eq!(
+            AbstractGeometryKind::from(parsed.into_value()),
+            geometries()[0]
+        );
+    }
+
+    #[test]
+    fn collection_document_declares_namespaces_and_round_trips() {
+        let geometries = geometries();
+        let mut out = Vec::new();
+
+        DocumentWriter::new()
+            .with_formatting(Formatting::Indent { char: ' ', size: 2 })
+            .write_collection(&mut out, &geometries)
+            .unwrap();
+
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<gml:MultiGeometry"));
+        assert!(xml.contains("xmlns:gml=\"http://www.opengis.net/gml/3.2\""));
+        assert!(xml.contains("xmlns:xlink=\"http://www.w3.org/1999/xlink\""));
+        assert!(xml.contains(
+            "xsi:schemaLocation=\"http://www.opengis.net/gml/3.2 \
+             http://schemas.opengis.net/gml/3.2.1/gml.xsd\""
+        ));
+        assert!(xml.ends_with("\n</gml:MultiGeometry>"));
+
+        let multi_geometry = deserialize_multi_geometry(xml.as_bytes()).unwrap();
+        assert_eq!(multi_geometry.geometry_member().len(), 2);
+    }
+
+    #[test]
+    fn single_geometry_document_uses_configured_prefixes() {
+        let polygon = &geometries()[0];
+        let mut out = Vec::new();
+
+        DocumentWriter::new()
+            .with_xml_declaration(false)
+            .with_gml_prefix("g")
+            .without_schema_locations()
+            .write_geometry(&mut out, polygon)
+            .unwrap();
+
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.starts_with(
+            "<g:Polygon xmlns:g=\"http://www.opengis.net/gml/3.2\" \
+             xmlns:xlink=\"http://www.w3.org/1999/xlink\" g:id=\"egml.1\">"
+        ));
+        assert!(!xml.contains("gml:"));
+        assert!(!xml.contains("xsi:"));
+    }
+
+    #[test]
+    fn empty_prefix_is_rejected() {
+        let result = DocumentWriter::new()
+            .with_gml_prefix("")
+            .write_collection(Vec::new(), &geometries());
+
+        assert!(matches!(result, Err(Error::InvalidNamespacePrefix(_))));
+    }
+
+    #[test]
+    fn colliding_and_undeclared_prefixes_are_rejected() {
+        let writers = [
+            DocumentWriter::new().with_namespace("gml", "http://example.com/other"),
+            DocumentWriter::new().with_xlink_prefix("gml"),
+            DocumentWriter::new().with_namespace("xsi", "http://example.com/xsi"),
+            DocumentWriter::new()
+                .with_collection_element("core:CityModel", "core:cityObjectMember"),
+        ];
+
+        for writer in writers {
+            let result = writer.write_collection(Vec::new(), &geometries());
+            assert!(
+                matches!(result, Err(Error::InvalidNamespacePrefix(_))),
+                "{writer:?}"
+            );
+        }
+
+        let declared = DocumentWriter::new()
+            .with_namespace("core", "http://www.opengis.net/citygml/2.0")
+            .with_collection_element("core:CityModel", "core:cityObjectMember");
+        assert!(declared.write_collection(Vec::new(), &geometries()).is_ok());
+    }
+
+    #[test]
+    fn geometries_without_ids_get_deterministic_ids() {
+        let mut geometries = geometries();
+        geometries[1].set_id(Id::try_from("point_1").unwrap());
+        let mut out = Vec::new();
+
+        DocumentWriter::new()
+            .with_formatting(Formatting::Compact)
+            .write_collection(&mut out, &geometries)
+            .unwrap();
+
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.contains("<gml:MultiGeometry xmlns:gml="));
+        assert!(xml.contains(" gml:id=\"egml\">"));
+        assert!(xml.contains("<gml:Polygon gml:id=\"egml.1\">"));
+        assert!(xml.contains("<gml:exterior><gml:LinearRing>"));
+        assert!(xml.contains("<gml:Point gml:id=\"point_1\">"));
+        let multi_geometry = deserialize_multi_geometry(xml.as_bytes()).unwrap();
+        assert_eq!(multi_geometry.id().map(|x| x.as_str()), Some("egml"));
+    }
+
+    #[test]
+    fn generated_ids_skip_ids_that_are_already_taken() {
+        let mut geometries = geometries();
+        geometries[1].set_id(Id::try_from("egml.1").unwrap());
+        let mut out = Vec::new();
+        DocumentWriter::new()
+            .with_formatting(Formatting::Compact)
+            .write_collection(&mut out, &geometries)
+            .unwrap();
+
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.contains("<gml:Polygon gml:id=\"egml.1_2\">"));
+        assert!(xml.contains("<gml:Point gml:id=\"egml.1\">"));
+
+        let multi_geometry = deserialize_multi_geometry(
+            b"<gml:MultiGeometry>\
+            <gml:geometryMember><gml:Point gml:id=\"egml.1.1\"><gml:pos>0 0 0</gml:pos></gml:Point></gml:geometryMember>\
+            <gml:geometryMember><gml:Point><gml:pos>1 0 0</gml:pos></gml:Point></gml:geometryMember>\
+            <gml:geometryMember><gml:Point gml:id=\"egml.0\"><gml:pos>2 0 0</gml:pos></gml:Point></gml:geometryMember>\
+            </gml:MultiGeometry>",
+        )
+        .unwrap();
+        let mut out = Vec::new();
+        DocumentWriter::new()
+            .with_formatting(Formatting::Compact)
+            .write_geometry(&mut out, &multi_geometry.into())
+            .unwrap();
+
+        let xml = String::from_utf8(out).unwrap();
+        let ids: Vec<&str> = xml
+            .split("gml:id=\"")
+            .skip(1)
+            .map(|x| &x[..x.find('"').unwrap()])
+            .collect();
+        assert_eq!(ids, ["egml.1", "egml.1.1", "egml.1.2", "egml.0"]);
+    }
+
+    #[test]
+    fn compact_encodings_are_declared_and_round_trip() {
+        let geometries = geometries();
+        let mut out = Vec::new();
+
+        DocumentWriter::new()
+            .with_write_options(WriteOptions::compact())
+            .write_collection(&mut out, &geometries)
+            .unwrap();
+
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.contains("xmlns:gmlce=\"http://www.opengis.net/gml/3.3/ce\""));
+        assert!(xml.contains("http://schemas.opengis.net/gml/3.3/geometryCompact.xsd"));
+        assert!(xml.contains("<gmlce:SimplePolygon gml:id=\"egml.1\">"));
+        assert!(!xml.contains("LinearRing"));
+
+        let multi_geometry = deserialize_multi_geometry(xml.as_bytes()).unwrap();
+        let mut polygon = multi_geometry.geometry_member()[0]
+            .object()
+            .unwrap()
+            .clone();
+        polygon.clear_id();
+        assert_eq!(polygon, geometries[0]);
+    }
+
+    #[test]
+    fn compact_encodings_are_ignored_for_gml_3_1() {
+        let mut out = Vec::new();
+
+        DocumentWriter::new()
+            .with_gml_version(GmlVersion::V3_1)
+            .with_write_options(WriteOptions::compact())
+            .write_geometry(&mut out, &geometries()[0])
+            .unwrap();
+
+        let xml = String::from_utf8(out).unwrap();
+        assert!(!xml.contains("gmlce"));
+        assert!(xml.contains("<gml:LinearRing>"));
+    }
+
+    #[test]
+    fn precision_and_snapping_apply_to_written_coordinates() {
+        let line_string: AbstractGeometryKind = LineString::new([
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0001, 2.5, 0.12345).unwrap(),
+            DirectPosition::new(1.0, 2.5, 0.123).unwrap(),
+        ])
+        .unwrap()
+        .into();
+        let writer = DocumentWriter::new()
+            .with_formatting(Formatting::Compact)
+            .with_write_options(
+                WriteOptions::default()
+                    .with_precision(CoordinatePrecision::horizontal_vertical(3, 2))
+                    .with_trim_trailing_zeros(true),
+            );
+        let mut rounded = Vec::new();
+        let mut snapped = Vec::new();
+
+        writer.write_geometry(&mut rounded, &line_string).unwrap();
+        writer
+            .with_snap_to_grid(true)
+            .write_geometry(&mut snapped, &line_string)
+            .unwrap();
+
+        let rounded = String::from_utf8(rounded).unwrap();
+        let snapped = String::from_utf8(snapped).unwrap();
+        assert!(rounded.contains(">0 0 0 1 2.5 0.12 1 2.5 0.12</gml:posList>"));
+        assert!(snapped.contains(">0 0 0 1 2.5 0.12</gml:posList>"));
+    }
+
+    #[test]
+    fn simple_features_solids_are_refused_or_downgraded() {
+        let solid = solid();
+        let refuse = DocumentWriter::new()
+            .with_simple_features(SimpleFeaturesLevel::Sf0, ProfileEnforcement::Refuse);
+        let downgrade = DocumentWriter::new()
+            .with_formatting(Formatting::Compact)
+            .with_simple_features(SimpleFeaturesLevel::Sf0, ProfileEnforcement::Downgrade);
+        let mut out = Vec::new();
+
+        let refused = refuse.write_geometry(Vec::new(), &solid);
+        downgrade.write_geometry(&mut out, &solid).unwrap();
+
+        let Err(Error::SimpleFeatures { level, violations }) = refused else {
+            panic!("solid should be refused");
+        };
+        assert_eq!(level, SimpleFeaturesLevel::Sf0);
+        assert_eq!(
+            violations[0].kind,
+            ProfileViolationKind::UnsupportedGeometry(GeometryType::Solid)
+        );
+        let without_violations = Error::SimpleFeatures {
+            level,
+            violations: Vec::new(),
+        };
+        assert_eq!(
+            without_violations.to_string(),
+            format!("geometry does not conform to GML Simple Features {level}")
+        );
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.contains("<gml:MultiSurface"));
+        assert!(xml.contains("<gml:surfaceMember><gml:Polygon gml:id=\"egml.1.1\">"));
+        assert!(!xml.contains("Solid") && !xml.contains("Shell"));
+    }
+
+    #[test]
+    fn gml_3_1_output_uses_legacy_names_and_round_trips() {
+        let solid = solid();
+        let mut out = Vec::new();
+
+        DocumentWriter::new()
+            .with_gml_version(GmlVersion::V3_1)
+            .with_xml_declaration(false)
+            .write_geometry(&mut out, &solid)
+            .unwrap();
+
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.contains("xmlns:gml=\"http://www.opengis.net/gml\""));
+        assert!(xml.contains("http://schemas.opengis.net/gml/3.1.1/base/gml.xsd"));
+        assert!(xml.contains("<gml:CompositeSurface>"));
+        assert!(!xml.contains("Shell"));
+
+        let solid = deserialize_solid(xml.as_bytes()).unwrap();
+        assert!(solid.exterior().unwrap().object().is_some());
+    }
+}
@@ -0,0 +1,259 @@
+use crate::Error;
+use encoding_rs::{Encoding, UTF_8, UTF_16BE, UTF_16LE};
+use quick_xml::Reader;
+use quick_xml::encoding::detect_encoding;
+use quick_xml::events::Event;
+use std::borrow::Cow;
+use std::io::Write;
+
+/// Returns the character encoding of `xml_document`.
+///
+/// A byte order mark takes precedence over the `encoding` of the XML
+/// declaration. Without either, the document is UTF-8 as required by the XML
+/// specification.
+///
+/// # Errors
+///
+/// Returns [`Error::UnsupportedEncoding`] if the declared encoding is not
+/// known, or [`Error::Xml`] if the XML declaration is malformed.
+pub fn detect_document_encoding(xml_document: &[u8]) -> Result<&'static Encoding, Error> {
+    Ok(detect(xml_document)?.0)
+}
+
+/// Returns `xml_document` transcoded to UTF-8, without byte order mark.
+///
+/// UTF-8 input without byte order mark is borrowed. Otherwise, the `encoding`
+/// of the XML declaration is rewritten to `UTF-8`, so that the result is read
+/// consistently even if the declaration disagreed with the byte order mark. The
+/// deserializers expect UTF-8, so documents in other encodings, such as
+/// ISO-8859-1 or UTF-16, must pass through this function first.
+/// [`deserialize_with_options`](crate::util::deserialize_with_options) and
+/// [`DocumentResolver`](crate::resolver::DocumentResolver) do so on their own.
+///
+/// # Errors
+///
+/// Returns [`Error::MalformedEncoding`] if the document contains byte
+/// sequences that are invalid in its encoding, and the errors of
+/// [`detect_document_encoding`].
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::util::decode_document;
+///
+/// let xml = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><gml:name>H\xf6he</gml:name>";
+///
+/// assert_eq!(
+///     decode_document(xml).unwrap().as_ref(),
+///     "<?xml version=\"1.0\" encoding=\"UTF-8\"?><gml:name>Höhe</gml:name>".as_bytes()
+/// );
+/// ```
+pub fn decode_document(xml_document: &[u8]) -> Result<Cow<'_, [u8]>, Error> {
+    let (encoding, bom_len) = detect(xml_document)?;
+    let content = &xml_document[bom_len..];
+    if encoding == UTF_8 && bom_len == 0 {
+        return Ok(Cow::Borrowed(content));
+    }
+
+    // With a byte order mark, the declaration may name another encoding.
+    let mut decoded = encoding
+        .decode_without_bom_handling_and_without_replacement(content)
+        .ok_or(Error::MalformedEncoding(encoding.name()))?
+        .into_owned();
+    declare_utf8(&mut decoded);
+    Ok(Cow::Owned(decoded.into_bytes()))
+}
+
+/// Returns the encoding and the length of the byte order mark.
+fn detect(xml_document: &[u8]) -> Result<(&'static Encoding, usize), Error> {
+    if let Some(detected) = detect_encoding(xml_document)
+        && (detected.bom_len() > 0 || detected.encoding() != UTF_8)
+    {
+        return Ok((detected.encoding(), detected.bom_len()));
+    }
+
+    let mut reader = Reader::from_reader(xml_document);
+    let encoding = match reader.read_event()? {
+        Event::Decl(declaration) => match declaration.encoding() {
+            Some(label) => {
+                let label = label.map_err(quick_xml::Error::from)?;
+                Encoding::for_label(&label).ok_or_else(|| {
+                    Error::UnsupportedEncoding(String::from_utf8_lossy(&label).into_owned())
+                })?
+            }
+            None => UTF_8,
+        },
+        _ => UTF_8,
+    };
+    Ok((encoding, 0))
+}
+
+/// Replaces the value of `encoding` in the XML declaration of `xml` by `UTF-8`.
+fn declare_utf8(xml: &mut String) {
+    let Some(end) = xml.starts_with("<?xml").then(|| xml.find("?>")).flatten() else {
+        return;
+    };
+    let Some(start) = xml[..end].find("encoding") else {
+        return;
+    };
+    let declaration = &xml[start..end];
+    let Some(open) = declaration.find(['"', '\'']) else {
+        return;
+    };
+    let quote = declaration[open..].chars().next().expect("quote was found");
+    let Some(length) = declaration[open + 1..].find(quote) else {
+        return;
+    };
+    let value = start + open + 1;
+    xml.replace_range(value..value + length, "UTF-8");
+}
+
+/// The name to declare for `encoding` in an XML declaration.
+pub(crate) fn declared_encoding_name(encoding: &'static Encoding) -> &'static str {
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        "UTF-16"
+    } else {
+        encoding.name()
+    }
+}
+
+/// Transcodes the UTF-8 written to it into `encoding` before passing it on.
+///
+/// Characters that `encoding` cannot represent are written as numeric
+/// character references, e.g. `&#8364;`. UTF-16 output starts with a byte
+/// order mark.
+pub(crate) struct EncodingWriter<W: Write> {
+    inner: W,
+    encoding: &'static Encoding,
+    /// An incomplete UTF-8 sequence at the end of the last write.
+    pending: Vec<u8>,
+}
+
+impl<W: Write> EncodingWriter<W> {
+    pub(crate) fn new(mut inner: W, encoding: &'static Encoding) -> Result<Self, Error> {
+        if encoding == UTF_16LE {
+            inner.write_all(&[0xFF, 0xFE])?;
+        } else if encoding == UTF_16BE {
+            inner.write_all(&[0xFE, 0xFF])?;
+        }
+        Ok(Self {
+            inner,
+            encoding,
+            pending: Vec::new(),
+        })
+    }
+
+    fn write_str(&mut self, text: &str) -> std::io::Result<()> {
+        if self.encoding == UTF_8 {
+            self.inner.write_all(text.as_bytes())
+        } else if self.encoding == UTF_16LE {
+            let bytes: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+            self.inner.write_all(&bytes)
+        } else if self.encoding == UTF_16BE {
+            let bytes: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+            self.inner.write_all(&bytes)
+        } else {
+            self.inner.write_all(&self.encoding.encode(text).0)
+        }
+    }
+
+    /// Checks that no incomplete character is left and returns the output.
+    pub(crate) fn finish(self) -> std::io::Result<W> {
+        if !self.pending.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "output ends with an incomplete UTF-8 sequence",
+            ));
+        }
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for EncodingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        let pending = std::mem::take(&mut self.pending);
+        let valid = match std::str::from_utf8(&pending) {
+            Ok(text) => text.len(),
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(error) => {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error));
+            }
+        };
+        let text = std::str::from_utf8(&pending[..valid]).expect("validated above");
+        self.write_str(text)?;
+        self.pending = pending[valid..].to_vec();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::WINDOWS_1252;
+
+    #[test]
+    fn bom_and_declaration_select_the_encoding() {
+        let latin1 = b"<?xml version='1.0' encoding='ISO-8859-1'?><a>\xe4</a>";
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("<a>ä</a>".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let unknown = b"<?xml version=\"1.0\" encoding=\"x-unknown\"?><a/>";
+
+        assert_eq!(detect_document_encoding(latin1).unwrap(), WINDOWS_1252);
+        assert_eq!(
+            decode_document(latin1).unwrap().as_ref(),
+            "<?xml version='1.0' encoding='UTF-8'?><a>ä</a>".as_bytes()
+        );
+        assert_eq!(
+            decode_document(&utf16).unwrap().as_ref(),
+            "<a>ä</a>".as_bytes()
+        );
+        assert_eq!(decode_document(b"<a/>").unwrap().as_ref(), b"<a/>");
+        assert!(matches!(
+            decode_document(unknown),
+            Err(Error::UnsupportedEncoding(x)) if x == "x-unknown"
+        ));
+    }
+
+    #[test]
+    fn bom_overrides_a_disagreeing_declaration() {
+        let declaration = "<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>";
+        let utf8: Vec<u8> = [0xEF, 0xBB, 0xBF]
+            .into_iter()
+            .chain(format!("{declaration}<a>ä</a>").into_bytes())
+            .collect();
+        let utf16: Vec<u8> = [0xFE, 0xFF]
+            .into_iter()
+            .chain(
+                format!("{declaration}<a>ä</a>")
+                    .encode_utf16()
+                    .flat_map(u16::to_be_bytes),
+            )
+            .collect();
+        let expected = "<?xml version=\"1.0\" encoding=\"UTF-8\"?><a>ä</a>".as_bytes();
+
+        assert_eq!(detect_document_encoding(&utf8).unwrap(), UTF_8);
+        assert_eq!(decode_document(&utf8).unwrap().as_ref(), expected);
+        assert_eq!(detect_document_encoding(&utf16).unwrap(), UTF_16BE);
+        assert_eq!(decode_document(&utf16).unwrap().as_ref(), expected);
+    }
+
+    #[test]
+    fn writer_transcodes_split_characters_and_escapes_unmappable_ones() {
+        let mut buffer = Vec::new();
+        let mut writer = EncodingWriter::new(&mut buffer, WINDOWS_1252).unwrap();
+        let text = "ä€ ✓".as_bytes();
+
+        writer.write_all(&text[..1]).unwrap();
+        writer.write_all(&text[1..]).unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(buffer, b"\xe4\x80 &#10003;");
+    }
+}
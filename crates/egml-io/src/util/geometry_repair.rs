@@ -1,24 +1,99 @@
+use crate::util::{ClosingVertexPolicy, DuplicatePositionPolicy, ParseContext, RepairKind};
+use egml_core::model::base::Id;
 use egml_core::model::geometry::DirectPosition;
 use tracing::debug;
 
-/// Collapses exact adjacent duplicate positions in a parsed coordinate sequence.
+/// Collapses adjacent duplicate positions in a parsed coordinate sequence.
+///
+/// Two positions count as duplicates if they differ by at most `tolerance` along
+/// every axis; `0.0` collapses exact duplicates only. The first position of a
+/// run of duplicates is kept. Returns the number of removed positions.
 ///
 /// Some real-world GML exports repeat a position between two elements (e.g. a stray
 /// duplicated vertex in a `posList`). The `egml-core` constructors reject this via
 /// `Error::AdjacentDuplicatePositions`, so deserialization repairs it here instead of
 /// failing on otherwise well-formed geometry.
-pub(crate) fn dedup_adjacent_positions(points: &mut Vec<DirectPosition>, geometry: &'static str) {
+pub(crate) fn dedup_adjacent_positions(points: &mut Vec<DirectPosition>, tolerance: f64) -> usize {
     let before = points.len();
-    points.dedup();
-    let removed = before - points.len();
+    points.dedup_by(|b, a| is_within(a, b, tolerance));
+    before - points.len()
+}
+
+/// Applies the duplicate position policy of the current parse to the positions
+/// of an open curve (e.g. a `gml:LineString`) and records the repair.
+pub(crate) fn repair_positions(
+    points: &mut Vec<DirectPosition>,
+    element: &'static str,
+    id: Option<&Id>,
+    xml_document: &[u8],
+) {
+    let options = ParseContext::options();
+    if options.duplicate_positions == DuplicatePositionPolicy::Fail {
+        return;
+    }
+
+    let removed = dedup_adjacent_positions(points, options.position_tolerance());
+    if removed > 0 {
+        debug!(removed, element, "repaired adjacent duplicate positions");
+        ParseContext::record(
+            RepairKind::RemovedDuplicatePositions { removed },
+            element,
+            id,
+            xml_document,
+        );
+    }
+}
+
+/// Converts the positions of a closed ring as encoded in GML into the open form
+/// stored by `egml-core`, applying the repair policies of the current parse.
+///
+/// The closing position required by the GML encoding is removed silently. Any
+/// further repetition of the first position is a defect handled according to
+/// [`ClosingVertexPolicy`], and adjacent duplicates according to
+/// [`DuplicatePositionPolicy`].
+pub(crate) fn repair_ring_positions(
+    points: &mut Vec<DirectPosition>,
+    element: &'static str,
+    id: Option<&Id>,
+    xml_document: &[u8],
+) {
+    let options = ParseContext::options();
+    let tolerance = options.position_tolerance();
+
+    if points.len() > 1 && is_within(&points[0], &points[points.len() - 1], tolerance) {
+        points.pop();
+    }
+
+    repair_positions(points, element, id, xml_document);
+
+    if options.repeated_closing_vertex == ClosingVertexPolicy::Fail {
+        return;
+    }
+    let mut removed = 0;
+    while points.len() > 1 && is_within(&points[0], &points[points.len() - 1], tolerance) {
+        points.pop();
+        removed += 1;
+    }
     if removed > 0 {
-        debug!(removed, geometry, "repaired adjacent duplicate positions");
+        debug!(removed, element, "stripped repeated closing vertex");
+        ParseContext::record(
+            RepairKind::StrippedClosingVertex { removed },
+            element,
+            id,
+            xml_document,
+        );
     }
 }
 
+fn is_within(a: &DirectPosition, b: &DirectPosition, tolerance: f64) -> bool {
+    (a.x() - b.x()).abs() <= tolerance
+        && (a.y() - b.y()).abs() <= tolerance
+        && (a.z() - b.z()).abs() <= tolerance
+}
+
 #[cfg(test)]
 mod tests {
-    use super::dedup_adjacent_positions;
+    use super::{dedup_adjacent_positions, repair_ring_positions};
     use egml_core::model::geometry::DirectPosition;
 
     #[test]
@@ -30,8 +105,9 @@ mod tests {
             DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
         ];
 
-        dedup_adjacent_positions(&mut points, "test");
+        let removed = dedup_adjacent_positions(&mut points, 0.0);
 
+        assert_eq!(removed, 1);
         assert_eq!(
             points,
             vec![
@@ -51,8 +127,39 @@ mod tests {
         ];
         let original = points.clone();
 
-        dedup_adjacent_positions(&mut points, "test");
+        dedup_adjacent_positions(&mut points, 0.0);
 
         assert_eq!(points, original);
     }
+
+    #[test]
+    fn removes_near_duplicates_within_tolerance() {
+        let mut points = vec![
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0004, 0.0003, 0.0).unwrap(),
+            DirectPosition::new(2.0, 0.0, 0.0).unwrap(),
+        ];
+
+        let removed = dedup_adjacent_positions(&mut points, 0.001);
+
+        assert_eq!(removed, 1);
+        assert_eq!(points[1], DirectPosition::new(1.0, 0.0, 0.0).unwrap());
+    }
+
+    #[test]
+    fn ring_with_doubled_closing_vertex_is_opened() {
+        let mut points = vec![
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(1.0, 1.0, 0.0).unwrap(),
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+            DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+        ];
+
+        repair_ring_positions(&mut points, "test", None, b"");
+
+        assert_eq!(points.len(), 3);
+        assert_ne!(points.first(), points.last());
+    }
 }
@@ -8,22 +8,32 @@ pub enum GmlElement {
     ExteriorProperty,
     GeometryMemberProperty,
     GeometryMembersProperty,
+    InnerBoundaryIsProperty,
     InteriorProperty,
     LineString,
+    LineStringMemberProperty,
     LinearRing,
     MultiCurve,
     MultiGeometry,
+    MultiLineString,
     MultiPoint,
+    MultiPolygon,
     MultiSurface,
+    OuterBoundaryIsProperty,
     PatchesProperty,
     Point,
     PointMemberProperty,
     PointMembersProperty,
     Polygon,
+    PolygonMemberProperty,
     PolygonPatch,
     PosListProperty,
     Ring,
     Shell,
+    SimpleMultiPoint,
+    SimplePolygon,
+    SimpleRectangle,
+    SimpleTriangle,
     Solid,
     Surface,
     SurfaceMemberProperty,
@@ -40,13 +50,19 @@ impl XmlElement for GmlElement {
             b"LinearRing" => Some(Self::LinearRing),
             b"MultiCurve" => Some(Self::MultiCurve),
             b"MultiGeometry" => Some(Self::MultiGeometry),
+            b"MultiLineString" => Some(Self::MultiLineString),
             b"MultiPoint" => Some(Self::MultiPoint),
+            b"MultiPolygon" => Some(Self::MultiPolygon),
             b"MultiSurface" => Some(Self::MultiSurface),
             b"Point" => Some(Self::Point),
             b"Polygon" => Some(Self::Polygon),
             b"PolygonPatch" => Some(Self::PolygonPatch),
             b"Ring" => Some(Self::Ring),
             b"Shell" => Some(Self::Shell),
+            b"SimpleMultiPoint" => Some(Self::SimpleMultiPoint),
+            b"SimplePolygon" => Some(Self::SimplePolygon),
+            b"SimpleRectangle" => Some(Self::SimpleRectangle),
+            b"SimpleTriangle" => Some(Self::SimpleTriangle),
             b"Solid" => Some(Self::Solid),
             b"Surface" => Some(Self::Surface),
             b"Triangle" => Some(Self::Triangle),
@@ -55,10 +71,14 @@ impl XmlElement for GmlElement {
             b"exterior" => Some(Self::ExteriorProperty),
             b"geometryMember" => Some(Self::GeometryMemberProperty),
             b"geometryMembers" => Some(Self::GeometryMembersProperty),
+            b"innerBoundaryIs" => Some(Self::InnerBoundaryIsProperty),
             b"interior" => Some(Self::InteriorProperty),
+            b"lineStringMember" => Some(Self::LineStringMemberProperty),
+            b"outerBoundaryIs" => Some(Self::OuterBoundaryIsProperty),
             b"patches" => Some(Self::PatchesProperty),
             b"pointMember" => Some(Self::PointMemberProperty),
             b"pointMembers" => Some(Self::PointMembersProperty),
+            b"polygonMember" => Some(Self::PolygonMemberProperty),
             b"posList" => Some(Self::PosListProperty),
             b"surfaceMember" => Some(Self::SurfaceMemberProperty),
             b"trianglePatches" => Some(Self::TrianglePatchesProperty),
@@ -79,22 +99,32 @@ impl XmlElement for GmlElement {
             GmlElement::ExteriorProperty => "gml:exterior",
             GmlElement::GeometryMemberProperty => "gml:geometryMember",
             GmlElement::GeometryMembersProperty => "gml:geometryMembers",
+            GmlElement::InnerBoundaryIsProperty => "gml:innerBoundaryIs",
             GmlElement::InteriorProperty => "gml:interior",
             GmlElement::LineString => "gml:LineString",
+            GmlElement::LineStringMemberProperty => "gml:lineStringMember",
             GmlElement::LinearRing => "gml:LinearRing",
             GmlElement::MultiCurve => "gml:MultiCurve",
             GmlElement::MultiGeometry => "gml:MultiGeometry",
+            GmlElement::MultiLineString => "gml:MultiLineString",
             GmlElement::MultiPoint => "gml:MultiPoint",
+            GmlElement::MultiPolygon => "gml:MultiPolygon",
             GmlElement::MultiSurface => "gml:MultiSurface",
+            GmlElement::OuterBoundaryIsProperty => "gml:outerBoundaryIs",
             GmlElement::PatchesProperty => "gml:patches",
             GmlElement::Point => "gml:Point",
             GmlElement::PointMemberProperty => "gml:pointMember",
             GmlElement::PointMembersProperty => "gml:pointMembers",
             GmlElement::Polygon => "gml:Polygon",
+            GmlElement::PolygonMemberProperty => "gml:polygonMember",
             GmlElement::PolygonPatch => "gml:PolygonPatch",
             GmlElement::PosListProperty => "gml:posList",
             GmlElement::Ring => "gml:Ring",
             GmlElement::Shell => "gml:Shell",
+            GmlElement::SimpleMultiPoint => "gmlce:SimpleMultiPoint",
+            GmlElement::SimplePolygon => "gmlce:SimplePolygon",
+            GmlElement::SimpleRectangle => "gmlce:SimpleRectangle",
+            GmlElement::SimpleTriangle => "gmlce:SimpleTriangle",
             GmlElement::Solid => "gml:Solid",
             GmlElement::Surface => "gml:Surface",
             GmlElement::SurfaceMemberProperty => "gml:surfaceMember",
@@ -0,0 +1,259 @@
+use crate::Error;
+use crate::util::{GML_NAMESPACE, GML_SCHEMA_LOCATION, ParseContext};
+use quick_xml::NsReader;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Namespace shared by GML 2 and GML 3.1.1.
+pub const GML_3_1_NAMESPACE: &str = "http://www.opengis.net/gml";
+/// Official location of the GML 3.1.1 schema.
+pub const GML_3_1_SCHEMA_LOCATION: &str = "http://schemas.opengis.net/gml/3.1.1/base/gml.xsd";
+/// Namespace of the GML 3.3 compact encodings (`gmlce:SimplePolygon` etc.).
+pub const GML_CE_NAMESPACE: &str = "http://www.opengis.net/gml/3.3/ce";
+/// Official location of the GML 3.3 compact encodings schema.
+pub const GML_CE_SCHEMA_LOCATION: &str = "http://schemas.opengis.net/gml/3.3/geometryCompact.xsd";
+
+/// The GML version of a document, as identified by its namespace URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GmlVersion {
+    /// `http://www.opengis.net/gml`, used by GML 3.1.1 (and GML 2).
+    V3_1,
+    /// `http://www.opengis.net/gml/3.2`, used by GML 3.2 and 3.3.
+    V3_2,
+}
+
+impl GmlVersion {
+    pub fn from_namespace(namespace: &[u8]) -> Option<Self> {
+        match namespace {
+            x if x == GML_3_1_NAMESPACE.as_bytes() => Some(Self::V3_1),
+            x if x == GML_NAMESPACE.as_bytes() => Some(Self::V3_2),
+            _ => None,
+        }
+    }
+
+    pub fn namespace(&self) -> &'static str {
+        match self {
+            Self::V3_1 => GML_3_1_NAMESPACE,
+            Self::V3_2 => GML_NAMESPACE,
+        }
+    }
+
+    pub fn schema_location(&self) -> &'static str {
+        match self {
+            Self::V3_1 => GML_3_1_SCHEMA_LOCATION,
+            Self::V3_2 => GML_SCHEMA_LOCATION,
+        }
+    }
+}
+
+impl fmt::Display for GmlVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V3_1 => write!(f, "GML 3.1.1"),
+            Self::V3_2 => write!(f, "GML 3.2"),
+        }
+    }
+}
+
+/// Returns the GML version of the first element of `xml_document` that is in a
+/// GML namespace, or `None` if no GML namespace is declared (e.g. for a bare
+/// `<gml:Polygon>` fragment).
+///
+/// # Errors
+///
+/// Returns [`Error::Xml`] if the document is not well-formed up to the first
+/// GML element.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::util::{GmlVersion, detect_gml_version};
+///
+/// let xml = b"<gml:Point xmlns:gml=\"http://www.opengis.net/gml\">\
+///     <gml:pos>1 2 3</gml:pos></gml:Point>";
+///
+/// assert_eq!(detect_gml_version(xml).unwrap(), Some(GmlVersion::V3_1));
+/// ```
+pub fn detect_gml_version(xml_document: &[u8]) -> Result<Option<GmlVersion>, Error> {
+    let mut reader = NsReader::from_reader(xml_document);
+    loop {
+        match reader.read_resolved_event()? {
+            (ResolveResult::Bound(ns), Event::Start(_) | Event::Empty(_)) => {
+                if let Some(version) = GmlVersion::from_namespace(ns.as_ref()) {
+                    return Ok(Some(version));
+                }
+            }
+            (_, Event::Eof) => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Decides whether `element` is a GML element, based on the namespace its
+/// prefix resolves to in `reader`.
+///
+/// Prefixes that are not declared within the scanned slice are common, since
+/// deserializers work on fragments whose declarations live on an ancestor.
+/// Those are looked up in the [`NamespaceIndex`] of the document being parsed
+/// first. Only a prefix that is declared nowhere in the document is guessed:
+/// `gml:` and `gmlce:` are taken as GML, and every other prefix as foreign
+/// when parsing via [`deserialize_with_options`](crate::util::deserialize_with_options).
+/// The plain deserializers match such elements by local name alone.
+pub(crate) fn is_gml_element<R>(
+    reader: &NsReader<R>,
+    element: &BytesStart<'_>,
+    xml_fragment: &[u8],
+) -> bool {
+    match reader.resolver().resolve_element(element.name()).0 {
+        ResolveResult::Bound(ns) => is_gml_namespace(ns.as_ref()),
+        ResolveResult::Unbound => ParseContext::namespace_correction(xml_fragment).unwrap_or(true),
+        ResolveResult::Unknown(prefix) => ParseContext::is_gml_element(xml_fragment, &prefix)
+            .unwrap_or_else(|| is_gml_prefix(&prefix) || !ParseContext::is_explicit()),
+    }
+}
+
+/// The GML namespaces of all versions, and the one of the GML 3.3 compact
+/// encodings, whose elements are read along with the GML 3.2 ones.
+pub(crate) fn is_gml_namespace(namespace: &[u8]) -> bool {
+    GmlVersion::from_namespace(namespace).is_some() || namespace == GML_CE_NAMESPACE.as_bytes()
+}
+
+pub(crate) fn is_gml_prefix(prefix: &[u8]) -> bool {
+    prefix == b"gml" || prefix == b"gmlce"
+}
+
+/// Namespace information of a whole document, collected once so that
+/// fragments can be dispatched correctly even though the declarations are not
+/// part of the fragment.
+#[derive(Debug, Default)]
+pub(crate) struct NamespaceIndex {
+    version: Option<GmlVersion>,
+    /// Offsets of elements for which the guess from the prefix alone, GML for
+    /// `gml:`, `gmlce:` and unprefixed names and foreign otherwise, is wrong,
+    /// mapped to the correct answer.
+    corrections: HashMap<usize, bool>,
+    /// Offsets of elements with a prefix other than `gml:` or `gmlce:` that is
+    /// not declared in the document.
+    undeclared: HashSet<usize>,
+}
+
+impl NamespaceIndex {
+    /// Scans `xml_document`. Malformed input ends the scan early; the error
+    /// itself is reported by the deserializers.
+    pub(crate) fn new(xml_document: &[u8]) -> Self {
+        let mut index = Self::default();
+        let mut reader = NsReader::from_reader(xml_document);
+
+        while let Ok((resolved, event)) = reader.read_resolved_event() {
+            let (element, tail) = match &event {
+                Event::Start(e) => (e, 2),
+                Event::Empty(e) => (e, 3),
+                Event::Eof => break,
+                _ => continue,
+            };
+            let actual = match resolved {
+                ResolveResult::Bound(ns) => {
+                    index.version = index.version.or(GmlVersion::from_namespace(ns.as_ref()));
+                    Some(is_gml_namespace(ns.as_ref()))
+                }
+                ResolveResult::Unbound => continue,
+                ResolveResult::Unknown(prefix) if is_gml_prefix(&prefix) => continue,
+                ResolveResult::Unknown(_) => None,
+            };
+            let start = reader.buffer_position() as usize - element.len() - tail;
+            let Some(actual) = actual else {
+                index.undeclared.insert(start);
+                continue;
+            };
+
+            let prefix = element.name().prefix();
+            let guess = prefix.is_none_or(|p| is_gml_prefix(p.as_ref()));
+            if guess != actual {
+                index.corrections.insert(start, actual);
+            }
+        }
+
+        index
+    }
+
+    pub(crate) fn version(&self) -> Option<GmlVersion> {
+        self.version
+    }
+
+    /// Returns whether the element starting at `offset` is a GML element, if
+    /// the guess without declarations would be wrong.
+    pub(crate) fn correction(&self, offset: usize) -> Option<bool> {
+        self.corrections.get(&offset).copied()
+    }
+
+    /// Returns whether the element starting at `offset`, whose `prefix` is not
+    /// declared within the fragment being read, is a GML element.
+    /// `undeclared_is_gml` decides for a prefix that is declared nowhere in
+    /// the document either.
+    pub(crate) fn is_gml(&self, offset: usize, prefix: &[u8], undeclared_is_gml: bool) -> bool {
+        match self.correction(offset) {
+            Some(x) => x,
+            None if is_gml_prefix(prefix) => true,
+            None => undeclared_is_gml && self.undeclared.contains(&offset),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_are_told_apart_by_namespace() {
+        let v32 = b"<x:Polygon xmlns:x=\"http://www.opengis.net/gml/3.2\"/>";
+        let none = b"<gml:Polygon/>";
+
+        assert_eq!(detect_gml_version(v32).unwrap(), Some(GmlVersion::V3_2));
+        assert_eq!(detect_gml_version(none).unwrap(), None);
+    }
+
+    #[test]
+    fn index_corrects_prefixes_bound_elsewhere() {
+        let xml = b"<root xmlns:g=\"http://www.opengis.net/gml/3.2\" \
+            xmlns:gml=\"urn:not-gml\"><g:Point/><gml:Point/></root>";
+
+        let index = NamespaceIndex::new(xml);
+
+        assert_eq!(index.version(), Some(GmlVersion::V3_2));
+        let g = xml.windows(9).position(|w| w == b"<g:Point/").unwrap();
+        let gml = xml.windows(11).position(|w| w == b"<gml:Point/").unwrap();
+        assert_eq!(index.correction(g), Some(true));
+        assert_eq!(index.correction(gml), Some(false));
+        // The unprefixed root has no namespace and is guessed correctly.
+        assert_eq!(index.correction(0), None);
+    }
+
+    #[test]
+    fn index_tells_declared_foreign_prefixes_from_undeclared_ones() {
+        let xml = b"<root xmlns:foo=\"urn:foo\"><foo:Polygon/><ext:Polygon/></root>";
+
+        let index = NamespaceIndex::new(xml);
+
+        let foo = xml.windows(5).position(|w| w == b"<foo:").unwrap();
+        let ext = xml.windows(5).position(|w| w == b"<ext:").unwrap();
+        assert!(!index.is_gml(foo, b"foo", true));
+        assert!(index.is_gml(ext, b"ext", true));
+        assert!(!index.is_gml(ext, b"ext", false));
+    }
+
+    #[test]
+    fn compact_encodings_namespace_counts_as_gml() {
+        let xml = b"<root xmlns:ce=\"http://www.opengis.net/gml/3.3/ce\" \
+            xmlns:gmlce=\"http://www.opengis.net/gml/3.3/ce\"><ce:SimplePolygon/><gmlce:SimplePolygon/></root>";
+
+        let index = NamespaceIndex::new(xml);
+
+        let ce = xml.windows(4).position(|w| w == b"<ce:").unwrap();
+        let gmlce = xml.windows(7).position(|w| w == b"<gmlce:").unwrap();
+        assert_eq!(index.correction(ce), Some(true));
+        assert_eq!(index.correction(gmlce), None);
+        assert_eq!(index.version(), None);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::Error;
+use crate::util::SimpleGeometry;
+use egml_core::model::geometry::{AbstractGeometryKind, DirectPosition};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Returns `value` with -0.0 turned into 0.0, so that both weld into one
+/// vertex and are written as `0`.
+pub(crate) fn unsigned_zero(value: f64) -> f64 {
+    value + 0.0
+}
+
+/// A vertex of an [`IndexedMesh`], welded with the vertices of equal bits.
+pub(crate) trait MeshVertex: Sized {
+    type Key: Debug + Clone + Eq + Hash;
+
+    /// Returns the vertex with every -0.0 turned into 0.0, together with the
+    /// key it is welded by.
+    fn weld(self) -> (Self, Self::Key);
+}
+
+impl MeshVertex for [f64; 3] {
+    type Key = [u64; 3];
+
+    fn weld(self) -> (Self, Self::Key) {
+        let coords = self.map(unsigned_zero);
+        (coords, coords.map(f64::to_bits))
+    }
+}
+
+impl MeshVertex for [f32; 3] {
+    type Key = [u32; 3];
+
+    fn weld(self) -> (Self, Self::Key) {
+        // Widening to f64 is exact, so the narrowing back is as well.
+        let coords = self.map(|x| unsigned_zero(f64::from(x)) as f32);
+        (coords, coords.map(f32::to_bits))
+    }
+}
+
+impl MeshVertex for DirectPosition {
+    type Key = [u64; 3];
+
+    fn weld(self) -> (Self, Self::Key) {
+        let ([x, y, z], key) = self.coords().weld();
+        (DirectPosition::new_unchecked(x, y, z), key)
+    }
+}
+
+/// A vertex with further attributes, such as a normal, welded only with
+/// vertices that share all of them.
+impl<A: MeshVertex, B: MeshVertex> MeshVertex for (A, B) {
+    type Key = (A::Key, B::Key);
+
+    fn weld(self) -> (Self, Self::Key) {
+        let (a, a_key) = self.0.weld();
+        let (b, b_key) = self.1.weld();
+        ((a, b), (a_key, b_key))
+    }
+}
+
+/// Indexed triangles, read from a mesh format such as STL, PLY or OBJ or
+/// written to one.
+///
+/// Vertices are added with [`push_vertex`](Self::push_vertex), which welds
+/// equal vertices into one, so that a mesh holds only the vertices it uses
+/// and a triangle touching a vertex twice is dropped even if the file lists
+/// that vertex under several indices.
+#[derive(Debug, Clone)]
+pub(crate) struct IndexedMesh<V: MeshVertex = DirectPosition> {
+    pub(crate) vertices: Vec<V>,
+    pub(crate) triangles: Vec<[usize; 3]>,
+    indices: HashMap<V::Key, usize>,
+}
+
+impl<V: MeshVertex> Default for IndexedMesh<V> {
+    fn default() -> Self {
+        Self {
+            vertices: Vec::new(),
+            triangles: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+}
+
+impl<V: MeshVertex> IndexedMesh<V> {
+    /// Returns the index of `vertex`, adding it unless an equal vertex was
+    /// added before.
+    pub(crate) fn push_vertex(&mut self, vertex: V) -> usize {
+        let (vertex, key) = vertex.weld();
+        *self.indices.entry(key).or_insert_with(|| {
+            self.vertices.push(vertex);
+            self.vertices.len() - 1
+        })
+    }
+
+    /// Adds the polygon of the vertex `indices`, as returned by
+    /// [`push_vertex`](Self::push_vertex), as a fan of triangles, which
+    /// is exact for convex polygons. Triangles using a vertex twice are
+    /// dropped.
+    pub(crate) fn push_polygon(&mut self, indices: &[usize]) {
+        for pair in indices.windows(2).skip(1) {
+            let triangle = [indices[0], pair[0], pair[1]];
+            let [a, b, c] = triangle;
+            if a != b && b != c && c != a {
+                self.triangles.push(triangle);
+            }
+        }
+    }
+}
+
+impl IndexedMesh {
+    /// Returns a `Solid` if the triangles close a volume, i.e. every edge
+    /// is shared by exactly two triangles that traverse it in opposite
+    /// directions, and a `TriangulatedSurface` otherwise.
+    ///
+    /// The vertex indices must be in range.
+    pub(crate) fn into_geometry(self) -> Result<AbstractGeometryKind, Error> {
+        let mut edges = HashSet::new();
+        let mut manifold = true;
+        for [a, b, c] in &self.triangles {
+            for edge in [(a, b), (b, c), (c, a)] {
+                manifold &= edges.insert(edge);
+            }
+        }
+        let closed =
+            manifold && !edges.is_empty() && edges.iter().all(|(a, b)| edges.contains(&(*b, *a)));
+
+        let faces = self
+            .triangles
+            .iter()
+            .map(|x| {
+                let [a, b, c] = x.map(|x| self.vertices[x]);
+                vec![vec![a, b, c, a]]
+            })
+            .collect();
+        if closed {
+            SimpleGeometry::PolyhedralSurface(faces).into_geometry()
+        } else {
+            SimpleGeometry::Tin(faces).into_geometry()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egml_core::model::common::{GeometryType, HasGeometryType};
+
+    #[test]
+    fn closed_meshes_become_solids() {
+        let mut tetrahedron = IndexedMesh::default();
+        for [x, y, z] in [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ] {
+            tetrahedron.push_vertex(DirectPosition::new(x, y, z).unwrap());
+        }
+        for triangle in [[0, 2, 1], [0, 1, 3], [1, 2, 3]] {
+            tetrahedron.push_polygon(&triangle);
+        }
+        let open = tetrahedron.clone().into_geometry().unwrap();
+        tetrahedron.push_polygon(&[0, 3, 2]);
+        let closed = tetrahedron.clone().into_geometry().unwrap();
+        // A flipped triangle makes the orientation inconsistent.
+        tetrahedron.triangles[3] = [0, 2, 3];
+        let flipped = tetrahedron.into_geometry().unwrap();
+
+        assert_eq!(open.geometry_type(), GeometryType::TriangulatedSurface);
+        assert_eq!(closed.geometry_type(), GeometryType::Solid);
+        assert_eq!(flipped.geometry_type(), GeometryType::TriangulatedSurface);
+    }
+}
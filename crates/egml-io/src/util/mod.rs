@@ -1,14 +1,55 @@
+mod container;
+mod crs;
+mod diagnostic;
+mod document_writer;
+mod encoding;
 mod formatting;
 mod geometry_repair;
 mod gml_element;
+mod gml_version;
+mod mesh;
+mod parse_context;
+mod parse_options;
+mod parsed;
+mod scene;
 pub mod serde_helpers;
+mod simple_geometry;
+mod srs;
+mod timestamp;
+mod write_options;
 mod xml_element;
 mod xml_element_reader;
 mod xml_element_writer;
 
+pub(crate) use container::CompressedWriter;
+pub use container::{
+    Compression, ZipDocument, ZipDocumentWriter, ZipDocuments, decompress_document, read_document,
+};
+pub use crs::PlacementCrs;
+pub(crate) use crs::ecef_to_geodetic;
+pub use diagnostic::*;
+pub use document_writer::*;
+pub(crate) use encoding::{EncodingWriter, declared_encoding_name};
+pub use encoding::{decode_document, detect_document_encoding};
 pub use formatting::*;
 pub(crate) use geometry_repair::*;
 pub use gml_element::*;
+pub use gml_version::{
+    GML_3_1_NAMESPACE, GML_3_1_SCHEMA_LOCATION, GML_CE_NAMESPACE, GML_CE_SCHEMA_LOCATION,
+    GmlVersion, detect_gml_version,
+};
+pub(crate) use gml_version::{NamespaceIndex, is_gml_element, is_gml_namespace, is_gml_prefix};
+pub(crate) use mesh::{IndexedMesh, MeshVertex, unsigned_zero};
+pub(crate) use parse_context::ParseContext;
+pub use parse_context::deserialize_with_options;
+pub use parse_options::*;
+pub use parsed::*;
+pub use scene::{LengthUnit, UpAxis};
+pub(crate) use scene::{SceneTransform, lower_corner};
+pub(crate) use simple_geometry::*;
+pub use srs::*;
+pub(crate) use timestamp::utc_now;
+pub use write_options::*;
 pub use xml_element::*;
 pub use xml_element_reader::*;
 pub use xml_element_writer::*;
@@ -0,0 +1,332 @@
+use crate::Error;
+use crate::util::{
+    Diagnostic, LineIndex, NamespaceIndex, ParseOptions, Parsed, Repair, RepairKind, RepairReport,
+    Severity, decode_document, decompress_document,
+};
+use egml_core::model::base::Id;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::cell::RefCell;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<ParseContext>>> = const { RefCell::new(None) };
+}
+
+/// State shared by all deserializers working on the same document.
+///
+/// The context is installed for the current thread by
+/// [`deserialize_with_options`] and carried over to rayon workers by the
+/// `collect_children*` helpers, so individual deserializers keep their plain
+/// `fn(&[u8]) -> Result<T, Error>` signatures. Outside of
+/// [`deserialize_with_options`], the helpers install an implicit context that
+/// only carries the namespace declarations of the document being parsed.
+#[derive(Debug)]
+pub(crate) struct ParseContext {
+    options: ParseOptions,
+    /// Whether the context was installed by [`deserialize_with_options`].
+    /// Implicit contexts record no repairs or diagnostics.
+    explicit: bool,
+    /// Address range of the root document, used to turn sub-slices back into
+    /// absolute byte offsets.
+    document: Range<usize>,
+    namespaces: NamespaceIndex,
+    repairs: Mutex<Vec<Pending<RepairKind>>>,
+    diagnostics: Mutex<Vec<Pending<(Severity, String)>>>,
+}
+
+/// A repair or diagnostic whose location has not been resolved to a line and
+/// column yet.
+#[derive(Debug)]
+struct Pending<T> {
+    item: T,
+    element: &'static str,
+    id: Option<Id>,
+    offset: Option<usize>,
+}
+
+impl ParseContext {
+    fn new(xml_document: &[u8], options: ParseOptions) -> Self {
+        let start = xml_document.as_ptr() as usize;
+        Self {
+            options,
+            explicit: true,
+            document: start..start + xml_document.len(),
+            namespaces: NamespaceIndex::new(xml_document),
+            repairs: Mutex::new(Vec::new()),
+            diagnostics: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the context installed on the current thread, if any.
+    pub(crate) fn current() -> Option<Arc<ParseContext>> {
+        CURRENT.with(|c| c.borrow().clone())
+    }
+
+    /// Returns the context installed on the current thread, or else an
+    /// implicit one for `xml_document` if it declares any namespaces.
+    pub(crate) fn current_or_implicit(xml_document: &[u8]) -> Option<Arc<ParseContext>> {
+        Self::current().or_else(|| {
+            let declares = xml_document.windows(5).any(|x| x == b"xmlns");
+            declares.then(|| {
+                Arc::new(Self {
+                    explicit: false,
+                    ..Self::new(xml_document, ParseOptions::default())
+                })
+            })
+        })
+    }
+
+    /// Returns whether the current parse was started by
+    /// [`deserialize_with_options`].
+    pub(crate) fn is_explicit() -> bool {
+        CURRENT.with(|c| c.borrow().as_ref().is_some_and(|x| x.explicit))
+    }
+
+    /// Runs `f` with `context` installed on the current thread, restoring the
+    /// previous context afterwards (also on unwind).
+    pub(crate) fn scope<R>(context: Option<Arc<ParseContext>>, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<Arc<ParseContext>>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|c| *c.borrow_mut() = self.0.take());
+            }
+        }
+
+        let previous = CURRENT.with(|c| c.replace(context));
+        let _restore = Restore(previous);
+        f()
+    }
+
+    /// Returns the options of the current context, or the defaults outside of
+    /// [`deserialize_with_options`].
+    pub(crate) fn options() -> ParseOptions {
+        CURRENT.with(|c| c.borrow().as_ref().map(|x| x.options).unwrap_or_default())
+    }
+
+    /// Records a repair applied to the element starting at `xml_fragment`.
+    /// Without an `id`, the `gml:id` is taken from the fragment as for
+    /// [`diagnose`](Self::diagnose). In lenient mode the repair is reported
+    /// as a [`Severity::Warning`] diagnostic as well, except for dropped
+    /// members, which are reported as errors by the caller.
+    ///
+    /// Outside of [`deserialize_with_options`] this is a no-op.
+    pub(crate) fn record(
+        kind: RepairKind,
+        element: &'static str,
+        id: Option<&Id>,
+        xml_fragment: &[u8],
+    ) {
+        Self::with_current(|context| {
+            let id = id.cloned().or_else(|| first_gml_id(xml_fragment));
+            let offset = context.offset_of(xml_fragment);
+            if context.options.lenient && !matches!(kind, RepairKind::DroppedMember { .. }) {
+                context
+                    .diagnostics
+                    .lock()
+                    .expect("diagnostic log must not be poisoned")
+                    .push(Pending {
+                        item: (Severity::Warning, kind.to_string()),
+                        element,
+                        id: id.clone(),
+                        offset,
+                    });
+            }
+            context
+                .repairs
+                .lock()
+                .expect("repair log must not be poisoned")
+                .push(Pending {
+                    item: kind,
+                    element,
+                    id,
+                    offset,
+                });
+        });
+    }
+
+    /// Records a diagnostic for the element starting at `xml_fragment`. The
+    /// `gml:id` is taken from the first element in the fragment that has one.
+    ///
+    /// Outside of [`deserialize_with_options`] this is a no-op.
+    pub(crate) fn diagnose(
+        severity: Severity,
+        element: &'static str,
+        message: String,
+        xml_fragment: &[u8],
+    ) {
+        Self::with_current(|context| {
+            context
+                .diagnostics
+                .lock()
+                .expect("diagnostic log must not be poisoned")
+                .push(Pending {
+                    item: (severity, message),
+                    element,
+                    id: first_gml_id(xml_fragment),
+                    offset: context.offset_of(xml_fragment),
+                });
+        });
+    }
+
+    /// Returns whether the element starting at `xml_fragment` is in a GML
+    /// namespace, if the namespace declarations of the root document say so
+    /// and a guess from the prefix alone would be wrong.
+    ///
+    /// Without a context covering `xml_fragment` this returns `None`.
+    pub(crate) fn namespace_correction(xml_fragment: &[u8]) -> Option<bool> {
+        CURRENT.with(|c| {
+            let context = c.borrow().clone()?;
+            context
+                .offset_of(xml_fragment)
+                .and_then(|offset| context.namespaces.correction(offset))
+        })
+    }
+
+    /// Returns whether the element starting at `xml_fragment`, whose `prefix`
+    /// is not declared within the fragment, is in a GML namespace according
+    /// to the declarations of the root document. A prefix declared nowhere is
+    /// only taken as GML by implicit contexts.
+    ///
+    /// Without a context covering `xml_fragment` this returns `None`.
+    pub(crate) fn is_gml_element(xml_fragment: &[u8], prefix: &[u8]) -> Option<bool> {
+        CURRENT.with(|c| {
+            let context = c.borrow().clone()?;
+            let offset = context.offset_of(xml_fragment)?;
+            Some(context.namespaces.is_gml(offset, prefix, !context.explicit))
+        })
+    }
+
+    fn with_current(f: impl FnOnce(&ParseContext)) {
+        CURRENT.with(|c| {
+            if let Some(context) = c.borrow().as_ref().filter(|x| x.explicit) {
+                f(context);
+            }
+        });
+    }
+
+    /// Returns the absolute byte offset of `xml_fragment` if it is a sub-slice
+    /// of the root document.
+    fn offset_of(&self, xml_fragment: &[u8]) -> Option<usize> {
+        let address = xml_fragment.as_ptr() as usize;
+        self.document
+            .contains(&address)
+            .then(|| address - self.document.start)
+    }
+
+    fn finish<T>(self, xml_document: &[u8], value: T) -> Parsed<T> {
+        let lines = LineIndex::new(xml_document);
+
+        let repairs = self
+            .repairs
+            .into_inner()
+            .expect("repair log must not be poisoned")
+            .into_iter()
+            .map(|x| Repair {
+                kind: x.item,
+                element: x.element,
+                id: x.id,
+                location: x.offset.map(|o| lines.location(o)),
+            })
+            .collect();
+
+        let mut diagnostics: Vec<Diagnostic> = self
+            .diagnostics
+            .into_inner()
+            .expect("diagnostic log must not be poisoned")
+            .into_iter()
+            .map(|x| Diagnostic {
+                severity: x.item.0,
+                element: x.element,
+                id: x.id,
+                location: x.offset.map(|o| lines.location(o)),
+                message: x.item.1,
+            })
+            .collect();
+        // Children are deserialized in parallel, so restore document order.
+        diagnostics.sort_by_key(|d| d.location.map_or(usize::MAX, |l| l.offset));
+
+        Parsed::new(value, RepairReport::new(repairs), diagnostics)
+            .with_gml_version(self.namespaces.version())
+    }
+}
+
+/// Returns the first `gml:id` attribute in `xml_fragment`, looking at the
+/// outermost element and its first child (the object inside a property).
+fn first_gml_id(xml_fragment: &[u8]) -> Option<Id> {
+    let mut reader = Reader::from_reader(xml_fragment);
+    for _ in 0..2 {
+        let element = loop {
+            match reader.read_event().ok()? {
+                Event::Start(e) | Event::Empty(e) => break e,
+                Event::Eof => return None,
+                _ => {}
+            }
+        };
+        let id = element
+            .attributes()
+            .flatten()
+            .find(|a| a.key.local_name().as_ref() == b"id")
+            .and_then(|a| Id::try_from(String::from_utf8_lossy(&a.value).into_owned()).ok());
+        if id.is_some() {
+            return id;
+        }
+    }
+    None
+}
+
+/// Deserializes `xml_document` with `deserializer`, applying `options` to
+/// every nested geometry.
+///
+/// Returns the deserialized value together with every repair that was applied
+/// and an error diagnostic for every element that had to be dropped, whether
+/// in [lenient](ParseOptions::lenient) mode or under
+/// [`DegeneratePolicy::Drop`](crate::util::DegeneratePolicy::Drop). Both lists
+/// are in document order.
+///
+/// Gzip streams are decompressed and documents in an encoding other than
+/// UTF-8 are transcoded first, see
+/// [`decompress_document`](crate::util::decompress_document) and
+/// [`decode_document`](crate::util::decode_document); locations then refer to
+/// the resulting document.
+///
+/// # Errors
+///
+/// Returns the error of the root element if it cannot be deserialized. Without
+/// lenient mode, any nested failure that is not covered by a repair policy is
+/// returned as well.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::codec::geometry::primitives::deserialize_linear_ring;
+/// use egml_io::util::{ParseOptions, RepairKind, deserialize_with_options};
+///
+/// let xml = b"<gml:LinearRing gml:id=\"r1\">\
+///     <gml:posList>0 0 0 1 0 0 1 0 0 1 1 0 0 0 0</gml:posList>\
+///     </gml:LinearRing>";
+///
+/// let parsed =
+///     deserialize_with_options(xml, &ParseOptions::default(), deserialize_linear_ring).unwrap();
+///
+/// assert_eq!(parsed.value().points().len(), 3);
+/// assert_eq!(
+///     parsed.repairs().repairs()[0].kind,
+///     RepairKind::RemovedDuplicatePositions { removed: 1 }
+/// );
+/// ```
+pub fn deserialize_with_options<T>(
+    xml_document: &[u8],
+    options: &ParseOptions,
+    deserializer: fn(&[u8]) -> Result<T, Error>,
+) -> Result<Parsed<T>, Error> {
+    let xml_document = decompress_document(xml_document)?;
+    let xml_document = decode_document(&xml_document)?;
+    let xml_document = xml_document.as_ref();
+    let context = Arc::new(ParseContext::new(xml_document, *options));
+    let value = ParseContext::scope(Some(context.clone()), || deserializer(xml_document))?;
+
+    let context = Arc::try_unwrap(context).expect("parse context must not outlive the parse");
+    Ok(context.finish(xml_document, value))
+}
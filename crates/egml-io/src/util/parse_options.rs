@@ -0,0 +1,234 @@
+use crate::util::Location;
+use egml_core::model::base::Id;
+use std::fmt;
+
+/// Controls how the deserializers react to common defects in real-world GML.
+///
+/// Every field selects a policy for one class of issue. The [`Default`]
+/// reproduces the historical behaviour of `egml-io`: exact adjacent duplicates
+/// are collapsed, a redundant closing vertex is stripped, and degenerate
+/// geometry fails the parse. Lenient recovery is off.
+///
+/// Pass the options to [`deserialize_with_options`](crate::util::deserialize_with_options)
+/// to apply them to a whole document. Every repair that is actually applied is
+/// recorded in the [`RepairReport`] of the returned [`Parsed`](crate::util::Parsed).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ParseOptions {
+    /// Policy for adjacent positions that are equal (or nearly equal).
+    pub duplicate_positions: DuplicatePositionPolicy,
+    /// Policy for rings whose closing vertex is repeated beyond the single
+    /// closing position required by the `gml:LinearRing` encoding.
+    pub repeated_closing_vertex: ClosingVertexPolicy,
+    /// Policy for rings and members that remain invalid after all other repairs
+    /// (e.g. a ring with fewer than 3 distinct positions).
+    pub degenerate: DegeneratePolicy,
+    /// Recover at every property boundary instead of failing the document.
+    ///
+    /// A child element that cannot be deserialized is dropped from its parent
+    /// and reported as a [`Diagnostic`](crate::util::Diagnostic); only a failure
+    /// of the root element itself is returned as an error. Repairs are
+    /// reported as warnings alongside.
+    pub lenient: bool,
+    /// Keep attributes, child elements and comments that no deserializer
+    /// reads as the [`ForeignContent`](egml_core::model::base::ForeignContent)
+    /// of the owning object, so that writing it again re-emits them.
+    pub preserve_unknown: bool,
+}
+
+impl ParseOptions {
+    /// Options that reject every defect instead of repairing it.
+    pub fn strict() -> Self {
+        Self {
+            duplicate_positions: DuplicatePositionPolicy::Fail,
+            repeated_closing_vertex: ClosingVertexPolicy::Fail,
+            degenerate: DegeneratePolicy::Fail,
+            lenient: false,
+            preserve_unknown: false,
+        }
+    }
+
+    /// The default options with [`lenient`](Self::lenient) recovery enabled.
+    pub fn lenient() -> Self {
+        Self {
+            lenient: true,
+            ..Self::default()
+        }
+    }
+
+    /// Options that repair or drop every defect they know how to handle.
+    ///
+    /// `tolerance` is the maximum per-axis distance under which two adjacent
+    /// positions are considered duplicates.
+    pub fn repair_all(tolerance: f64) -> Self {
+        Self {
+            duplicate_positions: DuplicatePositionPolicy::DedupWithin { tolerance },
+            repeated_closing_vertex: ClosingVertexPolicy::Strip,
+            degenerate: DegeneratePolicy::Drop,
+            lenient: false,
+            preserve_unknown: false,
+        }
+    }
+
+    pub fn with_duplicate_positions(mut self, policy: DuplicatePositionPolicy) -> Self {
+        self.duplicate_positions = policy;
+        self
+    }
+
+    pub fn with_repeated_closing_vertex(mut self, policy: ClosingVertexPolicy) -> Self {
+        self.repeated_closing_vertex = policy;
+        self
+    }
+
+    pub fn with_degenerate(mut self, policy: DegeneratePolicy) -> Self {
+        self.degenerate = policy;
+        self
+    }
+
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    pub fn with_preserve_unknown(mut self, preserve_unknown: bool) -> Self {
+        self.preserve_unknown = preserve_unknown;
+        self
+    }
+
+    /// Returns the tolerance used to compare positions, or `0.0` for exact comparison.
+    pub(crate) fn position_tolerance(&self) -> f64 {
+        match self.duplicate_positions {
+            DuplicatePositionPolicy::DedupWithin { tolerance } => tolerance,
+            DuplicatePositionPolicy::Fail | DuplicatePositionPolicy::Dedup => 0.0,
+        }
+    }
+}
+
+/// Policy for adjacent duplicate positions in a coordinate sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DuplicatePositionPolicy {
+    /// Keep the positions as they are; the `egml-core` constructors then reject
+    /// the geometry with `Error::AdjacentDuplicatePositions`.
+    Fail,
+    /// Collapse exactly equal adjacent positions.
+    #[default]
+    Dedup,
+    /// Collapse adjacent positions whose coordinates differ by at most
+    /// `tolerance` along every axis. The first position of a run is kept.
+    DedupWithin { tolerance: f64 },
+}
+
+/// Policy for a ring that repeats its closing vertex more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClosingVertexPolicy {
+    /// Strip the redundant trailing positions.
+    #[default]
+    Strip,
+    /// Reject the ring with `Error::RepeatedClosingVertex`.
+    Fail,
+}
+
+/// Policy for rings and members that cannot be constructed after repair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DegeneratePolicy {
+    /// Propagate the geometry error and fail the enclosing object.
+    #[default]
+    Fail,
+    /// Drop the offending ring or member from its parent and continue.
+    Drop,
+}
+
+/// A single repair applied during deserialization.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repair {
+    /// What was changed.
+    pub kind: RepairKind,
+    /// Qualified name of the element that was repaired (e.g. `"gml:LinearRing"`).
+    pub element: &'static str,
+    /// `gml:id` of the repaired element, if it has one.
+    pub id: Option<Id>,
+    /// Position of the element in the input document, if known.
+    pub location: Option<Location>,
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.element)?;
+        if let Some(id) = &self.id {
+            write!(f, " [id={id}]")?;
+        }
+        if let Some(location) = &self.location {
+            write!(f, " at {location}")?;
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+/// The kind of change recorded by a [`Repair`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepairKind {
+    /// `removed` redundant closing positions were stripped from a ring.
+    StrippedClosingVertex { removed: usize },
+    /// `removed` adjacent duplicate positions were collapsed.
+    RemovedDuplicatePositions { removed: usize },
+    /// The element was dropped from its parent because it could not be
+    /// constructed; `reason` is the rendered error that caused the drop.
+    DroppedMember { reason: String },
+}
+
+impl fmt::Display for RepairKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepairKind::StrippedClosingVertex { removed } => {
+                write!(f, "stripped {removed} repeated closing vertex/vertices")
+            }
+            RepairKind::RemovedDuplicatePositions { removed } => {
+                write!(f, "removed {removed} adjacent duplicate position(s)")
+            }
+            RepairKind::DroppedMember { reason } => write!(f, "dropped: {reason}"),
+        }
+    }
+}
+
+/// All repairs applied while deserializing a document, ordered by their
+/// position in the input.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RepairReport {
+    repairs: Vec<Repair>,
+}
+
+impl RepairReport {
+    pub fn new(mut repairs: Vec<Repair>) -> Self {
+        // Children are deserialized in parallel, so restore document order.
+        repairs.sort_by_key(|r| r.location.map_or(usize::MAX, |l| l.offset));
+        Self { repairs }
+    }
+
+    pub fn repairs(&self) -> &[Repair] {
+        &self.repairs
+    }
+
+    pub fn len(&self) -> usize {
+        self.repairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.repairs.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Repair> {
+        self.repairs.iter()
+    }
+
+    pub fn into_repairs(self) -> Vec<Repair> {
+        self.repairs
+    }
+}
+
+impl IntoIterator for RepairReport {
+    type Item = Repair;
+    type IntoIter = std::vec::IntoIter<Repair>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.repairs.into_iter()
+    }
+}
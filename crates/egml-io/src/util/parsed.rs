@@ -0,0 +1,78 @@
+use crate::util::{Diagnostic, GmlVersion, RepairReport, Severity};
+
+/// The result of deserializing a document with
+/// [`deserialize_with_options`](crate::util::deserialize_with_options): the
+/// value, plus the repairs that were applied and the diagnostics for every
+/// element that was dropped along the way. Mirrors
+/// [`Triangulation`](egml_core::model::common::Triangulation), which pairs a
+/// surface with its skipped members.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parsed<T> {
+    value: T,
+    repairs: RepairReport,
+    diagnostics: Vec<Diagnostic>,
+    gml_version: Option<GmlVersion>,
+}
+
+impl<T> Parsed<T> {
+    pub fn new(value: T, repairs: RepairReport, diagnostics: Vec<Diagnostic>) -> Self {
+        Self {
+            value,
+            repairs,
+            diagnostics,
+            gml_version: None,
+        }
+    }
+
+    pub fn with_gml_version(mut self, gml_version: Option<GmlVersion>) -> Self {
+        self.gml_version = gml_version;
+        self
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    pub fn repairs(&self) -> &RepairReport {
+        &self.repairs
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// The GML version of the document, detected from the namespace of its
+    /// GML elements. `None` for fragments without namespace declarations.
+    pub fn gml_version(&self) -> Option<GmlVersion> {
+        self.gml_version
+    }
+
+    pub fn has_diagnostics(&self) -> bool {
+        !self.diagnostics.is_empty()
+    }
+
+    /// Returns `true` if any element was dropped with [`Severity::Error`].
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+
+    /// Transforms the value while keeping repairs and diagnostics.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Parsed<U> {
+        Parsed {
+            value: f(self.value),
+            repairs: self.repairs,
+            diagnostics: self.diagnostics,
+            gml_version: self.gml_version,
+        }
+    }
+
+    pub fn into_parts(self) -> (T, RepairReport, Vec<Diagnostic>) {
+        (self.value, self.repairs, self.diagnostics)
+    }
+}
@@ -0,0 +1,134 @@
+use crate::Error;
+use crate::util::{IndexedMesh, WriteOptions, unsigned_zero};
+use egml_core::model::common::Triangulation;
+use egml_core::model::geometry::DirectPosition;
+
+/// The axis that points up in a written scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpAxis {
+    /// Y up, as in X3D and glTF. A Z-up point `(x, y, z)` is written as
+    /// `(x, z, -y)`.
+    Y,
+    /// Z up, as in GML and most CRSs. Points are written unchanged.
+    Z,
+}
+
+impl UpAxis {
+    /// The source axis written as `axis` of the scene.
+    fn source_axis(self, axis: usize) -> usize {
+        match self {
+            Self::Y => [0, 2, 1][axis],
+            Self::Z => axis,
+        }
+    }
+}
+
+/// The length unit of a written scene, e.g. centimetres for a pipeline
+/// that expects them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LengthUnit {
+    name: String,
+    meters: f64,
+}
+
+impl LengthUnit {
+    /// A unit named `name` that is `meters` metres long.
+    pub fn new(name: impl Into<String>, meters: f64) -> Self {
+        Self {
+            name: name.into(),
+            meters,
+        }
+    }
+
+    pub fn meter() -> Self {
+        Self::new("meter", 1.0)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn meters(&self) -> f64 {
+        self.meters
+    }
+}
+
+impl Default for LengthUnit {
+    fn default() -> Self {
+        Self::meter()
+    }
+}
+
+/// Moves metric Z-up coordinates into a scene relative to `origin`, in
+/// `unit` and with `up_axis` up.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SceneTransform {
+    pub(crate) origin: [f64; 3],
+    pub(crate) up_axis: UpAxis,
+    pub(crate) meters: f64,
+}
+
+impl SceneTransform {
+    pub(crate) fn apply(&self, coords: [f64; 3]) -> [f64; 3] {
+        self.axes(std::array::from_fn(|axis| coords[axis] - self.origin[axis]))
+    }
+
+    /// The origin in scene axes and units, for the translation of a root
+    /// node that restores the source coordinates.
+    pub(crate) fn translation(&self) -> [f64; 3] {
+        self.axes(self.origin)
+    }
+
+    fn axes(&self, coords: [f64; 3]) -> [f64; 3] {
+        let [x, y, z] = coords.map(|x| x / self.meters);
+        match self.up_axis {
+            UpAxis::Y => [x, z, -y],
+            UpAxis::Z => [x, y, z],
+        }
+    }
+
+    /// Formats scene `coords` as `x y z`, with the precision of the source
+    /// axis of each ordinate.
+    pub(crate) fn format(&self, options: &WriteOptions, coords: [f64; 3]) -> String {
+        let [x, y, z] = std::array::from_fn(|axis| {
+            options.format_ordinate(self.up_axis.source_axis(axis), unsigned_zero(coords[axis]))
+        });
+        format!("{x} {y} {z}")
+    }
+
+    /// The triangles of `triangulation` in the scene, with equal vertices
+    /// shared and triangles with repeated vertices dropped.
+    pub(crate) fn mesh(&self, triangulation: &Triangulation) -> Result<IndexedMesh, Error> {
+        let mut mesh = IndexedMesh::default();
+        for triangle in triangulation.surface().triangles() {
+            let mut indices = [0; 3];
+            for (corner, position) in [triangle.a(), triangle.b(), triangle.c()]
+                .into_iter()
+                .enumerate()
+            {
+                let [x, y, z] = self.apply(position.coords());
+                indices[corner] = mesh.push_vertex(DirectPosition::new(x, y, z)?);
+            }
+            mesh.push_polygon(&indices);
+        }
+        Ok(mesh)
+    }
+}
+
+/// The lowest coordinate on each axis over all triangles, or zero if there
+/// are none.
+pub(crate) fn lower_corner<'a>(
+    triangulations: impl IntoIterator<Item = &'a Triangulation>,
+) -> [f64; 3] {
+    let mut corner = [f64::INFINITY; 3];
+    for triangulation in triangulations {
+        for triangle in triangulation.surface().triangles() {
+            for position in [triangle.a(), triangle.b(), triangle.c()] {
+                for (axis, value) in position.coords().into_iter().enumerate() {
+                    corner[axis] = corner[axis].min(value);
+                }
+            }
+        }
+    }
+    corner.map(|x| if x.is_finite() { x } else { 0.0 })
+}
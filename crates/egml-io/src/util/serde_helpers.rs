@@ -1,3 +1,4 @@
+use crate::util::WriteOptions;
 use serde::{Deserialize, Deserializer, Serializer};
 
 pub fn deserialize_space_separated_f64<'de, D>(deserializer: D) -> Result<Vec<f64>, D::Error>
@@ -14,10 +15,12 @@ pub fn serialize_space_separated_f64<S>(values: &[f64], serializer: S) -> Result
 where
     S: Serializer,
 {
+    let options = WriteOptions::current();
     serializer.serialize_str(
         &values
             .iter()
-            .map(|v| v.to_string())
+            .enumerate()
+            .map(|(i, v)| options.format_ordinate(i % 3, *v))
             .collect::<Vec<_>>()
             .join(" "),
     )
@@ -45,7 +48,7 @@ pub fn serialize_space_separated_f64_3<S>(
 where
     S: Serializer,
 {
-    serializer.serialize_str(&values.map(|v| v.to_string()).join(" "))
+    serialize_space_separated_f64(values, serializer)
 }
 
 pub fn deserialize_space_separated_f64_4<'de, D>(deserializer: D) -> Result<[f64; 4], D::Error>
@@ -0,0 +1,426 @@
+use crate::Error;
+use egml_core::model::base::HasAssociationAttributes;
+use egml_core::model::geometry::aggregates::{
+    AbstractGeometricAggregateKind, MultiCurve, MultiGeometry, MultiPoint, MultiSurface,
+};
+use egml_core::model::geometry::primitives::{
+    AbstractCurveKind, AbstractCurveProperty, AbstractGeometricPrimitiveKind, AbstractRingKind,
+    AbstractRingProperty, AbstractSolidKind, AbstractSurfaceKind,
+    AbstractSurfacePatchArrayProperty, AbstractSurfacePatchKind, AbstractSurfaceProperty,
+    LineString, LinearRing, Point, PointProperty, Polygon, Shell, ShellProperty, Solid, Surface,
+    SurfaceKind, Triangle, TriangulatedSurface,
+};
+use egml_core::model::geometry::{AbstractGeometryKind, AbstractGeometryProperty, DirectPosition};
+
+/// A closed ring whose last position repeats the first.
+pub(crate) type Ring = Vec<DirectPosition>;
+
+/// A geometry of the OGC Simple Features model, which the WKT and WKB
+/// encodings are defined on.
+///
+/// Polygons are lists of closed rings, the exterior ring first. An empty list
+/// stands for an empty geometry.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SimpleGeometry {
+    Point(DirectPosition),
+    LineString(Vec<DirectPosition>),
+    Polygon(Vec<Ring>),
+    Triangle(Vec<Ring>),
+    MultiPoint(Vec<DirectPosition>),
+    MultiLineString(Vec<Vec<DirectPosition>>),
+    MultiPolygon(Vec<Vec<Ring>>),
+    PolyhedralSurface(Vec<Vec<Ring>>),
+    Tin(Vec<Vec<Ring>>),
+    GeometryCollection(Vec<SimpleGeometry>),
+}
+
+/// A polygon flattened from a surface, remembering whether it was a
+/// `Triangle` patch.
+struct Face {
+    rings: Vec<Ring>,
+    triangle: bool,
+}
+
+impl SimpleGeometry {
+    /// Maps `geometry` to its Simple Features counterpart:
+    ///
+    /// - `LinearRing` becomes a closed `LineString`,
+    /// - a `Surface` with a single patch becomes a `Polygon` or `Triangle`,
+    /// - `TriangulatedSurface` becomes a `Tin`,
+    /// - `CompositeSurface`, `Shell`, other `Surface`s and the exterior shell
+    ///   of a `Solid` become a `PolyhedralSurface`, or a `Tin` if all their
+    ///   faces are `Triangle` patches,
+    /// - the surfaces of a `MultiSurface` are flattened into a `MultiPolygon`,
+    /// - `MultiGeometry` becomes a `GeometryCollection`.
+    ///
+    /// `format` names the target encoding in errors.
+    pub(crate) fn from_geometry(
+        geometry: &AbstractGeometryKind,
+        format: &'static str,
+    ) -> Result<Self, Error> {
+        match geometry {
+            AbstractGeometryKind::AbstractGeometricAggregateKind(x) => from_aggregate(x, format),
+            AbstractGeometryKind::AbstractGeometricPrimitiveKind(x) => from_primitive(x, format),
+        }
+    }
+
+    /// Builds the egml geometry for `self`, the inverse of
+    /// [`from_geometry`](Self::from_geometry). A `PolyhedralSurface` becomes a
+    /// `Solid` and a `Triangle` a `Surface` with a single patch.
+    pub(crate) fn into_geometry(self) -> Result<AbstractGeometryKind, Error> {
+        Ok(match self {
+            Self::Point(x) => Point::new(x).into(),
+            Self::LineString(x) => LineString::new(x)?.into(),
+            Self::Polygon(x) => to_polygon(x)?.into(),
+            Self::Triangle(x) => {
+                let patch = AbstractSurfacePatchKind::Triangle(to_triangle(x)?);
+                Surface::new(AbstractSurfacePatchArrayProperty::from_objects(vec![patch])).into()
+            }
+            Self::MultiPoint(x) => {
+                let mut multi_point = MultiPoint::new(None)?;
+                multi_point.set_point_member(
+                    x.into_iter()
+                        .map(|x| PointProperty::from_object(Point::new(x)))
+                        .collect(),
+                );
+                multi_point.into()
+            }
+            Self::MultiLineString(x) => MultiCurve::new(
+                x.into_iter()
+                    .map(|x| {
+                        Ok(AbstractCurveProperty::from_object(
+                            LineString::new(x)?.into(),
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?,
+            )?
+            .into(),
+            Self::MultiPolygon(x) => MultiSurface::new(surface_properties(x)?)?.into(),
+            Self::PolyhedralSurface(x) if x.is_empty() => Solid::new(None)?.into(),
+            Self::PolyhedralSurface(x) => to_solid(vec![x])?.into(),
+            Self::Tin(x) => TriangulatedSurface::from_triangles(
+                x.into_iter().map(to_triangle).collect::<Result<_, _>>()?,
+            )?
+            .into(),
+            Self::GeometryCollection(x) => {
+                let mut multi_geometry = MultiGeometry::new(None)?;
+                multi_geometry.set_geometry_member(
+                    x.into_iter()
+                        .map(|x| Ok(AbstractGeometryProperty::from_object(x.into_geometry()?)))
+                        .collect::<Result<_, Error>>()?,
+                );
+                multi_geometry.into()
+            }
+        })
+    }
+}
+
+fn from_aggregate(
+    aggregate: &AbstractGeometricAggregateKind,
+    format: &'static str,
+) -> Result<SimpleGeometry, Error> {
+    Ok(match aggregate {
+        AbstractGeometricAggregateKind::MultiCurve(x) => SimpleGeometry::MultiLineString(
+            x.curve_member()
+                .iter()
+                .map(curve_positions)
+                .collect::<Result<_, _>>()?,
+        ),
+        AbstractGeometricAggregateKind::MultiGeometry(x) => {
+            let mut members = Vec::new();
+            for member in x.geometry_member() {
+                let object = member.object().ok_or(Error::UnsupportedXLink)?;
+                members.push(SimpleGeometry::from_geometry(object, format)?);
+            }
+            for object in x.geometry_members().map(|x| x.objects()).unwrap_or(&[]) {
+                members.push(SimpleGeometry::from_geometry(object, format)?);
+            }
+            SimpleGeometry::GeometryCollection(members)
+        }
+        AbstractGeometricAggregateKind::MultiPoint(x) => {
+            let mut points = Vec::new();
+            for member in x.point_member() {
+                points.push(*member.object().ok_or(Error::UnsupportedXLink)?.pos());
+            }
+            for object in x.point_members().map(|x| x.objects()).unwrap_or(&[]) {
+                points.push(*object.pos());
+            }
+            SimpleGeometry::MultiPoint(points)
+        }
+        AbstractGeometricAggregateKind::MultiSurface(x) => {
+            let mut faces = Vec::new();
+            for member in x.surface_member() {
+                push_surface_property_faces(member, &mut faces, format)?;
+            }
+            SimpleGeometry::MultiPolygon(faces.into_iter().map(|x| x.rings).collect())
+        }
+    })
+}
+
+fn from_primitive(
+    primitive: &AbstractGeometricPrimitiveKind,
+    format: &'static str,
+) -> Result<SimpleGeometry, Error> {
+    Ok(match primitive {
+        AbstractGeometricPrimitiveKind::Point(x) => SimpleGeometry::Point(*x.pos()),
+        AbstractGeometricPrimitiveKind::AbstractCurveKind(x) => {
+            SimpleGeometry::LineString(curve_kind_positions(x)?)
+        }
+        AbstractGeometricPrimitiveKind::AbstractSurfaceKind(x) => match x {
+            AbstractSurfaceKind::Polygon(x) => {
+                SimpleGeometry::Polygon(polygon_rings(x.exterior(), x.interior(), format)?)
+            }
+            AbstractSurfaceKind::Surface(x) if x.patches().objects_len() == 1 => {
+                let mut faces = Vec::new();
+                push_patch_faces(x.patches(), &mut faces, format)?;
+                let face = faces.remove(0);
+                if face.triangle {
+                    SimpleGeometry::Triangle(face.rings)
+                } else {
+                    SimpleGeometry::Polygon(face.rings)
+                }
+            }
+            AbstractSurfaceKind::SurfaceKind(SurfaceKind::TriangulatedSurface(x)) => {
+                let mut faces = Vec::new();
+                push_patch_faces(x.surface().patches(), &mut faces, format)?;
+                SimpleGeometry::Tin(faces.into_iter().map(|x| x.rings).collect())
+            }
+            surface => {
+                let mut faces = Vec::new();
+                push_surface_faces(surface, &mut faces, format)?;
+                polyhedral_surface(faces)
+            }
+        },
+        AbstractGeometricPrimitiveKind::AbstractSolidKind(AbstractSolidKind::Solid(x)) => {
+            if !x.interior().is_empty() {
+                return Err(Error::Unrepresentable {
+                    format,
+                    reason: "a solid with interior shells".to_string(),
+                });
+            }
+            let faces = match x.exterior() {
+                Some(exterior) => shell_faces(exterior, format)?,
+                None => Vec::new(),
+            };
+            polyhedral_surface(faces)
+        }
+    })
+}
+
+/// The faces of every shell of `solid`, the exterior shell first.
+pub(crate) fn solid_shells(
+    solid: &Solid,
+    format: &'static str,
+) -> Result<Vec<Vec<Vec<Ring>>>, Error> {
+    solid
+        .exterior()
+        .into_iter()
+        .chain(solid.interior())
+        .map(|x| {
+            Ok(shell_faces(x, format)?
+                .into_iter()
+                .map(|x| x.rings)
+                .collect())
+        })
+        .collect()
+}
+
+/// A `Solid` bounded by `shells` of polygons, the exterior shell first. No
+/// shells make an empty solid.
+pub(crate) fn to_solid(shells: Vec<Vec<Vec<Ring>>>) -> Result<Solid, Error> {
+    let mut shells = shells.into_iter().map(|x| {
+        Ok(ShellProperty::from_object(Shell::new(surface_properties(
+            x,
+        )?)?))
+    });
+    let mut solid = Solid::new(shells.next().transpose()?)?;
+    solid.set_interior(shells.collect::<Result<_, Error>>()?);
+    Ok(solid)
+}
+
+fn shell_faces(property: &ShellProperty, format: &'static str) -> Result<Vec<Face>, Error> {
+    let shell = property
+        .object()
+        .ok_or_else(|| egml_core::Error::UnresolvedShellReference {
+            href: property.href().map(|h| h.to_string()),
+        })?;
+    let mut faces = Vec::new();
+    for member in shell.members() {
+        push_surface_property_faces(member, &mut faces, format)?;
+    }
+    Ok(faces)
+}
+
+/// A `Tin` if every face is a triangle patch, otherwise a `PolyhedralSurface`.
+fn polyhedral_surface(faces: Vec<Face>) -> SimpleGeometry {
+    let tin = !faces.is_empty() && faces.iter().all(|x| x.triangle);
+    let faces = faces.into_iter().map(|x| x.rings).collect();
+    if tin {
+        SimpleGeometry::Tin(faces)
+    } else {
+        SimpleGeometry::PolyhedralSurface(faces)
+    }
+}
+
+fn push_surface_property_faces(
+    property: &AbstractSurfaceProperty,
+    faces: &mut Vec<Face>,
+    format: &'static str,
+) -> Result<(), Error> {
+    let surface =
+        property
+            .object()
+            .ok_or_else(|| egml_core::Error::UnresolvedSurfaceReference {
+                href: property.href().map(|h| h.to_string()),
+            })?;
+    push_surface_faces(surface, faces, format)
+}
+
+fn push_surface_faces(
+    surface: &AbstractSurfaceKind,
+    faces: &mut Vec<Face>,
+    format: &'static str,
+) -> Result<(), Error> {
+    match surface {
+        AbstractSurfaceKind::Polygon(x) => faces.push(Face {
+            rings: polygon_rings(x.exterior(), x.interior(), format)?,
+            triangle: false,
+        }),
+        AbstractSurfaceKind::CompositeSurface(x) => {
+            for member in x.surface_member() {
+                push_surface_property_faces(member, faces, format)?;
+            }
+        }
+        AbstractSurfaceKind::Shell(x) => {
+            for member in x.members() {
+                push_surface_property_faces(member, faces, format)?;
+            }
+        }
+        AbstractSurfaceKind::Surface(x) => push_patch_faces(x.patches(), faces, format)?,
+        AbstractSurfaceKind::SurfaceKind(SurfaceKind::TriangulatedSurface(x)) => {
+            push_patch_faces(x.surface().patches(), faces, format)?
+        }
+    }
+    Ok(())
+}
+
+fn push_patch_faces(
+    patches: &AbstractSurfacePatchArrayProperty,
+    faces: &mut Vec<Face>,
+    format: &'static str,
+) -> Result<(), Error> {
+    for patch in patches.objects() {
+        faces.push(match patch {
+            AbstractSurfacePatchKind::PolygonPatch(x) => Face {
+                rings: polygon_rings(x.exterior(), x.interior(), format)?,
+                triangle: false,
+            },
+            AbstractSurfacePatchKind::Triangle(x) => Face {
+                rings: vec![ring_positions(x.exterior())?],
+                triangle: true,
+            },
+        });
+    }
+    Ok(())
+}
+
+fn polygon_rings(
+    exterior: Option<&AbstractRingProperty>,
+    interior: &[AbstractRingProperty],
+    format: &'static str,
+) -> Result<Vec<Ring>, Error> {
+    let Some(exterior) = exterior else {
+        if interior.is_empty() {
+            return Ok(Vec::new());
+        }
+        return Err(Error::Unrepresentable {
+            format,
+            reason: "a polygon with interior rings but no exterior ring".to_string(),
+        });
+    };
+    std::iter::once(exterior)
+        .chain(interior)
+        .map(ring_positions)
+        .collect()
+}
+
+fn ring_positions(property: &AbstractRingProperty) -> Result<Ring, Error> {
+    let ring = property
+        .object()
+        .ok_or_else(|| egml_core::Error::UnresolvedRingReference {
+            href: property.href().map(|h| h.to_string()),
+        })?;
+    Ok(ring_kind_positions(ring))
+}
+
+fn ring_kind_positions(ring: &AbstractRingKind) -> Ring {
+    match ring {
+        AbstractRingKind::LinearRing(x) => closed(x.points()),
+        AbstractRingKind::AbstractRingKind(x) => ring_kind_positions(x),
+    }
+}
+
+fn curve_positions(property: &AbstractCurveProperty) -> Result<Vec<DirectPosition>, Error> {
+    let curve = property
+        .object()
+        .ok_or_else(|| egml_core::Error::UnresolvedCurveReference {
+            href: property.href().map(|h| h.to_string()),
+        })?;
+    curve_kind_positions(curve)
+}
+
+fn curve_kind_positions(curve: &AbstractCurveKind) -> Result<Vec<DirectPosition>, Error> {
+    Ok(match curve {
+        AbstractCurveKind::LineString(x) => x.points().to_vec(),
+        AbstractCurveKind::AbstractRingKind(x) => ring_kind_positions(x),
+    })
+}
+
+/// The positions of an open ring with the first one repeated at the end.
+fn closed(points: &[DirectPosition]) -> Ring {
+    points.iter().chain(points.first()).copied().collect()
+}
+
+/// A `LinearRing` from a closed ring, which must not repeat its closing
+/// vertex in the egml model.
+fn to_linear_ring(mut ring: Ring) -> Result<LinearRing, Error> {
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    Ok(LinearRing::new(ring)?)
+}
+
+fn to_ring_property(ring: Ring) -> Result<AbstractRingProperty, Error> {
+    Ok(AbstractRingProperty::from_object(
+        to_linear_ring(ring)?.into(),
+    ))
+}
+
+fn to_polygon(rings: Vec<Ring>) -> Result<Polygon, Error> {
+    let mut rings = rings.into_iter().map(to_ring_property);
+    let exterior = rings.next().transpose()?;
+    Ok(Polygon::new(
+        exterior,
+        rings.collect::<Result<Vec<_>, _>>()?,
+    )?)
+}
+
+fn to_triangle(rings: Vec<Ring>) -> Result<Triangle, Error> {
+    let [ring] =
+        <[Ring; 1]>::try_from(rings).map_err(|x| egml_core::Error::InvalidElementCount {
+            geometry: "gml:Triangle",
+            expected: 1,
+            actual: x.len(),
+            spec: None,
+        })?;
+    Ok(Triangle::new(to_ring_property(ring)?)?)
+}
+
+pub(crate) fn surface_properties(
+    polygons: Vec<Vec<Ring>>,
+) -> Result<Vec<AbstractSurfaceProperty>, Error> {
+    polygons
+        .into_iter()
+        .map(|x| Ok(AbstractSurfaceProperty::from_object(to_polygon(x)?.into())))
+        .collect()
+}
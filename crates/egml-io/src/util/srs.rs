@@ -0,0 +1,50 @@
+/// Returns the EPSG code named by `srs_name`, e.g. `25832` for
+/// `EPSG:25832`, `urn:ogc:def:crs:EPSG::25832` or
+/// `http://www.opengis.net/def/crs/EPSG/0/25832`.
+///
+/// For a compound CRS such as
+/// `urn:ogc:def:crs,crs:EPSG::25832,crs:EPSG::7837`, the code of the first
+/// component is returned. Other authorities yield `None`.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::util::epsg_code;
+///
+/// assert_eq!(epsg_code("urn:ogc:def:crs:EPSG::25832"), Some(25832));
+/// assert_eq!(epsg_code("http://www.opengis.net/def/crs/EPSG/0/4979"), Some(4979));
+/// assert_eq!(epsg_code("urn:ogc:def:crs:OGC:1.3:CRS84"), None);
+/// ```
+pub fn epsg_code(srs_name: &str) -> Option<u32> {
+    let start = srs_name.to_ascii_uppercase().find("EPSG")? + "EPSG".len();
+    let code = srs_name[start..].split(',').next()?;
+    code.rsplit([':', '/'])
+        .find(|x| !x.is_empty())?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Returns the `srsName` for an EPSG code, in the URN form recommended by
+/// GML 3.2, e.g. `urn:ogc:def:crs:EPSG::25832`.
+pub fn epsg_srs_name(code: u32) -> String {
+    format!("urn:ogc:def:crs:EPSG::{code}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epsg_codes_are_taken_from_common_srs_names() {
+        assert_eq!(epsg_code("EPSG:25832"), Some(25832));
+        assert_eq!(epsg_code("epsg:4326"), Some(4326));
+        assert_eq!(epsg_code("urn:ogc:def:crs:EPSG:6.6:4326"), Some(4326));
+        assert_eq!(
+            epsg_code("urn:ogc:def:crs,crs:EPSG::25832,crs:EPSG::7837"),
+            Some(25832)
+        );
+        assert_eq!(epsg_code("EPSG:"), None);
+        assert_eq!(epsg_code(&epsg_srs_name(31468)), Some(31468));
+    }
+}
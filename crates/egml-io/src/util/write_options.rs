@@ -0,0 +1,149 @@
+use egml_core::model::common::CoordinatePrecision;
+use std::cell::Cell;
+
+thread_local! {
+    static CURRENT: Cell<WriteOptions> = const { Cell::new(WriteOptions::DEFAULT) };
+}
+
+/// Selects optional encodings and the number format used by the serializers.
+///
+/// The [`Default`] writes every geometry in its plain GML 3.2 encoding, with
+/// coordinates in the shortest form that reads back to the same `f64`. Pass
+/// the options to [`serialize_with_options`] to apply them to all `serialize_*`
+/// calls, or to [`DocumentWriter::with_write_options`](crate::util::DocumentWriter::with_write_options)
+/// to apply them to a whole document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WriteOptions {
+    /// Write qualifying geometries in the compact encodings of the GML 3.3
+    /// `ce` extension, which drop the ring wrappers and the closing vertex:
+    ///
+    /// - a `Triangle` as `gmlce:SimpleTriangle`,
+    /// - a `PolygonPatch` with four corners as `gmlce:SimpleRectangle`,
+    /// - a `Polygon` as `gmlce:SimplePolygon`,
+    /// - a `MultiPoint` as `gmlce:SimpleMultiPoint`.
+    ///
+    /// A geometry qualifies if it has no interior rings and its exterior ring
+    /// (or each point member) is an inline object without `gml:id` or other
+    /// attributes that the compact form could not carry.
+    pub compact_encodings: bool,
+    /// Write coordinates with a fixed number of decimal places per axis
+    /// instead of full `f64` precision.
+    pub precision: Option<CoordinatePrecision>,
+    /// Drop trailing zeros after the decimal point, and the point itself if
+    /// nothing follows, e.g. `1.5` instead of `1.500`. Only has an effect
+    /// together with [`precision`](Self::precision).
+    pub trim_trailing_zeros: bool,
+}
+
+impl WriteOptions {
+    const DEFAULT: Self = Self {
+        compact_encodings: false,
+        precision: None,
+        trim_trailing_zeros: false,
+    };
+
+    /// The default options with [`compact_encodings`](Self::compact_encodings)
+    /// enabled.
+    pub fn compact() -> Self {
+        Self {
+            compact_encodings: true,
+            ..Self::DEFAULT
+        }
+    }
+
+    pub fn with_precision(mut self, precision: CoordinatePrecision) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn with_trim_trailing_zeros(mut self, trim_trailing_zeros: bool) -> Self {
+        self.trim_trailing_zeros = trim_trailing_zeros;
+        self
+    }
+
+    /// Formats the ordinate of `axis` (0 = x, 1 = y, 2 = z) as written to
+    /// `gml:pos` and `gml:posList`.
+    pub(crate) fn format_ordinate(&self, axis: usize, value: f64) -> String {
+        let Some(precision) = self.precision else {
+            return value.to_string();
+        };
+        let decimals = usize::from(precision.decimals()[axis]);
+        let mut formatted = format!("{value:.decimals$}");
+        if self.trim_trailing_zeros && formatted.contains('.') {
+            formatted.truncate(formatted.trim_end_matches('0').trim_end_matches('.').len());
+        }
+        // Rounding small negative values must not produce "-0".
+        if formatted.starts_with('-') && formatted[1..].bytes().all(|x| matches!(x, b'0' | b'.')) {
+            formatted.remove(0);
+        }
+        formatted
+    }
+
+    /// Returns the options installed on the current thread, or the defaults
+    /// outside of [`serialize_with_options`].
+    pub(crate) fn current() -> Self {
+        CURRENT.with(Cell::get)
+    }
+}
+
+/// Runs `f`, typically one or more `serialize_*` calls, with `options` in
+/// effect on the current thread.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::DirectPosition;
+/// use egml_core::model::geometry::primitives::Triangle;
+/// use egml_io::codec::geometry::primitives::serialize_triangle;
+/// use egml_io::util::{Formatting, WriteOptions, serialize_with_options};
+///
+/// let triangle = Triangle::from_points(
+///     DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+///     DirectPosition::new(1.0, 0.0, 0.0).unwrap(),
+///     DirectPosition::new(0.0, 1.0, 0.0).unwrap(),
+/// )
+/// .unwrap();
+///
+/// let node = serialize_with_options(WriteOptions::compact(), || {
+///     serialize_triangle(&triangle, Formatting::Compact)
+/// })
+/// .unwrap();
+///
+/// let xml = node.to_string(Formatting::Compact).unwrap();
+/// assert!(xml.starts_with("<gmlce:SimpleTriangle><gml:posList"));
+/// ```
+pub fn serialize_with_options<R>(options: WriteOptions, f: impl FnOnce() -> R) -> R {
+    struct Restore(WriteOptions);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|c| c.set(self.0));
+        }
+    }
+
+    let previous = CURRENT.with(|c| c.replace(options));
+    let _restore = Restore(previous);
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_ordinate_uses_precision_per_axis() {
+        let fixed =
+            WriteOptions::default().with_precision(CoordinatePrecision::horizontal_vertical(3, 2));
+        let trimmed = fixed.with_trim_trailing_zeros(true);
+
+        assert_eq!(
+            WriteOptions::default().format_ordinate(0, 0.1 + 0.2),
+            "0.30000000000000004"
+        );
+        assert_eq!(fixed.format_ordinate(0, 12.5), "12.500");
+        assert_eq!(fixed.format_ordinate(2, 417.3802376791456), "417.38");
+        assert_eq!(trimmed.format_ordinate(0, 12.5), "12.5");
+        assert_eq!(trimmed.format_ordinate(1, 3.0), "3");
+        assert_eq!(trimmed.format_ordinate(1, 300.0), "300");
+        assert_eq!(fixed.format_ordinate(2, -0.001), "0.00");
+    }
+}
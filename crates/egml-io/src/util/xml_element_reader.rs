@@ -1,30 +1,49 @@
 use crate::Error;
 use crate::util::xml_element::XmlElement;
-use quick_xml::Reader;
-use quick_xml::events::Event;
+use crate::util::{
+    DegeneratePolicy, GmlElement, ParseContext, RepairKind, Severity, XLINK_NAMESPACE,
+    XSI_NAMESPACE, is_gml_element, is_gml_namespace, is_gml_prefix,
+};
+use egml_core::model::base::{ChildAnchor, ForeignContent};
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+use quick_xml::{NsReader, XmlVersion};
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use std::collections::HashMap;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::ops::Range;
 use tracing::debug;
 
 #[derive(Debug, Clone)]
 pub struct XmlElementSpans<Elem> {
     spans: HashMap<Elem, Vec<Range<usize>>>,
+    only_foreign_children: bool,
 }
 
 impl<Elem: XmlElement> XmlElementSpans<Elem> {
     pub fn new(spans: HashMap<Elem, Vec<Range<usize>>>) -> Self {
-        Self { spans }
+        Self {
+            spans,
+            only_foreign_children: false,
+        }
     }
 
     /// Constructs spans that make `element` appear as a single direct child covering `[0..len]`.
     /// Used to bridge element-level bytes into parent-level dispatchers.
+    #[allow(clippy::single_range_in_vec_init)]
     pub fn single(element: Elem, len: usize) -> Self {
         let mut spans = HashMap::new();
         spans.insert(element, vec![0..len]);
-        Self { spans }
+        Self::new(spans)
+    }
+
+    /// Returns `true` if the scanned element has child elements, all from
+    /// foreign namespaces, and no `xlink:href`. As a property, it carries
+    /// neither an object nor a reference.
+    pub fn only_foreign_children(&self) -> bool {
+        self.only_foreign_children
     }
 
     pub fn spans(&self) -> &HashMap<Elem, Vec<Range<usize>>> {
@@ -57,7 +76,7 @@ pub fn collect_child<Elem, T, E>(
 ) -> Result<Option<T>, E>
 where
     Elem: XmlElement,
-    E: From<Error>,
+    E: From<Error> + Display,
 {
     let all_spans = spans.get(element);
     if all_spans.len() >= 2 {
@@ -71,12 +90,47 @@ where
         None => Ok(None),
         Some(x) => {
             let slice = &xml_document[x.start..x.end];
-            let child_spans = extract_xml_element_spans(slice)?;
-            deserializer(slice, &child_spans).map(Some)
+            let context = ParseContext::current_or_implicit(xml_document);
+            let result =
+                ParseContext::scope(context, || deserialize_child(slice, element, deserializer));
+            match result {
+                Ok(value) => Ok(value),
+                Err(error) => recover_child(slice, element, error).map(|_| None),
+            }
         }
     }
 }
 
+/// Deserializes the child `element` spanning `xml_fragment`.
+///
+/// Returns `None` if the child only has elements from foreign namespaces,
+/// which no deserializer reads, and reports it as dropped.
+fn deserialize_child<Elem, T, E>(
+    xml_fragment: &[u8],
+    element: Elem,
+    deserializer: fn(&[u8], &XmlElementSpans<Elem>) -> Result<T, E>,
+) -> ChildResult<T, E>
+where
+    Elem: XmlElement,
+    E: From<Error>,
+{
+    let spans = extract_xml_element_spans(xml_fragment)?;
+    if spans.only_foreign_children() {
+        debug!(?element, "dropping element with only foreign content");
+        ParseContext::diagnose(
+            Severity::Error,
+            element.as_str(),
+            "only contains elements from foreign namespaces".to_string(),
+            xml_fragment,
+        );
+        return Ok(None);
+    }
+    deserializer(xml_fragment, &spans).map(Some)
+}
+
+/// The result of [`deserialize_child`].
+type ChildResult<T, E> = Result<Option<T>, E>;
+
 /// Deserializes every span of `element`, pairing each result with the byte
 /// range it came from. Shared by [`collect_children`] (fail-fast) and
 /// [`collect_children_lenient`] (skip-and-continue).
@@ -85,24 +139,31 @@ fn collect_children_raw<Elem, T, E>(
     spans: &XmlElementSpans<Elem>,
     element: Elem,
     deserializer: fn(&[u8], &XmlElementSpans<Elem>) -> Result<T, E>,
-) -> Vec<(Range<usize>, Result<T, E>)>
+) -> Vec<(Range<usize>, ChildResult<T, E>)>
 where
     Elem: XmlElement + Send + Sync,
     T: Send,
     E: From<Error> + Send,
 {
+    let context = ParseContext::current_or_implicit(xml_document);
     spans
         .get(element)
         .into_par_iter()
         .map(|x| {
-            let result = extract_xml_element_spans(&xml_document[x.start..x.end])
-                .map_err(E::from)
-                .and_then(|child_spans| deserializer(&xml_document[x.start..x.end], &child_spans));
+            let result = ParseContext::scope(context.clone(), || {
+                deserialize_child(&xml_document[x.start..x.end], element, deserializer)
+            });
             (x.clone(), result)
         })
         .collect()
 }
 
+/// Deserializes every span of `element`, failing on the first child that
+/// cannot be deserialized.
+///
+/// If the current parse uses [`DegeneratePolicy::Drop`] or lenient mode,
+/// failing children are dropped instead (see [`recover_child`]). Children that
+/// only contain elements from foreign namespaces are always dropped.
 pub fn collect_children<Elem, T, E>(
     xml_document: &[u8],
     spans: &XmlElementSpans<Elem>,
@@ -112,12 +173,16 @@ pub fn collect_children<Elem, T, E>(
 where
     Elem: XmlElement + Send + Sync,
     T: Send,
-    E: From<Error> + Send,
+    E: From<Error> + Send + Display,
 {
-    collect_children_raw(xml_document, spans, element, deserializer)
-        .into_iter()
-        .map(|(_, result)| result)
-        .collect()
+    let mut values = Vec::new();
+    for (span, result) in collect_children_raw(xml_document, spans, element, deserializer) {
+        match result {
+            Ok(value) => values.extend(value),
+            Err(error) => recover_child(&xml_document[span], element, error)?,
+        }
+    }
+    Ok(values)
 }
 
 /// A child element that failed to deserialize and was dropped by
@@ -131,10 +196,10 @@ pub struct SkippedChild<Elem, E> {
 }
 
 /// Like [`collect_children`], but never fails outright: children that fail to
-/// deserialize are dropped (and logged at debug level) instead of aborting
-/// the whole collection. Use this where individual members are allowed to be
-/// invalid without invalidating the rest of the document (e.g. `surfaceMember`),
-/// not for required singleton children.
+/// deserialize are dropped (logged at debug level and reported as repairs or
+/// diagnostics) instead of aborting the whole collection. Use this where individual members
+/// are allowed to be invalid without invalidating the rest of the document
+/// (e.g. `surfaceMember`), not for required singleton children.
 pub fn collect_children_lenient<Elem, T, E>(
     xml_document: &[u8],
     spans: &XmlElementSpans<Elem>,
@@ -144,14 +209,14 @@ pub fn collect_children_lenient<Elem, T, E>(
 where
     Elem: XmlElement + Send + Sync,
     T: Send,
-    E: From<Error> + Send + Debug,
+    E: From<Error> + Send + Debug + Display,
 {
     let mut values = Vec::new();
     let mut skipped = Vec::new();
 
     for (span, result) in collect_children_raw(xml_document, spans, element, deserializer) {
         match result {
-            Ok(value) => values.push(value),
+            Ok(value) => values.extend(value),
             Err(error) => {
                 debug!(
                     ?element,
@@ -160,6 +225,7 @@ where
                     ?error,
                     "skipping invalid child element"
                 );
+                report_dropped_child(&xml_document[span.clone()], element, &error);
                 skipped.push(SkippedChild {
                     element,
                     span,
@@ -172,6 +238,49 @@ where
     (values, skipped)
 }
 
+/// Decides what happens to a child element that failed to deserialize.
+///
+/// Returns `Ok(())` if the current parse allows the child to be dropped, under
+/// [`DegeneratePolicy::Drop`] or in lenient mode, and hands the error back
+/// otherwise. A dropped child is always reported as a [`Severity::Error`]
+/// diagnostic, and under [`DegeneratePolicy::Drop`] as a repair as well.
+pub(crate) fn recover_child<Elem: XmlElement, E: Display>(
+    xml_fragment: &[u8],
+    element: Elem,
+    error: E,
+) -> Result<(), E> {
+    let options = ParseContext::options();
+    if options.degenerate == DegeneratePolicy::Fail && !options.lenient {
+        return Err(error);
+    }
+    debug!(?element, %error, "dropping invalid child element");
+    report_dropped_child(xml_fragment, element, &error);
+    Ok(())
+}
+
+fn report_dropped_child<Elem: XmlElement, E: Display>(
+    xml_fragment: &[u8],
+    element: Elem,
+    error: &E,
+) {
+    if ParseContext::options().degenerate == DegeneratePolicy::Drop {
+        ParseContext::record(
+            RepairKind::DroppedMember {
+                reason: error.to_string(),
+            },
+            element.as_str(),
+            None,
+            xml_fragment,
+        );
+    }
+    ParseContext::diagnose(
+        Severity::Error,
+        element.as_str(),
+        error.to_string(),
+        xml_fragment,
+    );
+}
+
 /// Like [`collect_children`], but for deserializers that manage their own span
 /// extraction and only need the raw element bytes.
 pub fn collect_children_simple<Elem, T, E>(
@@ -185,21 +294,38 @@ where
     T: Send,
     E: Send,
 {
+    let context = ParseContext::current_or_implicit(xml_document);
     spans
         .get(element)
         .into_par_iter()
-        .map(|x| deserializer(&xml_document[x.start..x.end]))
+        .map(|x| {
+            ParseContext::scope(context.clone(), || {
+                deserializer(&xml_document[x.start..x.end])
+            })
+        })
         .collect()
 }
 
+/// Records the byte spans of the direct children of the root element of
+/// `xml_document`.
+///
+/// Children are matched by local name and namespace: elements whose prefix
+/// resolves to a namespace other than GML 3.1.1 or 3.2 are skipped, even if
+/// their local name is a GML one. Declarations outside of `xml_document` are
+/// taken from the document being parsed by the enclosing deserializer; a
+/// prefix that is declared nowhere is only assumed to be GML if it is `gml:` or `gmlce:`,
+/// or outside of [`deserialize_with_options`](crate::util::deserialize_with_options).
 pub fn extract_xml_element_spans<Elem: XmlElement>(
     xml_document: &[u8],
 ) -> Result<XmlElementSpans<Elem>, Error> {
-    let mut reader = Reader::from_reader(xml_document);
+    let mut reader = NsReader::from_reader(xml_document);
     reader.config_mut().trim_text(true);
 
     let mut depth = 0;
     let mut element_spans: HashMap<Elem, Vec<Range<usize>>> = HashMap::new();
+    let mut has_href = false;
+    let mut gml_children = 0;
+    let mut foreign_children = 0;
 
     loop {
         match reader.read_event() {
@@ -213,13 +339,22 @@ pub fn extract_xml_element_spans<Elem: XmlElement>(
                 // depth == 1 means this is the root element (the document being scanned).
                 // Skip recording it; traversal continues into its children.
                 if depth == 1 {
+                    has_href = has_href_attribute(&e);
                     continue;
                 }
 
-                if let Some(x) = Elem::from_local_name(e.local_name().as_ref()) {
-                    // buffer_position() is right after `>` of the start tag,
-                    // so the `<` of the start tag is e.len() + 2 bytes back.
-                    let pos_start = reader.buffer_position() as usize - e.len() - 2;
+                // buffer_position() is right after `>` of the start tag,
+                // so the `<` of the start tag is e.len() + 2 bytes back.
+                let pos_start = reader.buffer_position() as usize - e.len() - 2;
+                let is_gml = is_gml(&reader, &e, &xml_document[pos_start..]);
+                if is_gml {
+                    gml_children += 1;
+                } else {
+                    foreign_children += 1;
+                }
+                if let Some(x) = Elem::from_local_name(e.local_name().as_ref())
+                    && is_gml
+                {
                     reader.read_to_end(e.name())?;
                     // buffer_position() is now right after `>` of the closing tag.
                     let pos_end = reader.buffer_position() as usize;
@@ -230,10 +365,18 @@ pub fn extract_xml_element_spans<Elem: XmlElement>(
             }
             Ok(Event::Empty(e)) if depth == 1 => {
                 // Self-closing elements (<foo/>) only appear as direct children (depth == 1).
-                if let Some(x) = Elem::from_local_name(e.local_name().as_ref()) {
-                    // buffer_position() is right after `>` of `<foo/>`,
-                    // so `<` is e.len() + 3 bytes back (for `<`, `/`, `>`).
-                    let pos_start = reader.buffer_position() as usize - e.len() - 3;
+                // buffer_position() is right after `>` of `<foo/>`,
+                // so `<` is e.len() + 3 bytes back (for `<`, `/`, `>`).
+                let pos_start = reader.buffer_position() as usize - e.len() - 3;
+                let is_gml = is_gml(&reader, &e, &xml_document[pos_start..]);
+                if is_gml {
+                    gml_children += 1;
+                } else {
+                    foreign_children += 1;
+                }
+                if let Some(x) = Elem::from_local_name(e.local_name().as_ref())
+                    && is_gml
+                {
                     let pos_end = reader.buffer_position() as usize;
                     element_spans.entry(x).or_default().push(pos_start..pos_end);
                 }
@@ -247,5 +390,179 @@ pub fn extract_xml_element_spans<Elem: XmlElement>(
         }
     }
 
-    Ok(XmlElementSpans::new(element_spans))
+    Ok(XmlElementSpans {
+        spans: element_spans,
+        only_foreign_children: !has_href && gml_children == 0 && foreign_children > 0,
+    })
+}
+
+/// Fails with [`Error::ForeignElement`] if the root element of `xml_document`
+/// is not in a GML namespace. Used by the deserializers of GML objects, whose
+/// root no parent has checked when they are called directly.
+pub(crate) fn check_gml_root(xml_document: &[u8]) -> Result<(), Error> {
+    let mut reader = NsReader::from_reader(xml_document);
+    let (root, tail) = loop {
+        match reader.read_event()? {
+            Event::Start(e) => break (e, 2),
+            Event::Empty(e) => break (e, 3),
+            Event::Eof => return Ok(()),
+            _ => {}
+        }
+    };
+    let pos_start = reader.buffer_position() as usize - root.len() - tail;
+    if is_gml(&reader, &root, &xml_document[pos_start..]) {
+        Ok(())
+    } else {
+        Err(Error::ForeignElement(
+            String::from_utf8_lossy(root.name().as_ref()).into_owned(),
+        ))
+    }
+}
+
+fn has_href_attribute(element: &BytesStart<'_>) -> bool {
+    element
+        .attributes()
+        .flatten()
+        .any(|a| a.key.local_name().as_ref() == b"href")
+}
+
+/// Collects the attributes, child elements and comments of the root element of
+/// `xml_document` that no deserializer reads.
+///
+/// Namespace declarations of the GML, XLink and XSI namespaces, attributes in
+/// those namespaces and `srsName`/`srsDimension` are read by the
+/// deserializers. So are direct children that are GML elements known to
+/// [`GmlElement`], `gml:name` and the coordinate and envelope elements. Every
+/// other child is kept anchored to the read child before it.
+pub(crate) fn extract_foreign_content(xml_document: &[u8]) -> Result<ForeignContent, Error> {
+    let mut reader = NsReader::from_reader(xml_document);
+    reader.config_mut().trim_text(true);
+
+    let mut foreign_content = ForeignContent::new();
+    let mut depth = 0;
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    let mut anchor: Option<ChildAnchor> = None;
+    loop {
+        // Whitespace before an event is skipped by trim_text and trimmed below.
+        let pos_start = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::Start(e) if depth == 0 => {
+                depth += 1;
+                collect_foreign_attributes(&reader, &e, &mut foreign_content)?;
+            }
+            Event::Empty(e) if depth == 0 => {
+                collect_foreign_attributes(&reader, &e, &mut foreign_content)?;
+                break;
+            }
+            Event::Start(e) => {
+                let is_modelled = is_modelled_child(&reader, &e, &xml_document[pos_start..]);
+                reader.read_to_end(e.name())?;
+                if is_modelled {
+                    anchor = Some(next_anchor(&mut occurrences, &e));
+                } else {
+                    let xml = &xml_document[pos_start..reader.buffer_position() as usize];
+                    foreign_content.push_child(anchor.clone(), raw_fragment(xml));
+                }
+            }
+            Event::Empty(e) => {
+                if is_modelled_child(&reader, &e, &xml_document[pos_start..]) {
+                    anchor = Some(next_anchor(&mut occurrences, &e));
+                } else {
+                    let xml = &xml_document[pos_start..reader.buffer_position() as usize];
+                    foreign_content.push_child(anchor.clone(), raw_fragment(xml));
+                }
+            }
+            Event::Comment(_) if depth == 1 => {
+                let xml = &xml_document[pos_start..reader.buffer_position() as usize];
+                foreign_content.push_child(anchor.clone(), raw_fragment(xml));
+            }
+            Event::End(_) | Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(foreign_content)
+}
+
+/// Returns the anchor of `element` and counts it among its siblings.
+fn next_anchor(occurrences: &mut HashMap<String, usize>, element: &BytesStart<'_>) -> ChildAnchor {
+    let local_name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+    let occurrence = occurrences.entry(local_name.clone()).or_default();
+    let anchor = ChildAnchor::new(local_name, *occurrence);
+    *occurrence += 1;
+    anchor
+}
+
+fn collect_foreign_attributes<R>(
+    reader: &NsReader<R>,
+    element: &BytesStart<'_>,
+    foreign_content: &mut ForeignContent,
+) -> Result<(), Error> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(quick_xml::Error::from)?;
+        if is_modelled_attribute(reader, &attribute) {
+            continue;
+        }
+        let value =
+            attribute.decoded_and_normalized_value(XmlVersion::Implicit1_0, reader.decoder())?;
+        foreign_content.push_attribute(
+            String::from_utf8_lossy(attribute.key.as_ref()),
+            value.into_owned(),
+        );
+    }
+    Ok(())
+}
+
+fn is_modelled_attribute<R>(reader: &NsReader<R>, attribute: &Attribute<'_>) -> bool {
+    let is_known_namespace = |namespace: &[u8]| {
+        is_gml_namespace(namespace)
+            || namespace == XLINK_NAMESPACE.as_bytes()
+            || namespace == XSI_NAMESPACE.as_bytes()
+    };
+    if attribute.key.as_namespace_binding().is_some() {
+        return is_known_namespace(&attribute.value);
+    }
+    match reader.resolver().resolve_attribute(attribute.key) {
+        (ResolveResult::Bound(namespace), _) => is_known_namespace(namespace.as_ref()),
+        (ResolveResult::Unbound, local_name) => {
+            matches!(local_name.as_ref(), b"srsName" | b"srsDimension")
+        }
+        (ResolveResult::Unknown(prefix), _) => {
+            is_gml_prefix(&prefix) || prefix == b"xlink" || prefix == b"xsi"
+        }
+    }
+}
+
+fn is_modelled_child<R>(
+    reader: &NsReader<R>,
+    element: &BytesStart<'_>,
+    xml_fragment: &[u8],
+) -> bool {
+    let local_name = element.local_name();
+    let is_known = matches!(
+        local_name.as_ref(),
+        b"name"
+            | b"pos"
+            | b"coordinates"
+            | b"coord"
+            | b"boundedBy"
+            | b"lowerCorner"
+            | b"upperCorner"
+    ) || GmlElement::from_local_name(local_name.as_ref()).is_some();
+    is_known && is_gml(reader, element, xml_fragment)
+}
+
+fn raw_fragment(xml: &[u8]) -> String {
+    String::from_utf8_lossy(xml.trim_ascii()).into_owned()
+}
+
+fn is_gml<R>(reader: &NsReader<R>, element: &BytesStart<'_>, xml_fragment: &[u8]) -> bool {
+    let is_gml = is_gml_element(reader, element, xml_fragment);
+    if !is_gml {
+        debug!(
+            "skipping element from a foreign namespace: {}",
+            String::from_utf8_lossy(element.name().as_ref())
+        );
+    }
+    is_gml
 }
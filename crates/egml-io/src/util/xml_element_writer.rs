@@ -1,15 +1,21 @@
 use crate::Error;
 use crate::util::formatting::Formatting;
-use crate::util::xml_element::XmlElement;
-use quick_xml::Writer;
+use crate::util::{WriteOptions, serialize_with_options};
+use egml_core::model::base::ForeignChild;
 use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::se::Serializer;
+use quick_xml::{Reader, Writer};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::collections::HashMap;
 use std::io::Write;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct XmlNodeParts {
     pub attributes: Vec<(String, String)>,
     pub content: Vec<XmlNodeContent>,
+    /// Preserved XML that is written among the child elements of `content`,
+    /// see [`ForeignContent`](egml_core::model::base::ForeignContent).
+    pub foreign_children: Vec<ForeignChild>,
 }
 
 impl XmlNodeParts {
@@ -17,6 +23,7 @@ impl XmlNodeParts {
         Self {
             attributes: vec![],
             content,
+            foreign_children: vec![],
         }
     }
 
@@ -24,9 +31,15 @@ impl XmlNodeParts {
         Self {
             attributes: vec![],
             content: vec![],
+            foreign_children: vec![],
         }
     }
 
+    pub fn with_attributes(mut self, attributes: Vec<(String, String)>) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
     pub fn write_contents_to_at_depth<W: Write>(
         &self,
         writer: &mut Writer<W>,
@@ -123,7 +136,7 @@ impl XmlNode {
             .iter()
             .map(|(k, v)| (k.as_str(), v.as_str()))
             .collect();
-        if self.parts.content.is_empty() {
+        if self.parts.content.is_empty() && self.parts.foreign_children.is_empty() {
             writer.write_event(Event::Empty(
                 BytesStart::new(self.name).with_attributes(attributes),
             ))?;
@@ -131,8 +144,12 @@ impl XmlNode {
             writer.write_event(Event::Start(
                 BytesStart::new(self.name).with_attributes(attributes),
             ))?;
-            for item in &self.parts.content {
-                item.write_to(writer, formatting, depth)?;
+            if self.parts.foreign_children.is_empty() {
+                for item in &self.parts.content {
+                    item.write_to(writer, formatting, depth)?;
+                }
+            } else {
+                self.write_content_with_foreign_children(writer, formatting, depth)?;
             }
             write_prefix(writer.get_mut(), formatting, depth)?;
             writer.write_event(Event::End(BytesEnd::new(self.name)))?;
@@ -140,6 +157,189 @@ impl XmlNode {
 
         Ok(())
     }
+
+    /// Writes the content and puts every foreign child after the child
+    /// element it is anchored to. Raw content is split into its top-level
+    /// elements for matching. Elements that are written under another name,
+    /// e.g. several `gml:pos` written as one `gml:posList`, match by
+    /// [`anchor_group`]; an anchor that is not written falls back to the last
+    /// written element of its group, or to the end.
+    fn write_content_with_foreign_children<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        formatting: Formatting,
+        depth: usize,
+    ) -> Result<(), quick_xml::Error> {
+        let mut elements: Vec<(&str, Option<&XmlNode>, &str)> = Vec::new();
+        for item in &self.parts.content {
+            match item {
+                XmlNodeContent::Child(node) => elements.push((node.name, Some(node), "")),
+                XmlNodeContent::Raw(raw) => elements.extend(
+                    split_top_level_elements(raw)?
+                        .into_iter()
+                        .map(|(name, element)| (name, None, element)),
+                ),
+            }
+        }
+
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+        let anchors: Vec<(&str, usize)> = elements
+            .iter()
+            .map(|(name, _, _)| {
+                let group = anchor_group(name.rsplit(':').next().unwrap_or(name));
+                let occurrence = occurrences.entry(group).or_default();
+                *occurrence += 1;
+                (group, *occurrence - 1)
+            })
+            .collect();
+        // Number of written elements that precede each foreign child.
+        let slots: Vec<usize> = self
+            .parts
+            .foreign_children
+            .iter()
+            .map(|x| match &x.anchor {
+                None => 0,
+                Some(anchor) => {
+                    let group = anchor_group(&anchor.local_name);
+                    anchors
+                        .iter()
+                        .rposition(|(g, o)| *g == group && *o <= anchor.occurrence)
+                        .map_or(elements.len(), |i| i + 1)
+                }
+            })
+            .collect();
+
+        for slot in 0..=elements.len() {
+            for (x, _) in self
+                .parts
+                .foreign_children
+                .iter()
+                .zip(&slots)
+                .filter(|(_, s)| **s == slot)
+            {
+                write_foreign_child(writer.get_mut(), x, formatting, depth + 1)?;
+            }
+            match elements.get(slot) {
+                Some((_, Some(node), _)) => {
+                    node.write_to_at_depth(writer, formatting, depth + 1)?
+                }
+                Some((_, None, element)) => {
+                    write_raw(writer.get_mut(), element, formatting, depth + 1)?
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the name under which a child element with `local_name` is
+/// written, so that foreign children anchored to the read name find it.
+fn anchor_group(local_name: &str) -> &str {
+    match local_name {
+        "pos" | "coordinates" | "coord" => "posList",
+        "outerBoundaryIs" => "exterior",
+        "innerBoundaryIs" => "interior",
+        _ => local_name,
+    }
+}
+
+/// An element `name` with `attributes` and `content`.
+pub(crate) fn element(
+    name: &'static str,
+    attributes: &[(&str, String)],
+    content: Vec<XmlNodeContent>,
+) -> XmlNodeContent {
+    let attributes = attributes
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect();
+    XmlNode::new(name, XmlNodeParts::new(content).with_attributes(attributes)).into()
+}
+
+/// A leaf element `name` holding `text`, as raw content so that formatting
+/// does not put the text on a line of its own.
+pub(crate) fn text_element(
+    name: &str,
+    attributes: &[(&str, String)],
+    text: &str,
+) -> XmlNodeContent {
+    let mut element = format!("<{name}");
+    for (key, value) in attributes {
+        element.push_str(&format!(
+            " {key}=\"{}\"",
+            quick_xml::escape::escape(value.as_str())
+        ));
+    }
+    element.push_str(&format!(">{}</{name}>", quick_xml::escape::escape(text)));
+    XmlNodeContent::Raw(element)
+}
+
+/// `id` with every character that an XML id cannot hold replaced by `_`,
+/// and `_` prepended if it does not start with a letter or `_`.
+pub(crate) fn xml_id(id: &str) -> String {
+    let id: String = id
+        .chars()
+        .map(|x| {
+            if x.is_alphanumeric() || matches!(x, '_' | '-' | '.') {
+                x
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if id.starts_with(|x: char| x.is_alphabetic() || x == '_') {
+        id
+    } else {
+        format!("_{id}")
+    }
+}
+
+/// Splits serialized content into its top-level elements, each as qualified
+/// name and the element with the indentation that precedes it.
+fn split_top_level_elements(raw: &str) -> Result<Vec<(&str, &str)>, quick_xml::Error> {
+    let mut reader = Reader::from_str(raw);
+    let mut elements = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    loop {
+        match reader.read_event()? {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            Event::Empty(_) => {}
+            Event::Eof => break,
+            _ => continue,
+        }
+        if depth == 0 {
+            let end = reader.buffer_position() as usize;
+            let element = &raw[start..end];
+            let element = element.strip_prefix('\n').unwrap_or(element);
+            elements.push((element_name(element), element));
+            start = end;
+        }
+    }
+    Ok(elements)
+}
+
+/// Returns the qualified name of the first start tag in `element`.
+fn element_name(element: &str) -> &str {
+    let tag = element.trim_start().trim_start_matches('<');
+    let end = tag
+        .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+        .unwrap_or(tag.len());
+    &tag[..end]
+}
+
+/// Writes a preserved fragment as it was read, only indenting its first line.
+fn write_foreign_child<W: Write>(
+    writer: &mut W,
+    foreign_child: &ForeignChild,
+    formatting: Formatting,
+    depth: usize,
+) -> Result<(), quick_xml::Error> {
+    write_prefix(writer, formatting, depth)?;
+    writer.write_all(foreign_child.xml.as_bytes())?;
+    Ok(())
 }
 
 fn write_raw<W: Write>(
@@ -163,7 +363,13 @@ fn write_raw<W: Write>(
                 writer.write_all(indent.as_bytes())?;
                 // Strip serde's root-level indentation (one level = size chars),
                 // preserving relative nesting beyond that.
-                let stripped = line.get(size..).unwrap_or(line);
+                let level: usize = line
+                    .chars()
+                    .take(size)
+                    .take_while(|x| *x == char)
+                    .map(char::len_utf8)
+                    .sum();
+                let stripped = &line[level..];
                 writer.write_all(stripped.as_bytes())?;
             }
         }
@@ -171,7 +377,7 @@ fn write_raw<W: Write>(
     Ok(())
 }
 
-fn write_prefix<W: Write>(
+pub(crate) fn write_prefix<W: Write>(
     writer: &mut W,
     formatting: Formatting,
     depth: usize,
@@ -190,6 +396,53 @@ fn write_prefix<W: Write>(
     Ok(())
 }
 
+/// Number of members from which [`serialize_members`] works in parallel.
+const PARALLEL_MEMBERS: usize = 64;
+
+/// Serializes the members of an aggregate or collection as child content, in
+/// the order of `members`.
+///
+/// Large member lists are serialized in parallel, with the [`WriteOptions`]
+/// of the calling thread. Each member is then rendered into a byte buffer
+/// right away, so only the written members are kept instead of their
+/// [`XmlNode`] trees. The output is the same as when serializing serially.
+pub fn serialize_members<T, F>(
+    members: &[T],
+    formatting: Formatting,
+    serializer: F,
+) -> Result<Vec<XmlNodeContent>, Error>
+where
+    T: Sync,
+    F: Fn(&T, Formatting) -> Result<XmlNode, Error> + Sync,
+{
+    if members.len() < PARALLEL_MEMBERS {
+        return members
+            .iter()
+            .map(|x| serializer(x, formatting).map(XmlNodeContent::Child))
+            .collect();
+    }
+
+    let options = WriteOptions::current();
+    members
+        .par_iter()
+        .map(|x| {
+            serialize_with_options(options, || {
+                let node = serializer(x, formatting)?;
+                Ok(XmlNodeContent::Raw(render_member(&node, formatting)?))
+            })
+        })
+        .collect()
+}
+
+/// Renders `node` as it is written one level below its parent, as expected by
+/// [`write_raw`].
+fn render_member(node: &XmlNode, formatting: Formatting) -> Result<String, quick_xml::Error> {
+    let mut buffer = Vec::new();
+    node.write_to_at_depth(&mut Writer::new(&mut buffer), formatting, 1)?;
+    let rendered = String::from_utf8_lossy(&buffer);
+    Ok(rendered.strip_prefix('\n').unwrap_or(&rendered).to_string())
+}
+
 pub fn serialize_inner<T: serde::Serialize>(
     gml: T,
     formatting: Formatting,
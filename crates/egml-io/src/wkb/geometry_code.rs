@@ -0,0 +1,62 @@
+use crate::util::SimpleGeometry;
+
+/// Byte order of the numbers in WKB, given by the first byte of each
+/// geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    /// XDR, marked by `0`.
+    BigEndian,
+    /// NDR, marked by `1`, as written by PostGIS on common hardware.
+    #[default]
+    LittleEndian,
+}
+
+impl ByteOrder {
+    pub(super) fn marker(self) -> u8 {
+        match self {
+            Self::BigEndian => 0,
+            Self::LittleEndian => 1,
+        }
+    }
+
+    pub(super) fn from_marker(marker: u8) -> Option<Self> {
+        match marker {
+            0 => Some(Self::BigEndian),
+            1 => Some(Self::LittleEndian),
+            _ => None,
+        }
+    }
+}
+
+pub(super) const POINT: u32 = 1;
+pub(super) const LINE_STRING: u32 = 2;
+pub(super) const POLYGON: u32 = 3;
+pub(super) const MULTI_POINT: u32 = 4;
+pub(super) const MULTI_LINE_STRING: u32 = 5;
+pub(super) const MULTI_POLYGON: u32 = 6;
+pub(super) const GEOMETRY_COLLECTION: u32 = 7;
+pub(super) const POLYHEDRAL_SURFACE: u32 = 15;
+pub(super) const TIN: u32 = 16;
+pub(super) const TRIANGLE: u32 = 17;
+
+/// Added to the type code by ISO WKB for Z coordinates; M adds 2000 and ZM 3000.
+pub(super) const ISO_Z: u32 = 1000;
+pub(super) const EWKB_Z: u32 = 0x8000_0000;
+pub(super) const EWKB_M: u32 = 0x4000_0000;
+pub(super) const EWKB_SRID: u32 = 0x2000_0000;
+
+/// The type code of `geometry`, without dimension or flags.
+pub(super) fn geometry_code(geometry: &SimpleGeometry) -> u32 {
+    match geometry {
+        SimpleGeometry::Point(_) => POINT,
+        SimpleGeometry::LineString(_) => LINE_STRING,
+        SimpleGeometry::Polygon(_) => POLYGON,
+        SimpleGeometry::Triangle(_) => TRIANGLE,
+        SimpleGeometry::MultiPoint(_) => MULTI_POINT,
+        SimpleGeometry::MultiLineString(_) => MULTI_LINE_STRING,
+        SimpleGeometry::MultiPolygon(_) => MULTI_POLYGON,
+        SimpleGeometry::PolyhedralSurface(_) => POLYHEDRAL_SURFACE,
+        SimpleGeometry::Tin(_) => TIN,
+        SimpleGeometry::GeometryCollection(_) => GEOMETRY_COLLECTION,
+    }
+}
@@ -0,0 +1,32 @@
+//! Well-Known Binary (WKB) in the ISO flavour and PostGIS Extended WKB (EWKB).
+//!
+//! Geometries are mapped onto the Simple Features types as for
+//! [WKT](crate::wkt) and always carry Z coordinates: ISO WKB adds 1000 to the
+//! type code, EWKB sets the Z flag and, if the `srsName` names an EPSG code,
+//! the SRID flag followed by the SRID. Members of collections are written
+//! without SRID, as PostGIS does, so that `ST_AsEWKB` and `ST_AsBinary`
+//! output reads and writes back byte for byte.
+//!
+//! [`read_wkb`] accepts both flavours in either byte order and decodes the
+//! borrowed bytes directly, without copying them first. The result converts
+//! to the concrete type with `TryFrom`:
+//!
+//! ```rust
+//! use egml_core::model::geometry::primitives::Polygon;
+//! use egml_io::wkb::{ByteOrder, read_wkb, write_ewkb};
+//! use egml_io::wkt::read_wkt;
+//!
+//! let geometry = read_wkt("SRID=25832;POLYGON Z ((0 0 0, 1 0 0, 0 1 0, 0 0 0))").unwrap();
+//! let ewkb = write_ewkb(&geometry, ByteOrder::LittleEndian).unwrap();
+//!
+//! let polygon = Polygon::try_from(read_wkb(&ewkb).unwrap()).unwrap();
+//! assert_eq!(polygon.exterior().unwrap().object().unwrap().points().len(), 3);
+//! ```
+
+mod geometry_code;
+mod reader;
+mod writer;
+
+pub use geometry_code::ByteOrder;
+pub use reader::*;
+pub use writer::*;
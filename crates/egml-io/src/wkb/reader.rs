@@ -0,0 +1,332 @@
+use crate::Error;
+use crate::util::{Ring, SimpleGeometry, epsg_srs_name};
+use crate::wkb::ByteOrder;
+use crate::wkb::geometry_code::{
+    EWKB_M, EWKB_SRID, EWKB_Z, GEOMETRY_COLLECTION, ISO_Z, LINE_STRING, MULTI_LINE_STRING,
+    MULTI_POINT, MULTI_POLYGON, POINT, POLYGON, POLYHEDRAL_SURFACE, TIN, TRIANGLE,
+};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometryMut, DirectPosition};
+
+/// Size of an encoded position with Z coordinate.
+const POSITION_SIZE: usize = 24;
+
+/// Reads a geometry from ISO WKB or PostGIS EWKB in either byte order.
+///
+/// The SRID of EWKB becomes the `srsName` of the geometry, e.g.
+/// `urn:ogc:def:crs:EPSG::4326` for SRID 4326. Positions must have a Z
+/// coordinate and no M coordinate.
+///
+/// # Errors
+///
+/// Returns [`Error::Wkb`] for truncated or malformed input or trailing bytes,
+/// [`Error::UnsupportedDimension`] for positions without Z or with M
+/// coordinates, and an error if the geometry violates a constraint of the
+/// egml model, e.g. a ring with adjacent duplicate positions.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::AsAbstractGeometry;
+/// use egml_io::wkb::read_wkb;
+///
+/// // SELECT ST_AsEWKB('SRID=4326;POINT Z (1 2 3)'::geometry)
+/// let ewkb = [
+///     0x01, 0x01, 0x00, 0x00, 0xa0, 0xe6, 0x10, 0x00, 0x00, //
+///     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x3f, //
+///     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, //
+///     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x40,
+/// ];
+///
+/// let point = read_wkb(&ewkb).unwrap();
+/// assert_eq!(point.srs_name().unwrap(), "urn:ogc:def:crs:EPSG::4326");
+/// ```
+pub fn read_wkb(wkb: &[u8]) -> Result<AbstractGeometryKind, Error> {
+    let mut reader = WkbReader {
+        wkb,
+        position: 0,
+        byte_order: ByteOrder::LittleEndian,
+    };
+    let (code, srid) = reader.header()?;
+    let simple = reader.body(code)?;
+    if reader.position < wkb.len() {
+        return Err(reader.error("unexpected bytes after the geometry"));
+    }
+
+    let mut geometry = simple.into_geometry()?;
+    if let Some(srid) = srid {
+        geometry.set_srs_name(epsg_srs_name(srid));
+    }
+    Ok(geometry)
+}
+
+struct WkbReader<'a> {
+    wkb: &'a [u8],
+    position: usize,
+    /// Byte order of the geometry being read, which each nested geometry
+    /// declares anew.
+    byte_order: ByteOrder,
+}
+
+impl WkbReader<'_> {
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::Wkb {
+            position: self.position,
+            message: message.into(),
+        }
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let bytes = self
+            .wkb
+            .get(self.position..self.position + N)
+            .ok_or_else(|| self.error("unexpected end of input"))?;
+        self.position += N;
+        Ok(bytes.try_into().expect("slice has length N"))
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.take()?;
+        Ok(match self.byte_order {
+            ByteOrder::BigEndian => u32::from_be_bytes(bytes),
+            ByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+        })
+    }
+
+    fn f64(&mut self) -> Result<f64, Error> {
+        let bytes = self.take()?;
+        Ok(match self.byte_order {
+            ByteOrder::BigEndian => f64::from_be_bytes(bytes),
+            ByteOrder::LittleEndian => f64::from_le_bytes(bytes),
+        })
+    }
+
+    /// Reads a count of items that take at least `item_size` bytes each,
+    /// rejecting counts the remaining input cannot hold.
+    fn count(&mut self, item_size: usize) -> Result<usize, Error> {
+        let count = self.u32()? as usize;
+        if count.saturating_mul(item_size) > self.wkb.len() - self.position {
+            return Err(self.error(format!("count {count} exceeds the input")));
+        }
+        Ok(count)
+    }
+
+    /// Reads byte order and type of a geometry and returns its type code
+    /// without dimension, along with its SRID if it has one.
+    fn header(&mut self) -> Result<(u32, Option<u32>), Error> {
+        let [marker] = self.take()?;
+        self.byte_order = ByteOrder::from_marker(marker)
+            .ok_or_else(|| self.error(format!("invalid byte order {marker}")))?;
+        let code = self.u32()?;
+
+        let extended = code & (EWKB_Z | EWKB_M | EWKB_SRID) != 0;
+        let srid = if code & EWKB_SRID != 0 {
+            Some(self.u32()?)
+        } else {
+            None
+        };
+        let (code, z, m) = if extended {
+            (code & 0x0fff_ffff, code & EWKB_Z != 0, code & EWKB_M != 0)
+        } else {
+            let dimension = code / ISO_Z;
+            (
+                code % ISO_Z,
+                matches!(dimension, 1 | 3),
+                matches!(dimension, 2 | 3),
+            )
+        };
+        match (z, m) {
+            (true, false) => Ok((code, srid)),
+            (true, true) => Err(Error::UnsupportedDimension { found: 4 }),
+            (false, m) => Err(Error::UnsupportedDimension {
+                found: if m { 3 } else { 2 },
+            }),
+        }
+    }
+
+    /// Reads a nested geometry, which must be of type `expected`.
+    fn member(&mut self, expected: u32) -> Result<SimpleGeometry, Error> {
+        let start = self.position;
+        let (code, _) = self.header()?;
+        if code != expected {
+            self.position = start;
+            return Err(self.error(format!(
+                "unexpected member of type {code}, expected {expected}"
+            )));
+        }
+        self.body(code)
+    }
+
+    fn body(&mut self, code: u32) -> Result<SimpleGeometry, Error> {
+        Ok(match code {
+            POINT => SimpleGeometry::Point(self.coordinate()?),
+            LINE_STRING => SimpleGeometry::LineString(self.positions()?),
+            POLYGON => SimpleGeometry::Polygon(self.rings()?),
+            TRIANGLE => SimpleGeometry::Triangle(self.rings()?),
+            MULTI_POINT => SimpleGeometry::MultiPoint(self.members(POINT, |x| match x {
+                SimpleGeometry::Point(x) => x,
+                _ => unreachable!("member type is checked"),
+            })?),
+            MULTI_LINE_STRING => {
+                SimpleGeometry::MultiLineString(self.members(LINE_STRING, |x| match x {
+                    SimpleGeometry::LineString(x) => x,
+                    _ => unreachable!("member type is checked"),
+                })?)
+            }
+            MULTI_POLYGON => SimpleGeometry::MultiPolygon(self.polygons(POLYGON)?),
+            POLYHEDRAL_SURFACE => SimpleGeometry::PolyhedralSurface(self.polygons(POLYGON)?),
+            TIN => SimpleGeometry::Tin(self.polygons(TRIANGLE)?),
+            GEOMETRY_COLLECTION => {
+                let count = self.count(5)?;
+                let mut members = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let (code, _) = self.header()?;
+                    members.push(self.body(code)?);
+                }
+                SimpleGeometry::GeometryCollection(members)
+            }
+            code => return Err(self.error(format!("unsupported geometry type {code}"))),
+        })
+    }
+
+    fn members<T>(
+        &mut self,
+        expected: u32,
+        unwrap: impl Fn(SimpleGeometry) -> T,
+    ) -> Result<Vec<T>, Error> {
+        let count = self.count(5)?;
+        let mut members = Vec::with_capacity(count);
+        for _ in 0..count {
+            members.push(unwrap(self.member(expected)?));
+        }
+        Ok(members)
+    }
+
+    fn polygons(&mut self, expected: u32) -> Result<Vec<Vec<Ring>>, Error> {
+        self.members(expected, |x| match x {
+            SimpleGeometry::Polygon(x) | SimpleGeometry::Triangle(x) => x,
+            _ => unreachable!("member type is checked"),
+        })
+    }
+
+    fn rings(&mut self) -> Result<Vec<Ring>, Error> {
+        let count = self.count(4)?;
+        let mut rings = Vec::with_capacity(count);
+        for _ in 0..count {
+            let start = self.position;
+            let ring = self.positions()?;
+            if ring.first() != ring.last() {
+                self.position = start;
+                return Err(self.error("ring is not closed"));
+            }
+            rings.push(ring);
+        }
+        Ok(rings)
+    }
+
+    fn positions(&mut self) -> Result<Vec<DirectPosition>, Error> {
+        let count = self.count(POSITION_SIZE)?;
+        (0..count).map(|_| self.coordinate()).collect()
+    }
+
+    fn coordinate(&mut self) -> Result<DirectPosition, Error> {
+        Ok(DirectPosition::new(self.f64()?, self.f64()?, self.f64()?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkb::{write_ewkb, write_wkb};
+    use crate::wkt::{read_wkt, write_wkt};
+    use egml_core::model::geometry::AsAbstractGeometry;
+
+    /// `ST_AsEWKB('SRID=4326;POINT Z (1 2 3)'::geometry)`.
+    const POINT_EWKB: &str = "01010000a0e6100000000000000000f03f00000000000000400000000000000840";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|x| format!("{x:02x}")).collect()
+    }
+
+    fn unhex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|x| u8::from_str_radix(&hex[x..x + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn postgis_ewkb_round_trips_bit_exactly() {
+        let point = read_wkb(&unhex(POINT_EWKB)).unwrap();
+
+        assert_eq!(write_wkt(&point).unwrap(), "POINT Z (1 2 3)");
+        assert_eq!(
+            hex(&write_ewkb(&point, ByteOrder::LittleEndian).unwrap()),
+            POINT_EWKB
+        );
+        assert_eq!(
+            hex(&write_wkb(&point, ByteOrder::LittleEndian).unwrap()),
+            "01e9030000000000000000f03f00000000000000400000000000000840"
+        );
+        assert_eq!(
+            hex(&write_wkb(&point, ByteOrder::BigEndian).unwrap()),
+            "00000003e93ff000000000000040000000000000004008000000000000"
+        );
+    }
+
+    #[test]
+    fn every_geometry_type_round_trips_in_both_flavours_and_byte_orders() {
+        for wkt in [
+            "LINESTRING Z (0 0 0, 1 0 0, 1 1 0)",
+            "POLYGON Z ((0 0 0, 4 0 0, 4 4 0, 0 4 0, 0 0 0), (1 1 0, 2 1 0, 2 2 0, 1 1 0))",
+            "TRIANGLE Z ((0 0 0, 1 0 0, 0 1 0, 0 0 0))",
+            "MULTIPOINT Z ((1 2 3), (4 5 6))",
+            "MULTILINESTRING Z ((0 0 0, 1 0 0), (0 1 0, 1 1 0))",
+            "MULTIPOLYGON Z (((0 0 0, 1 0 0, 1 1 0, 0 0 0)), ((0 0 1, 1 0 1, 1 1 1, 0 0 1)))",
+            "POLYHEDRALSURFACE Z (((0 0 0, 1 0 0, 0 1 0, 0 0 0)), ((0 0 0, 0 1 0, 0 0 1, 0 0 0)))",
+            "TIN Z (((0 0 0, 1 0 0, 0 1 0, 0 0 0)), ((1 0 0, 1 1 0, 0 1 0, 1 0 0)))",
+            "GEOMETRYCOLLECTION Z (POINT Z (1 2 3), POLYGON Z EMPTY)",
+        ] {
+            let geometry = read_wkt(&format!("SRID=25832;{wkt}")).unwrap();
+            for byte_order in [ByteOrder::LittleEndian, ByteOrder::BigEndian] {
+                for write in [write_wkb, write_ewkb] {
+                    let wkb = write(&geometry, byte_order).unwrap();
+                    let read = read_wkb(&wkb).unwrap();
+
+                    assert_eq!(write_wkt(&read).unwrap(), wkt);
+                    assert_eq!(write(&read, byte_order).unwrap(), wkb);
+                }
+            }
+            let ewkb = write_ewkb(&geometry, ByteOrder::BigEndian).unwrap();
+            assert_eq!(
+                read_wkb(&ewkb).unwrap().srs_name().map(String::as_str),
+                Some("urn:ogc:def:crs:EPSG::25832")
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        let point = unhex(POINT_EWKB);
+
+        assert!(matches!(
+            read_wkb(&point[..20]),
+            Err(Error::Wkb { position: 17, .. })
+        ));
+        assert!(matches!(
+            read_wkb(&[point.as_slice(), &[0]].concat()),
+            Err(Error::Wkb { position: 33, .. })
+        ));
+        assert!(matches!(
+            read_wkb(&unhex("0101000000000000000000f03f0000000000000040")),
+            Err(Error::UnsupportedDimension { found: 2 })
+        ));
+        assert!(matches!(
+            read_wkb(&unhex("0104000080ffffffff")),
+            Err(Error::Wkb { position: 9, .. })
+        ));
+        assert!(matches!(
+            read_wkb(&unhex("02")),
+            Err(Error::Wkb { position: 1, .. })
+        ));
+    }
+}
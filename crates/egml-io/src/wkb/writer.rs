@@ -0,0 +1,147 @@
+use crate::Error;
+use crate::util::{Ring, SimpleGeometry, epsg_code};
+use crate::wkb::ByteOrder;
+use crate::wkb::geometry_code::{
+    EWKB_SRID, EWKB_Z, ISO_Z, LINE_STRING, POINT, POLYGON, TRIANGLE, geometry_code,
+};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometry, DirectPosition};
+
+/// Writes `geometry` as ISO WKB with Z coordinates, as `ST_AsBinary` does.
+///
+/// # Errors
+///
+/// Returns [`Error::Unrepresentable`] for geometries without a Simple
+/// Features counterpart, such as a `Solid` with interior shells, and an
+/// error if a member is an unresolved `xlink:href` reference.
+pub fn write_wkb(geometry: &AbstractGeometryKind, byte_order: ByteOrder) -> Result<Vec<u8>, Error> {
+    let simple = SimpleGeometry::from_geometry(geometry, "WKB")?;
+    let mut writer = WkbWriter {
+        wkb: Vec::new(),
+        byte_order,
+        extended: false,
+    };
+    writer.geometry(&simple, None);
+    Ok(writer.wkb)
+}
+
+/// Writes `geometry` as PostGIS EWKB, as `ST_AsEWKB` does. The SRID is
+/// included if the `srsName` of `geometry` names an EPSG code.
+///
+/// # Errors
+///
+/// Returns the errors of [`write_wkb`].
+pub fn write_ewkb(
+    geometry: &AbstractGeometryKind,
+    byte_order: ByteOrder,
+) -> Result<Vec<u8>, Error> {
+    let simple = SimpleGeometry::from_geometry(geometry, "WKB")?;
+    let mut writer = WkbWriter {
+        wkb: Vec::new(),
+        byte_order,
+        extended: true,
+    };
+    writer.geometry(&simple, geometry.srs_name().and_then(|x| epsg_code(x)));
+    Ok(writer.wkb)
+}
+
+struct WkbWriter {
+    wkb: Vec<u8>,
+    byte_order: ByteOrder,
+    extended: bool,
+}
+
+impl WkbWriter {
+    fn u32(&mut self, value: u32) {
+        match self.byte_order {
+            ByteOrder::BigEndian => self.wkb.extend_from_slice(&value.to_be_bytes()),
+            ByteOrder::LittleEndian => self.wkb.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    fn f64(&mut self, value: f64) {
+        match self.byte_order {
+            ByteOrder::BigEndian => self.wkb.extend_from_slice(&value.to_be_bytes()),
+            ByteOrder::LittleEndian => self.wkb.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    fn count(&mut self, count: usize) {
+        self.u32(count as u32);
+    }
+
+    /// Writes the byte order, type and, for EWKB with `srid`, the SRID.
+    fn header(&mut self, code: u32, srid: Option<u32>) {
+        self.wkb.push(self.byte_order.marker());
+        if !self.extended {
+            self.u32(code + ISO_Z);
+        } else if let Some(srid) = srid {
+            self.u32(code | EWKB_Z | EWKB_SRID);
+            self.u32(srid);
+        } else {
+            self.u32(code | EWKB_Z);
+        }
+    }
+
+    fn geometry(&mut self, geometry: &SimpleGeometry, srid: Option<u32>) {
+        self.header(geometry_code(geometry), srid);
+        match geometry {
+            SimpleGeometry::Point(x) => self.position(x),
+            SimpleGeometry::LineString(x) => self.positions(x),
+            SimpleGeometry::Polygon(x) | SimpleGeometry::Triangle(x) => self.rings(x),
+            SimpleGeometry::MultiPoint(x) => {
+                self.count(x.len());
+                for point in x {
+                    self.header(POINT, None);
+                    self.position(point);
+                }
+            }
+            SimpleGeometry::MultiLineString(x) => {
+                self.count(x.len());
+                for line in x {
+                    self.header(LINE_STRING, None);
+                    self.positions(line);
+                }
+            }
+            SimpleGeometry::MultiPolygon(x) | SimpleGeometry::PolyhedralSurface(x) => {
+                self.count(x.len());
+                for polygon in x {
+                    self.header(POLYGON, None);
+                    self.rings(polygon);
+                }
+            }
+            SimpleGeometry::Tin(x) => {
+                self.count(x.len());
+                for triangle in x {
+                    self.header(TRIANGLE, None);
+                    self.rings(triangle);
+                }
+            }
+            SimpleGeometry::GeometryCollection(x) => {
+                self.count(x.len());
+                for member in x {
+                    self.geometry(member, None);
+                }
+            }
+        }
+    }
+
+    fn rings(&mut self, rings: &[Ring]) {
+        self.count(rings.len());
+        for ring in rings {
+            self.positions(ring);
+        }
+    }
+
+    fn positions(&mut self, positions: &[DirectPosition]) {
+        self.count(positions.len());
+        for position in positions {
+            self.position(position);
+        }
+    }
+
+    fn position(&mut self, position: &DirectPosition) {
+        for value in position.coords() {
+            self.f64(value);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! Well-Known Text (WKT) and PostGIS Extended WKT (EWKT) for egml geometries.
+//!
+//! Geometries are written with Z coordinates and mapped onto the Simple
+//! Features types as follows:
+//!
+//! | egml geometry | WKT |
+//! |---------------|-----|
+//! | `Point` | `POINT Z` |
+//! | `LineString`, `LinearRing` | `LINESTRING Z` |
+//! | `Polygon`, `Surface` with one polygon patch | `POLYGON Z` |
+//! | `Surface` with one triangle patch | `TRIANGLE Z` |
+//! | `TriangulatedSurface` | `TIN Z` |
+//! | `Solid`, `Shell`, `CompositeSurface`, other `Surface`s | `POLYHEDRALSURFACE Z`, or `TIN Z` if all faces are triangles |
+//! | `MultiPoint` | `MULTIPOINT Z` |
+//! | `MultiCurve` | `MULTILINESTRING Z` |
+//! | `MultiSurface` | `MULTIPOLYGON Z`, with composite members flattened |
+//! | `MultiGeometry` | `GEOMETRYCOLLECTION Z` |
+//!
+//! Reading maps each type back to the first geometry of its row, with
+//! `POLYHEDRALSURFACE` read as a `Solid`. Closing vertices, which WKT
+//! requires, are removed from the rings. Coordinates are formatted according
+//! to the [`WriteOptions`](crate::util::WriteOptions) installed with
+//! [`serialize_with_options`](crate::util::serialize_with_options).
+//!
+//! EWKT prefixes the text with the EPSG code of the `srsName` as `SRID=…;`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use egml_core::model::geometry::AsAbstractGeometry;
+//! use egml_io::wkt::{read_wkt, write_ewkt};
+//!
+//! let point = read_wkt("SRID=25832;POINT Z (1 2 3)").unwrap();
+//!
+//! assert_eq!(point.srs_name().unwrap(), "urn:ogc:def:crs:EPSG::25832");
+//! assert_eq!(write_ewkt(&point).unwrap(), "SRID=25832;POINT Z (1 2 3)");
+//! ```
+
+mod reader;
+mod writer;
+
+pub use reader::*;
+pub use writer::*;
@@ -0,0 +1,420 @@
+use crate::Error;
+use crate::util::{Ring, SimpleGeometry, epsg_srs_name};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometryMut, DirectPosition};
+
+/// Reads a geometry from WKT or EWKT.
+///
+/// Keywords are case-insensitive, and the `Z` may be omitted or attached to
+/// the type name (`POINTZ`). Coordinates must have three ordinates; `M` and
+/// `ZM` geometries are rejected. The `SRID` of EWKT becomes the `srsName` of
+/// the geometry, e.g. `urn:ogc:def:crs:EPSG::4326` for `SRID=4326;`.
+///
+/// # Errors
+///
+/// Returns [`Error::Wkt`] for malformed text, [`Error::UnsupportedDimension`]
+/// for coordinates with other than three ordinates, and an error if the
+/// geometry violates a constraint of the egml model, e.g. a ring with
+/// adjacent duplicate positions.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::common::{GeometryType, HasGeometryType};
+/// use egml_io::wkt::read_wkt;
+///
+/// let tin = read_wkt("TIN Z (((0 0 0, 1 0 0, 0 1 0, 0 0 0)))").unwrap();
+/// assert_eq!(tin.geometry_type(), GeometryType::TriangulatedSurface);
+/// ```
+pub fn read_wkt(wkt: &str) -> Result<AbstractGeometryKind, Error> {
+    let mut reader = WktReader { wkt, position: 0 };
+    let srid = reader.srid()?;
+    let simple = reader.geometry()?;
+    reader.skip_whitespace();
+    if reader.position < wkt.len() {
+        return Err(reader.error("unexpected text after the geometry"));
+    }
+
+    let mut geometry = simple.into_geometry()?;
+    if let Some(srid) = srid {
+        geometry.set_srs_name(epsg_srs_name(srid));
+    }
+    Ok(geometry)
+}
+
+struct WktReader<'a> {
+    wkt: &'a str,
+    position: usize,
+}
+
+impl WktReader<'_> {
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::Wkt {
+            position: self.position,
+            message: message.into(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.wkt[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.wkt.as_bytes().get(self.position).copied()
+    }
+
+    fn consume(&mut self, expected: u8) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), Error> {
+        if self.consume(expected) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", expected as char)))
+        }
+    }
+
+    /// Reads a run of letters, upper-cased.
+    fn word(&mut self) -> String {
+        self.skip_whitespace();
+        let rest = &self.wkt[self.position..];
+        let length = rest
+            .find(|x: char| !x.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        self.position += length;
+        rest[..length].to_ascii_uppercase()
+    }
+
+    /// Reads the `SRID=<code>;` prefix of EWKT, if present.
+    fn srid(&mut self) -> Result<Option<u32>, Error> {
+        self.skip_whitespace();
+        if !self.wkt[self.position..]
+            .get(..4)
+            .is_some_and(|x| x.eq_ignore_ascii_case("SRID"))
+        {
+            return Ok(None);
+        }
+        self.position += 4;
+        self.expect(b'=')?;
+        self.skip_whitespace();
+        let rest = &self.wkt[self.position..];
+        let length = rest
+            .find(|x: char| !x.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let srid = rest[..length]
+            .parse()
+            .map_err(|_| self.error("expected an SRID"))?;
+        self.position += length;
+        self.expect(b';')?;
+        Ok(Some(srid))
+    }
+
+    fn geometry(&mut self) -> Result<SimpleGeometry, Error> {
+        let start = self.position;
+        let mut keyword = self.word();
+        let mut dimension = String::new();
+        if !is_keyword(&keyword) {
+            for suffix in ["ZM", "Z", "M"] {
+                if let Some(x) = keyword.strip_suffix(suffix)
+                    && is_keyword(x)
+                {
+                    dimension = suffix.to_string();
+                    keyword.truncate(x.len());
+                    break;
+                }
+            }
+        }
+        if !is_keyword(&keyword) {
+            self.position = start;
+            return Err(self.error(format!("unknown geometry type '{keyword}'")));
+        }
+
+        let mut empty = false;
+        if self.peek().is_some_and(|x| x.is_ascii_alphabetic()) {
+            match self.word().as_str() {
+                "EMPTY" => empty = true,
+                x if dimension.is_empty() && matches!(x, "Z" | "M" | "ZM") => dimension = x.into(),
+                x => return Err(self.error(format!("unexpected '{x}'"))),
+            }
+        }
+        match dimension.as_str() {
+            "ZM" => return Err(Error::UnsupportedDimension { found: 4 }),
+            "M" => return Err(self.error("M coordinates are not supported")),
+            _ => {}
+        }
+        if !empty && self.peek().is_some_and(|x| x.is_ascii_alphabetic()) {
+            if self.word() != "EMPTY" {
+                return Err(self.error("expected '(' or EMPTY"));
+            }
+            empty = true;
+        }
+
+        if empty {
+            return match keyword.as_str() {
+                "POINT" => Err(self.error("empty points are not supported")),
+                "LINESTRING" => Ok(SimpleGeometry::LineString(Vec::new())),
+                "POLYGON" => Ok(SimpleGeometry::Polygon(Vec::new())),
+                "TRIANGLE" => Ok(SimpleGeometry::Triangle(Vec::new())),
+                "MULTIPOINT" => Ok(SimpleGeometry::MultiPoint(Vec::new())),
+                "MULTILINESTRING" => Ok(SimpleGeometry::MultiLineString(Vec::new())),
+                "MULTIPOLYGON" => Ok(SimpleGeometry::MultiPolygon(Vec::new())),
+                "POLYHEDRALSURFACE" => Ok(SimpleGeometry::PolyhedralSurface(Vec::new())),
+                "TIN" => Ok(SimpleGeometry::Tin(Vec::new())),
+                _ => Ok(SimpleGeometry::GeometryCollection(Vec::new())),
+            };
+        }
+
+        Ok(match keyword.as_str() {
+            "POINT" => {
+                self.expect(b'(')?;
+                let position = self.coordinate()?;
+                self.expect(b')')?;
+                SimpleGeometry::Point(position)
+            }
+            "LINESTRING" => SimpleGeometry::LineString(self.positions()?),
+            "POLYGON" => SimpleGeometry::Polygon(self.rings()?),
+            "TRIANGLE" => SimpleGeometry::Triangle(self.rings()?),
+            "MULTIPOINT" => SimpleGeometry::MultiPoint(self.list(|x| {
+                // Both `MULTIPOINT ((1 2 3), (4 5 6))` and `MULTIPOINT (1 2 3, 4 5 6)`.
+                if x.consume(b'(') {
+                    let position = x.coordinate()?;
+                    x.expect(b')')?;
+                    Ok(position)
+                } else {
+                    x.coordinate()
+                }
+            })?),
+            "MULTILINESTRING" => SimpleGeometry::MultiLineString(self.list(Self::positions)?),
+            "MULTIPOLYGON" => SimpleGeometry::MultiPolygon(self.list(Self::rings)?),
+            "POLYHEDRALSURFACE" => SimpleGeometry::PolyhedralSurface(self.list(Self::rings)?),
+            "TIN" => SimpleGeometry::Tin(self.list(Self::rings)?),
+            _ => SimpleGeometry::GeometryCollection(self.list(Self::geometry)?),
+        })
+    }
+
+    /// Reads `item`s separated by commas and enclosed in parentheses.
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error> {
+        self.expect(b'(')?;
+        let mut items = vec![item(self)?];
+        while self.consume(b',') {
+            items.push(item(self)?);
+        }
+        self.expect(b')')?;
+        Ok(items)
+    }
+
+    fn rings(&mut self) -> Result<Vec<Ring>, Error> {
+        self.list(|x| {
+            let start = x.position;
+            let ring = x.positions()?;
+            if ring.first() != ring.last() {
+                x.position = start;
+                return Err(x.error("ring is not closed"));
+            }
+            Ok(ring)
+        })
+    }
+
+    fn positions(&mut self) -> Result<Vec<DirectPosition>, Error> {
+        self.list(Self::coordinate)
+    }
+
+    fn coordinate(&mut self) -> Result<DirectPosition, Error> {
+        let mut ordinates = Vec::with_capacity(3);
+        while self
+            .peek()
+            .is_some_and(|x| x.is_ascii_digit() || matches!(x, b'-' | b'+' | b'.'))
+        {
+            ordinates.push(self.number()?);
+        }
+        match ordinates[..] {
+            [x, y, z] => Ok(DirectPosition::new(x, y, z)?),
+            [] => Err(self.error("expected a coordinate")),
+            _ => Err(Error::UnsupportedDimension {
+                found: ordinates.len() as u32,
+            }),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, Error> {
+        let rest = &self.wkt[self.position..];
+        let length = rest
+            .find(|x: char| !(x.is_ascii_digit() || matches!(x, '-' | '+' | '.' | 'e' | 'E')))
+            .unwrap_or(rest.len());
+        let number = rest[..length]
+            .parse()
+            .map_err(|_| self.error(format!("invalid number '{}'", &rest[..length])))?;
+        self.position += length;
+        Ok(number)
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    matches!(
+        word,
+        "POINT"
+            | "LINESTRING"
+            | "POLYGON"
+            | "TRIANGLE"
+            | "MULTIPOINT"
+            | "MULTILINESTRING"
+            | "MULTIPOLYGON"
+            | "POLYHEDRALSURFACE"
+            | "TIN"
+            | "GEOMETRYCOLLECTION"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{WriteOptions, serialize_with_options};
+    use crate::wkt::{write_ewkt, write_wkt};
+    use egml_core::model::common::{CoordinatePrecision, GeometryType, HasGeometryType};
+    use egml_core::model::geometry::AsAbstractGeometry;
+    use egml_core::model::geometry::primitives::{
+        AbstractSurfaceProperty, Polygon, Shell, ShellProperty, Solid,
+    };
+
+    fn round_trip(wkt: &str) -> String {
+        write_wkt(&read_wkt(wkt).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn every_geometry_type_round_trips() {
+        for wkt in [
+            "POINT Z (1 2 3)",
+            "LINESTRING Z (0 0 0, 1 0 0, 1 1 0)",
+            "POLYGON Z ((0 0 0, 4 0 0, 4 4 0, 0 4 0, 0 0 0), (1 1 0, 2 1 0, 2 2 0, 1 1 0))",
+            "TRIANGLE Z ((0 0 0, 1 0 0, 0 1 0, 0 0 0))",
+            "MULTIPOINT Z ((1 2 3), (4 5 6))",
+            "MULTILINESTRING Z ((0 0 0, 1 0 0), (0 1 0, 1 1 0))",
+            "MULTIPOLYGON Z (((0 0 0, 1 0 0, 1 1 0, 0 0 0)), ((0 0 1, 1 0 1, 1 1 1, 0 0 1)))",
+            "POLYHEDRALSURFACE Z (((0 0 0, 1 0 0, 0 1 0, 0 0 0)), ((0 0 0, 0 1 0, 0 0 1, 0 0 0)))",
+            "TIN Z (((0 0 0, 1 0 0, 0 1 0, 0 0 0)), ((1 0 0, 1 1 0, 0 1 0, 1 0 0)))",
+            "GEOMETRYCOLLECTION Z (POINT Z (1 2 3), LINESTRING Z (0 0 0, 1 1 1))",
+            "GEOMETRYCOLLECTION Z EMPTY",
+            "POLYGON Z EMPTY",
+        ] {
+            assert_eq!(round_trip(wkt), wkt);
+        }
+    }
+
+    #[test]
+    fn types_map_to_the_egml_model() {
+        let cases = [
+            ("POLYHEDRALSURFACE Z EMPTY", GeometryType::Solid),
+            (
+                "TRIANGLE Z ((0 0 0, 1 0 0, 0 1 0, 0 0 0))",
+                GeometryType::Surface,
+            ),
+            (
+                "MULTILINESTRING Z ((0 0 0, 1 0 0))",
+                GeometryType::MultiCurve,
+            ),
+            ("GEOMETRYCOLLECTION Z EMPTY", GeometryType::MultiGeometry),
+        ];
+        for (wkt, geometry_type) in cases {
+            assert_eq!(read_wkt(wkt).unwrap().geometry_type(), geometry_type);
+        }
+        // A MultiSurface needs at least one member in the egml model.
+        assert!(matches!(
+            read_wkt("MULTIPOLYGON Z EMPTY"),
+            Err(Error::EgmlError(_))
+        ));
+    }
+
+    #[test]
+    fn lenient_syntax_is_accepted() {
+        assert_eq!(round_trip("pointz(1 2 3)"), "POINT Z (1 2 3)");
+        assert_eq!(round_trip(" Point ( 1e1 -2.5 +3 ) "), "POINT Z (10 -2.5 3)");
+        assert_eq!(
+            round_trip("MULTIPOINT Z (1 2 3, 4 5 6)"),
+            "MULTIPOINT Z ((1 2 3), (4 5 6))"
+        );
+    }
+
+    #[test]
+    fn malformed_text_reports_the_position() {
+        assert!(matches!(
+            read_wkt("POINT Z (1 2 3"),
+            Err(Error::Wkt { position: 14, .. })
+        ));
+        assert!(matches!(
+            read_wkt("CIRCLE (1 2 3)"),
+            Err(Error::Wkt { position: 0, .. })
+        ));
+        assert!(matches!(
+            read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1 1 0))"),
+            Err(Error::Wkt { position: 11, .. })
+        ));
+        assert!(matches!(
+            read_wkt("POINT (1 2)"),
+            Err(Error::UnsupportedDimension { found: 2 })
+        ));
+        assert!(matches!(
+            read_wkt("POINT ZM (1 2 3 4)"),
+            Err(Error::UnsupportedDimension { found: 4 })
+        ));
+        assert!(read_wkt("POINT Z (1 2 3) POINT").is_err());
+    }
+
+    #[test]
+    fn ewkt_carries_the_srid_as_srs_name() {
+        let geometry = read_wkt("srid=4979; LINESTRING Z (0 0 0, 1 1 1)").unwrap();
+
+        assert_eq!(
+            geometry.srs_name().map(String::as_str),
+            Some("urn:ogc:def:crs:EPSG::4979")
+        );
+        assert_eq!(
+            write_ewkt(&geometry).unwrap(),
+            "SRID=4979;LINESTRING Z (0 0 0, 1 1 1)"
+        );
+        assert_eq!(
+            write_ewkt(&read_wkt("POINT Z (1 2 3)").unwrap()).unwrap(),
+            "POINT Z (1 2 3)"
+        );
+    }
+
+    #[test]
+    fn solids_are_written_from_their_exterior_shell() {
+        let polygon: Polygon = match read_wkt("POLYGON Z ((0 0 0, 1 0 0, 0 1 0, 0 0 0))").unwrap() {
+            AbstractGeometryKind::AbstractGeometricPrimitiveKind(x) => x.try_into().unwrap(),
+            _ => unreachable!(),
+        };
+        let shell = Shell::new([AbstractSurfaceProperty::from_object(polygon.into())]).unwrap();
+        let mut solid = Solid::new(Some(ShellProperty::from_object(shell.clone()))).unwrap();
+
+        assert_eq!(
+            write_wkt(&solid.clone().into()).unwrap(),
+            "POLYHEDRALSURFACE Z (((0 0 0, 1 0 0, 0 1 0, 0 0 0)))"
+        );
+
+        solid.push_interior(ShellProperty::from_object(shell));
+        assert!(matches!(
+            write_wkt(&solid.into()),
+            Err(Error::Unrepresentable { format: "WKT", .. })
+        ));
+    }
+
+    #[test]
+    fn coordinates_follow_the_write_options() {
+        let geometry = read_wkt("POINT Z (1.23456 2 -0.0001)").unwrap();
+        let options = WriteOptions::default().with_precision(CoordinatePrecision::uniform(2));
+
+        let wkt = serialize_with_options(options, || write_wkt(&geometry)).unwrap();
+
+        assert_eq!(wkt, "POINT Z (1.23 2.00 0.00)");
+    }
+}
@@ -0,0 +1,139 @@
+use crate::Error;
+use crate::util::{Ring, SimpleGeometry, WriteOptions, epsg_code};
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometry, DirectPosition};
+
+/// Writes `geometry` as WKT with Z coordinates.
+///
+/// # Errors
+///
+/// Returns [`Error::Unrepresentable`] for geometries without a WKT
+/// counterpart, such as a `Solid` with interior shells, and an error if a
+/// member is an unresolved `xlink:href` reference.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_core::model::geometry::DirectPosition;
+/// use egml_core::model::geometry::primitives::LineString;
+/// use egml_io::wkt::write_wkt;
+///
+/// let line = LineString::new([
+///     DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+///     DirectPosition::new(1.5, 0.0, 2.0).unwrap(),
+/// ])
+/// .unwrap();
+///
+/// assert_eq!(
+///     write_wkt(&line.into()).unwrap(),
+///     "LINESTRING Z (0 0 0, 1.5 0 2)"
+/// );
+/// ```
+pub fn write_wkt(geometry: &AbstractGeometryKind) -> Result<String, Error> {
+    let simple = SimpleGeometry::from_geometry(geometry, "WKT")?;
+    let mut wkt = String::new();
+    write_tagged(&mut wkt, &simple, &WriteOptions::current());
+    Ok(wkt)
+}
+
+/// Writes `geometry` as EWKT, i.e. WKT prefixed with `SRID=<code>;` if the
+/// `srsName` of `geometry` names an EPSG code.
+///
+/// # Errors
+///
+/// Returns the errors of [`write_wkt`].
+pub fn write_ewkt(geometry: &AbstractGeometryKind) -> Result<String, Error> {
+    let wkt = write_wkt(geometry)?;
+    Ok(match geometry.srs_name().and_then(|x| epsg_code(x)) {
+        Some(srid) => format!("SRID={srid};{wkt}"),
+        None => wkt,
+    })
+}
+
+fn write_tagged(wkt: &mut String, geometry: &SimpleGeometry, options: &WriteOptions) {
+    wkt.push_str(keyword(geometry));
+    wkt.push_str(" Z ");
+    if is_empty(geometry) {
+        wkt.push_str("EMPTY");
+        return;
+    }
+    match geometry {
+        SimpleGeometry::Point(x) => {
+            wkt.push('(');
+            write_position(wkt, x, options);
+            wkt.push(')');
+        }
+        SimpleGeometry::LineString(x) => write_positions(wkt, x, options),
+        SimpleGeometry::Polygon(x) | SimpleGeometry::Triangle(x) => write_rings(wkt, x, options),
+        SimpleGeometry::MultiPoint(x) => write_list(wkt, x, |wkt, x| {
+            wkt.push('(');
+            write_position(wkt, x, options);
+            wkt.push(')');
+        }),
+        SimpleGeometry::MultiLineString(x) => {
+            write_list(wkt, x, |wkt, x| write_positions(wkt, x, options))
+        }
+        SimpleGeometry::MultiPolygon(x)
+        | SimpleGeometry::PolyhedralSurface(x)
+        | SimpleGeometry::Tin(x) => write_list(wkt, x, |wkt, x| write_rings(wkt, x, options)),
+        SimpleGeometry::GeometryCollection(x) => {
+            write_list(wkt, x, |wkt, x| write_tagged(wkt, x, options))
+        }
+    }
+}
+
+fn keyword(geometry: &SimpleGeometry) -> &'static str {
+    match geometry {
+        SimpleGeometry::Point(_) => "POINT",
+        SimpleGeometry::LineString(_) => "LINESTRING",
+        SimpleGeometry::Polygon(_) => "POLYGON",
+        SimpleGeometry::Triangle(_) => "TRIANGLE",
+        SimpleGeometry::MultiPoint(_) => "MULTIPOINT",
+        SimpleGeometry::MultiLineString(_) => "MULTILINESTRING",
+        SimpleGeometry::MultiPolygon(_) => "MULTIPOLYGON",
+        SimpleGeometry::PolyhedralSurface(_) => "POLYHEDRALSURFACE",
+        SimpleGeometry::Tin(_) => "TIN",
+        SimpleGeometry::GeometryCollection(_) => "GEOMETRYCOLLECTION",
+    }
+}
+
+fn is_empty(geometry: &SimpleGeometry) -> bool {
+    match geometry {
+        SimpleGeometry::Point(_) => false,
+        SimpleGeometry::LineString(x) | SimpleGeometry::MultiPoint(x) => x.is_empty(),
+        SimpleGeometry::Polygon(x) | SimpleGeometry::Triangle(x) => x.is_empty(),
+        SimpleGeometry::MultiLineString(x) => x.is_empty(),
+        SimpleGeometry::MultiPolygon(x)
+        | SimpleGeometry::PolyhedralSurface(x)
+        | SimpleGeometry::Tin(x) => x.is_empty(),
+        SimpleGeometry::GeometryCollection(x) => x.is_empty(),
+    }
+}
+
+/// Writes `items` comma-separated in parentheses.
+fn write_list<T>(wkt: &mut String, items: &[T], mut write_item: impl FnMut(&mut String, &T)) {
+    wkt.push('(');
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            wkt.push_str(", ");
+        }
+        write_item(wkt, item);
+    }
+    wkt.push(')');
+}
+
+fn write_rings(wkt: &mut String, rings: &[Ring], options: &WriteOptions) {
+    write_list(wkt, rings, |wkt, x| write_positions(wkt, x, options));
+}
+
+fn write_positions(wkt: &mut String, positions: &[DirectPosition], options: &WriteOptions) {
+    write_list(wkt, positions, |wkt, x| write_position(wkt, x, options));
+}
+
+fn write_position(wkt: &mut String, position: &DirectPosition, options: &WriteOptions) {
+    for (axis, value) in position.coords().into_iter().enumerate() {
+        if axis > 0 {
+            wkt.push(' ');
+        }
+        wkt.push_str(&options.format_ordinate(axis, value));
+    }
+}
@@ -0,0 +1,9 @@
+//! X3D 3.3 export of triangulated geometries, e.g. for web viewers built
+//! on X3DOM.
+//!
+//! X3D is written in its XML encoding. It holds meshes only, so surfaces and
+//! solids are triangulated first and points and curves cannot be written.
+
+mod writer;
+
+pub use writer::*;
@@ -0,0 +1,348 @@
+use crate::Error;
+use crate::util::{
+    Formatting, LengthUnit, SceneTransform, UpAxis, WriteOptions, XmlNode, XmlNodeParts, element,
+    lower_corner, xml_id,
+};
+use egml_core::model::base::AsAbstractGml;
+use egml_core::model::common::Triangulate;
+use egml_core::model::geometry::{AbstractGeometryKind, AsAbstractGeometry, DirectPosition};
+use quick_xml::Writer;
+use quick_xml::events::{BytesDecl, Event};
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Location of the X3D 3.3 schema.
+pub const X3D_SCHEMA_LOCATION: &str = "http://www.web3d.org/specifications/x3d-3.3.xsd";
+
+/// The `DEF` of the root `<Transform>`.
+const ROOT_DEF: &str = "root";
+
+/// Writes geometries as an X3D 3.3 document, triangulated with
+/// [`Triangulate`].
+///
+/// Each geometry becomes a `<Shape>` with an `<IndexedTriangleSet>`, whose
+/// `DEF` is its `gml:id`, or `geometry_<n>` for the `n`-th geometry without
+/// one. Characters that an XML id cannot hold are replaced by `_`, and a
+/// `DEF` that is already taken, including `root` for the root `<Transform>`,
+/// gets the first free suffix `_2`, `_3`, … The shapes are children of the
+/// root `<Transform>`, which is translated by the local origin.
+///
+/// X3D is Y-up. With [`UpAxis::Z`], coordinates are written unchanged and
+/// the root `<Transform>` rotates them upright instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use egml_io::wkt::read_wkt;
+/// use egml_io::x3d::X3dWriter;
+///
+/// let square = read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1 1 0, 0 1 0, 0 0 0))").unwrap();
+///
+/// let mut x3d = Vec::new();
+/// X3dWriter::new().write(&[square], &mut x3d).unwrap();
+///
+/// let x3d = String::from_utf8(x3d).unwrap();
+/// assert!(x3d.contains("<Shape DEF=\"geometry_1\">"));
+/// // The point (1, 1, 0) is written in Y-up axes.
+/// assert!(x3d.contains("1 0 -1"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct X3dWriter {
+    up_axis: UpAxis,
+    unit: LengthUnit,
+    origin: Option<DirectPosition>,
+    formatting: Formatting,
+}
+
+impl Default for X3dWriter {
+    fn default() -> Self {
+        Self {
+            up_axis: UpAxis::Y,
+            unit: LengthUnit::meter(),
+            origin: None,
+            formatting: Formatting::default(),
+        }
+    }
+}
+
+impl X3dWriter {
+    /// Creates a writer of Y-up coordinates in metres, relative to the lower
+    /// corner of the written geometries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_up_axis(mut self, up_axis: UpAxis) -> Self {
+        self.up_axis = up_axis;
+        self
+    }
+
+    /// Writes coordinates, which are taken to be metres, in `unit`, declared
+    /// in a `<unit>` statement.
+    pub fn with_unit(mut self, unit: LengthUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Uses `origin` as the local origin instead, e.g. to share one origin
+    /// among several files.
+    pub fn with_origin(mut self, origin: DirectPosition) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    pub fn with_formatting(mut self, formatting: Formatting) -> Self {
+        self.formatting = formatting;
+        self
+    }
+
+    /// Writes `geometries` to `out`. Coordinates are formatted according to
+    /// the current [`WriteOptions`](crate::util::WriteOptions).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a geometry cannot be triangulated at all, e.g. a
+    /// `MultiCurve`, and [`Error::Io`] if writing fails.
+    pub fn write<'a, W: Write>(
+        &self,
+        geometries: impl IntoIterator<Item = &'a AbstractGeometryKind>,
+        out: W,
+    ) -> Result<(), Error> {
+        let geometries = geometries
+            .into_iter()
+            .map(|x| Ok((x, x.triangulate()?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let origin = match self.origin {
+            Some(origin) => origin.coords(),
+            None => lower_corner(geometries.iter().map(|(_, x)| x)),
+        };
+        let transform = SceneTransform {
+            origin,
+            up_axis: self.up_axis,
+            meters: self.unit.meters(),
+        };
+        let options = WriteOptions::current();
+
+        let mut shapes = Vec::new();
+        let mut names = HashSet::from([ROOT_DEF.to_string()]);
+        for (index, (geometry, triangulation)) in geometries.iter().enumerate() {
+            let name = match geometry.abstract_geometry().abstract_gml.id() {
+                Some(id) => xml_id(id.as_str()),
+                None => format!("geometry_{}", index + 1),
+            };
+            let name = unique_name(&mut names, name);
+            let mesh = transform.mesh(triangulation)?;
+            let points = mesh
+                .vertices
+                .iter()
+                .map(|x| transform.format(&options, x.coords()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let indices = mesh
+                .triangles
+                .iter()
+                .flatten()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(" ");
+            shapes.push(element(
+                "Shape",
+                &[("DEF", name)],
+                vec![
+                    element("Appearance", &[], vec![element("Material", &[], vec![])]),
+                    // GML surfaces may be seen from both sides.
+                    element(
+                        "IndexedTriangleSet",
+                        &[("solid", "false".to_string()), ("index", indices)],
+                        vec![element("Coordinate", &[("point", points)], vec![])],
+                    ),
+                ],
+            ));
+        }
+
+        // The translation applies after the rotation, so it is in Y-up axes.
+        let translation = SceneTransform {
+            up_axis: UpAxis::Y,
+            ..transform
+        }
+        .translation();
+        let mut root_attributes = vec![
+            ("DEF", ROOT_DEF.to_string()),
+            ("translation", transform.format(&options, translation)),
+        ];
+        if self.up_axis == UpAxis::Z {
+            root_attributes.push((
+                "rotation",
+                format!("1 0 0 {}", -std::f64::consts::FRAC_PI_2),
+            ));
+        }
+        let head = element(
+            "head",
+            &[],
+            vec![
+                element(
+                    "unit",
+                    &[
+                        ("category", "length".to_string()),
+                        ("name", self.unit.name().to_string()),
+                        ("conversionFactor", self.unit.meters().to_string()),
+                    ],
+                    vec![],
+                ),
+                element(
+                    "meta",
+                    &[
+                        ("name", "generator".to_string()),
+                        ("content", "egml".to_string()),
+                    ],
+                    vec![],
+                ),
+            ],
+        );
+        let scene = element(
+            "Scene",
+            &[],
+            vec![element("Transform", &root_attributes, shapes)],
+        );
+        let document = XmlNode::new(
+            "X3D",
+            XmlNodeParts::new(vec![head, scene]).with_attributes(vec![
+                ("profile".to_string(), "Interchange".to_string()),
+                ("version".to_string(), "3.3".to_string()),
+                (
+                    "xmlns:xsd".to_string(),
+                    "http://www.w3.org/2001/XMLSchema-instance".to_string(),
+                ),
+                (
+                    "xsd:noNamespaceSchemaLocation".to_string(),
+                    X3D_SCHEMA_LOCATION.to_string(),
+                ),
+            ]),
+        );
+
+        let mut writer = Writer::new(out);
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        if self.formatting != Formatting::Compact {
+            writer.get_mut().write_all(b"\n")?;
+        }
+        document.write_to(&mut writer, self.formatting)?;
+        writer.get_mut().write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+/// `name`, or `name` with the first suffix `_<k>` that is not in `names`,
+/// which it is added to.
+fn unique_name(names: &mut HashSet<String>, name: String) -> String {
+    let name = if names.contains(&name) {
+        (2..)
+            .map(|k| format!("{name}_{k}"))
+            .find(|x| !names.contains(x))
+            .expect("a free suffix")
+    } else {
+        name
+    };
+    names.insert(name.clone());
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt::read_wkt;
+    use egml_core::model::base::{AsAbstractGmlMut, Id};
+    use egml_core::model::geometry::AsAbstractGeometryMut;
+
+    #[test]
+    fn z_up_scenes_are_rotated_upright() {
+        let mut wall =
+            read_wkt("POLYGON Z ((10 20 5, 11 20 5, 11 20 6, 10 20 6, 10 20 5))").unwrap();
+        wall.abstract_geometry_mut()
+            .abstract_gml
+            .set_id(Id::try_from("1st wall").unwrap());
+
+        let mut x3d = Vec::new();
+        X3dWriter::new()
+            .with_up_axis(UpAxis::Z)
+            .with_unit(LengthUnit::new("centimeter", 0.01))
+            .with_formatting(Formatting::Indent { char: ' ', size: 2 })
+            .write(&[wall], &mut x3d)
+            .unwrap();
+        let x3d = String::from_utf8(x3d).unwrap();
+
+        assert!(x3d.contains("\n  <head>\n    <unit category=\"length\" name=\"centimeter\" conversionFactor=\"0.01\"/>\n"));
+        assert!(x3d.contains(
+            "<Transform DEF=\"root\" translation=\"1000 500 -2000\" \
+             rotation=\"1 0 0 -1.5707963267948966\">"
+        ));
+        assert!(x3d.contains("<Shape DEF=\"_1st_wall\">"));
+        // Coordinates stay Z-up, relative to the origin and in centimetres.
+        let points = x3d
+            .split("point=\"")
+            .nth(1)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap();
+        let mut points: Vec<&str> = points.split(", ").collect();
+        points.sort();
+        assert_eq!(points, ["0 0 0", "0 0 100", "100 0 0", "100 0 100"]);
+        let indices = x3d
+            .split("index=\"")
+            .nth(1)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap();
+        assert_eq!(indices.split(' ').count(), 6);
+    }
+
+    #[test]
+    fn ids_are_made_valid_xml_ids() {
+        assert_eq!(xml_id("DEBY_LOD2_1"), "DEBY_LOD2_1");
+        assert_eq!(xml_id("wall 1/a"), "wall_1_a");
+        assert_eq!(xml_id("1"), "_1");
+    }
+
+    #[test]
+    fn defs_are_unique() {
+        let square = |id: Option<&str>| {
+            let mut square = read_wkt("POLYGON Z ((0 0 0, 1 0 0, 1 1 0, 0 1 0, 0 0 0))").unwrap();
+            if let Some(id) = id {
+                square
+                    .abstract_geometry_mut()
+                    .abstract_gml
+                    .set_id(Id::try_from(id).unwrap());
+            }
+            square
+        };
+        let geometries = [
+            square(Some("a b")),
+            square(Some("a_b")),
+            square(Some("geometry_4")),
+            square(None),
+            square(Some("root")),
+        ];
+
+        let mut x3d = Vec::new();
+        X3dWriter::new().write(&geometries, &mut x3d).unwrap();
+        let x3d = String::from_utf8(x3d).unwrap();
+
+        let defs: Vec<&str> = x3d
+            .split("DEF=\"")
+            .skip(1)
+            .map(|x| x.split('"').next().unwrap())
+            .collect();
+        assert_eq!(
+            defs,
+            [
+                "root",
+                "a_b",
+                "a_b_2",
+                "geometry_4",
+                "geometry_4_2",
+                "root_2"
+            ]
+        );
+    }
+}
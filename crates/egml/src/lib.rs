@@ -6,6 +6,7 @@
 //! | Re-export path | Source crate | Purpose |
 //! |----------------|-------------|---------|
 //! | `egml::model::…` | `egml-core` | Geometry types, envelopes, IDs |
+//! | `egml::profile::…` | `egml-core` | GML Simple Features profile checks |
 //! | `egml::util::…` | `egml-core` | Plane and triangulation utilities |
 //! | `egml::io::…` | `egml-io` | XML parsing of GML documents |
 //! | `egml::transform::…` | `egml-transform` | Geometric transforms (in development) |
@@ -23,7 +24,7 @@
 //! ```
 //!
 
-pub use egml_core::{Error, model, resolver, util};
+pub use egml_core::{Error, model, profile, resolver, util};
 
 pub use egml_core::{
     impl_abstract_feature_mut_traits, impl_abstract_feature_traits, impl_abstract_gml_mut_traits,